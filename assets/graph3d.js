@@ -22,16 +22,30 @@
     archived: '#6b7280'
   };
 
+  // Entity type colors (color mode "type")
+  var TYPE_COLORS = {
+    person:       '#f472b6',
+    organization: '#60a5fa',
+    place:        '#34d399',
+    date:         '#facc15',
+    quantity:     '#fb923c',
+    document:     '#e2e8f0',
+    notion:       '#a78bfa'
+  };
+
   var LINK_COLORS = {
-    'é um':         '#4ade80',
-    '≈':            '#22d3ee',
-    '⇒':            '#a78bfa',
-    '⇔':            '#a78bfa',
-    'parte de':     '#fb923c',
-    'tem':          '#fbbf24',
-    'instância de': '#4ade80',
-    'catalisa':     '#22d3ee',
-    'inibe':        '#f87171'
+    'é um':          '#4ade80',
+    '≈':             '#22d3ee',
+    '⇒':             '#a78bfa',
+    '⇔':             '#a78bfa',
+    'parte de':      '#fb923c',
+    'tem':           '#fbbf24',
+    'instância de':  '#4ade80',
+    'catalisa':      '#22d3ee',
+    'inibe':         '#f87171',
+    'autor de':      '#f472b6',
+    'trata de':      '#e2e8f0',
+    'mencionado em': '#64748b'
   };
 
   function Graph3D() {
@@ -55,6 +69,8 @@
     this.pollTimer = null;
     this.statsEl = null;
     this.onSelect = null; // callback(type, data) — type: 'node'|'edge'|null
+    this.colorBy = 'state'; // 'state' | 'type'
+    this.typeFilter = '';   // '' = todos, ou entity_type ('person', 'place'...)
     this._bound = {};
   }
 
@@ -129,7 +145,7 @@
   /**
    * Adds a single node (concept) to the graph incrementally.
    * If the node already exists, updates its properties.
-   * @param {Object} data - {id, label, frequency, confidence, energy, state}
+   * @param {Object} data - {id, label, frequency, confidence, energy, state, entity_type}
    */
   Graph3D.prototype.addNode = function (data) {
     var existing = this.nodeMap[data.id];
@@ -139,6 +155,7 @@
       existing.confidence = data.confidence;
       existing.energy = data.energy;
       existing.state = data.state;
+      existing.entityType = data.entity_type || existing.entityType || null;
       existing.mentionCount = data.mention_count || existing.mentionCount || 1;
     } else {
       var spread = 120;
@@ -149,6 +166,7 @@
         confidence: data.confidence,
         energy: data.energy,
         state: data.state,
+        entityType: data.entity_type || null,
         mentionCount: data.mention_count || 1,
         x: (Math.random() - 0.5) * spread,
         y: (Math.random() - 0.5) * spread,
@@ -219,6 +237,7 @@
         node.confidence = c.confidence;
         node.energy = c.energy;
         node.state = c.state;
+        node.entityType = c.entity_type || null;
         node.mentionCount = c.mention_count;
       } else {
         node = {
@@ -228,6 +247,7 @@
           confidence: c.confidence,
           energy: c.energy,
           state: c.state,
+          entityType: c.entity_type || null,
           mentionCount: c.mention_count,
           x: (Math.random() - 0.5) * spread,
           y: (Math.random() - 0.5) * spread,
@@ -261,16 +281,49 @@
     this._updateStats();
   };
 
+  /**
+   * Sets the node color mode: 'state' (lifecycle) or 'type' (entity type).
+   */
+  Graph3D.prototype.setColorBy = function (mode) {
+    this.colorBy = mode === 'type' ? 'type' : 'state';
+  };
+
+  /**
+   * Shows only nodes of the given entity type ('' shows all).
+   */
+  Graph3D.prototype.setTypeFilter = function (entityType) {
+    this.typeFilter = entityType || '';
+    if (this.selectedNode && this.nodeMap[this.selectedNode] &&
+        !this._isVisible(this.nodeMap[this.selectedNode])) {
+      this.selectedNode = null;
+    }
+    this._updateStats();
+  };
+
+  Graph3D.prototype._isVisible = function (node) {
+    return !this.typeFilter || node.entityType === this.typeFilter;
+  };
+
+  Graph3D.prototype._nodeColor = function (node) {
+    if (this.colorBy === 'type') {
+      return TYPE_COLORS[node.entityType] || STATE_COLORS.archived;
+    }
+    return STATE_COLORS[node.state] || STATE_COLORS.archived;
+  };
+
   Graph3D.prototype._updateStats = function () {
     if (!this.statsEl) return;
     var active = 0;
+    var visible = 0;
     for (var i = 0; i < this.nodes.length; i++) {
       if (this.nodes[i].state === 'active') active++;
+      if (this._isVisible(this.nodes[i])) visible++;
     }
     this.statsEl.textContent =
       'Conceitos: ' + this.nodes.length +
       '  Links: ' + this.edges.length +
-      '  Ativos: ' + active;
+      '  Ativos: ' + active +
+      (this.typeFilter ? '  Filtrados: ' + visible : '');
   };
 
   // --- Physics ---
@@ -377,6 +430,7 @@
     var projected = [];
     for (var i = 0; i < this.nodes.length; i++) {
       var nd = this.nodes[i];
+      if (!this._isVisible(nd)) continue;
       var p = this._project(nd.x, nd.y, nd.z);
       projected.push({
         node: nd,
@@ -466,7 +520,7 @@
         }
       }
 
-      var baseColor = STATE_COLORS[nd2.state] || STATE_COLORS.archived;
+      var baseColor = this._nodeColor(nd2);
 
       // Glow for high energy
       if (nd2.energy > 0.5 && r > 3) {
@@ -668,6 +722,7 @@
 
     for (var i = 0; i < this.nodes.length; i++) {
       var nd = this.nodes[i];
+      if (!this._isVisible(nd)) continue;
       var p = this._project(nd.x, nd.y, nd.z);
       var r = (4 + nd.confidence * 18) * p.scale;
       var dx = mx - p.sx, dy = my - p.sy;
@@ -692,6 +747,7 @@
       var srcNode = this.nodeMap[edge.source];
       var tgtNode = this.nodeMap[edge.target];
       if (!srcNode || !tgtNode) continue;
+      if (!this._isVisible(srcNode) || !this._isVisible(tgtNode)) continue;
       var sp = this._project(srcNode.x, srcNode.y, srcNode.z);
       var tp = this._project(tgtNode.x, tgtNode.y, tgtNode.z);
       var d = distPointToSegment(mx, my, sp.sx, sp.sy, tp.sx, tp.sy);
@@ -7,6 +7,8 @@
 
   var graph = null;
   var eventSource = null;
+  var currentJob = null; // job acompanhado (ID), ou null
+  var extraction = { pagesDone: 0, totalPages: 0, done: true }; // páginas do job acompanhado
 
   function init() {
     // Init Graph3D
@@ -262,7 +264,10 @@
       eventSource.close();
     }
 
-    eventSource = new EventSource('/events');
+    // ?job=<id> segue só aquele job; sem ele, segue o primeiro job que aparecer
+    var pinnedJob = new URLSearchParams(window.location.search).get('job');
+    currentJob = pinnedJob;
+    eventSource = new EventSource(pinnedJob ? '/events?job=' + encodeURIComponent(pinnedJob) : '/events');
 
     eventSource.onopen = function () {
       statusEl.textContent = 'conectado';
@@ -285,10 +290,27 @@
   }
 
   function handleEvent(event) {
+    if (!currentJob && (event.type === 'Queued' || event.type === 'Started')) {
+      currentJob = event.job_id;
+    }
+    if (event.job_id !== currentJob) return;
+
     switch (event.type) {
+      case 'Queued':
+        onQueued(event);
+        break;
       case 'Started':
         onStarted(event);
         break;
+      case 'PageExtracted':
+        onPageExtracted(event);
+        break;
+      case 'PageFailed':
+        onPageFailed(event);
+        break;
+      case 'ExtractionCompleted':
+        onExtractionCompleted(event);
+        break;
       case 'ChunkStarted':
         onChunkStarted(event);
         break;
@@ -307,36 +329,127 @@
       case 'Completed':
         onCompleted(event);
         break;
+      case 'Cancelled':
+        onCancelled(event);
+        break;
       case 'Error':
         onError(event);
         break;
     }
   }
 
+  // Job encerrado: libera o Visualizador para seguir o próximo (sem ?job=)
+  function finishJob() {
+    document.getElementById('progress-cancel').style.display = 'none';
+    if (!new URLSearchParams(window.location.search).get('job')) {
+      currentJob = null;
+    }
+  }
+
+  window.cancelCurrentJob = function () {
+    if (!currentJob) return;
+    var button = document.getElementById('progress-cancel');
+    button.disabled = true;
+    button.textContent = 'Cancelando...';
+    fetch('/jobs/' + encodeURIComponent(currentJob), { method: 'DELETE' })
+      .catch(function () {
+        button.disabled = false;
+        button.textContent = 'Cancelar';
+      });
+  };
+
   // --- SSE Event Handlers ---
 
+  function onQueued(ev) {
+    switchVizTab('activity');
+
+    var log = document.getElementById('activity-log');
+    log.innerHTML = '';
+
+    document.getElementById('progress-container').style.display = 'block';
+    document.getElementById('progress-fill').style.width = '0%';
+    document.getElementById('progress-label').textContent = ev.position > 0
+      ? 'Na fila: ' + ev.filename + ' (' + ev.position + ' à frente)'
+      : 'Na fila: ' + ev.filename;
+    showCancelButton();
+
+    addLogEntry('chunk-info', '\u23F3', 'Job ' + ev.job_id.slice(0, 8) + ' na fila: ' + ev.filename + ' (' + ev.format + ')');
+  }
+
+  function showCancelButton() {
+    var button = document.getElementById('progress-cancel');
+    button.style.display = '';
+    button.disabled = false;
+    button.textContent = 'Cancelar';
+  }
+
   function onStarted(ev) {
     // Switch to activity tab
     switchVizTab('activity');
 
     var log = document.getElementById('activity-log');
-    log.innerHTML = '';
+    if (log.querySelector('.log-empty')) log.innerHTML = '';
+    showCancelButton();
 
     var container = document.getElementById('progress-container');
     container.style.display = 'block';
 
+    extraction = { pagesDone: 0, totalPages: ev.pages || 0, done: false };
+
     var label = document.getElementById('progress-label');
-    label.textContent = 'Iniciando... ' + ev.total_chunks + ' chunks (' + formatBytes(ev.text_len) + ')';
+    label.textContent = 'Extraindo... ' + ev.format + (ev.pages ? ', ' + ev.pages + ' páginas' : '');
 
-    var fill = document.getElementById('progress-fill');
-    fill.style.width = '0%';
+    document.getElementById('progress-fill').style.width = '0%';
 
-    addLogEntry('chunk-info', '\uD83D\uDCC4', 'Ingestão iniciada: ' + ev.total_chunks + ' chunks');
+    var what = (ev.title ? '"' + ev.title + '" ' : '') + '(' + ev.format + (ev.pages ? ', ' + ev.pages + ' p.' : '') + ')';
+    if (ev.resumed_from) {
+      addLogEntry('chunk-info', '\u21BB', 'Ingestão retomada ' + what + ': a partir do chunk ' + (ev.resumed_from + 1));
+    } else {
+      addLogEntry('chunk-info', '\uD83D\uDCC4', 'Ingestão iniciada ' + what);
+    }
+  }
+
+  // Fração concluída: enquanto o PDF é extraído, o total de chunks é
+  // parcial e a fração dos chunks é escalada pela das páginas lidas
+  function progressPct(chunk, total) {
+    if (!total) return 0;
+    var pages = extraction.done || !extraction.totalPages ? 1 : extraction.pagesDone / extraction.totalPages;
+    return Math.min(100, chunk / total * pages * 100);
+  }
+
+  function onPageExtracted(ev) {
+    extraction.pagesDone = ev.page;
+    var label = document.getElementById('progress-label');
+    label.textContent = 'Página ' + ev.page + '/' + ev.total_pages + ' extraída (' + ev.total_chunks + ' chunks até aqui)';
+    if (!ev.chars) {
+      addLogEntry('chunk-info', '\u25CB', 'Página ' + ev.page + ' sem texto');
+    }
+  }
+
+  function onPageFailed(ev) {
+    extraction.pagesDone = ev.page;
+    addLogEntry('error', '\u26A0', 'Página ' + ev.page + '/' + ev.total_pages + ' não extraída: ' + ev.error);
+  }
+
+  function onExtractionCompleted(ev) {
+    extraction.done = true;
+    var failed = ev.failed_pages.length ? ', ' + ev.failed_pages.length + ' página(s) com falha' : '';
+    addLogEntry(
+      'chunk-info', '\uD83D\uDCC3',
+      'Extração concluída em ' + formatDuration(ev.extract_ms) + ': ' + ev.sections + ' seções, ' +
+      ev.total_chunks + ' chunks (' + formatBytes(ev.text_len) + ')' + failed
+    );
   }
 
   function onChunkStarted(ev) {
     var label = document.getElementById('progress-label');
-    label.textContent = 'Chunk ' + ev.chunk + '/' + ev.total + ' (' + ev.chars + ' chars)';
+    label.textContent = 'Chunk ' + ev.chunk + '/' + ev.total + ' (' + ev.chars + ' chars, ' + ev.language + ')';
+    if (ev.page) {
+      label.textContent += ' — p. ' + ev.page;
+    }
+    if (ev.section) {
+      label.textContent += ' — ' + ev.section;
+    }
   }
 
   function onConceptCreated(ev) {
@@ -349,6 +462,7 @@
         confidence: ev.confidence,
         energy: ev.energy,
         state: ev.state,
+        entity_type: ev.entity_type,
         mention_count: 1
       });
     }
@@ -378,7 +492,7 @@
   }
 
   function onChunkCompleted(ev) {
-    var pct = ((ev.chunk / ev.total) * 100).toFixed(0);
+    var pct = progressPct(ev.chunk, ev.total).toFixed(0);
     var fill = document.getElementById('progress-fill');
     fill.style.width = pct + '%';
 
@@ -404,6 +518,10 @@
       'KB: ' + ev.kb_concepts + ' conceitos, ' + ev.kb_links + ' links'
     );
 
+    if (ev.failed_pages && ev.failed_pages.length) {
+      addLogEntry('error', '\u26A0', 'Páginas não extraídas: ' + ev.failed_pages.join(', '));
+    }
+
     addLogEntry(
       'chunk-info', '\u23F1',
       'Tempo: leitura ' + formatDuration(ev.extract_ms) +
@@ -429,10 +547,26 @@
     }
 
     if (graph) graph.refresh();
+    finishJob();
+  }
+
+  function onCancelled(ev) {
+    var label = document.getElementById('progress-label');
+    label.textContent = 'Cancelado (' + ev.chunks_processed + '/' + ev.total_chunks + ' chunks)';
+
+    addLogEntry(
+      'error', '\u26D4',
+      'Ingestão cancelada após ' + ev.chunks_processed + ' de ' + ev.total_chunks +
+      ' chunks. Os chunks processados permanecem na KB.'
+    );
+
+    if (graph) graph.refresh();
+    finishJob();
   }
 
   function onError(ev) {
     addLogEntry('error', '\u26A0', ev.message);
+    finishJob();
   }
 
   // --- Helpers ---
@@ -0,0 +1,176 @@
+//! # Módulo Agent — Modo Agente do Chat com Ferramentas sobre a KB
+//!
+//! Em vez de enviar ao LLM os cinco conceitos mais similares à pergunta,
+//! o modo agente deixa o modelo **consultar a KB por conta própria**,
+//! chamando ferramentas no formato de *function calling* da API OpenAI.
+//!
+//! ## Loop do Agente
+//!
+//! ```text
+//! Pergunta do usuário
+//!   ├── messages = [system, histórico da conversa, user]
+//!   └── até max_steps vezes:
+//!       ├── LLM(messages, tools)
+//!       ├── tool_calls?  → executa cada ferramenta sobre a KB
+//!       │                 → anexa resultados (role "tool") e continua
+//!       └── texto final → retorna resposta
+//!   Limite atingido → última chamada sem ferramentas (força resposta)
+//! ```
+//!
+//! O prompt de sistema e os parâmetros de chat vêm do template
+//! `prompts/agent.prompt` (veja [`crate::nlu::prompts`]).
+//!
+//! Cada chamada de ferramenta vira um [`AgentStep`], exibido ao usuário
+//! no chat como mensagem de ferramenta (estilo "Inferência").
+//!
+//! ## Configuração
+//!
+//! | Variável | Default | Descrição |
+//! |----------|---------|-----------|
+//! | `CHAT_AGENT_MODE` | `false` | Ativa o modo agente para perguntas |
+//! | `CHAT_AGENT_MAX_STEPS` | `5` | Máximo de rodadas de ferramentas por pergunta |
+
+/// Sub-módulo com as ferramentas do agente sobre a KB.
+pub mod tools;
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use parking_lot::RwLock;
+
+use crate::core::KnowledgeBase;
+use crate::nlu::embedder::ChatMsg;
+use crate::nlu::language::Language;
+use crate::nlu::prompts::{PromptTask, PromptVars};
+use crate::nlu::NluPipeline;
+
+use tools::KbTools;
+
+/// Configuração do modo agente.
+#[derive(Clone, Debug)]
+pub struct AgentConfig {
+    /// Se `true`, perguntas (`Intent::Querying`) são respondidas pelo agente.
+    pub enabled: bool,
+    /// Máximo de rodadas de chamadas de ferramenta por pergunta.
+    pub max_steps: usize,
+}
+
+impl AgentConfig {
+    /// Cria configuração a partir de variáveis de ambiente.
+    ///
+    /// | Variável | Default |
+    /// |----------|---------|
+    /// | `CHAT_AGENT_MODE` | `false` (`1`/`true` ativa) |
+    /// | `CHAT_AGENT_MAX_STEPS` | `5` |
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("CHAT_AGENT_MODE")
+                .map(|v| matches!(v.trim(), "1" | "true" | "on"))
+                .unwrap_or(false),
+            max_steps: std::env::var("CHAT_AGENT_MAX_STEPS")
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(5),
+        }
+    }
+}
+
+/// Uma chamada de ferramenta executada pelo agente.
+pub struct AgentStep {
+    /// Nome da ferramenta.
+    pub tool: String,
+    /// Argumentos JSON enviados pelo modelo.
+    pub arguments: String,
+    /// Resumo legível do resultado.
+    pub summary: String,
+    /// Labels dos conceitos devolvidos pela ferramenta.
+    pub concepts: Vec<String>,
+}
+
+/// Resultado de uma execução do agente.
+pub struct AgentOutcome {
+    /// Resposta final do modelo.
+    pub answer: String,
+    /// Ferramentas chamadas, em ordem.
+    pub steps: Vec<AgentStep>,
+}
+
+impl AgentOutcome {
+    /// Conceitos consultados pelo agente, sem repetição, das ferramentas
+    /// mais recentes às mais antigas — o foco da conversa após a resposta.
+    pub fn concepts(&self) -> Vec<String> {
+        let mut concepts: Vec<String> = Vec::new();
+        for label in self.steps.iter().rev().flat_map(|s| &s.concepts) {
+            if !concepts.contains(label) {
+                concepts.push(label.clone());
+            }
+        }
+        concepts
+    }
+}
+
+/// Executa o loop do agente para uma pergunta do usuário.
+///
+/// Erros de ferramenta são devolvidos ao modelo; erros de comunicação com
+/// o LM Studio abortam o loop e são propagados ao chamador.
+pub async fn run(
+    nlu: &NluPipeline,
+    kb: &Arc<RwLock<KnowledgeBase>>,
+    config: &AgentConfig,
+    question: &str,
+    language: Language,
+    history: &[ChatMsg],
+) -> Result<AgentOutcome> {
+    let tools = KbTools::new(nlu, kb);
+    let specs = tools::tool_specs();
+    let template = nlu.prompt(PromptTask::Agent);
+    let mut messages = template.render_with_history(
+        &PromptVars::new(question).with("language", language.prompt_name()),
+        history,
+    );
+    let mut steps = Vec::new();
+
+    for round in 0..config.max_steps {
+        let reply = nlu
+            .chat_with_tools(&messages, &specs, &template.params)
+            .await?;
+        let Some(calls) = reply.tool_calls.clone() else {
+            tracing::info!(rounds = round, steps = steps.len(), "Agente respondeu");
+            return Ok(AgentOutcome {
+                answer: reply.content.unwrap_or_default(),
+                steps,
+            });
+        };
+        messages.push(reply);
+
+        for call in calls {
+            let output = tools
+                .execute(&call.function.name, &call.function.arguments)
+                .await;
+            tracing::info!(tool = %call.function.name, summary = %output.summary, "Agente chamou ferramenta");
+            messages.push(ChatMsg::tool(call.id.clone(), output.content));
+            steps.push(AgentStep {
+                tool: call.function.name,
+                arguments: call.function.arguments,
+                summary: output.summary,
+                concepts: output.concepts,
+            });
+        }
+    }
+
+    // Limite de passos atingido — força uma resposta sem ferramentas
+    tracing::warn!(
+        max_steps = config.max_steps,
+        "Agente atingiu o limite de passos"
+    );
+    messages.push(ChatMsg::user(
+        "Limite de consultas atingido. Responda agora com base no que foi encontrado.",
+    ));
+    let reply = nlu
+        .chat_with_tools(&messages, &[], &template.params)
+        .await?;
+    Ok(AgentOutcome {
+        answer: reply.content.unwrap_or_default(),
+        steps,
+    })
+}
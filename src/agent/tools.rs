@@ -0,0 +1,497 @@
+//! # Ferramentas do Agente — Operações sobre a KnowledgeBase
+//!
+//! Cada ferramenta é descrita no formato de *function calling* da API
+//! OpenAI (JSON Schema dos parâmetros) e executada diretamente sobre a
+//! [`KnowledgeBase`].
+//!
+//! | Ferramenta | Parâmetros | Resultado |
+//! |------------|------------|-----------|
+//! | `search_concepts` | `query`, `limit?` | Conceitos mais similares à consulta |
+//! | `get_neighbors` | `concept` | Links e vizinhos de um conceito |
+//! | `find_path` | `from`, `to` | Menor caminho de links entre dois conceitos |
+//! | `get_link_proof` | `link_id` | Origem, TruthValue e premissas de um link |
+//! | `propose_link` | `subject`, `object`, `kind` | Cria um link com origem `Proposed` e verdade proto |
+//!
+//! O link proposto entra na KB na hora, com verdade mínima
+//! ([`TruthValue::proto`]); o usuário o confirma ou refuta depois, na
+//! revisão espaçada e nas lacunas (origem "proposto").
+//!
+//! Conceitos podem ser referenciados por UUID ou por label (case-insensitive).
+//! Erros de argumento são devolvidos ao modelo como resultado da ferramenta
+//! (não abortam o loop do agente), para que ele possa se corrigir.
+
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::core::concept::ConceptId;
+use crate::core::knowledge_base::cosine_similarity;
+use crate::core::{KnowledgeBase, Link, LinkKind, LinkOrigin, Participant, Role, TruthValue};
+use crate::nlu::NluPipeline;
+
+/// Similaridade mínima para `search_concepts` (limiar de query calibrado
+/// para `nomic-embed-text`, veja `docs/calibracao-nomic-embed-text.md`).
+const SEARCH_THRESHOLD: f32 = 0.35;
+
+/// Profundidade máxima da busca de caminho em `find_path`.
+const MAX_PATH_DEPTH: usize = 6;
+
+/// Resultado da execução de uma ferramenta.
+pub struct ToolOutput {
+    /// Conteúdo devolvido ao modelo (JSON serializado).
+    pub content: String,
+    /// Resumo legível em PT-BR para exibição no chat.
+    pub summary: String,
+    /// Labels dos conceitos que a ferramenta devolveu (viram o foco da
+    /// conversa).
+    pub concepts: Vec<String>,
+}
+
+impl ToolOutput {
+    fn ok(content: Value, summary: String) -> Self {
+        Self {
+            content: content.to_string(),
+            summary,
+            concepts: Vec::new(),
+        }
+    }
+
+    fn with_concepts(mut self, concepts: Vec<String>) -> Self {
+        self.concepts = concepts;
+        self
+    }
+
+    fn error(message: String) -> Self {
+        Self {
+            content: json!({ "error": message }).to_string(),
+            summary: format!("erro: {}", message),
+            concepts: Vec::new(),
+        }
+    }
+}
+
+/// Especificações das ferramentas no formato OpenAI (`tools`).
+pub fn tool_specs() -> Vec<Value> {
+    vec![
+        function_spec(
+            "search_concepts",
+            "Busca conceitos da base de conhecimento semanticamente similares a uma consulta.",
+            json!({
+                "query": { "type": "string", "description": "Texto da busca" },
+                "limit": { "type": "integer", "description": "Máximo de resultados (padrão 5)" }
+            }),
+            &["query"],
+        ),
+        function_spec(
+            "get_neighbors",
+            "Lista os links de um conceito e os conceitos vizinhos.",
+            json!({
+                "concept": { "type": "string", "description": "Label ou UUID do conceito" }
+            }),
+            &["concept"],
+        ),
+        function_spec(
+            "find_path",
+            "Encontra o menor caminho de links entre dois conceitos.",
+            json!({
+                "from": { "type": "string", "description": "Label ou UUID do conceito de origem" },
+                "to": { "type": "string", "description": "Label ou UUID do conceito de destino" }
+            }),
+            &["from", "to"],
+        ),
+        function_spec(
+            "get_link_proof",
+            "Explica um link: tipo, participantes, grau de verdade, origem e premissas (se inferido).",
+            json!({
+                "link_id": { "type": "string", "description": "UUID do link" }
+            }),
+            &["link_id"],
+        ),
+        function_spec(
+            "propose_link",
+            "Cria na base uma nova relação entre dois conceitos, com grau de verdade mínimo e origem 'proposto'. \
+             A relação passa a valer na hora; o usuário a confirma ou refuta depois, na revisão.",
+            json!({
+                "subject": { "type": "string", "description": "Label ou UUID do conceito sujeito" },
+                "object": { "type": "string", "description": "Label ou UUID do conceito objeto" },
+                "kind": {
+                    "type": "string",
+                    "description": "Tipo da relação",
+                    "enum": ["Inheritance", "Similarity", "Implication", "Equivalence", "PartOf",
+                             "HasProperty", "InstanceOf", "Catalyzes", "Inhibits"]
+                }
+            }),
+            &["subject", "object", "kind"],
+        ),
+    ]
+}
+
+/// Monta a especificação de uma função no formato OpenAI.
+fn function_spec(name: &str, description: &str, properties: Value, required: &[&str]) -> Value {
+    json!({
+        "type": "function",
+        "function": {
+            "name": name,
+            "description": description,
+            "parameters": {
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            }
+        }
+    })
+}
+
+/// Executor das ferramentas sobre a KB compartilhada.
+pub struct KbTools<'a> {
+    /// Pipeline NLU (embeddings para `search_concepts`).
+    nlu: &'a NluPipeline,
+    /// Base de conhecimento compartilhada.
+    kb: &'a Arc<RwLock<KnowledgeBase>>,
+}
+
+impl<'a> KbTools<'a> {
+    /// Cria o executor de ferramentas.
+    pub fn new(nlu: &'a NluPipeline, kb: &'a Arc<RwLock<KnowledgeBase>>) -> Self {
+        Self { nlu, kb }
+    }
+
+    /// Executa a ferramenta `name` com os argumentos JSON `arguments`.
+    pub async fn execute(&self, name: &str, arguments: &str) -> ToolOutput {
+        let args: Value = match serde_json::from_str(if arguments.trim().is_empty() {
+            "{}"
+        } else {
+            arguments
+        }) {
+            Ok(v) => v,
+            Err(e) => return ToolOutput::error(format!("argumentos inválidos: {}", e)),
+        };
+        match name {
+            "search_concepts" => self.search_concepts(&args).await,
+            "get_neighbors" => self.get_neighbors(&args),
+            "find_path" => self.find_path(&args),
+            "get_link_proof" => self.get_link_proof(&args),
+            "propose_link" => self.propose_link(&args),
+            other => ToolOutput::error(format!("ferramenta desconhecida: {}", other)),
+        }
+    }
+
+    async fn search_concepts(&self, args: &Value) -> ToolOutput {
+        let Some(query) = args.get("query").and_then(Value::as_str) else {
+            return ToolOutput::error("parâmetro 'query' obrigatório".into());
+        };
+        let limit = args
+            .get("limit")
+            .and_then(Value::as_u64)
+            .unwrap_or(5)
+            .clamp(1, 20) as usize;
+        let embedding = match self.nlu.embed_query(query).await {
+            Ok(e) => e,
+            Err(e) => return ToolOutput::error(format!("falha ao embeddar consulta: {}", e)),
+        };
+
+        let kb = self.kb.read();
+        let mut matches: Vec<(f32, Value, String)> = kb
+            .concepts
+            .values()
+            .filter_map(|c| {
+                let sim = cosine_similarity(&embedding, c.embedding.as_ref()?);
+                (sim > SEARCH_THRESHOLD).then(|| {
+                    (
+                        sim,
+                        json!({
+                            "id": c.id.to_string(),
+                            "label": c.label,
+                            "similarity": sim,
+                            "truth": c.truth.to_string(),
+                            "energy": c.energy,
+                        }),
+                        c.label.clone(),
+                    )
+                })
+            })
+            .collect();
+        matches.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        matches.truncate(limit);
+
+        let labels: Vec<String> = matches.iter().map(|(_, _, l)| l.clone()).collect();
+        let results: Vec<Value> = matches.into_iter().map(|(_, v, _)| v).collect();
+        ToolOutput::ok(
+            json!({ "results": results }),
+            format!("{} conceito(s): {}", labels.len(), labels.join(", ")),
+        )
+        .with_concepts(labels)
+    }
+
+    fn get_neighbors(&self, args: &Value) -> ToolOutput {
+        let kb = self.kb.read();
+        let id = match resolve_concept(&kb, args, "concept") {
+            Ok(id) => id,
+            Err(e) => return ToolOutput::error(e),
+        };
+        let neighbors: Vec<Value> = kb
+            .neighbors(id)
+            .into_iter()
+            .map(|(link, other)| {
+                json!({
+                    "link_id": link.id.to_string(),
+                    "neighbor": kb.concepts.get(&other).map(|c| c.label.as_str()).unwrap_or("?"),
+                    "description": kb.describe_link(link),
+                })
+            })
+            .collect();
+        let label = kb
+            .concepts
+            .get(&id)
+            .map(|c| c.label.clone())
+            .unwrap_or_default();
+        ToolOutput::ok(
+            json!({ "concept": label, "neighbors": neighbors }),
+            format!("{} vizinho(s) de {}", neighbors.len(), label),
+        )
+        .with_concepts(vec![label])
+    }
+
+    fn find_path(&self, args: &Value) -> ToolOutput {
+        let kb = self.kb.read();
+        let (from, to) = match (
+            resolve_concept(&kb, args, "from"),
+            resolve_concept(&kb, args, "to"),
+        ) {
+            (Ok(f), Ok(t)) => (f, t),
+            (Err(e), _) | (_, Err(e)) => return ToolOutput::error(e),
+        };
+        let output = match kb.find_path(from, to, MAX_PATH_DEPTH) {
+            Some(path) => {
+                let steps: Vec<String> = path
+                    .iter()
+                    .filter_map(|id| kb.links.get(id))
+                    .map(|l| kb.describe_link(l))
+                    .collect();
+                ToolOutput::ok(
+                    json!({ "found": true, "links": path.iter().map(|id| id.to_string()).collect::<Vec<_>>(), "steps": steps }),
+                    format!("caminho com {} link(s)", steps.len()),
+                )
+            }
+            None => ToolOutput::ok(
+                json!({ "found": false }),
+                format!("nenhum caminho em até {} links", MAX_PATH_DEPTH),
+            ),
+        };
+        output.with_concepts(labels(&kb, &[from, to]))
+    }
+
+    fn get_link_proof(&self, args: &Value) -> ToolOutput {
+        let kb = self.kb.read();
+        let Some(link) = args
+            .get("link_id")
+            .and_then(Value::as_str)
+            .and_then(|s| Uuid::parse_str(s.trim()).ok())
+            .and_then(|id| kb.links.get(&id))
+        else {
+            return ToolOutput::error("link não encontrado (use um UUID de link)".into());
+        };
+        let premises: Vec<String> = match &link.origin {
+            LinkOrigin::Inferred { premises, .. } => premises
+                .iter()
+                .map(|id| {
+                    kb.links
+                        .get(id)
+                        .map(|l| kb.describe_link(l))
+                        .unwrap_or_else(|| format!("{} (removido)", id))
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        let rule = match &link.origin {
+            LinkOrigin::Inferred { rule, .. } => Some(rule.clone()),
+            _ => None,
+        };
+        let participants: Vec<ConceptId> = link.participants.iter().map(|p| p.concept_id).collect();
+        let concepts = labels(&kb, &participants);
+        ToolOutput::ok(
+            json!({
+                "description": kb.describe_link(link),
+                "kind": link.kind.label(),
+                "frequency": link.truth.frequency(),
+                "confidence": link.truth.confidence(),
+                "expectation": link.truth.expectation(),
+                "energy": link.energy,
+                "origin": link.origin.label(),
+                "rule": rule,
+                "premises": premises,
+            }),
+            format!("{} ({})", kb.describe_link(link), link.origin.label()),
+        )
+        .with_concepts(concepts)
+    }
+
+    fn propose_link(&self, args: &Value) -> ToolOutput {
+        let mut kb = self.kb.write();
+        let (subject, object) = match (
+            resolve_concept(&kb, args, "subject"),
+            resolve_concept(&kb, args, "object"),
+        ) {
+            (Ok(s), Ok(o)) => (s, o),
+            (Err(e), _) | (_, Err(e)) => return ToolOutput::error(e),
+        };
+        if subject == object {
+            return ToolOutput::error("sujeito e objeto são o mesmo conceito".into());
+        }
+        let Some(kind) = args
+            .get("kind")
+            .and_then(Value::as_str)
+            .and_then(parse_link_kind)
+        else {
+            return ToolOutput::error("tipo de relação inválido".into());
+        };
+        if kb.link_exists(&kind, subject, object) {
+            return ToolOutput::ok(
+                json!({ "created": false, "reason": "link já existe" }),
+                "link já existe".into(),
+            );
+        }
+        let link = Link::new(
+            kind,
+            vec![
+                Participant {
+                    concept_id: subject,
+                    role: Role::Subject,
+                },
+                Participant {
+                    concept_id: object,
+                    role: Role::Object,
+                },
+            ],
+            TruthValue::proto(),
+        )
+        .with_origin(LinkOrigin::Proposed);
+        let desc = kb.describe_link(&link);
+        let id = kb.add_link(link);
+        tracing::info!(link = %desc, "Agente propôs novo link");
+        ToolOutput::ok(
+            json!({ "created": true, "link_id": id.to_string(), "description": desc }),
+            format!("proposto: {}", desc),
+        )
+        .with_concepts(labels(&kb, &[subject, object]))
+    }
+}
+
+/// Labels dos conceitos `ids` que ainda existem na KB.
+fn labels(kb: &KnowledgeBase, ids: &[ConceptId]) -> Vec<String> {
+    ids.iter()
+        .filter_map(|id| kb.concepts.get(id))
+        .map(|c| c.label.clone())
+        .collect()
+}
+
+/// Resolve o argumento `field` (UUID ou label) para um [`ConceptId`] existente.
+fn resolve_concept(kb: &KnowledgeBase, args: &Value, field: &str) -> Result<ConceptId, String> {
+    let Some(raw) = args.get(field).and_then(Value::as_str) else {
+        return Err(format!("parâmetro '{}' obrigatório", field));
+    };
+    let raw = raw.trim();
+    if let Ok(id) = Uuid::parse_str(raw) {
+        if kb.concepts.contains_key(&id) {
+            return Ok(id);
+        }
+    }
+    kb.find_concept_by_label(raw)
+        .map(|c| c.id)
+        .ok_or_else(|| format!("conceito '{}' não encontrado — use search_concepts", raw))
+}
+
+/// Converte o nome de um tipo de relação (variante ou label PT-BR) em [`LinkKind`].
+fn parse_link_kind(name: &str) -> Option<LinkKind> {
+    let kind = match name.trim() {
+        "Inheritance" | "é um" => LinkKind::Inheritance,
+        "Similarity" | "≈" => LinkKind::Similarity,
+        "Implication" | "⇒" => LinkKind::Implication,
+        "Equivalence" | "⇔" => LinkKind::Equivalence,
+        "PartOf" | "parte de" => LinkKind::PartOf,
+        "HasProperty" | "tem" => LinkKind::HasProperty,
+        "InstanceOf" | "instância de" => LinkKind::InstanceOf,
+        "Catalyzes" | "catalisa" => LinkKind::Catalyzes,
+        "Inhibits" | "inibe" => LinkKind::Inhibits,
+        _ => return None,
+    };
+    Some(kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Concept;
+    use crate::nlu::embedder::Embedder;
+
+    #[tokio::test]
+    async fn dispatches_tools_and_reports_argument_errors() {
+        let nlu = NluPipeline::new(Embedder::offline()).await.unwrap();
+        let kb = Arc::new(RwLock::new(KnowledgeBase::new()));
+        {
+            let mut kb = kb.write();
+            let chuva = kb.add_concept(Concept::new("Chuva".into(), TruthValue::proto()));
+            let solo = kb.add_concept(Concept::new("Solo".into(), TruthValue::proto()));
+            kb.add_link(Link::new(
+                LinkKind::Implication,
+                vec![
+                    Participant {
+                        concept_id: chuva,
+                        role: Role::Subject,
+                    },
+                    Participant {
+                        concept_id: solo,
+                        role: Role::Object,
+                    },
+                ],
+                TruthValue::proto(),
+            ));
+            kb.add_concept(Concept::new("Planta".into(), TruthValue::proto()));
+        }
+        let tools = KbTools::new(&nlu, &kb);
+        let result = |output: ToolOutput| serde_json::from_str::<Value>(&output.content).unwrap();
+
+        let neighbors = result(
+            tools
+                .execute("get_neighbors", r#"{"concept": "chuva"}"#)
+                .await,
+        );
+        assert_eq!(neighbors["neighbors"][0]["neighbor"], "Solo");
+        let path = result(
+            tools
+                .execute("find_path", r#"{"from": "Solo", "to": "Planta"}"#)
+                .await,
+        );
+        assert_eq!(path["found"], false);
+
+        let args = r#"{"subject": "Solo", "object": "Planta", "kind": "Implication"}"#;
+        let proposed = result(tools.execute("propose_link", args).await);
+        assert_eq!(proposed["created"], true);
+        assert!(kb
+            .read()
+            .links
+            .values()
+            .any(|l| l.origin == LinkOrigin::Proposed));
+        assert_eq!(
+            result(tools.execute("propose_link", args).await)["created"],
+            false
+        );
+        let path = result(
+            tools
+                .execute("find_path", r#"{"from": "Chuva", "to": "Planta"}"#)
+                .await,
+        );
+        assert_eq!(path["steps"].as_array().map(Vec::len), Some(2));
+
+        let output = tools
+            .execute("find_path", r#"{"from": "Chuva", "to": "Planta"}"#)
+            .await;
+        assert_eq!(output.concepts, ["Chuva", "Planta"]);
+
+        assert!(result(tools.execute("get_neighbors", "{}").await)["error"].is_string());
+        assert!(result(tools.execute("get_link_proof", "{not json").await)["error"].is_string());
+        assert!(result(tools.execute("delete_everything", "").await)["error"].is_string());
+    }
+}
@@ -0,0 +1,533 @@
+//! # Avaliação — Extração, Relações, Intents e Normalização
+//!
+//! Roda datasets anotados (pt-BR) pelo pipeline NLU com o
+//! [`Embedder::offline`] — sem LM Studio, resultados reprodutíveis — e
+//! reporta precisão/recall/F1 por componente, com a diferença para o
+//! baseline gravado.
+//!
+//! ## Uso
+//!
+//! ```text
+//! cargo run --bin eval                       # avalia e compara com eval/baseline.json
+//! cargo run --bin eval -- --update-baseline  # grava os resultados como novo baseline
+//! cargo run --bin eval -- --check            # sai com código 1 se algum F1 piorar
+//! cargo run --bin eval -- --verbose          # lista acertos e erros por frase
+//! cargo run --bin eval -- --data outro/dir --baseline outro/baseline.json
+//! ```
+//!
+//! ## Datasets (`eval/`)
+//!
+//! | Arquivo | Linha (JSON) | Componentes |
+//! |---------|--------------|-------------|
+//! | `sentences.*.jsonl` | `{text, entities?, relations?, intent?}` | `entities`, `relations`, `intents` |
+//! | `normalization.*.jsonl` | `{stage, raw, expected}` | `normalize_broken_words`, `normalize_pdf_text` |
+//!
+//! Campos ausentes não são avaliados naquela frase. Cada frase roda numa
+//! KB vazia; links `InstanceOf` (tipagem) ficam fora de `relations`.
+//!
+//! ## Métricas
+//!
+//! | Componente | Unidade comparada |
+//! |------------|-------------------|
+//! | `entities` | label do conceito (sem diferenciar maiúsculas) |
+//! | `relations` | tripla (origem, tipo, destino) |
+//! | `intents` | média macro por intent (P/R/F1 de cada classe) |
+//! | `normalize_*` | tokens da saída vs. tokens esperados |
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tracing_subscriber::EnvFilter;
+
+use semantic_chat::core::{EntityType, KnowledgeBase};
+use semantic_chat::document::pdf::normalize_pdf_text;
+use semantic_chat::nlu::embedder::Embedder;
+use semantic_chat::nlu::extractor::normalize_broken_words;
+use semantic_chat::nlu::intent::Intent;
+use semantic_chat::nlu::NluPipeline;
+
+/// Queda de F1 tolerada por `--check` antes de acusar regressão.
+const REGRESSION_TOLERANCE: f64 = 0.005;
+
+/// Frase anotada de `sentences.*.jsonl`.
+#[derive(Deserialize)]
+struct GoldSentence {
+    /// Texto da frase.
+    text: String,
+    /// Entidades esperadas.
+    entities: Option<Vec<String>>,
+    /// Relações esperadas.
+    relations: Option<Vec<GoldRelation>>,
+    /// Intent esperado (`querying`, `narrating`...).
+    intent: Option<String>,
+}
+
+/// Relação anotada.
+#[derive(Deserialize)]
+struct GoldRelation {
+    /// Label do conceito de origem.
+    source: String,
+    /// Tipo do link (`Implication`, `Similarity`...).
+    kind: String,
+    /// Label do conceito de destino.
+    target: String,
+}
+
+/// Caso de normalização de `normalization.*.jsonl`.
+#[derive(Deserialize)]
+struct GoldNormalization {
+    /// `broken_words` ou `pdf`.
+    stage: String,
+    /// Texto quebrado.
+    raw: String,
+    /// Texto corrigido esperado.
+    expected: String,
+}
+
+/// Contagens de acertos e erros de um componente.
+#[derive(Clone, Copy, Default)]
+struct Counts {
+    /// Verdadeiros positivos.
+    tp: usize,
+    /// Falsos positivos.
+    fp: usize,
+    /// Falsos negativos.
+    fn_: usize,
+}
+
+impl Counts {
+    /// Soma a comparação entre multiconjuntos previsto e esperado.
+    fn add<T: Eq + std::hash::Hash>(
+        &mut self,
+        predicted: impl IntoIterator<Item = T>,
+        gold: impl IntoIterator<Item = T>,
+    ) {
+        let mut remaining: HashMap<T, usize> = HashMap::new();
+        let mut gold_total = 0;
+        for item in gold {
+            *remaining.entry(item).or_default() += 1;
+            gold_total += 1;
+        }
+        let mut tp = 0;
+        let mut predicted_total = 0;
+        for item in predicted {
+            predicted_total += 1;
+            if let Some(n) = remaining.get_mut(&item).filter(|n| **n > 0) {
+                *n -= 1;
+                tp += 1;
+            }
+        }
+        self.tp += tp;
+        self.fp += predicted_total - tp;
+        self.fn_ += gold_total - tp;
+    }
+
+    /// Precisão, recall e F1.
+    fn scores(&self) -> Scores {
+        let ratio = |num: usize, den: usize| {
+            if den == 0 {
+                0.0
+            } else {
+                num as f64 / den as f64
+            }
+        };
+        let precision = ratio(self.tp, self.tp + self.fp);
+        let recall = ratio(self.tp, self.tp + self.fn_);
+        Scores {
+            precision,
+            recall,
+            f1: f1(precision, recall),
+            support: self.tp + self.fn_,
+        }
+    }
+}
+
+/// Média harmônica de precisão e recall.
+fn f1(precision: f64, recall: f64) -> f64 {
+    if precision + recall == 0.0 {
+        0.0
+    } else {
+        2.0 * precision * recall / (precision + recall)
+    }
+}
+
+/// Métricas de um componente (também o formato do baseline).
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+struct Scores {
+    /// Precisão.
+    precision: f64,
+    /// Recall.
+    recall: f64,
+    /// F1.
+    f1: f64,
+    /// Itens esperados no dataset.
+    support: usize,
+}
+
+/// Opções de linha de comando.
+struct Options {
+    /// Diretório dos datasets.
+    data: PathBuf,
+    /// Arquivo de baseline.
+    baseline: PathBuf,
+    /// Grava os resultados como novo baseline.
+    update_baseline: bool,
+    /// Sai com erro se algum F1 cair além da tolerância.
+    check: bool,
+    /// Lista erros por frase.
+    verbose: bool,
+}
+
+impl Options {
+    /// Interpreta os argumentos (`--data`, `--baseline`, `--update-baseline`,
+    /// `--check`, `--verbose`).
+    fn parse(args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut data = PathBuf::from("eval");
+        let mut baseline = None;
+        let mut update_baseline = false;
+        let mut check = false;
+        let mut verbose = false;
+
+        let mut args = args.peekable();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--data" => data = args.next().context("--data requer um diretório")?.into(),
+                "--baseline" => {
+                    baseline = Some(args.next().context("--baseline requer um arquivo")?.into())
+                }
+                "--update-baseline" => update_baseline = true,
+                "--check" => check = true,
+                "--verbose" | "-v" => verbose = true,
+                "--help" | "-h" => {
+                    println!("uso: eval [--data DIR] [--baseline ARQUIVO] [--update-baseline] [--check] [--verbose]");
+                    std::process::exit(0);
+                }
+                other => bail!("argumento desconhecido: {}", other),
+            }
+        }
+
+        let baseline = baseline.unwrap_or_else(|| data.join("baseline.json"));
+        Ok(Self {
+            data,
+            baseline,
+            update_baseline,
+            check,
+            verbose,
+        })
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")),
+        )
+        .init();
+
+    let options = Options::parse(std::env::args().skip(1))?;
+    let sentences: Vec<GoldSentence> = read_datasets(&options.data, "sentences")?;
+    let normalizations: Vec<GoldNormalization> = read_datasets(&options.data, "normalization")?;
+    if sentences.is_empty() && normalizations.is_empty() {
+        bail!("nenhum dataset em {}", options.data.display());
+    }
+
+    let nlu = NluPipeline::new(Embedder::offline()).await?;
+    let mut results: BTreeMap<String, Scores> = BTreeMap::new();
+
+    let (entities, relations, intents) =
+        evaluate_sentences(&nlu, &sentences, options.verbose).await?;
+    results.insert("entities".into(), entities.scores());
+    results.insert("relations".into(), relations.scores());
+    if let Some(scores) = macro_scores(&intents) {
+        results.insert("intents".into(), scores);
+    }
+    for (component, counts) in evaluate_normalization(&normalizations, options.verbose)? {
+        results.insert(component, counts.scores());
+    }
+
+    let baseline = read_baseline(&options.baseline)?;
+    let regressions = report(&results, baseline.as_ref());
+
+    if options.update_baseline {
+        let json = serde_json::to_string_pretty(&results)?;
+        std::fs::write(&options.baseline, json + "\n")
+            .with_context(|| format!("falha ao gravar {}", options.baseline.display()))?;
+        println!("\nBaseline gravado em {}", options.baseline.display());
+    } else if options.check && !regressions.is_empty() {
+        bail!("F1 piorou em: {}", regressions.join(", "));
+    }
+    Ok(())
+}
+
+/// Lê e concatena os arquivos `<prefixo>.*.jsonl` do diretório.
+fn read_datasets<T: for<'de> Deserialize<'de>>(dir: &Path, prefix: &str) -> Result<Vec<T>> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("falha ao ler {}", dir.display()))?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            let name = p.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            name.starts_with(&format!("{}.", prefix)) && name.ends_with(".jsonl")
+        })
+        .collect();
+    paths.sort();
+
+    let mut items = Vec::new();
+    for path in paths {
+        let source = std::fs::read_to_string(&path)?;
+        for (n, line) in source.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let item = serde_json::from_str(line)
+                .with_context(|| format!("{}:{}: linha inválida", path.display(), n + 1))?;
+            items.push(item);
+        }
+    }
+    Ok(items)
+}
+
+/// Lê o baseline, se existir.
+fn read_baseline(path: &Path) -> Result<Option<BTreeMap<String, Scores>>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let source = std::fs::read_to_string(path)?;
+    let baseline = serde_json::from_str(&source)
+        .with_context(|| format!("baseline inválido: {}", path.display()))?;
+    Ok(Some(baseline))
+}
+
+/// Roda cada frase pelo pipeline numa KB vazia e compara entidades,
+/// relações e intent com o esperado.
+///
+/// Retorna as contagens de entidades, de relações e uma matriz
+/// `(esperado, previsto)` dos intents.
+async fn evaluate_sentences(
+    nlu: &NluPipeline,
+    sentences: &[GoldSentence],
+    verbose: bool,
+) -> Result<(Counts, Counts, Vec<(Intent, Intent)>)> {
+    let mut entities = Counts::default();
+    let mut relations = Counts::default();
+    let mut intents = Vec::new();
+    let type_labels: Vec<&str> = EntityType::ALL.iter().map(|t| t.label()).collect();
+
+    for sentence in sentences {
+        let kb = Arc::new(RwLock::new(KnowledgeBase::new()));
+        let result = nlu.process_message(&sentence.text, &kb, &[]).await?;
+
+        if let Some(gold) = &sentence.entities {
+            // Conceitos-tipo ("Pessoa", "Lugar") vêm da tipagem, não da extração
+            let predicted: Vec<String> = result
+                .concept_details
+                .iter()
+                .filter(|c| c.entity_type.is_some() || !type_labels.contains(&c.label.as_str()))
+                .map(|c| c.label.to_lowercase())
+                .collect();
+            let gold: Vec<String> = gold.iter().map(|e| e.to_lowercase()).collect();
+            if verbose {
+                print_diff(&sentence.text, "entidades", &predicted, &gold);
+            }
+            entities.add(predicted, gold);
+        }
+
+        if let Some(gold) = &sentence.relations {
+            let predicted: Vec<String> = result
+                .link_details
+                .iter()
+                .filter(|l| l.kind != "InstanceOf")
+                .map(|l| relation_key(&l.source_label, &l.kind, &l.target_label))
+                .collect();
+            let gold: Vec<String> = gold
+                .iter()
+                .map(|r| relation_key(&r.source, &r.kind, &r.target))
+                .collect();
+            if verbose {
+                print_diff(&sentence.text, "relações", &predicted, &gold);
+            }
+            relations.add(predicted, gold);
+        }
+
+        if let Some(name) = &sentence.intent {
+            let Some(expected) = Intent::from_name(name) else {
+                bail!("intent desconhecido no dataset: {}", name);
+            };
+            let classification = nlu.classify_intent(&sentence.text).await?;
+            if verbose && classification.intent != expected {
+                println!(
+                    "✗ intent  \"{}\": esperado {}, obtido {}",
+                    sentence.text,
+                    expected.label(),
+                    classification.describe()
+                );
+            }
+            intents.push((expected, classification.intent));
+        }
+    }
+    Ok((entities, relations, intents))
+}
+
+/// Chave normalizada de uma relação.
+fn relation_key(source: &str, kind: &str, target: &str) -> String {
+    format!(
+        "{} —{}→ {}",
+        source.to_lowercase(),
+        kind.to_lowercase(),
+        target.to_lowercase()
+    )
+}
+
+/// Média macro de P/R/F1 sobre os intents presentes (esperados ou previstos).
+fn macro_scores(pairs: &[(Intent, Intent)]) -> Option<Scores> {
+    if pairs.is_empty() {
+        return None;
+    }
+    let classes: Vec<Intent> = Intent::ALL
+        .into_iter()
+        .filter(|i| {
+            pairs
+                .iter()
+                .any(|(gold, predicted)| gold == i || predicted == i)
+        })
+        .collect();
+    let per_class: Vec<Scores> = classes
+        .iter()
+        .map(|class| {
+            let tp = pairs
+                .iter()
+                .filter(|(g, p)| g == class && p == class)
+                .count();
+            let fp = pairs
+                .iter()
+                .filter(|(g, p)| g != class && p == class)
+                .count();
+            let fn_ = pairs
+                .iter()
+                .filter(|(g, p)| g == class && p != class)
+                .count();
+            Counts { tp, fp, fn_ }.scores()
+        })
+        .collect();
+    let n = per_class.len() as f64;
+    Some(Scores {
+        precision: per_class.iter().map(|s| s.precision).sum::<f64>() / n,
+        recall: per_class.iter().map(|s| s.recall).sum::<f64>() / n,
+        f1: per_class.iter().map(|s| s.f1).sum::<f64>() / n,
+        support: pairs.len(),
+    })
+}
+
+/// Compara tokens da saída de cada normalizador com o texto esperado.
+fn evaluate_normalization(
+    cases: &[GoldNormalization],
+    verbose: bool,
+) -> Result<Vec<(String, Counts)>> {
+    let mut broken_words = Counts::default();
+    let mut pdf = Counts::default();
+    for case in cases {
+        let (output, counts) = match case.stage.as_str() {
+            "broken_words" => (normalize_broken_words(&case.raw), &mut broken_words),
+            "pdf" => (normalize_pdf_text(&case.raw), &mut pdf),
+            other => bail!("estágio de normalização desconhecido: {}", other),
+        };
+        if verbose && output != case.expected {
+            println!(
+                "✗ {}  \"{}\": esperado \"{}\", obtido \"{}\"",
+                case.stage, case.raw, case.expected, output
+            );
+        }
+        counts.add(output.split_whitespace(), case.expected.split_whitespace());
+    }
+
+    let mut results = Vec::new();
+    if cases.iter().any(|c| c.stage == "broken_words") {
+        results.push(("normalize_broken_words".to_string(), broken_words));
+    }
+    if cases.iter().any(|c| c.stage == "pdf") {
+        results.push(("normalize_pdf_text".to_string(), pdf));
+    }
+    Ok(results)
+}
+
+/// Imprime falsos positivos e negativos de uma frase.
+fn print_diff(text: &str, what: &str, predicted: &[String], gold: &[String]) {
+    let extra: Vec<&String> = predicted.iter().filter(|p| !gold.contains(p)).collect();
+    let missing: Vec<&String> = gold.iter().filter(|g| !predicted.contains(g)).collect();
+    if extra.is_empty() && missing.is_empty() {
+        return;
+    }
+    println!("✗ {}  \"{}\"", what, text);
+    for item in extra {
+        println!("    + {}", item);
+    }
+    for item in missing {
+        println!("    - {}", item);
+    }
+}
+
+/// Imprime a tabela de resultados com ΔF1 e retorna os componentes que
+/// pioraram além da tolerância.
+fn report(
+    results: &BTreeMap<String, Scores>,
+    baseline: Option<&BTreeMap<String, Scores>>,
+) -> Vec<String> {
+    let mut regressions = Vec::new();
+    println!(
+        "{:<24} {:>6} {:>6} {:>6} {:>7} {:>9}",
+        "componente", "P", "R", "F1", "n", "ΔF1"
+    );
+    for (component, scores) in results {
+        let delta = match baseline.and_then(|b| b.get(component)) {
+            Some(before) => {
+                // Ruído de arredondamento do JSON não é variação
+                let delta = match scores.f1 - before.f1 {
+                    d if d.abs() < 5e-4 => 0.0,
+                    d => d,
+                };
+                if delta < -REGRESSION_TOLERANCE {
+                    regressions.push(component.clone());
+                }
+                format!("{:+.3}", delta)
+            }
+            None => "novo".to_string(),
+        };
+        println!(
+            "{:<24} {:>6.3} {:>6.3} {:>6.3} {:>7} {:>9}",
+            component, scores.precision, scores.recall, scores.f1, scores.support, delta
+        );
+    }
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_compare_multisets() {
+        let mut counts = Counts::default();
+        counts.add(["a", "b", "b", "c"], ["a", "b", "d"]);
+        let scores = counts.scores();
+        assert_eq!((counts.tp, counts.fp, counts.fn_), (2, 2, 1));
+        assert!((scores.precision - 0.5).abs() < 1e-9);
+        assert!((scores.recall - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn macro_scores_average_over_classes() {
+        let pairs = [
+            (Intent::Querying, Intent::Querying),
+            (Intent::Narrating, Intent::Querying),
+            (Intent::Narrating, Intent::Narrating),
+        ];
+        let scores = macro_scores(&pairs).unwrap();
+        // Querying: P=0.5 R=1.0; Narrating: P=1.0 R=0.5
+        assert!((scores.precision - 0.75).abs() < 1e-9);
+        assert!((scores.recall - 0.75).abs() < 1e-9);
+    }
+}
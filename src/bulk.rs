@@ -0,0 +1,874 @@
+//! # Ingestão em Lote — Semear a KB a Partir de um Diretório
+//!
+//! Subcomando `ingest` do binário `semantic-chat`: roda a mesma ingestão
+//! do `/upload` ([`crate::jobs`] → [`crate::ingest`]) sem o servidor web,
+//! sobre a KB persistida em `data/kb.json`.
+//!
+//! ```text
+//! semantic-chat ingest ./corpus --recursive --glob '*.pdf'
+//!   ├── 1. Carrega data/kb.json e conecta ao LM Studio
+//!   ├── 2. Lista os arquivos (recursivo, filtrados pelos --glob)
+//!   ├── 3. Para cada arquivo, quando há worker livre: lê, detecta formato, calcula hash
+//!   │      ├── formato não suportado ─────────────▶ unsupported
+//!   │      ├── já no registro (sem --reingest) ───▶ skipped
+//!   │      └── submete job (INGEST_WORKERS em paralelo; checkpoint → retoma)
+//!   ├── 4. Barra de progresso (arquivos + chunks dos jobs em execução)
+//!   ├── 5. Relatório por arquivo → data/ingest-report.json
+//!   └── 6. --watch: varre o diretório a cada --interval s e ingere os novos
+//! ```
+//!
+//! ## Opções
+//!
+//! | Opção | Padrão | Efeito |
+//! |-------|--------|--------|
+//! | `--recursive`, `-r` | desligado | Desce nos subdiretórios |
+//! | `--glob PADRÃO` | formatos suportados | Filtra por nome (`*`, `?`); pode repetir. Com `/`, casa o caminho relativo |
+//! | `--reingest` | desligado | Reingere documentos já registrados e recomeça os interrompidos |
+//! | `--report ARQUIVO` | `data/ingest-report.json` | Relatório JSON por arquivo |
+//! | `--watch` | desligado | Continua observando o diretório (Ctrl-C encerra) |
+//! | `--interval SEGUNDOS` | `5` | Intervalo entre varreduras no modo `--watch` |
+//!
+//! Arquivos e diretórios ocultos (`.nome`) são ignorados. No modo
+//! `--watch`, um arquivo modificado há menos de 2s fica para a próxima
+//! varredura (pode estar sendo copiado), e um arquivo que falhou é
+//! tentado de novo com espera crescente (`--interval` dobrando a cada
+//! falha, até 10 min; modificá-lo zera a espera). O relatório guarda uma
+//! linha por arquivo, substituída a cada tentativa. Ctrl-C cancela os
+//! jobs em execução — seus checkpoints permitem retomar na próxima
+//! execução.
+//!
+//! O servidor inicia com a KB vazia: para usar a KB semeada, inicie-o com
+//! `KB_RESTORE=1`. Não rode o subcomando com o servidor no ar — os dois
+//! gravariam `data/kb.json`.
+
+use std::collections::{HashMap, VecDeque};
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::checkpoint::CheckpointStore;
+use crate::core::KnowledgeBase;
+use crate::document::DocumentFormat;
+use crate::ingest::DocumentUpload;
+use crate::jobs::{JobConfig, JobId, JobInfo, JobManager, JobStatus};
+use crate::nlu::embedder::{Embedder, EmbedderConfig};
+use crate::nlu::NluPipeline;
+use crate::persistence;
+
+/// Relatório padrão.
+const DEFAULT_REPORT: &str = "data/ingest-report.json";
+
+/// Idade mínima de um arquivo no modo `--watch` (evita pegar cópias pela metade).
+const SETTLE_TIME: Duration = Duration::from_secs(2);
+
+/// Intervalo de atualização da barra de progresso.
+const PROGRESS_TICK: Duration = Duration::from_millis(200);
+
+/// Largura da barra de progresso (caracteres).
+const BAR_WIDTH: usize = 30;
+
+/// Espera máxima antes de tentar de novo um arquivo que falhou (`--watch`).
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(600);
+
+/// Uso do subcomando.
+pub const USAGE: &str = "uso: semantic-chat ingest DIR [--recursive] [--glob PADRÃO]... [--reingest] [--report ARQUIVO] [--watch] [--interval SEGUNDOS]";
+
+/// Opções do subcomando `ingest`.
+#[derive(Clone, Debug)]
+pub struct BulkOptions {
+    /// Diretório do corpus.
+    pub dir: PathBuf,
+    /// Desce nos subdiretórios.
+    pub recursive: bool,
+    /// Padrões de nome; vazio = extensões dos formatos suportados.
+    pub globs: Vec<String>,
+    /// Reingere documentos já registrados.
+    pub reingest: bool,
+    /// Arquivo do relatório JSON.
+    pub report: PathBuf,
+    /// Continua observando o diretório.
+    pub watch: bool,
+    /// Intervalo entre varreduras no modo `--watch`.
+    pub interval: Duration,
+}
+
+impl BulkOptions {
+    /// Interpreta os argumentos após `ingest`.
+    pub fn parse(args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut dir = None;
+        let mut recursive = false;
+        let mut globs = Vec::new();
+        let mut reingest = false;
+        let mut report = PathBuf::from(DEFAULT_REPORT);
+        let mut watch = false;
+        let mut interval = Duration::from_secs(5);
+
+        let mut args = args.peekable();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--recursive" | "-r" => recursive = true,
+                "--glob" | "-g" => globs.push(args.next().context("--glob requer um padrão")?),
+                "--reingest" => reingest = true,
+                "--report" => report = args.next().context("--report requer um arquivo")?.into(),
+                "--watch" | "-w" => watch = true,
+                "--interval" => {
+                    let secs: u64 = args
+                        .next()
+                        .context("--interval requer segundos")?
+                        .trim()
+                        .parse()
+                        .context("--interval requer um número inteiro de segundos")?;
+                    interval = Duration::from_secs(secs.max(1));
+                }
+                "--help" | "-h" => {
+                    println!("{}", USAGE);
+                    std::process::exit(0);
+                }
+                other if other.starts_with('-') => {
+                    bail!("argumento desconhecido: {}\n{}", other, USAGE)
+                }
+                other if dir.is_none() => dir = Some(PathBuf::from(other)),
+                other => bail!("mais de um diretório: {}\n{}", other, USAGE),
+            }
+        }
+
+        let dir = dir.with_context(|| format!("informe o diretório\n{}", USAGE))?;
+        anyhow::ensure!(dir.is_dir(), "{} não é um diretório", dir.display());
+        Ok(Self {
+            dir,
+            recursive,
+            globs,
+            reingest,
+            report,
+            watch,
+            interval,
+        })
+    }
+
+    /// `true` se o arquivo (caminho relativo ao diretório) passa pelos filtros.
+    fn matches(&self, relative: &Path) -> bool {
+        let name = relative.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if self.globs.is_empty() {
+            return DocumentFormat::from_filename(name).is_some();
+        }
+        let path = relative.to_string_lossy().replace('\\', "/");
+        self.globs.iter().any(|glob| {
+            let target = if glob.contains('/') {
+                path.as_str()
+            } else {
+                name
+            };
+            glob_match(glob, target)
+        })
+    }
+}
+
+/// Desfecho da ingestão de um arquivo.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileStatus {
+    /// Ingerido.
+    Done,
+    /// Já registrado (ou repetido no corpus) — não reingerido.
+    Skipped,
+    /// Formato não suportado.
+    Unsupported,
+    /// Falhou (leitura, extração, LM Studio...).
+    Failed,
+    /// Cancelado (Ctrl-C); o checkpoint permite retomar.
+    Cancelled,
+}
+
+impl FileStatus {
+    /// Símbolo na saída do terminal.
+    fn symbol(&self) -> &'static str {
+        match self {
+            FileStatus::Done => "✓",
+            FileStatus::Skipped => "↷",
+            FileStatus::Unsupported => "∅",
+            FileStatus::Failed => "✗",
+            FileStatus::Cancelled => "⏸",
+        }
+    }
+
+    /// Rótulo no resumo.
+    fn label(&self) -> &'static str {
+        match self {
+            FileStatus::Done => "ingeridos",
+            FileStatus::Skipped => "pulados",
+            FileStatus::Unsupported => "não suportados",
+            FileStatus::Failed => "falharam",
+            FileStatus::Cancelled => "cancelados",
+        }
+    }
+}
+
+/// Linha do relatório: um arquivo do corpus.
+#[derive(Clone, Debug, Serialize)]
+pub struct FileReport {
+    /// Caminho do arquivo.
+    pub path: PathBuf,
+    /// SHA-256 do conteúdo (se lido).
+    pub hash: Option<String>,
+    /// Formato detectado.
+    pub format: Option<String>,
+    /// Desfecho.
+    pub status: FileStatus,
+    /// Job que processou o arquivo.
+    pub job: Option<JobId>,
+    /// Chunks aplicados / total.
+    pub chunks_done: usize,
+    /// Total de chunks.
+    pub total_chunks: usize,
+    /// Duração do job (ms).
+    pub duration_ms: Option<u64>,
+    /// Resumo da ingestão, motivo do salto ou erro.
+    pub message: String,
+    /// Quando o arquivo foi processado.
+    pub processed_at: DateTime<Utc>,
+}
+
+impl FileReport {
+    /// Arquivo que não virou job.
+    fn unprocessed(
+        path: &Path,
+        upload: Option<&DocumentUpload>,
+        status: FileStatus,
+        message: String,
+    ) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            hash: upload.map(|u| u.hash.clone()),
+            format: upload.map(|u| u.format.name().to_string()),
+            status,
+            job: None,
+            chunks_done: 0,
+            total_chunks: 0,
+            duration_ms: None,
+            message,
+            processed_at: Utc::now(),
+        }
+    }
+
+    /// Arquivo processado por um job encerrado.
+    fn from_job(path: &Path, info: &JobInfo) -> Self {
+        let status = match info.status {
+            JobStatus::Done => FileStatus::Done,
+            JobStatus::Cancelled => FileStatus::Cancelled,
+            _ => FileStatus::Failed,
+        };
+        let duration_ms = info
+            .started_at
+            .zip(info.finished_at)
+            .map(|(start, end)| (end - start).num_milliseconds().max(0) as u64);
+        Self {
+            path: path.to_path_buf(),
+            hash: Some(info.content_hash.clone()),
+            format: Some(info.format.clone()),
+            status,
+            job: Some(info.id),
+            chunks_done: info.chunks_done,
+            total_chunks: info.total_chunks,
+            duration_ms,
+            message: info.message.clone().unwrap_or_default(),
+            processed_at: info.finished_at.unwrap_or_else(Utc::now),
+        }
+    }
+}
+
+/// Ingestão em lote em andamento: KB, NLU e fila de jobs sem o servidor.
+struct BulkIngest {
+    /// Opções da linha de comando.
+    options: BulkOptions,
+    /// KB persistida.
+    kb: Arc<RwLock<KnowledgeBase>>,
+    /// Pipeline NLU (LM Studio).
+    nlu: Arc<NluPipeline>,
+    /// Fila de jobs (mesmos workers do servidor).
+    jobs: JobManager,
+    /// Relatório acumulado (todas as varreduras), uma linha por arquivo.
+    reports: Vec<FileReport>,
+    /// Arquivos já processados (ingeridos ou pulados) → data de modificação.
+    seen: HashMap<PathBuf, SystemTime>,
+    /// Arquivos que falharam, à espera da próxima tentativa.
+    retries: Retries,
+    /// Jobs simultâneos (`INGEST_WORKERS`): limite de arquivos lidos de uma vez.
+    workers: usize,
+    /// Ctrl-C recebido.
+    stop: Arc<AtomicBool>,
+}
+
+/// Roda o subcomando `ingest`.
+///
+/// Falha se o LM Studio não responde ou, sem `--watch`, se algum arquivo falhou.
+pub async fn run(options: BulkOptions) -> Result<()> {
+    let kb = persistence::load_kb()?;
+    eprintln!(
+        "KB: {} conceitos, {} links, {} documentos",
+        kb.concept_count(),
+        kb.link_count(),
+        kb.documents.list().len()
+    );
+
+    let embedder = Embedder::new(EmbedderConfig::from_env());
+    embedder
+        .health_check()
+        .await
+        .context("LM Studio inacessível — a ingestão precisa dos embeddings")?;
+    let nlu = Arc::new(NluPipeline::new(embedder).await?);
+
+    // Ninguém assina os eventos: o progresso vem do estado dos jobs
+    let (events_tx, _) = broadcast::channel(1024);
+    let stop = Arc::new(AtomicBool::new(false));
+    {
+        let stop = stop.clone();
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                stop.store(true, Ordering::Relaxed);
+            }
+        });
+    }
+
+    let config = JobConfig::from_env();
+    let mut bulk = BulkIngest {
+        kb: Arc::new(RwLock::new(kb)),
+        nlu,
+        workers: config.workers,
+        jobs: JobManager::new(config, events_tx),
+        reports: Vec::new(),
+        seen: HashMap::new(),
+        retries: Retries::default(),
+        stop,
+        options,
+    };
+
+    loop {
+        let files = bulk.scan()?;
+        if !files.is_empty() {
+            bulk.ingest(&files).await;
+            bulk.write_report()?;
+        } else if !bulk.options.watch {
+            eprintln!(
+                "Nenhum arquivo em {} corresponde aos filtros.",
+                bulk.options.dir.display()
+            );
+        }
+        if !bulk.options.watch || bulk.stopped() {
+            break;
+        }
+        let deadline = Instant::now() + bulk.options.interval;
+        while Instant::now() < deadline && !bulk.stopped() {
+            tokio::time::sleep(PROGRESS_TICK).await;
+        }
+        if bulk.stopped() {
+            break;
+        }
+    }
+
+    let failed = bulk
+        .reports
+        .iter()
+        .filter(|r| r.status == FileStatus::Failed)
+        .count();
+    if failed > 0 && !bulk.options.watch {
+        bail!(
+            "{} arquivo(s) falharam — veja {}",
+            failed,
+            bulk.options.report.display()
+        );
+    }
+    Ok(())
+}
+
+impl BulkIngest {
+    /// `true` após Ctrl-C.
+    fn stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    /// Arquivos novos, modificados ou que falharam antes (passada a espera)
+    /// e que passam pelos filtros, em ordem, com a data de modificação.
+    fn scan(&self) -> Result<Vec<(PathBuf, SystemTime)>> {
+        let mut files = Vec::new();
+        walk(&self.options.dir, self.options.recursive, &mut files)
+            .with_context(|| format!("falha ao listar {}", self.options.dir.display()))?;
+        files.retain(|path| {
+            path.strip_prefix(&self.options.dir)
+                .is_ok_and(|rel| self.options.matches(rel))
+        });
+        files.sort();
+
+        let now = SystemTime::now();
+        let mut fresh = Vec::new();
+        for path in files {
+            let Ok(modified) = std::fs::metadata(&path).and_then(|m| m.modified()) else {
+                continue;
+            };
+            let settling = now
+                .duration_since(modified)
+                .is_ok_and(|age| age < SETTLE_TIME);
+            if self.options.watch && settling {
+                continue;
+            }
+            if self.seen.get(&path) != Some(&modified)
+                && !self.retries.waiting(&path, modified, Instant::now())
+            {
+                fresh.push((path, modified));
+            }
+        }
+        Ok(fresh)
+    }
+
+    /// Lê e submete os arquivos aos poucos, acompanha os jobs e registra o
+    /// desfecho de cada um.
+    ///
+    /// No máximo um job por worker fica na fila: os demais arquivos só são
+    /// lidos quando um job encerra. Ctrl-C cancela os jobs em execução e
+    /// encerra sem ler o resto. Arquivos que falharam ou foram cancelados
+    /// não ficam em `seen` — o `--watch` tenta de novo numa próxima
+    /// varredura (os que falharam, após a espera de [`Retries`]).
+    async fn ingest(&mut self, files: &[(PathBuf, SystemTime)]) {
+        let checkpoints = CheckpointStore::default();
+        let mut outcomes: Vec<(PathBuf, FileStatus)> = Vec::new();
+        let mut queue: VecDeque<&Path> = files.iter().map(|(path, _)| path.as_path()).collect();
+        let mut pending: Vec<(PathBuf, JobId)> = Vec::new();
+        let mut hashes: HashMap<String, PathBuf> = HashMap::new();
+        let interactive = std::io::stderr().is_terminal();
+        let mut cancelling = false;
+
+        while !pending.is_empty() || (!queue.is_empty() && !self.stopped()) {
+            if self.stopped() && !cancelling {
+                cancelling = true;
+                clear_line(interactive);
+                eprintln!("Cancelando {} job(s)...", pending.len());
+                for (_, id) in &pending {
+                    self.jobs.cancel(*id);
+                }
+            }
+
+            while pending.len() < self.workers && !self.stopped() {
+                let Some(path) = queue.pop_front() else {
+                    break;
+                };
+                if let Some(report) = self
+                    .read(path, &checkpoints, &mut hashes, &mut pending)
+                    .await
+                {
+                    outcomes.push((path.to_path_buf(), self.finish(report)));
+                }
+            }
+
+            let mut still_running = Vec::new();
+            for (path, id) in pending {
+                match self.jobs.get(id) {
+                    Some(info) if info.status.is_finished() => {
+                        clear_line(interactive);
+                        let status = self.finish(FileReport::from_job(&path, &info));
+                        outcomes.push((path, status));
+                    }
+                    Some(_) => still_running.push((path, id)),
+                    None => {}
+                }
+            }
+            pending = still_running;
+
+            if pending.is_empty() {
+                continue;
+            }
+            if interactive {
+                let done = outcomes.len();
+                let running: Vec<JobInfo> = pending
+                    .iter()
+                    .filter_map(|(_, id)| self.jobs.get(*id))
+                    .collect();
+                eprint!("\r\x1b[2K{}", progress_line(done, files.len(), &running));
+                let _ = std::io::stderr().flush();
+            }
+            tokio::time::sleep(PROGRESS_TICK).await;
+        }
+        clear_line(interactive);
+
+        for path in queue {
+            let message = "interrompido antes de começar".to_string();
+            self.finish(FileReport::unprocessed(
+                path,
+                None,
+                FileStatus::Cancelled,
+                message,
+            ));
+        }
+        let now = Instant::now();
+        for (path, status) in outcomes {
+            let Some(&(_, modified)) = files.iter().find(|(file, _)| *file == path) else {
+                continue;
+            };
+            match status {
+                FileStatus::Failed => {
+                    self.retries
+                        .failed(&path, modified, now, self.options.interval)
+                }
+                FileStatus::Cancelled => {}
+                _ => {
+                    self.retries.clear(&path);
+                    self.seen.insert(path, modified);
+                }
+            }
+        }
+    }
+
+    /// Lê um arquivo e o submete, ou devolve o relatório de por que não foi
+    /// submetido.
+    async fn read(
+        &self,
+        path: &Path,
+        checkpoints: &CheckpointStore,
+        hashes: &mut HashMap<String, PathBuf>,
+        pending: &mut Vec<(PathBuf, JobId)>,
+    ) -> Option<FileReport> {
+        let bytes = match tokio::fs::read(path).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                let message = format!("falha ao ler: {}", e);
+                return Some(FileReport::unprocessed(
+                    path,
+                    None,
+                    FileStatus::Failed,
+                    message,
+                ));
+            }
+        };
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        let Some(format) = DocumentFormat::detect(&bytes, None, Some(name)) else {
+            let message = "formato não suportado".to_string();
+            return Some(FileReport::unprocessed(
+                path,
+                None,
+                FileStatus::Unsupported,
+                message,
+            ));
+        };
+        let upload = DocumentUpload::new(name.to_string(), format, bytes);
+        self.submit(path, upload, checkpoints, hashes, pending)
+    }
+
+    /// Submete um upload, ou devolve o relatório de por que não foi submetido.
+    fn submit(
+        &self,
+        path: &Path,
+        upload: DocumentUpload,
+        checkpoints: &CheckpointStore,
+        hashes: &mut HashMap<String, PathBuf>,
+        pending: &mut Vec<(PathBuf, JobId)>,
+    ) -> Option<FileReport> {
+        if let Some(first) = hashes.get(&upload.hash) {
+            let message = format!("mesmo conteúdo que {}", first.display());
+            return Some(FileReport::unprocessed(
+                path,
+                Some(&upload),
+                FileStatus::Skipped,
+                message,
+            ));
+        }
+        hashes.insert(upload.hash.clone(), path.to_path_buf());
+
+        // Como no /upload: checkpoint retoma (--reingest recomeça); registro só reingere se pedido
+        let interrupted = checkpoints.load(&upload.hash).ok().flatten().is_some();
+        if self.options.reingest && interrupted {
+            if let Err(e) = checkpoints.remove(&upload.hash) {
+                tracing::warn!(error = %e, path = %path.display(), "Falha ao descartar checkpoint na reingestão");
+            }
+        }
+        if !self.options.reingest && !interrupted {
+            if let Some(record) = self.kb.read().documents.get(&upload.hash) {
+                let message = format!(
+                    "já lido em {} UTC",
+                    record.ingested_at.format("%d/%m/%Y %H:%M")
+                );
+                return Some(FileReport::unprocessed(
+                    path,
+                    Some(&upload),
+                    FileStatus::Skipped,
+                    message,
+                ));
+            }
+        }
+
+        let id = self.jobs.submit(upload, self.nlu.clone(), self.kb.clone());
+        pending.push((path.to_path_buf(), id));
+        None
+    }
+
+    /// Registra o desfecho de um arquivo e imprime sua linha.
+    fn finish(&mut self, report: FileReport) -> FileStatus {
+        let detail = match report.status {
+            FileStatus::Done | FileStatus::Cancelled => format!(
+                "{}/{} chunks{}",
+                report.chunks_done,
+                report.total_chunks,
+                report
+                    .duration_ms
+                    .map(|ms| format!(", {:.1}s", ms as f64 / 1000.0))
+                    .unwrap_or_default()
+            ),
+            _ => report.message.clone(),
+        };
+        eprintln!(
+            "{} {} — {}",
+            report.status.symbol(),
+            report.path.display(),
+            detail
+        );
+        let status = report.status;
+        record_report(&mut self.reports, report);
+        status
+    }
+
+    /// Grava o relatório acumulado e imprime o resumo.
+    fn write_report(&self) -> Result<()> {
+        let json =
+            serde_json::to_vec_pretty(&self.reports).context("falha ao serializar relatório")?;
+        crate::checkpoint::write_atomic(&self.options.report, &json)
+            .with_context(|| format!("falha ao gravar {}", self.options.report.display()))?;
+
+        let mut counts: Vec<(FileStatus, usize)> = Vec::new();
+        for report in &self.reports {
+            match counts
+                .iter_mut()
+                .find(|(status, _)| *status == report.status)
+            {
+                Some((_, n)) => *n += 1,
+                None => counts.push((report.status, 1)),
+            }
+        }
+        let summary: Vec<String> = counts
+            .iter()
+            .map(|(status, n)| format!("{} {}", n, status.label()))
+            .collect();
+        let kb = self.kb.read();
+        eprintln!(
+            "{} arquivos: {} · KB: {} conceitos, {} links · relatório em {}",
+            self.reports.len(),
+            summary.join(", "),
+            kb.concept_count(),
+            kb.link_count(),
+            self.options.report.display()
+        );
+        Ok(())
+    }
+}
+
+/// Registra a linha de um arquivo no relatório, substituindo a de uma
+/// tentativa anterior: no `--watch`, um arquivo que falha de novo não
+/// acumula linhas.
+fn record_report(reports: &mut Vec<FileReport>, report: FileReport) {
+    match reports.iter_mut().find(|r| r.path == report.path) {
+        Some(previous) => *previous = report,
+        None => reports.push(report),
+    }
+}
+
+/// Novas tentativas de arquivos que falharam, com espera crescente.
+#[derive(Debug, Default)]
+struct Retries {
+    /// Arquivo → data de modificação na falha, falhas seguidas e próxima tentativa.
+    pending: HashMap<PathBuf, (SystemTime, u32, Instant)>,
+}
+
+impl Retries {
+    /// Registra uma falha: a próxima tentativa espera `interval × 2^(falhas - 1)`,
+    /// até [`MAX_RETRY_DELAY`].
+    fn failed(&mut self, path: &Path, modified: SystemTime, now: Instant, interval: Duration) {
+        let failures = match self.pending.get(path) {
+            Some(&(previous, failures, _)) if previous == modified => failures + 1,
+            _ => 1,
+        };
+        let delay = interval
+            .saturating_mul(1 << (failures - 1).min(16))
+            .min(MAX_RETRY_DELAY);
+        self.pending
+            .insert(path.to_path_buf(), (modified, failures, now + delay));
+    }
+
+    /// `true` se o arquivo falhou e ainda espera — a menos que tenha sido modificado.
+    fn waiting(&self, path: &Path, modified: SystemTime, now: Instant) -> bool {
+        self.pending
+            .get(path)
+            .is_some_and(|&(previous, _, retry_at)| previous == modified && now < retry_at)
+    }
+
+    /// Esquece as falhas de um arquivo que passou.
+    fn clear(&mut self, path: &Path) {
+        self.pending.remove(path);
+    }
+}
+
+/// Linha da barra de progresso: arquivos encerrados + fração dos jobs em execução.
+fn progress_line(done: usize, total: usize, running: &[JobInfo]) -> String {
+    let partial: f64 = running.iter().map(JobInfo::progress).sum();
+    let fraction = ((done as f64 + partial) / total.max(1) as f64).clamp(0.0, 1.0);
+    let filled = (fraction * BAR_WIDTH as f64).round() as usize;
+    let current = running
+        .iter()
+        .find(|j| j.status == JobStatus::Running)
+        .map(|j| {
+            let pages = if j.extracted || j.total_pages == 0 {
+                String::new()
+            } else {
+                format!(" (p. {}/{})", j.pages_done, j.total_pages)
+            };
+            format!(
+                " · {} {}/{}{}",
+                j.filename, j.chunks_done, j.total_chunks, pages
+            )
+        })
+        .unwrap_or_default();
+    format!(
+        "[{}{}] {:>3.0}% {}/{} arquivos{}",
+        "█".repeat(filled),
+        "░".repeat(BAR_WIDTH - filled),
+        fraction * 100.0,
+        done,
+        total,
+        current
+    )
+}
+
+/// Apaga a barra de progresso antes de imprimir uma linha.
+fn clear_line(interactive: bool) {
+    if interactive {
+        eprint!("\r\x1b[2K");
+    }
+}
+
+/// Coleta os arquivos do diretório (e subdiretórios, se `recursive`), sem ocultos.
+fn walk(dir: &Path, recursive: bool, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let kind = entry.file_type()?;
+        if kind.is_dir() {
+            if recursive {
+                walk(&path, recursive, files)?;
+            }
+        } else if kind.is_file() || (kind.is_symlink() && path.is_file()) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Casa um padrão glob (`*` = qualquer sequência, `?` = um caractere), sem diferenciar maiúsculas.
+///
+/// `*` não atravessa `/`: `docs/*.pdf` não casa `docs/a/b.pdf`.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some('?') if text[t] != '/' => {
+                p += 1;
+                t += 1;
+            }
+            Some(&c) if c != '?' && c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, from)) if text[from] != '/' => {
+                    backtrack = Some((star, from + 1));
+                    p = star + 1;
+                    t = from + 1;
+                }
+                _ => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_matches_names_and_relative_paths() {
+        assert!(glob_match("*.pdf", "Relatório Final.PDF"));
+        assert!(glob_match("cap-??.md", "cap-03.md"));
+        assert!(!glob_match("cap-??.md", "cap-3.md"));
+        assert!(glob_match("docs/*.pdf", "docs/a.pdf"));
+        assert!(!glob_match("docs/*.pdf", "docs/sub/a.pdf"));
+        assert!(glob_match("*/*", "docs/a.pdf"));
+
+        let options = BulkOptions {
+            dir: PathBuf::from("."),
+            recursive: true,
+            globs: Vec::new(),
+            reingest: false,
+            report: PathBuf::from(DEFAULT_REPORT),
+            watch: false,
+            interval: Duration::from_secs(5),
+        };
+        assert!(options.matches(Path::new("sub/notas.md")));
+        assert!(!options.matches(Path::new("sub/foto.png")));
+    }
+
+    #[test]
+    fn failing_files_keep_one_report_row_and_back_off() {
+        let path = Path::new("corpus/quebrado.pdf");
+        let mut reports = Vec::new();
+        for attempt in 0..3 {
+            let message = format!("tentativa {}", attempt);
+            record_report(
+                &mut reports,
+                FileReport::unprocessed(path, None, FileStatus::Failed, message),
+            );
+        }
+        record_report(
+            &mut reports,
+            FileReport::unprocessed(
+                Path::new("corpus/ok.md"),
+                None,
+                FileStatus::Skipped,
+                String::new(),
+            ),
+        );
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].message, "tentativa 2");
+
+        let (interval, start) = (Duration::from_secs(5), Instant::now());
+        let modified = SystemTime::UNIX_EPOCH;
+        let mut retries = Retries::default();
+        retries.failed(path, modified, start, interval);
+        assert!(retries.waiting(path, modified, start + Duration::from_secs(4)));
+        assert!(!retries.waiting(path, modified, start + Duration::from_secs(5)));
+
+        // Segunda falha seguida: espera o dobro; o arquivo modificado não espera
+        retries.failed(path, modified, start, interval);
+        assert!(retries.waiting(path, modified, start + Duration::from_secs(9)));
+        assert!(!retries.waiting(path, modified + Duration::from_secs(1), start));
+        for _ in 0..20 {
+            retries.failed(path, modified, start, interval);
+        }
+        assert!(!retries.waiting(path, modified, start + MAX_RETRY_DELAY));
+
+        retries.clear(path);
+        assert!(!retries.waiting(path, modified, start));
+    }
+}
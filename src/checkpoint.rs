@@ -0,0 +1,330 @@
+//! # Checkpoints de Ingestão — Retomar de Onde Parou
+//!
+//! Se o processo morre ou o LM Studio cai no meio de um documento, os
+//! chunks já aplicados ficam na KB, mas nada dizia onde a ingestão parou:
+//! reenviar o arquivo recomeçava do zero e reforçava tudo duas vezes.
+//! Agora cada job grava um [`Checkpoint`] logo depois de **salvar a KB**
+//! (a cada `KB_SAVE_INTERVAL_SECS`, e ao cancelar ou falhar):
+//!
+//! ```text
+//! data/checkpoints/
+//!   ├── {hash}.json   progresso: próximo chunk, totais, config de chunking
+//!   └── {hash}.bin    bytes do documento (para retomar sem novo upload)
+//!
+//! chunks 0..4 ─▶ aplica (índices na KB) ─▶ save_kb ─▶ checkpoint(next_chunk = 5)
+//! chunks 5..7 ─▶ aplica (índices na KB) ─▶ save_kb de outro job
+//!    ✗ (crash, timeout, cancelamento)
+//! retomar ─▶ extrai e chunka de novo (mesma config) ─▶ pula os chunks 0..7 da KB
+//! ```
+//!
+//! O checkpoint guarda o que a retomada precisa (bytes, config de
+//! chunking, totais), mas os chunks pulados vêm da KB carregada
+//! ([`crate::core::DocumentRegistry::applied_chunks`]): os índices são
+//! salvos junto com a evidência de cada chunk, então a retomada é
+//! idempotente mesmo que a KB tenha sido salva depois do checkpoint —
+//! nenhum chunk é revisado duas vezes nem pulado. Ambas as escritas são
+//! atômicas (arquivo temporário + `rename`), e os salvamentos da KB são
+//! ordenados entre jobs ([`crate::persistence::save_kb`]).
+//!
+//! O checkpoint é removido quando o job conclui; jobs cancelados ou que
+//! falharam mantêm o seu, inclusive após reiniciar o servidor com a KB
+//! restaurada (`KB_RESTORE=1`). Sem restauração a KB começa vazia e a
+//! inicialização descarta todos; reenviar com "Reingerir" também
+//! descarta o checkpoint e recomeça do chunk 0. A retomada recusa um
+//! checkpoint gravado por outra versão do extrator (os chunks seriam
+//! outros).
+//!
+//! | Rota | Efeito |
+//! |------|--------|
+//! | `POST /documents/{hash}/resume` | Enfileira a retomada de um documento interrompido |
+//! | `POST /upload` (mesmo conteúdo) | Retoma em vez de recomeçar |
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::document::chunk::ChunkConfig;
+use crate::document::{self, DocumentFormat};
+use crate::ingest::DocumentUpload;
+
+/// Diretório dos checkpoints (relativo à raiz do projeto).
+const CHECKPOINT_DIR: &str = "data/checkpoints";
+
+/// Progresso de uma ingestão interrompível.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// SHA-256 do conteúdo (chave do checkpoint).
+    pub hash: String,
+    /// Nome do arquivo enviado.
+    pub filename: String,
+    /// Formato do documento ([`DocumentFormat::name`]).
+    pub format: String,
+    /// Versão do extrator que gerou os chunks.
+    pub extractor_version: u32,
+    /// Caracteres por chunk usados na primeira execução.
+    pub chunk_size: usize,
+    /// Sobreposição usada na primeira execução.
+    pub chunk_overlap: usize,
+    /// Total de chunks do documento (0 até a extração terminar).
+    pub total_chunks: usize,
+    /// Índice (0-indexed) do primeiro chunk ainda não aplicado.
+    pub next_chunk: usize,
+    /// Conceitos criados até aqui.
+    pub new_concepts: usize,
+    /// Links criados até aqui.
+    pub new_links: usize,
+    /// Início da primeira execução.
+    pub started_at: DateTime<Utc>,
+    /// Última gravação.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Checkpoint {
+    /// Checkpoint inicial (nenhum chunk aplicado).
+    pub fn new(
+        upload: &DocumentUpload,
+        extractor_version: u32,
+        config: &ChunkConfig,
+        total_chunks: usize,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            hash: upload.hash.clone(),
+            filename: upload.filename.clone(),
+            format: upload.format.name().to_string(),
+            extractor_version,
+            chunk_size: config.max_chars,
+            chunk_overlap: config.overlap_chars,
+            total_chunks,
+            next_chunk: 0,
+            new_concepts: 0,
+            new_links: 0,
+            started_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Configuração de chunking da primeira execução (a retomada repete os mesmos chunks).
+    pub fn chunk_config(&self) -> ChunkConfig {
+        ChunkConfig {
+            max_chars: self.chunk_size,
+            overlap_chars: self.chunk_overlap,
+        }
+    }
+
+    /// Registra um chunk aplicado.
+    pub fn advance(&mut self, chunk_index: usize, new_concepts: usize, new_links: usize) {
+        self.next_chunk = chunk_index + 1;
+        self.new_concepts += new_concepts;
+        self.new_links += new_links;
+        self.updated_at = Utc::now();
+    }
+}
+
+/// Armazenamento de checkpoints em disco (um par `.json`/`.bin` por documento).
+#[derive(Clone, Debug)]
+pub struct CheckpointStore {
+    /// Diretório dos arquivos.
+    dir: PathBuf,
+}
+
+impl Default for CheckpointStore {
+    fn default() -> Self {
+        Self::new(CHECKPOINT_DIR)
+    }
+}
+
+impl CheckpointStore {
+    /// Armazenamento em `dir` (criado na primeira gravação).
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Guarda os bytes do documento para uma retomada sem novo upload.
+    pub fn store_document(&self, upload: &DocumentUpload) -> Result<()> {
+        write_atomic(&self.path(&upload.hash, "bin")?, &upload.bytes)
+    }
+
+    /// Grava o progresso.
+    pub fn save(&self, checkpoint: &Checkpoint) -> Result<()> {
+        let json =
+            serde_json::to_vec_pretty(checkpoint).context("Falha ao serializar checkpoint")?;
+        write_atomic(&self.path(&checkpoint.hash, "json")?, &json)
+    }
+
+    /// Checkpoint de um documento, se houver.
+    pub fn load(&self, hash: &str) -> Result<Option<Checkpoint>> {
+        let path = self.path(hash, "json")?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json =
+            std::fs::read(&path).with_context(|| format!("Falha ao ler {}", path.display()))?;
+        let checkpoint = serde_json::from_slice(&json)
+            .with_context(|| format!("Checkpoint corrompido: {}", path.display()))?;
+        Ok(Some(checkpoint))
+    }
+
+    /// Reconstrói o upload de um documento interrompido (checkpoint + bytes guardados).
+    pub fn load_upload(&self, hash: &str) -> Result<Option<DocumentUpload>> {
+        let Some(checkpoint) = self.load(hash)? else {
+            return Ok(None);
+        };
+        let format = DocumentFormat::from_name(&checkpoint.format).with_context(|| {
+            format!("Formato desconhecido no checkpoint: {}", checkpoint.format)
+        })?;
+        let path = self.path(hash, "bin")?;
+        let bytes =
+            std::fs::read(&path).with_context(|| format!("Falha ao ler {}", path.display()))?;
+        let upload = DocumentUpload::new(checkpoint.filename, format, bytes);
+        anyhow::ensure!(
+            upload.hash == hash,
+            "Bytes guardados não correspondem ao checkpoint {}",
+            hash
+        );
+        Ok(Some(upload))
+    }
+
+    /// Checkpoints existentes, os mais recentes primeiro. Arquivos ilegíveis são ignorados.
+    pub fn list(&self) -> Vec<Checkpoint> {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut checkpoints: Vec<Checkpoint> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+                let hash = path.file_stem()?.to_str()?.to_string();
+                (path.extension()? == "json" && document::is_content_hash(&hash)).then_some(hash)
+            })
+            .filter_map(|hash| match self.load(&hash) {
+                Ok(checkpoint) => checkpoint,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Checkpoint ignorado");
+                    None
+                }
+            })
+            .collect();
+        checkpoints.sort_by_key(|c| std::cmp::Reverse(c.updated_at));
+        checkpoints
+    }
+
+    /// Remove o checkpoint e os bytes de um documento. `true` se existia.
+    pub fn remove(&self, hash: &str) -> Result<bool> {
+        let mut removed = false;
+        for ext in ["json", "bin"] {
+            let path = self.path(hash, ext)?;
+            if path.exists() {
+                std::fs::remove_file(&path)
+                    .with_context(|| format!("Falha ao remover {}", path.display()))?;
+                removed = true;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Remove todos os checkpoints (reset da KB). Devolve quantos havia.
+    pub fn clear(&self) -> Result<usize> {
+        let checkpoints = self.list();
+        for checkpoint in &checkpoints {
+            self.remove(&checkpoint.hash)?;
+        }
+        Ok(checkpoints.len())
+    }
+
+    /// `{dir}/{hash}.{ext}`. Recusa o que não for um hash de conteúdo:
+    /// o hash vem da URL, e `../kb` apontaria para fora do diretório.
+    fn path(&self, hash: &str, ext: &str) -> Result<PathBuf> {
+        anyhow::ensure!(
+            document::is_content_hash(hash),
+            "Hash de documento inválido: {:?}",
+            hash
+        );
+        Ok(self.dir.join(format!("{}.{}", hash, ext)))
+    }
+}
+
+/// Escreve num arquivo temporário ao lado e renomeia: um crash no meio
+/// não corrompe o arquivo anterior.
+///
+/// O nome temporário é único por escrita — dois jobs salvando a KB ao
+/// mesmo tempo não escrevem no mesmo `.tmp`.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Falha ao criar {}", parent.display()))?;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".{}.tmp", uuid::Uuid::new_v4().simple()));
+    let tmp = PathBuf::from(tmp);
+    std::fs::write(&tmp, contents)
+        .with_context(|| format!("Falha ao escrever {}", tmp.display()))?;
+    std::fs::rename(&tmp, path).with_context(|| format!("Falha ao renomear {}", tmp.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkpoint_round_trips_with_document_bytes() {
+        let store = CheckpointStore::new(
+            std::env::temp_dir().join(format!("checkpoints-{}", uuid::Uuid::new_v4())),
+        );
+        let upload = DocumentUpload::new(
+            "notas.md".into(),
+            DocumentFormat::Markdown,
+            b"# Solo\n\nA chuva molha o solo.".to_vec(),
+        );
+        let mut checkpoint = Checkpoint::new(
+            &upload,
+            1,
+            &ChunkConfig {
+                max_chars: 300,
+                overlap_chars: 20,
+            },
+            4,
+        );
+        checkpoint.advance(1, 3, 2);
+
+        store.store_document(&upload).unwrap();
+        store.save(&checkpoint).unwrap();
+
+        let loaded = store.load(&upload.hash).unwrap().expect("checkpoint");
+        assert_eq!(
+            (loaded.next_chunk, loaded.new_concepts, loaded.new_links),
+            (2, 3, 2)
+        );
+        assert_eq!(loaded.chunk_config().max_chars, 300);
+        let resumed = store.load_upload(&upload.hash).unwrap().expect("upload");
+        assert_eq!(
+            (resumed.format, resumed.bytes),
+            (DocumentFormat::Markdown, upload.bytes.clone())
+        );
+        assert_eq!(store.list().len(), 1);
+
+        assert!(store.remove(&upload.hash).unwrap());
+        assert!(store.load(&upload.hash).unwrap().is_none());
+        assert!(store.list().is_empty());
+        let _ = std::fs::remove_dir_all(&store.dir);
+    }
+
+    #[test]
+    fn rejects_hashes_that_leave_the_directory() {
+        let root = std::env::temp_dir().join(format!("checkpoints-{}", uuid::Uuid::new_v4()));
+        let store = CheckpointStore::new(root.join("checkpoints"));
+        std::fs::create_dir_all(&store.dir).unwrap();
+        std::fs::write(root.join("kb.json"), b"{}").unwrap();
+
+        // `DELETE /documents/..%2Fkb` chega decodificado como `../kb`
+        for hash in ["../kb", "..%2Fkb", &"A".repeat(64)] {
+            assert!(store.load(hash).is_err());
+            assert!(store.remove(hash).is_err());
+        }
+        assert!(root.join("kb.json").exists());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
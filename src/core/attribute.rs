@@ -0,0 +1,102 @@
+//! # Attribute — Valores Numéricos, Datas e Quantidades de um Conceito
+//!
+//! Frases como "o lote 42 atrasou 3 dias em março de 2024" carregam
+//! **valores** que não são conceitos: ninguém quer um nó "42" no grafo.
+//! Um [`Attribute`] guarda esses valores tipados no próprio conceito ao
+//! qual se referem (extraídos por [`crate::nlu::quantity`]).
+//!
+//! | Tipo | Exemplo | `value` | `unit` | `date` |
+//! |------|---------|---------|--------|--------|
+//! | [`Number`](ValueKind::Number) | "lote 42" | 42 | — | — |
+//! | [`Quantity`](ValueKind::Quantity) | "3 dias" | 3 | dias | — |
+//! | [`Percentage`](ValueKind::Percentage) | "30%" | 30 | % | — |
+//! | [`Money`](ValueKind::Money) | "R$ 1.500,00" | 1500 | BRL | — |
+//! | [`Date`](ValueKind::Date) | "março de 2024" | — | — | 2024-03 |
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::Source;
+
+/// Tipo de valor de um [`Attribute`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueKind {
+    /// Número sem unidade.
+    Number,
+    /// Número com unidade de medida ou contagem ("3 dias", "12 km").
+    Quantity,
+    /// Percentual.
+    Percentage,
+    /// Valor monetário (unidade = código da moeda).
+    Money,
+    /// Data ou período (ISO 8601, possivelmente parcial).
+    Date,
+}
+
+impl ValueKind {
+    /// Identificador estável (serde e filtros da API).
+    pub fn name(&self) -> &'static str {
+        match self {
+            ValueKind::Number => "number",
+            ValueKind::Quantity => "quantity",
+            ValueKind::Percentage => "percentage",
+            ValueKind::Money => "money",
+            ValueKind::Date => "date",
+        }
+    }
+
+    /// Interpreta o identificador de [`name()`](Self::name).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "number" => Some(ValueKind::Number),
+            "quantity" => Some(ValueKind::Quantity),
+            "percentage" => Some(ValueKind::Percentage),
+            "money" => Some(ValueKind::Money),
+            "date" => Some(ValueKind::Date),
+            _ => None,
+        }
+    }
+}
+
+/// Valor tipado associado a um conceito.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Attribute {
+    /// Tipo do valor.
+    pub kind: ValueKind,
+    /// Valor numérico normalizado (`None` para datas).
+    pub value: Option<f64>,
+    /// Unidade ("dias", "km", "%", "BRL").
+    pub unit: Option<String>,
+    /// Data ISO 8601 — `2024`, `2024-03` ou `2024-03-12`.
+    pub date: Option<String>,
+    /// Trecho original do texto ("R$ 1.500,00").
+    pub text: String,
+    /// Quando o valor foi registrado.
+    pub recorded_at: DateTime<Utc>,
+    /// De onde veio o valor (`None` em KBs antigas).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<Source>,
+}
+
+impl Attribute {
+    /// Descrição curta para o chat (`3 dias`, `2024-03`).
+    pub fn describe(&self) -> String {
+        match (&self.date, self.value, &self.unit) {
+            (Some(date), _, _) => date.clone(),
+            (None, Some(value), Some(unit)) if unit == "%" => format!("{}%", format_number(value)),
+            (None, Some(value), Some(unit)) => format!("{} {}", format_number(value), unit),
+            (None, Some(value), None) => format_number(value),
+            _ => self.text.clone(),
+        }
+    }
+}
+
+/// Formata sem casas decimais supérfluas (`42`, `3.5`).
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{}", value)
+    }
+}
@@ -23,6 +23,11 @@
 //! | `state` | [ConceptState] | Ciclo de vida atual |
 //! | `embedding` | Option<Vec<f32>> | Vetor de embeddings BERTimbau (768 dimensões) |
 //! | `mention_count` | u32 | Quantas vezes foi mencionado pelo usuário |
+//! | `language` | Option<String> | Idioma de origem (`pt-BR`, `en`) |
+//! | `entity_type` | Option<[EntityType]> | Tipo de entidade (pessoa, lugar, data...) |
+//! | `aliases` | Vec<String> | Siglas e nomes alternativos ("IA") |
+//! | `attributes` | Vec<[Attribute]> | Valores tipados (números, datas, quantidades) |
+//! | `review` | Option<[ReviewSchedule]> | Agenda de revisão espaçada |
 //!
 //! ## Exemplo de Uso
 //!
@@ -49,7 +54,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::TruthValue;
+use super::{Attribute, Provenance, ReviewSchedule, TruthValue};
 
 /// Alias de tipo para o identificador de um [Concept].
 ///
@@ -111,6 +116,88 @@ impl ConceptState {
     }
 }
 
+/// Tipo de entidade nomeada de um conceito.
+///
+/// Atribuído pelo NLU ([`crate::nlu::entity_type`]) na criação do
+/// conceito. Exceto [`Notion`](EntityType::Notion), cada tipo tem um
+/// **conceito-tipo** na KB ("Pessoa", "Lugar"...) ligado às instâncias
+/// por `InstanceOf`.
+///
+/// | Tipo | Label | Exemplo |
+/// |------|-------|---------|
+/// | `Person` | Pessoa | "Carlos", "Dr. Silva" |
+/// | `Organization` | Organização | "Universidade de São Paulo" |
+/// | `Place` | Lugar | "São Paulo", "Lisboa" |
+/// | `Date` | Data | "1998", "março de 2020" |
+/// | `Quantity` | Quantidade | "30%", "12 km" |
+/// | `Document` | Documento | Um documento ingerido (metadados do arquivo) |
+/// | `Notion` | Noção | "sustentabilidade" |
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityType {
+    /// Pessoa (prenome conhecido ou título como "Dr.").
+    Person,
+    /// Organização (universidade, empresa, instituto...).
+    Organization,
+    /// Lugar geográfico.
+    Place,
+    /// Data ou período.
+    Date,
+    /// Quantidade numérica (com ou sem unidade).
+    Quantity,
+    /// Documento ingerido ([`crate::nlu::structure`]).
+    Document,
+    /// Noção abstrata — o tipo padrão.
+    Notion,
+}
+
+impl EntityType {
+    /// Todos os tipos, na ordem de exibição.
+    pub const ALL: [EntityType; 7] = [
+        EntityType::Person,
+        EntityType::Organization,
+        EntityType::Place,
+        EntityType::Date,
+        EntityType::Quantity,
+        EntityType::Document,
+        EntityType::Notion,
+    ];
+
+    /// Label em PT-BR — também o label do conceito-tipo na KB.
+    pub fn label(&self) -> &'static str {
+        match self {
+            EntityType::Person => "Pessoa",
+            EntityType::Organization => "Organização",
+            EntityType::Place => "Lugar",
+            EntityType::Date => "Data",
+            EntityType::Quantity => "Quantidade",
+            EntityType::Document => "Documento",
+            EntityType::Notion => "Noção",
+        }
+    }
+
+    /// Identificador estável (serde, classes CSS e filtros do grafo).
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            EntityType::Person => "person",
+            EntityType::Organization => "organization",
+            EntityType::Place => "place",
+            EntityType::Date => "date",
+            EntityType::Quantity => "quantity",
+            EntityType::Document => "document",
+            EntityType::Notion => "notion",
+        }
+    }
+
+    /// Interpreta um identificador (`person`) ou label (`Pessoa`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|t| t.css_class() == name || t.label().to_lowercase() == name)
+    }
+}
+
 /// Unidade atômica de conhecimento no Cultivo Epistêmico.
 ///
 /// Cada conceito é uma "semente plantada" no jardim de conhecimento.
@@ -174,6 +261,46 @@ pub struct Concept {
     /// Timestamp da última vez que o conceito foi mencionado pelo usuário.
     /// Atualizado por `reinforce()`.
     pub last_mentioned: DateTime<Utc>,
+
+    /// Chave canônica (radicais RSLP do label) — veja [`crate::nlu::stemmer`].
+    ///
+    /// Variantes morfológicas ("projeto"/"projetos") compartilham a chave e
+    /// são fundidas antes da comparação por embedding. Vazia em KBs antigas
+    /// até o recálculo no carregamento.
+    #[serde(default)]
+    pub key: String,
+
+    /// Código do idioma em que o conceito foi extraído (`pt-BR`, `en`) —
+    /// veja [`crate::nlu::language`]. `None` em KBs antigas.
+    #[serde(default)]
+    pub language: Option<String>,
+
+    /// Tipo de entidade nomeada. `None` em KBs antigas e nos próprios
+    /// conceitos-tipo.
+    #[serde(default)]
+    pub entity_type: Option<EntityType>,
+
+    /// Nomes alternativos do conceito — siglas detectadas em padrões
+    /// definicionais ("Inteligência Artificial (IA)") ou adicionados pela
+    /// interface. Considerados por `KnowledgeBase::find_concept_by_label`.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+
+    /// Valores tipados mencionados junto ao conceito ("lote 42",
+    /// "atrasou 3 dias"), uma entrada por fonte. Veja [`Attribute`] e
+    /// [`Concept::distinct_attributes`].
+    #[serde(default)]
+    pub attributes: Vec<Attribute>,
+
+    /// Agenda de revisão espaçada — `None` até a primeira revisão na
+    /// página `/revisao`. Veja [`crate::core::review`].
+    #[serde(default)]
+    pub review: Option<ReviewSchedule>,
+
+    /// Menções por fonte (conversa ou documento). Vazia em KBs antigas.
+    /// Veja [`crate::core::provenance`].
+    #[serde(default, skip_serializing_if = "Provenance::is_empty")]
+    pub provenance: Provenance,
 }
 
 impl Concept {
@@ -202,7 +329,40 @@ impl Concept {
             mention_count: 1,
             created_at: now,
             last_mentioned: now,
+            key: String::new(),
+            language: None,
+            entity_type: None,
+            aliases: Vec::new(),
+            attributes: Vec::new(),
+            review: None,
+            provenance: Provenance::default(),
+        }
+    }
+
+    /// `true` se `name` é o label ou um dos aliases (case-insensitive).
+    pub fn is_named(&self, name: &str) -> bool {
+        let name = name.trim().to_lowercase();
+        self.label.to_lowercase() == name || self.aliases.iter().any(|a| a.to_lowercase() == name)
+    }
+
+    /// Adiciona um alias. Retorna `false` se vazio ou se já nomeia o conceito.
+    pub fn add_alias(&mut self, alias: &str) -> bool {
+        let alias = alias.trim();
+        if alias.is_empty() || self.is_named(alias) {
+            return false;
         }
+        self.aliases.push(alias.to_string());
+        true
+    }
+
+    /// Atributos sem repetir o mesmo valor citado por fontes diferentes.
+    pub fn distinct_attributes(&self) -> impl Iterator<Item = &Attribute> {
+        self.attributes.iter().enumerate().filter_map(|(i, a)| {
+            let repeated = self.attributes[..i]
+                .iter()
+                .any(|b| b.kind == a.kind && b.text == a.text);
+            (!repeated).then_some(a)
+        })
     }
 
     /// Reforça o conceito — chamado cada vez que o usuário menciona este conceito.
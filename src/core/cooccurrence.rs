@@ -0,0 +1,234 @@
+//! # Estatísticas de Coocorrência — Quem Aparece com Quem
+//!
+//! Os links extraídos do texto nascem da **coocorrência** de conceitos
+//! numa mesma janela de sentenças ([`crate::nlu::cooccurrence`]). Para que
+//! a verdade de um link reflita o corpus inteiro — e não só o chunk em que
+//! o par apareceu —, a KB acumula três contagens:
+//!
+//! | Contagem | Significado |
+//! |----------|-------------|
+//! | `N` | Janelas observadas (com ou sem conceitos) |
+//! | `n(a)` | Janelas em que o conceito `a` apareceu |
+//! | `n(a,b)` | Janelas em que `a` e `b` apareceram juntos |
+//!
+//! Delas saem as medidas de associação:
+//!
+//! ```text
+//! NPMI(a,b) = ln( p(a,b) / (p(a)·p(b)) ) / −ln p(a,b)     ∈ [−1, 1]
+//! Dice(a,b) = 2·n(a,b) / (n(a) + n(b))                    ∈ [0, 1]
+//! ```
+//!
+//! As contagens são persistidas com a KB. Cada documento guarda também as
+//! suas próprias contagens, retiradas do total quando ele é
+//! [esquecido](super::provenance::forget_document); conceitos removidos
+//! saem das estatísticas.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::concept::ConceptId;
+use super::provenance::Source;
+
+/// Contagens `N`, `n(a)` e `n(a,b)` de um conjunto de janelas.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct Counts {
+    /// Janelas observadas (`N`).
+    windows: u64,
+    /// Janelas por conceito (`n(a)`).
+    concepts: HashMap<ConceptId, u64>,
+    /// Janelas por par (`n(a,b)`), com `a < b`.
+    pairs: HashMap<ConceptId, HashMap<ConceptId, u64>>,
+}
+
+impl Counts {
+    /// Soma uma janela (conceitos já ordenados e sem repetição).
+    fn observe(&mut self, ids: &[ConceptId]) {
+        self.windows += 1;
+        for (i, &a) in ids.iter().enumerate() {
+            *self.concepts.entry(a).or_insert(0) += 1;
+            for &b in &ids[i + 1..] {
+                *self.pairs.entry(a).or_default().entry(b).or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// Retira as contagens de `other` (nunca abaixo de zero).
+    fn subtract(&mut self, other: &Counts) {
+        self.windows = self.windows.saturating_sub(other.windows);
+        for (id, n) in &other.concepts {
+            if let Some(count) = self.concepts.get_mut(id) {
+                *count = count.saturating_sub(*n);
+            }
+        }
+        self.concepts.retain(|_, n| *n > 0);
+        for (a, partners) in &other.pairs {
+            let Some(mine) = self.pairs.get_mut(a) else {
+                continue;
+            };
+            for (b, n) in partners {
+                if let Some(count) = mine.get_mut(b) {
+                    *count = count.saturating_sub(*n);
+                }
+            }
+            mine.retain(|_, n| *n > 0);
+        }
+        self.pairs.retain(|_, partners| !partners.is_empty());
+    }
+
+    fn remove_concept(&mut self, id: ConceptId) {
+        self.concepts.remove(&id);
+        self.pairs.remove(&id);
+        for partners in self.pairs.values_mut() {
+            partners.remove(&id);
+        }
+        self.pairs.retain(|_, partners| !partners.is_empty());
+    }
+}
+
+/// Contagens de coocorrência por janela de sentenças.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CooccurrenceStats {
+    /// Contagens do corpus inteiro.
+    #[serde(flatten)]
+    total: Counts,
+    /// Contagens de cada documento, pelo hash do conteúdo.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    documents: HashMap<String, Counts>,
+}
+
+impl CooccurrenceStats {
+    /// Registra uma janela com os conceitos que apareceram nela.
+    pub fn observe(&mut self, window: &[ConceptId], source: &Source) {
+        let mut ids = window.to_vec();
+        ids.sort();
+        ids.dedup();
+        self.total.observe(&ids);
+        if let Some(hash) = source.document() {
+            self.documents
+                .entry(hash.to_string())
+                .or_default()
+                .observe(&ids);
+        }
+    }
+
+    /// Janelas observadas.
+    pub fn windows(&self) -> u64 {
+        self.total.windows
+    }
+
+    /// Janelas em que o conceito apareceu.
+    pub fn count(&self, id: ConceptId) -> u64 {
+        self.total.concepts.get(&id).copied().unwrap_or(0)
+    }
+
+    /// Janelas em que os dois conceitos apareceram juntos.
+    pub fn pair_count(&self, a: ConceptId, b: ConceptId) -> u64 {
+        let (a, b) = if a < b { (a, b) } else { (b, a) };
+        self.total
+            .pairs
+            .get(&a)
+            .and_then(|m| m.get(&b))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// PMI normalizado, em `[−1, 1]` (−1 se o par nunca coocorreu).
+    pub fn npmi(&self, a: ConceptId, b: ConceptId) -> f64 {
+        let n_ab = self.pair_count(a, b);
+        if n_ab == 0 || self.total.windows == 0 {
+            return -1.0;
+        }
+        let total = self.total.windows as f64;
+        let p_ab = n_ab as f64 / total;
+        if p_ab >= 1.0 {
+            return 1.0;
+        }
+        let p_a = self.count(a) as f64 / total;
+        let p_b = self.count(b) as f64 / total;
+        ((p_ab / (p_a * p_b)).ln() / -p_ab.ln()).clamp(-1.0, 1.0)
+    }
+
+    /// Coeficiente de Dice, em `[0, 1]`.
+    pub fn dice(&self, a: ConceptId, b: ConceptId) -> f64 {
+        let total = self.count(a) + self.count(b);
+        if total == 0 {
+            return 0.0;
+        }
+        2.0 * self.pair_count(a, b) as f64 / total as f64
+    }
+
+    /// Retira as janelas de um documento esquecido e devolve quantas eram.
+    pub fn forget_document(&mut self, hash: &str) -> u64 {
+        let Some(counts) = self.documents.remove(hash) else {
+            return 0;
+        };
+        self.total.subtract(&counts);
+        counts.windows
+    }
+
+    /// Retira um conceito removido da KB.
+    pub fn remove_concept(&mut self, id: ConceptId) {
+        self.total.remove_concept(id);
+        for counts in self.documents.values_mut() {
+            counts.remove_concept(id);
+        }
+    }
+
+    /// Zera as estatísticas (reset da KB).
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn association_grows_with_exclusive_cooccurrence() {
+        let (chuva, solo, sol) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let chat = Source::Conversation;
+        let mut stats = CooccurrenceStats::default();
+        stats.observe(&[chuva, solo], &chat);
+        stats.observe(&[chuva, solo, chuva], &chat);
+        stats.observe(&[sol], &chat);
+        stats.observe(&[sol, chuva], &chat);
+
+        assert_eq!(stats.count(chuva), 3);
+        assert_eq!(stats.pair_count(solo, chuva), 2);
+        assert!((stats.dice(chuva, solo) - 0.8).abs() < 1e-9);
+        assert!(stats.npmi(chuva, solo) > stats.npmi(chuva, sol));
+        assert_eq!(stats.npmi(solo, sol), -1.0);
+
+        stats.remove_concept(solo);
+        assert_eq!(stats.pair_count(chuva, solo), 0);
+        assert_eq!(stats.count(solo), 0);
+    }
+
+    #[test]
+    fn forgetting_a_document_subtracts_its_windows() {
+        let (chuva, solo) = (Uuid::new_v4(), Uuid::new_v4());
+        let doc = Source::Document("abc".into());
+        let mut stats = CooccurrenceStats::default();
+        stats.observe(&[chuva, solo], &Source::Conversation);
+        stats.observe(&[chuva, solo], &doc);
+        stats.observe(&[chuva], &doc);
+
+        let restored: CooccurrenceStats =
+            serde_json::from_str(&serde_json::to_string(&stats).unwrap()).unwrap();
+        assert_eq!(restored.pair_count(chuva, solo), 2);
+
+        stats.remove_concept(solo);
+        assert_eq!(stats.forget_document("abc"), 2);
+        assert_eq!(stats.windows(), 1);
+        assert_eq!(stats.count(chuva), 1);
+        assert_eq!(stats.forget_document("abc"), 0);
+
+        let old = format!(r#"{{"windows":3,"concepts":{{"{chuva}":2}},"pairs":{{}}}}"#);
+        let old: CooccurrenceStats = serde_json::from_str(&old).unwrap();
+        assert_eq!((old.windows(), old.count(chuva)), (3, 2));
+    }
+}
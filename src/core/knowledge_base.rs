@@ -15,6 +15,7 @@
 //! - **Conceitos**: `HashMap<ConceptId, Concept>` — busca O(1) por ID
 //! - **Links**: `HashMap<LinkId, Link>` — busca O(1) por ID
 //! - **Índice reverso**: `HashMap<ConceptId, Vec<LinkId>>` — "quais links envolvem este conceito?"
+//! - **Coocorrência**: [`CooccurrenceStats`] — janelas por conceito e por par (verdade dos links extraídos)
 //!
 //! O índice reverso é construído em memória e **não é serializado** (`#[serde(skip)]`).
 //! Após desserialização, deve ser reconstruído via [`rebuild_index()`](KnowledgeBase::rebuild_index).
@@ -50,12 +51,14 @@
 //! assert!(kb.find_concept_by_label("rust").is_some());
 //! ```
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use serde::{Deserialize, Serialize};
 
 use super::concept::{Concept, ConceptId, ConceptState};
+use super::cooccurrence::CooccurrenceStats;
 use super::link::{Link, LinkId, LinkKind};
+use super::registry::DocumentRegistry;
 
 /// Base de conhecimento in-memory — contêiner central de [Concept]s e [Link]s.
 ///
@@ -85,6 +88,20 @@ pub struct KnowledgeBase {
     /// Isso evita duplicação de dados no JSON e mantém o arquivo compacto.
     #[serde(skip, default)]
     concept_links: HashMap<ConceptId, Vec<LinkId>>,
+
+    /// Documentos já ingeridos, pelo hash do conteúdo (KBs antigas: vazio).
+    #[serde(default)]
+    pub documents: DocumentRegistry,
+
+    /// Contagens de coocorrência por janela de sentenças (KBs antigas: vazias).
+    #[serde(default)]
+    pub cooccurrence: CooccurrenceStats,
+}
+
+impl Default for KnowledgeBase {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl KnowledgeBase {
@@ -97,6 +114,8 @@ impl KnowledgeBase {
             concepts: HashMap::new(),
             links: HashMap::new(),
             concept_links: HashMap::new(),
+            documents: DocumentRegistry::default(),
+            cooccurrence: CooccurrenceStats::default(),
         }
     }
 
@@ -119,7 +138,8 @@ impl KnowledgeBase {
         }
     }
 
-    /// Limpa toda a KB — remove todos os conceitos, links e índices.
+    /// Limpa toda a KB — remove conceitos, links, índices, o registro de
+    /// documentos e as estatísticas de coocorrência.
     ///
     /// Usado quando o usuário solicita "reset" da base de conhecimento.
     /// Não afeta o arquivo em disco até que `save_kb()` seja chamado.
@@ -127,6 +147,8 @@ impl KnowledgeBase {
         self.concepts.clear();
         self.links.clear();
         self.concept_links.clear();
+        self.documents.clear();
+        self.cooccurrence.clear();
     }
 
     /// Adiciona um conceito à KB e retorna seu [ConceptId].
@@ -161,10 +183,40 @@ impl KnowledgeBase {
         id
     }
 
-    /// Busca conceito por label (case-insensitive).
+    /// Remove um link e o retira do índice reverso.
+    pub fn remove_link(&mut self, id: LinkId) -> Option<Link> {
+        let link = self.links.remove(&id)?;
+        for p in &link.participants {
+            if let Some(ids) = self.concept_links.get_mut(&p.concept_id) {
+                ids.retain(|l| *l != id);
+            }
+        }
+        tracing::debug!(id = %id, "KB: link removido");
+        Some(link)
+    }
+
+    /// Remove um conceito, todos os links de que ele participa e suas
+    /// contagens de coocorrência.
+    ///
+    /// Retorna o conceito removido e quantos links saíram junto.
+    pub fn remove_concept(&mut self, id: ConceptId) -> Option<(Concept, usize)> {
+        let concept = self.concepts.remove(&id)?;
+        self.cooccurrence.remove_concept(id);
+        let link_ids = self.concept_links.remove(&id).unwrap_or_default();
+        let removed = link_ids
+            .into_iter()
+            .filter_map(|l| self.remove_link(l))
+            .count();
+        tracing::debug!(id = %id, label = %concept.label, links = removed, "KB: conceito removido");
+        Some((concept, removed))
+    }
+
+    /// Busca conceito por label ou alias (case-insensitive).
     ///
     /// Converte ambos os labels para lowercase antes de comparar.
-    /// Retorna o primeiro conceito encontrado com label exato (após lowercase).
+    /// Retorna o primeiro conceito encontrado com label exato (após lowercase);
+    /// sem label exato, procura entre os aliases ("IA" → "Inteligência
+    /// Artificial").
     ///
     /// # Exemplo
     ///
@@ -182,6 +234,25 @@ impl KnowledgeBase {
         self.concepts
             .values()
             .find(|c| c.label.to_lowercase() == label_lower)
+            .or_else(|| self.find_concept_by_alias(label))
+    }
+
+    /// Busca conceito apenas pelos aliases (case-insensitive).
+    pub fn find_concept_by_alias(&self, alias: &str) -> Option<&Concept> {
+        let alias_lower = alias.to_lowercase();
+        self.concepts
+            .values()
+            .find(|c| c.aliases.iter().any(|a| a.to_lowercase() == alias_lower))
+    }
+
+    /// Busca um conceito pela chave canônica (radicais RSLP do label).
+    ///
+    /// Chaves vazias nunca casam.
+    pub fn find_concept_by_key(&self, key: &str) -> Option<&Concept> {
+        if key.is_empty() {
+            return None;
+        }
+        self.concepts.values().find(|c| c.key == key)
     }
 
     /// Busca o conceito mais similar por embedding (cosine similarity).
@@ -213,10 +284,8 @@ impl KnowledgeBase {
         for concept in self.concepts.values() {
             if let Some(ref emb) = concept.embedding {
                 let sim = cosine_similarity(embedding, emb);
-                if sim >= threshold {
-                    if best.is_none() || sim > best.unwrap().1 {
-                        best = Some((concept.id, sim));
-                    }
+                if sim >= threshold && (best.is_none() || sim > best.unwrap().1) {
+                    best = Some((concept.id, sim));
                 }
             }
         }
@@ -306,6 +375,67 @@ impl KnowledgeBase {
             .unwrap_or_default()
     }
 
+    /// Retorna os vizinhos de um conceito: cada link que o envolve,
+    /// pareado com o outro participante do link.
+    ///
+    /// Links N-ários geram um par por participante distinto do conceito.
+    pub fn neighbors(&self, concept_id: ConceptId) -> Vec<(&Link, ConceptId)> {
+        self.links_for_concept(concept_id)
+            .into_iter()
+            .flat_map(|link| {
+                link.participants
+                    .iter()
+                    .filter(move |p| p.concept_id != concept_id)
+                    .map(move |p| (link, p.concept_id))
+            })
+            .collect()
+    }
+
+    /// Encontra o menor caminho (em número de links) entre dois conceitos.
+    ///
+    /// Busca em largura (BFS) sobre o índice reverso, ignorando a direção
+    /// dos links. Retorna os IDs dos links percorridos, na ordem
+    /// `from → to`, ou `None` se não houver caminho com até `max_depth` links.
+    pub fn find_path(
+        &self,
+        from: ConceptId,
+        to: ConceptId,
+        max_depth: usize,
+    ) -> Option<Vec<LinkId>> {
+        if from == to {
+            return Some(Vec::new());
+        }
+        let mut visited: HashSet<ConceptId> = HashSet::from([from]);
+        // Para cada conceito alcançado: (conceito anterior, link usado)
+        let mut came_from: HashMap<ConceptId, (ConceptId, LinkId)> = HashMap::new();
+        let mut queue: VecDeque<(ConceptId, usize)> = VecDeque::from([(from, 0)]);
+
+        while let Some((current, depth)) = queue.pop_front() {
+            if depth >= max_depth {
+                continue;
+            }
+            for (link, next) in self.neighbors(current) {
+                if !visited.insert(next) {
+                    continue;
+                }
+                came_from.insert(next, (current, link.id));
+                if next == to {
+                    // Reconstrói o caminho de trás para frente
+                    let mut path = Vec::new();
+                    let mut node = to;
+                    while let Some(&(prev, link_id)) = came_from.get(&node) {
+                        path.push(link_id);
+                        node = prev;
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                queue.push_back((next, depth + 1));
+            }
+        }
+        None
+    }
+
     /// Retorna links binários ativos (com Subject e Object) acima de um threshold de energia.
     ///
     /// "Binário" aqui significa que o link tem tanto Subject quanto Object —
@@ -362,6 +492,18 @@ impl KnowledgeBase {
         })
     }
 
+    /// Link de determinado tipo entre dois conceitos, para atualização.
+    pub fn find_link_mut(
+        &mut self,
+        kind: &LinkKind,
+        subject: ConceptId,
+        object: ConceptId,
+    ) -> Option<&mut Link> {
+        self.links
+            .values_mut()
+            .find(|l| l.kind == *kind && l.subject() == Some(subject) && l.object() == Some(object))
+    }
+
     /// Executa um ciclo de decaimento ("Poda Natural") em toda a KB.
     ///
     /// Decai a energia de **todos** os conceitos e links com fator 0.95
@@ -481,3 +623,51 @@ pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
         dot / denom
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Participant, Role, TruthValue};
+
+    fn link(kb: &mut KnowledgeBase, subject: ConceptId, object: ConceptId) -> LinkId {
+        kb.add_link(Link::new(
+            LinkKind::Implication,
+            vec![
+                Participant {
+                    concept_id: subject,
+                    role: Role::Subject,
+                },
+                Participant {
+                    concept_id: object,
+                    role: Role::Object,
+                },
+            ],
+            TruthValue::proto(),
+        ))
+    }
+
+    #[test]
+    fn find_path_ignores_direction_and_respects_depth() {
+        let mut kb = KnowledgeBase::new();
+        let [chuva, solo, planta, sol] = ["Chuva", "Solo", "Planta", "Sol"]
+            .map(|label| kb.add_concept(Concept::new(label.into(), TruthValue::proto())));
+        let molha = link(&mut kb, chuva, solo);
+        let nutre = link(&mut kb, planta, solo);
+
+        let mut neighbors: Vec<ConceptId> = kb
+            .neighbors(solo)
+            .into_iter()
+            .map(|(_, other)| other)
+            .collect();
+        neighbors.sort();
+        let mut expected = vec![chuva, planta];
+        expected.sort();
+        assert_eq!(neighbors, expected);
+        assert!(kb.neighbors(sol).is_empty());
+
+        assert_eq!(kb.find_path(chuva, planta, 6), Some(vec![molha, nutre]));
+        assert_eq!(kb.find_path(chuva, planta, 1), None);
+        assert_eq!(kb.find_path(chuva, sol, 6), None);
+        assert_eq!(kb.find_path(sol, sol, 6), Some(Vec::new()));
+    }
+}
@@ -55,7 +55,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use super::concept::ConceptId;
-use super::TruthValue;
+use super::{Provenance, ReviewSchedule, TruthValue};
 
 /// Alias de tipo para o identificador de um [Link].
 ///
@@ -131,6 +131,15 @@ impl LinkKind {
             LinkKind::Custom(s) => s.as_str(),
         }
     }
+
+    /// Relação sem direção: "A ≈ B" diz o mesmo que "B ≈ A".
+    ///
+    /// Só [`Similarity`](LinkKind::Similarity) e
+    /// [`Equivalence`](LinkKind::Equivalence); nas demais, trocar sujeito e
+    /// objeto muda a afirmação.
+    pub fn is_symmetric(&self) -> bool {
+        matches!(self, LinkKind::Similarity | LinkKind::Equivalence)
+    }
 }
 
 /// Papel semântico de um participante em um [Link].
@@ -186,6 +195,43 @@ impl Role {
     }
 }
 
+/// Origem de um [Link] — como a relação entrou na base de conhecimento.
+///
+/// Permite distinguir relações extraídas de texto, derivadas pelo motor de
+/// inferência e propostas pelo agente do chat. Links inferidos guardam a
+/// regra e os links-premissa, o que permite reconstruir sua "prova".
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum LinkOrigin {
+    /// Extraído de texto (mensagem do usuário ou documento).
+    #[default]
+    Extracted,
+    /// Derivado pelo motor de inferência NARS.
+    Inferred {
+        /// Regra aplicada (ex: "Dedução", "Indução").
+        rule: String,
+        /// Links usados como premissas.
+        premises: Vec<LinkId>,
+    },
+    /// Proposto pelo agente do chat — entra com verdade proto e o usuário
+    /// o confirma ou refuta na revisão.
+    Proposed,
+    /// Estrutura do documento (autoria, seções, menções) — lida dos
+    /// metadados e do sumário, não de texto: fora de lacunas e revisão.
+    Structural,
+}
+
+impl LinkOrigin {
+    /// Retorna o label legível em PT-BR da origem.
+    pub fn label(&self) -> &str {
+        match self {
+            LinkOrigin::Extracted => "extraído",
+            LinkOrigin::Inferred { .. } => "inferido",
+            LinkOrigin::Proposed => "proposto",
+            LinkOrigin::Structural => "estrutural",
+        }
+    }
+}
+
 /// Um participante em um [Link] N-ário.
 ///
 /// Associa um conceito a um papel semântico dentro da relação.
@@ -251,6 +297,20 @@ pub struct Link {
     /// Nível de energia (0.0 a 1.0) — determina a relevância temporal.
     /// Inicia em 0.8, decai ao longo do tempo como os conceitos.
     pub energy: f64,
+
+    /// Origem da relação (extraída, inferida, proposta ou estrutural). Veja [`LinkOrigin`].
+    #[serde(default)]
+    pub origin: LinkOrigin,
+
+    /// Agenda de revisão espaçada — `None` até a primeira revisão.
+    /// Veja [`crate::core::review`].
+    #[serde(default)]
+    pub review: Option<ReviewSchedule>,
+
+    /// Observações por fonte (conversa ou documento). Vazia em KBs antigas,
+    /// em links inferidos e nos propostos pelo agente.
+    #[serde(default, skip_serializing_if = "Provenance::is_empty")]
+    pub provenance: Provenance,
 }
 
 impl Link {
@@ -268,9 +328,18 @@ impl Link {
             participants,
             truth,
             energy: 0.8,
+            origin: LinkOrigin::Extracted,
+            review: None,
+            provenance: Provenance::default(),
         }
     }
 
+    /// Define a origem do link (builder). Veja [`LinkOrigin`].
+    pub fn with_origin(mut self, origin: LinkOrigin) -> Self {
+        self.origin = origin;
+        self
+    }
+
     /// Retorna o [ConceptId] do participante com papel `Subject`, se houver.
     ///
     /// Usado extensivamente no motor de inferência para acessar
@@ -325,4 +394,10 @@ impl Link {
         // Energia nunca fica negativa
         self.energy = (self.energy * factor).max(0.0);
     }
+
+    /// Reforça o link (+0.3 de energia, limitada a 1.0) — usado quando o
+    /// usuário confirma a relação na revisão espaçada.
+    pub fn reinforce(&mut self) {
+        self.energy = (self.energy + 0.3).min(1.0);
+    }
 }
@@ -9,6 +9,11 @@
 //! - [`Link`] — Relação N-ária entre conceitos (ex: "Rust" → "linguagem de programação")
 //! - [`LinkKind`] — Tipo de relação semântica (Herança, Similaridade, Implicação, etc.)
 //! - [`KnowledgeBase`] — Contêiner central que armazena todos os conceitos e links
+//! - [`Attribute`] — Valor tipado (número, data, quantidade) de um conceito
+//! - [`ReviewSchedule`] — Agenda de revisão espaçada (SM-2) de conceitos e links
+//! - [`DocumentRecord`] — Documento já lido pela KB (hash do conteúdo, título, páginas)
+//! - [`Provenance`] — Fontes (conversa, documentos) de cada menção e link
+//! - [`CooccurrenceStats`] — Contagens de coocorrência que dão a verdade dos links extraídos
 //!
 //! ## Analogia com o Mundo Real
 //!
@@ -35,17 +40,37 @@
 /// Sub-módulo com a implementação de [`TruthValue`] — grau de verdade NARS.
 pub mod truth_value;
 
-/// Sub-módulo com a implementação de [`Concept`] e [`ConceptState`].
+/// Sub-módulo com a implementação de [`Concept`], [`ConceptState`] e [`EntityType`].
 pub mod concept;
 
-/// Sub-módulo com a implementação de [`Link`], [`LinkKind`], [`Participant`] e [`Role`].
+/// Sub-módulo com a implementação de [`Link`], [`LinkKind`], [`LinkOrigin`], [`Participant`] e [`Role`].
 pub mod link;
 
 /// Sub-módulo com a implementação de [`KnowledgeBase`] — contêiner central.
 pub mod knowledge_base;
 
+/// Sub-módulo com a implementação de [`Attribute`] e [`ValueKind`].
+pub mod attribute;
+
+/// Sub-módulo de revisão espaçada: [`ReviewSchedule`], cartões e respostas.
+pub mod review;
+
+/// Sub-módulo com o registro de documentos lidos: [`DocumentRecord`], [`DocumentRegistry`].
+pub mod registry;
+
+/// Sub-módulo de proveniência: [`Source`], [`Provenance`] e esquecimento de documentos.
+pub mod provenance;
+
+/// Sub-módulo com as estatísticas de coocorrência: [`CooccurrenceStats`] (NPMI, Dice).
+pub mod cooccurrence;
+
 // Re-exports para conveniência — permite usar `crate::core::TruthValue` diretamente.
 pub use truth_value::TruthValue;
-pub use concept::{Concept, ConceptState};
-pub use link::{Link, LinkKind, Participant, Role};
+pub use concept::{Concept, ConceptState, EntityType};
+pub use link::{Link, LinkKind, LinkOrigin, Participant, Role};
 pub use knowledge_base::KnowledgeBase;
+pub use attribute::{Attribute, ValueKind};
+pub use review::{ReviewAnswer, ReviewSchedule, ReviewTarget};
+pub use registry::{DocumentRecord, DocumentRegistry};
+pub use provenance::{Citation, Provenance, Source};
+pub use cooccurrence::CooccurrenceStats;
@@ -0,0 +1,568 @@
+//! # Proveniência — De Onde Veio Cada Conhecimento
+//!
+//! Cada menção de conceito e cada link extraído registra a sua **fonte**
+//! ([`Source`]): a conversa ou um documento (pelo hash do conteúdo, a
+//! chave do [registro](super::registry)). Atributos tipados guardam a
+//! fonte em [`Attribute::source`](super::Attribute::source).
+//!
+//! A ingestão cita também a página do chunk ([`Citation`]): a evidência
+//! de um documento paginado lista as páginas onde o item apareceu.
+//!
+//! Com isso um documento ruim pode ser **esquecido** sem resetar a KB:
+//!
+//! ```text
+//! forget_document(kb, hash)
+//!   ├── 1. Links com evidência do documento
+//!   │     ├── só o documento    → removidos
+//!   │     └── outras fontes     → evidência e verdade do documento
+//!   │                             retiradas, energia proporcional
+//!   ├── 2. Conceitos com menções do documento
+//!   │     ├── todas as menções  → removidos (com seus links)
+//!   │     └── outras menções    → mention_count e energia reduzidos
+//!   ├── 3. Atributos vindos do documento → removidos
+//!   ├── 4. Janelas de coocorrência do documento → retiradas das estatísticas
+//!   ├── 5. Inferências cujas premissas mudaram ou sumiram (em cascata)
+//!   │     ├── ainda deriváveis       → verdade recalculada (premissas
+//!   │     │                            restantes ou outra derivação)
+//!   │     ├── revisadas pelo usuário → mantidas, premissas atualizadas
+//!   │     └── demais                 → removidas
+//!   └── 6. Registro do documento → removido
+//! ```
+//!
+//! A verdade que cada fonte revisou num link fica na sua [`Evidence`]
+//! (`truth`), para que esquecer a fonte desfaça exatamente a revisão.
+//! Respostas da [revisão espaçada](super::review) citam a conversa: o que
+//! o usuário confirmou continua na KB quando o documento é esquecido.
+//!
+//! Conceitos-tipo e links `InstanceOf` citam a fonte que tipou a entidade,
+//! como os demais links extraídos: esquecer o documento remove a tipagem
+//! que só ele sustentava. Conceitos e links sem proveniência (KBs antigas,
+//! relações propostas pelo agente) nunca são removidos por esquecimento —
+//! exceto quando um participante do link é removido.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::concept::ConceptId;
+use super::knowledge_base::KnowledgeBase;
+use super::link::{LinkId, LinkOrigin};
+use super::truth_value::TruthValue;
+use crate::inference::InferenceEngine;
+
+/// Fonte de uma evidência.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+    /// Mensagem do usuário no chat.
+    Conversation,
+    /// Documento ingerido, pelo SHA-256 do conteúdo.
+    Document(String),
+}
+
+impl Source {
+    /// Hash do documento, se a fonte for um documento.
+    pub fn document(&self) -> Option<&str> {
+        match self {
+            Source::Document(hash) => Some(hash),
+            Source::Conversation => None,
+        }
+    }
+}
+
+/// Fonte de uma observação e onde, nela, o item apareceu.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Citation {
+    /// Fonte.
+    pub source: Source,
+    /// Página do chunk (1-indexed), em documentos paginados.
+    pub page: Option<usize>,
+}
+
+impl From<Source> for Citation {
+    fn from(source: Source) -> Self {
+        Self { source, page: None }
+    }
+}
+
+/// Quantas vezes uma fonte sustentou o item.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Evidence {
+    /// Fonte.
+    pub source: Source,
+    /// Menções (conceitos) ou observações (links) vindas dessa fonte.
+    pub count: u32,
+    /// Páginas citadas, em ordem crescente (documentos paginados).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pages: Vec<usize>,
+    /// Evidência de verdade que a fonte revisou no item (soma das
+    /// observações) — retirada do link quando a fonte é esquecida.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truth: Option<TruthValue>,
+}
+
+/// Evidências de um conceito ou link, por fonte.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Provenance(Vec<Evidence>);
+
+impl Provenance {
+    /// Registra uma observação vinda de `source`.
+    pub fn record(&mut self, source: &Source) {
+        self.cite(&Citation::from(source.clone()));
+    }
+
+    /// Registra uma observação com a página de onde veio.
+    pub fn cite(&mut self, citation: &Citation) {
+        let i = self
+            .0
+            .iter()
+            .position(|e| e.source == citation.source)
+            .unwrap_or_else(|| {
+                self.0.push(Evidence {
+                    source: citation.source.clone(),
+                    count: 0,
+                    pages: Vec::new(),
+                    truth: None,
+                });
+                self.0.len() - 1
+            });
+        let evidence = &mut self.0[i];
+        evidence.count += 1;
+        if let Some(page) = citation.page {
+            if let Err(i) = evidence.pages.binary_search(&page) {
+                evidence.pages.insert(i, page);
+            }
+        }
+    }
+
+    /// Registra uma observação que revisou a verdade do item.
+    pub fn cite_truth(&mut self, citation: &Citation, observation: &TruthValue) {
+        self.cite(citation);
+        if let Some(evidence) = self.0.iter_mut().find(|e| e.source == citation.source) {
+            evidence.truth = Some(match &evidence.truth {
+                Some(truth) => truth.revision(observation),
+                None => observation.clone(),
+            });
+        }
+    }
+
+    /// Evidência de verdade trazida por `source`, se ela revisou o item.
+    pub fn truth(&self, source: &Source) -> Option<&TruthValue> {
+        self.0
+            .iter()
+            .find(|e| e.source == *source)
+            .and_then(|e| e.truth.as_ref())
+    }
+
+    /// Observações vindas de `source`.
+    pub fn count(&self, source: &Source) -> u32 {
+        self.0
+            .iter()
+            .find(|e| e.source == *source)
+            .map_or(0, |e| e.count)
+    }
+
+    /// Total de observações registradas.
+    pub fn total(&self) -> u32 {
+        self.0.iter().map(|e| e.count).sum()
+    }
+
+    /// Retira a evidência de `source` e devolve quantas observações eram.
+    pub fn retract(&mut self, source: &Source) -> u32 {
+        let removed = self.count(source);
+        self.0.retain(|e| e.source != *source);
+        removed
+    }
+
+    /// Evidências por fonte.
+    pub fn sources(&self) -> &[Evidence] {
+        &self.0
+    }
+
+    /// `true` sem nenhuma evidência registrada (KBs antigas).
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Passadas máximas da cascata de inferências (ciclos de premissas).
+const MAX_CASCADE: usize = 32;
+
+/// Resultado de [`forget_document`].
+#[derive(Clone, Debug, Default)]
+pub struct ForgetReport {
+    /// Labels dos conceitos removidos.
+    pub removed_concepts: Vec<String>,
+    /// Conceitos mantidos com menções reduzidas.
+    pub downgraded_concepts: usize,
+    /// Links removidos (evidência só do documento, ou participante removido).
+    pub removed_links: usize,
+    /// Links mantidos com evidência reduzida.
+    pub downgraded_links: usize,
+    /// Atributos removidos.
+    pub removed_attributes: usize,
+    /// Inferências removidas porque alguma premissa sumiu.
+    pub invalidated_inferences: usize,
+    /// Inferências mantidas (revisadas pelo usuário) sem as premissas removidas.
+    pub revalidated_inferences: usize,
+    /// Inferências recalculadas das premissas restantes ou de outra derivação.
+    pub rederived_inferences: usize,
+    /// Janelas de coocorrência do documento retiradas das estatísticas.
+    pub removed_windows: u64,
+    /// `true` se o documento estava no registro.
+    pub was_registered: bool,
+}
+
+impl ForgetReport {
+    /// `true` se nada mudou na KB.
+    pub fn is_empty(&self) -> bool {
+        self.removed_concepts.is_empty()
+            && self.downgraded_concepts == 0
+            && self.removed_links == 0
+            && self.downgraded_links == 0
+            && self.removed_attributes == 0
+            && self.invalidated_inferences == 0
+            && self.revalidated_inferences == 0
+            && self.rederived_inferences == 0
+            && self.removed_windows == 0
+    }
+}
+
+/// Esquece um documento: retira sua evidência da KB e remove o que só
+/// ele sustentava. Veja o diagrama no topo do módulo.
+pub fn forget_document(kb: &mut KnowledgeBase, hash: &str) -> ForgetReport {
+    let source = Source::Document(hash.to_string());
+    let mut report = ForgetReport::default();
+    let mut changed: HashSet<LinkId> = HashSet::new();
+
+    // ─── 1. Links ───
+    let link_ids: Vec<LinkId> = kb
+        .links
+        .values()
+        .filter(|l| l.provenance.count(&source) > 0)
+        .map(|l| l.id)
+        .collect();
+    for id in link_ids {
+        let Some(link) = kb.links.get_mut(&id) else {
+            continue;
+        };
+        let total = link.provenance.total();
+        let truth = link.provenance.truth(&source).cloned();
+        let removed = link.provenance.retract(&source);
+        if link.provenance.is_empty() {
+            kb.remove_link(id);
+            report.removed_links += 1;
+        } else {
+            if let Some(truth) = truth {
+                link.truth = link.truth.without(&truth);
+            }
+            link.energy *= (total - removed) as f64 / total as f64;
+            changed.insert(id);
+            report.downgraded_links += 1;
+        }
+    }
+
+    // ─── 2. Conceitos ───
+    let concept_ids: Vec<ConceptId> = kb
+        .concepts
+        .values()
+        .filter(|c| c.provenance.count(&source) > 0)
+        .map(|c| c.id)
+        .collect();
+    for id in concept_ids {
+        let Some(concept) = kb.concepts.get_mut(&id) else {
+            continue;
+        };
+        let removed = concept.provenance.retract(&source);
+        let total = concept.mention_count.max(removed);
+        let remaining = total - removed;
+        if remaining == 0 {
+            if let Some((concept, links)) = kb.remove_concept(id) {
+                report.removed_concepts.push(concept.label);
+                report.removed_links += links;
+            }
+        } else {
+            concept.mention_count = remaining;
+            concept.energy *= remaining as f64 / total as f64;
+            concept.update_state();
+            report.downgraded_concepts += 1;
+        }
+    }
+
+    // ─── 3. Atributos ───
+    for concept in kb.concepts.values_mut() {
+        let before = concept.attributes.len();
+        concept
+            .attributes
+            .retain(|a| a.source.as_ref() != Some(&source));
+        report.removed_attributes += before - concept.attributes.len();
+    }
+
+    // ─── 4. Coocorrência ───
+    report.removed_windows = kb.cooccurrence.forget_document(hash);
+
+    // ─── 5. Inferências afetadas (em cascata) ───
+    let mut rederived: HashSet<LinkId> = HashSet::new();
+    for _ in 0..MAX_CASCADE {
+        let affected: Vec<LinkId> = kb
+            .links
+            .values()
+            .filter(|l| match &l.origin {
+                LinkOrigin::Inferred { premises, .. } => premises
+                    .iter()
+                    .any(|p| changed.contains(p) || !kb.links.contains_key(p)),
+                _ => false,
+            })
+            .map(|l| l.id)
+            .collect();
+        if affected.is_empty() {
+            break;
+        }
+        changed.clear();
+        for id in affected {
+            let Some(link) = kb.links.get(&id) else {
+                continue;
+            };
+            let derivation = InferenceEngine::rederive(kb, link);
+            let reviewed = link.review.as_ref().is_some_and(|r| r.repetitions > 0);
+            match derivation {
+                Some((truth, sources)) => {
+                    let Some(link) = kb.links.get_mut(&id) else {
+                        continue;
+                    };
+                    link.truth = truth;
+                    if let LinkOrigin::Inferred { premises, .. } = &mut link.origin {
+                        *premises = sources;
+                    }
+                    rederived.insert(id);
+                    changed.insert(id);
+                }
+                None if reviewed => {
+                    let existing: HashSet<LinkId> = kb.links.keys().copied().collect();
+                    let Some(link) = kb.links.get_mut(&id) else {
+                        continue;
+                    };
+                    if let LinkOrigin::Inferred { premises, .. } = &mut link.origin {
+                        premises.retain(|p| existing.contains(p));
+                    }
+                    rederived.remove(&id);
+                    report.revalidated_inferences += 1;
+                }
+                None => {
+                    kb.remove_link(id);
+                    rederived.remove(&id);
+                    report.invalidated_inferences += 1;
+                }
+            }
+        }
+    }
+    report.rederived_inferences = rederived.len();
+
+    // ─── 6. Registro ───
+    report.was_registered = kb.documents.remove(hash).is_some();
+
+    tracing::info!(
+        hash,
+        removed_concepts = report.removed_concepts.len(),
+        downgraded_concepts = report.downgraded_concepts,
+        removed_links = report.removed_links,
+        downgraded_links = report.downgraded_links,
+        removed_attributes = report.removed_attributes,
+        invalidated_inferences = report.invalidated_inferences,
+        rederived_inferences = report.rederived_inferences,
+        removed_windows = report.removed_windows,
+        "Documento esquecido"
+    );
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Concept, Link, LinkKind, Participant, Role, TruthValue};
+
+    fn concept(kb: &mut KnowledgeBase, label: &str, sources: &[&Source]) -> ConceptId {
+        let mut concept = Concept::new(label.to_string(), TruthValue::proto());
+        concept.mention_count = sources.len() as u32;
+        for source in sources {
+            concept.provenance.record(source);
+        }
+        kb.add_concept(concept)
+    }
+
+    fn link(kb: &mut KnowledgeBase, a: ConceptId, b: ConceptId, sources: &[&Source]) -> LinkId {
+        let mut link = Link::new(
+            LinkKind::Implication,
+            vec![
+                Participant {
+                    concept_id: a,
+                    role: Role::Subject,
+                },
+                Participant {
+                    concept_id: b,
+                    role: Role::Object,
+                },
+            ],
+            TruthValue::proto(),
+        );
+        for source in sources {
+            link.provenance.record(source);
+        }
+        kb.add_link(link)
+    }
+
+    #[test]
+    fn forgetting_removes_exclusive_knowledge_and_downgrades_shared() {
+        let doc = Source::Document("abc".into());
+        let chat = Source::Conversation;
+        let mut kb = KnowledgeBase::new();
+        let chuva = concept(&mut kb, "Chuva", &[&doc, &chat]);
+        let solo = concept(&mut kb, "Solo", &[&doc]);
+        let planta = concept(&mut kb, "Planta", &[&chat]);
+        let shared = link(&mut kb, chuva, planta, &[&doc, &chat]);
+        let only_doc = link(&mut kb, chuva, solo, &[&doc]);
+
+        let inferred = Link::new(
+            LinkKind::Implication,
+            vec![
+                Participant {
+                    concept_id: chuva,
+                    role: Role::Subject,
+                },
+                Participant {
+                    concept_id: planta,
+                    role: Role::Object,
+                },
+            ],
+            TruthValue::proto(),
+        )
+        .with_origin(LinkOrigin::Inferred {
+            rule: "Dedução".into(),
+            premises: vec![only_doc, shared],
+        });
+        let inferred = kb.add_link(inferred);
+
+        let report = forget_document(&mut kb, "abc");
+
+        assert_eq!(report.removed_concepts, vec!["Solo".to_string()]);
+        assert!(kb.concepts.contains_key(&chuva));
+        assert_eq!(kb.concepts[&chuva].mention_count, 1);
+        assert!(kb.links.contains_key(&shared));
+        assert!(!kb.links.contains_key(&only_doc));
+        assert!(!kb.links.contains_key(&inferred));
+        assert_eq!(report.invalidated_inferences, 1);
+        assert_eq!(kb.links_for_concept(chuva).len(), 1);
+    }
+
+    #[test]
+    fn forgetting_restores_the_truth_the_other_document_observed() {
+        let (a, b) = (Source::Document("a".into()), Source::Document("b".into()));
+        let mut kb = KnowledgeBase::new();
+        let chuva = concept(&mut kb, "Chuva", &[&a, &b]);
+        let solo = concept(&mut kb, "Solo", &[&a, &b]);
+        let planta = concept(&mut kb, "Planta", &[&b]);
+        let (from_a, from_b) = (TruthValue::new(0.2, 0.6), TruthValue::new(0.9, 0.5));
+        let shared = link(&mut kb, chuva, solo, &[]);
+        let shared_link = kb.links.get_mut(&shared).unwrap();
+        shared_link.truth = from_a.revision(&from_b);
+        shared_link
+            .provenance
+            .cite_truth(&a.clone().into(), &from_a);
+        shared_link
+            .provenance
+            .cite_truth(&b.clone().into(), &from_b);
+        let only_b = link(&mut kb, solo, planta, &[&b]);
+        kb.links.get_mut(&only_b).unwrap().truth = TruthValue::new(0.8, 0.9);
+        kb.cooccurrence.observe(&[chuva, solo], &a);
+        kb.cooccurrence.observe(&[chuva, solo], &b);
+
+        let deduced = kb.links[&shared].truth.deduction(&kb.links[&only_b].truth);
+        let inferred = link(&mut kb, chuva, planta, &[]);
+        let inferred_link = kb.links.get_mut(&inferred).unwrap();
+        inferred_link.truth = deduced;
+        inferred_link.origin = LinkOrigin::Inferred {
+            rule: "Dedução".into(),
+            premises: vec![shared, only_b],
+        };
+
+        let report = forget_document(&mut kb, "a");
+
+        let truth = &kb.links[&shared].truth;
+        assert!((truth.frequency() - from_b.frequency()).abs() < 1e-9);
+        assert!((truth.confidence() - from_b.confidence()).abs() < 1e-9);
+        assert_eq!(kb.cooccurrence.pair_count(chuva, solo), 1);
+        assert_eq!(report.removed_windows, 1);
+
+        let expected = from_b.deduction(&kb.links[&only_b].truth);
+        assert!((kb.links[&inferred].truth.confidence() - expected.confidence()).abs() < 1e-9);
+        assert_eq!(report.rederived_inferences, 1);
+    }
+
+    #[test]
+    fn a_value_cited_by_two_documents_survives_forgetting_one() {
+        use crate::nlu::{quantity::ValueExtractor, register_attributes};
+        use parking_lot::RwLock;
+        use std::sync::Arc;
+
+        let (a, b) = (Source::Document("a".into()), Source::Document("b".into()));
+        let mut kb = KnowledgeBase::new();
+        let lote = concept(&mut kb, "Lote", &[&a, &b]);
+        let kb = Arc::new(RwLock::new(kb));
+        let attributes: Vec<_> = ValueExtractor::new()
+            .extract("atrasou 3 dias")
+            .into_iter()
+            .map(|m| (0, m.attribute))
+            .collect();
+
+        assert_eq!(register_attributes(&attributes, &[lote], &a, &kb).len(), 1);
+        assert!(register_attributes(&attributes, &[lote], &b, &kb).is_empty());
+        assert!(register_attributes(&attributes, &[lote], &b, &kb).is_empty());
+        assert_eq!(kb.read().concepts[&lote].attributes.len(), 2);
+
+        let mut kb = kb.write();
+        forget_document(&mut kb, "a");
+        let remaining: Vec<_> = kb.concepts[&lote].distinct_attributes().collect();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].source, Some(b));
+    }
+
+    #[test]
+    fn links_confirmed_in_review_survive_forgetting() {
+        use crate::core::review::{apply_answer, ReviewAnswer, ReviewTarget};
+
+        let doc = Source::Document("abc".into());
+        let mut kb = KnowledgeBase::new();
+        let chuva = concept(&mut kb, "Chuva", &[&doc]);
+        let solo = concept(&mut kb, "Solo", &[&doc]);
+        let planta = concept(&mut kb, "Planta", &[&doc]);
+        let confirmed = link(&mut kb, chuva, solo, &[&doc]);
+        let unsure = link(&mut kb, solo, planta, &[&doc]);
+        let now = chrono::Utc::now();
+        apply_answer(
+            &mut kb,
+            ReviewTarget::Link(confirmed),
+            ReviewAnswer::True,
+            now,
+        )
+        .unwrap();
+        apply_answer(
+            &mut kb,
+            ReviewTarget::Link(unsure),
+            ReviewAnswer::Unsure,
+            now,
+        )
+        .unwrap();
+
+        let report = forget_document(&mut kb, "abc");
+
+        assert_eq!(report.removed_concepts, vec!["Planta".to_string()]);
+        assert!(kb.links.contains_key(&confirmed));
+        assert!(!kb.links.contains_key(&unsure));
+        assert_eq!(
+            kb.links[&confirmed].provenance.count(&Source::Conversation),
+            1
+        );
+        assert!(kb.links[&confirmed].truth.frequency() > 0.9);
+    }
+}
@@ -0,0 +1,193 @@
+//! # Registro de Documentos — O Que a KB Já Leu
+//!
+//! Cada documento ingerido deixa um [`DocumentRecord`] na
+//! [`KnowledgeBase`](super::KnowledgeBase), indexado pelo SHA-256 dos
+//! bytes. Reenviar o mesmo arquivo reforçaria todos os conceitos de novo
+//! (energia e `mention_count` inflados); com o registro, o upload
+//! duplicado é reconhecido e só é reprocessado se o usuário pedir.
+//!
+//! ```text
+//! upload ──▶ content_hash(bytes) ──▶ registro?
+//!                                     ├── não  → ingere e registra
+//!                                     └── sim  → pula (ou reingere, se pedido:
+//!                                                ingest_count += 1)
+//! ```
+//!
+//! Só ingestões concluídas são registradas: um job cancelado ou com erro
+//! pode ser reenviado normalmente.
+//!
+//! Enquanto a ingestão não conclui, o registro guarda os índices dos
+//! chunks já aplicados ([`DocumentRegistry::applied_chunks`]). Como eles
+//! são salvos junto com a KB, a retomada pula exatamente os chunks cuja
+//! evidência está na KB carregada: nem reaplica um chunk salvo (revisão
+//! em dobro), nem pula um que se perdeu com o processo.
+
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Documento lido pela KB.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DocumentRecord {
+    /// SHA-256 dos bytes (hexadecimal).
+    pub hash: String,
+    /// Nome do arquivo no último upload.
+    pub filename: String,
+    /// Título extraído do documento, se houver.
+    pub title: Option<String>,
+    /// Formato (`pdf`, `markdown`...).
+    pub format: String,
+    /// Páginas (só formatos paginados).
+    pub pages: Option<usize>,
+    /// Tamanho do arquivo em bytes.
+    pub size_bytes: usize,
+    /// Versão do extrator que leu o documento na última ingestão.
+    pub extractor_version: u32,
+    /// Chunks analisados na última ingestão.
+    pub chunks: usize,
+    /// Conceitos criados na última ingestão.
+    pub new_concepts: usize,
+    /// Links criados na última ingestão.
+    pub new_links: usize,
+    /// Quantas vezes o documento foi ingerido.
+    pub ingest_count: u32,
+    /// Primeira ingestão.
+    pub first_ingested_at: DateTime<Utc>,
+    /// Última ingestão.
+    pub ingested_at: DateTime<Utc>,
+}
+
+impl DocumentRecord {
+    /// Nome exibido: o título, ou o nome do arquivo.
+    pub fn display_name(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.filename)
+    }
+}
+
+/// Documentos lidos, indexados pelo hash do conteúdo.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DocumentRegistry {
+    /// Hash → registro.
+    documents: HashMap<String, DocumentRecord>,
+    /// Hash → chunks já aplicados de uma ingestão ainda não concluída
+    /// (KBs antigas: vazio).
+    #[serde(default)]
+    in_progress: HashMap<String, BTreeSet<usize>>,
+}
+
+impl DocumentRegistry {
+    /// Registro de um documento já lido.
+    pub fn get(&self, hash: &str) -> Option<&DocumentRecord> {
+        self.documents.get(hash)
+    }
+
+    /// Registra uma ingestão concluída.
+    ///
+    /// Numa reingestão, preserva a data da primeira leitura e incrementa
+    /// `ingest_count`; os demais campos refletem a ingestão mais recente.
+    pub fn record(&mut self, mut record: DocumentRecord) -> &DocumentRecord {
+        if let Some(previous) = self.documents.get(&record.hash) {
+            record.first_ingested_at = previous.first_ingested_at;
+            record.ingest_count = previous.ingest_count + 1;
+        }
+        let hash = record.hash.clone();
+        self.in_progress.remove(&hash);
+        self.documents.insert(hash.clone(), record);
+        &self.documents[&hash]
+    }
+
+    /// Remove o registro de um documento (esquecimento), com o progresso
+    /// de uma ingestão interrompida.
+    pub fn remove(&mut self, hash: &str) -> Option<DocumentRecord> {
+        self.in_progress.remove(hash);
+        self.documents.remove(hash)
+    }
+
+    /// Chunks já aplicados de uma ingestão não concluída.
+    ///
+    /// `Some` (mesmo vazio) desde que a estrutura do documento entrou na KB.
+    pub fn applied_chunks(&self, hash: &str) -> Option<&BTreeSet<usize>> {
+        self.in_progress.get(hash)
+    }
+
+    /// Marca o início de uma ingestão (estrutura do documento aplicada).
+    pub fn begin_ingestion(&mut self, hash: &str) {
+        self.in_progress.entry(hash.to_string()).or_default();
+    }
+
+    /// Marca um chunk como aplicado.
+    pub fn mark_applied(&mut self, hash: &str, chunk_index: usize) {
+        self.in_progress
+            .entry(hash.to_string())
+            .or_default()
+            .insert(chunk_index);
+    }
+
+    /// Descarta o progresso de uma ingestão (recomeça do chunk 0).
+    pub fn discard_progress(&mut self, hash: &str) -> bool {
+        self.in_progress.remove(hash).is_some()
+    }
+
+    /// Documentos lidos, mais recentes primeiro.
+    pub fn list(&self) -> Vec<&DocumentRecord> {
+        let mut records: Vec<&DocumentRecord> = self.documents.values().collect();
+        records.sort_by_key(|r| std::cmp::Reverse(r.ingested_at));
+        records
+    }
+
+    /// Número de documentos registrados.
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// `true` se nenhum documento foi lido.
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Esquece todos os documentos (reset da KB).
+    pub fn clear(&mut self) {
+        self.documents.clear();
+        self.in_progress.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(hash: &str, filename: &str, at: DateTime<Utc>) -> DocumentRecord {
+        DocumentRecord {
+            hash: hash.into(),
+            filename: filename.into(),
+            title: None,
+            format: "pdf".into(),
+            pages: Some(12),
+            size_bytes: 2048,
+            extractor_version: 1,
+            chunks: 8,
+            new_concepts: 20,
+            new_links: 5,
+            ingest_count: 1,
+            first_ingested_at: at,
+            ingested_at: at,
+        }
+    }
+
+    #[test]
+    fn reingestion_keeps_first_date_and_counts() {
+        let first = Utc::now();
+        let later = first + chrono::Duration::hours(2);
+        let mut registry = DocumentRegistry::default();
+        registry.record(record("abc", "solo.pdf", first));
+
+        let updated = registry.record(record("abc", "solo-copia.pdf", later));
+
+        assert_eq!(updated.ingest_count, 2);
+        assert_eq!(updated.first_ingested_at, first);
+        assert_eq!(updated.ingested_at, later);
+        assert_eq!(updated.filename, "solo-copia.pdf");
+        assert_eq!(registry.len(), 1);
+    }
+}
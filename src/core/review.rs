@@ -0,0 +1,491 @@
+//! # Revisão Espaçada — Reavivando o Conhecimento que Esmaece
+//!
+//! O ciclo de poda só emitia alertas "está esmaecendo". A revisão espaçada
+//! transforma conceitos e links esmaecidos em **cartões** (flashcards) que
+//! o usuário confirma ou nega na página `/revisao`, agendados no estilo
+//! SM-2 a partir da energia e da confiança de cada item.
+//!
+//! ## Quando um Item Entra na Fila
+//!
+//! | Item | Sem agenda | Com agenda |
+//! |------|-----------|------------|
+//! | Conceito | Estado Dormente ou Esmaecendo (nunca Arquivado nem conceito-tipo) | `due ≤ agora` |
+//! | Link | Energia ≤ 0.5 (exceto `InstanceOf` e links estruturais) | `due ≤ agora` |
+//!
+//! A fila é ordenada por urgência:
+//!
+//! ```text
+//! urgência = (1 − energia) + (1 − confiança) + min(atraso_dias, 7) / 7
+//! ```
+//!
+//! ## Agenda SM-2
+//!
+//! ```text
+//! ease inicial  = 1.3 + 1.2 × confiança          (confiança 1.0 → 2.5, o padrão SM-2)
+//! 1ª repetição  = 1 + 3 × confiança × energia dias
+//! 2ª repetição  = 6 dias
+//! n-ésima       = intervalo anterior × ease
+//! ease'         = max(1.3, ease + 0.1 − (5 − q) × (0.08 + (5 − q) × 0.02))
+//! q < 3         → repetições zeradas, lapso, intervalo de 1 dia
+//! ```
+//!
+//! ## Respostas
+//!
+//! | Resposta | q | Evidência | Reforço |
+//! |----------|---|-----------|---------|
+//! | [`False`](ReviewAnswer::False) | 1 | `observed(false)` | — |
+//! | [`Unsure`](ReviewAnswer::Unsure) | 2 | — | — |
+//! | [`True`](ReviewAnswer::True) | 4 | `observed(true)` | +0.3 energia |
+//! | [`Easy`](ReviewAnswer::Easy) | 5 | `observed(true)` | +0.3 energia |
+//!
+//! A evidência entra por revisão NARS no TruthValue do item; um link
+//! negado continua na KB, com frequência menor, e volta a decair.
+//!
+//! Respostas com evidência citam a conversa ([`Source::Conversation`]) na
+//! proveniência do item — e, num link confirmado, dos seus conceitos —,
+//! para que o que o usuário revisou sobreviva ao esquecimento do documento
+//! que o trouxe.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::concept::ConceptId;
+use super::link::LinkId;
+use super::{Citation, ConceptState, EntityType, KnowledgeBase, LinkKind, Source, TruthValue};
+
+/// Energia máxima de um link ainda não agendado para entrar na fila.
+const LINK_REVIEW_ENERGY: f64 = 0.5;
+
+/// Ease mínimo do SM-2.
+const MIN_EASE: f64 = 1.3;
+
+/// Agenda de revisão de um conceito ou link.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReviewSchedule {
+    /// Fator de facilidade SM-2 (≥ 1.3).
+    pub ease: f64,
+    /// Intervalo atual em dias.
+    pub interval_days: f64,
+    /// Revisões corretas consecutivas.
+    pub repetitions: u32,
+    /// Quantas vezes o item foi negado ou esquecido.
+    pub lapses: u32,
+    /// Próxima revisão.
+    pub due: DateTime<Utc>,
+    /// Última revisão respondida.
+    pub last_reviewed: DateTime<Utc>,
+}
+
+impl ReviewSchedule {
+    /// Agenda inicial, com ease derivado da confiança do item.
+    pub fn new(confidence: f64, now: DateTime<Utc>) -> Self {
+        Self {
+            ease: MIN_EASE + 1.2 * confidence.clamp(0.0, 1.0),
+            interval_days: 0.0,
+            repetitions: 0,
+            lapses: 0,
+            due: now,
+            last_reviewed: now,
+        }
+    }
+
+    /// Registra uma resposta e reagenda o item.
+    pub fn record(
+        &mut self,
+        answer: ReviewAnswer,
+        energy: f64,
+        confidence: f64,
+        now: DateTime<Utc>,
+    ) {
+        let q = answer.quality();
+        if q < 3 {
+            self.repetitions = 0;
+            self.lapses += 1;
+            self.interval_days = 1.0;
+        } else {
+            self.repetitions += 1;
+            self.interval_days = match self.repetitions {
+                1 => 1.0 + 3.0 * confidence * energy.min(1.0),
+                2 => 6.0,
+                _ => self.interval_days * self.ease,
+            };
+        }
+        let miss = f64::from(5 - q);
+        self.ease = (self.ease + 0.1 - miss * (0.08 + miss * 0.02)).max(MIN_EASE);
+        self.last_reviewed = now;
+        self.due = now + Duration::seconds((self.interval_days * 86_400.0) as i64);
+    }
+
+    /// `true` se a revisão já venceu.
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.due <= now
+    }
+}
+
+/// Resposta do usuário a um cartão.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReviewAnswer {
+    /// O item é falso.
+    False,
+    /// O usuário não sabe — reagenda sem evidência.
+    Unsure,
+    /// O item é verdadeiro.
+    True,
+    /// Verdadeiro e óbvio — intervalo cresce mais rápido.
+    Easy,
+}
+
+impl ReviewAnswer {
+    /// Todas as respostas, na ordem dos botões.
+    pub const ALL: [ReviewAnswer; 4] = [
+        ReviewAnswer::False,
+        ReviewAnswer::Unsure,
+        ReviewAnswer::True,
+        ReviewAnswer::Easy,
+    ];
+
+    /// Identificador estável (formulários).
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReviewAnswer::False => "false",
+            ReviewAnswer::Unsure => "unsure",
+            ReviewAnswer::True => "true",
+            ReviewAnswer::Easy => "easy",
+        }
+    }
+
+    /// Interpreta o identificador de [`name()`](Self::name).
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.name() == name.trim())
+    }
+
+    /// Label do botão em PT-BR.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReviewAnswer::False => "Falso",
+            ReviewAnswer::Unsure => "Não sei",
+            ReviewAnswer::True => "Verdadeiro",
+            ReviewAnswer::Easy => "Óbvio",
+        }
+    }
+
+    /// Qualidade SM-2 (0–5).
+    fn quality(&self) -> u8 {
+        match self {
+            ReviewAnswer::False => 1,
+            ReviewAnswer::Unsure => 2,
+            ReviewAnswer::True => 4,
+            ReviewAnswer::Easy => 5,
+        }
+    }
+
+    /// Evidência NARS da resposta, se houver.
+    fn evidence(&self) -> Option<bool> {
+        match self {
+            ReviewAnswer::False => Some(false),
+            ReviewAnswer::Unsure => None,
+            ReviewAnswer::True | ReviewAnswer::Easy => Some(true),
+        }
+    }
+}
+
+/// Item revisável da KB.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReviewTarget {
+    /// Um conceito.
+    Concept(ConceptId),
+    /// Um link.
+    Link(LinkId),
+}
+
+impl ReviewTarget {
+    /// Tipo do item (`concept`/`link`), usado nos formulários.
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            ReviewTarget::Concept(_) => "concept",
+            ReviewTarget::Link(_) => "link",
+        }
+    }
+
+    /// UUID do item.
+    pub fn id(&self) -> uuid::Uuid {
+        match self {
+            ReviewTarget::Concept(id) | ReviewTarget::Link(id) => *id,
+        }
+    }
+
+    /// Reconstrói o alvo a partir de [`kind_name()`](Self::kind_name) e do UUID.
+    pub fn parse(kind: &str, id: &str) -> Option<Self> {
+        let id = uuid::Uuid::parse_str(id.trim()).ok()?;
+        match kind.trim() {
+            "concept" => Some(ReviewTarget::Concept(id)),
+            "link" => Some(ReviewTarget::Link(id)),
+            _ => None,
+        }
+    }
+}
+
+/// Cartão de revisão pronto para exibição.
+#[derive(Clone, Debug)]
+pub struct ReviewCard {
+    /// Item revisado.
+    pub target: ReviewTarget,
+    /// Frente do cartão: o conceito ou a afirmação da relação.
+    pub front: String,
+    /// Pergunta feita ao usuário.
+    pub prompt: String,
+    /// Verso do cartão: atributos, relações e origem.
+    pub context: Vec<String>,
+    /// TruthValue atual.
+    pub truth: TruthValue,
+    /// Energia atual.
+    pub energy: f64,
+    /// Agenda atual (`None` se nunca revisado).
+    pub schedule: Option<ReviewSchedule>,
+    /// Prioridade na fila.
+    pub urgency: f64,
+}
+
+/// Urgência de um item na fila.
+fn urgency(
+    truth: &TruthValue,
+    energy: f64,
+    schedule: Option<&ReviewSchedule>,
+    now: DateTime<Utc>,
+) -> f64 {
+    let overdue = schedule
+        .map(|s| (now - s.due).num_seconds() as f64 / 86_400.0)
+        .unwrap_or(0.0)
+        .clamp(0.0, 7.0);
+    (1.0 - energy) + (1.0 - truth.confidence()) + overdue / 7.0
+}
+
+/// Cartões vencidos, do mais urgente ao menos urgente.
+pub fn due_cards(kb: &KnowledgeBase, now: DateTime<Utc>) -> Vec<ReviewCard> {
+    let mut cards = Vec::new();
+
+    for concept in kb.concepts.values() {
+        let is_type = concept.entity_type.is_none()
+            && EntityType::ALL.iter().any(|t| t.label() == concept.label);
+        if concept.state == ConceptState::Archived || is_type {
+            continue;
+        }
+        let due = match &concept.review {
+            Some(schedule) => schedule.is_due(now),
+            None => matches!(concept.state, ConceptState::Dormant | ConceptState::Fading),
+        };
+        if !due {
+            continue;
+        }
+        let context = concept
+            .distinct_attributes()
+            .map(|a| a.describe())
+            .chain(
+                kb.links_for_concept(concept.id)
+                    .into_iter()
+                    .take(3)
+                    .map(|l| kb.describe_link(l)),
+            )
+            .collect();
+        cards.push(ReviewCard {
+            target: ReviewTarget::Concept(concept.id),
+            front: concept.label.clone(),
+            prompt: format!("'{}' ainda é correto e relevante para você?", concept.label),
+            context,
+            truth: concept.truth.clone(),
+            energy: concept.energy,
+            schedule: concept.review.clone(),
+            urgency: urgency(&concept.truth, concept.energy, concept.review.as_ref(), now),
+        });
+    }
+
+    for link in kb
+        .links
+        .values()
+        .filter(|l| l.kind != LinkKind::InstanceOf && l.origin != super::LinkOrigin::Structural)
+    {
+        let due = match &link.review {
+            Some(schedule) => schedule.is_due(now),
+            None => link.energy <= LINK_REVIEW_ENERGY,
+        };
+        if !due {
+            continue;
+        }
+        let endpoints = link
+            .subject()
+            .and_then(|s| kb.concepts.get(&s))
+            .zip(link.object().and_then(|o| kb.concepts.get(&o)));
+        if endpoints.is_some_and(|(s, o)| {
+            s.state == ConceptState::Archived || o.state == ConceptState::Archived
+        }) {
+            continue;
+        }
+        let front = match endpoints {
+            Some((s, o)) => format!("{} {} {}", s.label, link.kind.label(), o.label),
+            None => kb.describe_link(link),
+        };
+        let origin = match &link.origin {
+            super::LinkOrigin::Inferred { rule, .. } => {
+                format!("Origem: inferido por {}", rule.to_lowercase())
+            }
+            other => format!("Origem: {}", other.label()),
+        };
+        cards.push(ReviewCard {
+            target: ReviewTarget::Link(link.id),
+            front,
+            prompt: "Esta relação é verdadeira?".to_string(),
+            context: vec![origin],
+            truth: link.truth.clone(),
+            energy: link.energy,
+            schedule: link.review.clone(),
+            urgency: urgency(&link.truth, link.energy, link.review.as_ref(), now),
+        });
+    }
+
+    cards.sort_by(|a, b| {
+        b.urgency
+            .partial_cmp(&a.urgency)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    cards
+}
+
+/// Aplica a resposta do usuário: evidência NARS, reforço e nova agenda.
+///
+/// Retorna a mensagem de feedback, ou `None` se o item não existe.
+pub fn apply_answer(
+    kb: &mut KnowledgeBase,
+    target: ReviewTarget,
+    answer: ReviewAnswer,
+    now: DateTime<Utc>,
+) -> Option<String> {
+    let (label, truth, schedule) = match target {
+        ReviewTarget::Concept(id) => {
+            let concept = kb.concepts.get_mut(&id)?;
+            if let Some(positive) = answer.evidence() {
+                concept.truth = concept.truth.revision(&TruthValue::observed(positive));
+            }
+            if answer.evidence() == Some(true) {
+                concept.reinforce();
+                concept.provenance.record(&Source::Conversation);
+            }
+            let schedule = concept
+                .review
+                .get_or_insert_with(|| ReviewSchedule::new(concept.truth.confidence(), now));
+            schedule.record(answer, concept.energy, concept.truth.confidence(), now);
+            (
+                concept.label.clone(),
+                concept.truth.clone(),
+                schedule.clone(),
+            )
+        }
+        ReviewTarget::Link(id) => {
+            let link = kb.links.get_mut(&id)?;
+            if let Some(positive) = answer.evidence() {
+                let observation = TruthValue::observed(positive);
+                link.truth = link.truth.revision(&observation);
+                link.provenance
+                    .cite_truth(&Citation::from(Source::Conversation), &observation);
+            }
+            if answer.evidence() == Some(true) {
+                link.reinforce();
+                let participants: Vec<ConceptId> =
+                    link.participants.iter().map(|p| p.concept_id).collect();
+                for id in &participants {
+                    if let Some(concept) = kb.concepts.get_mut(id) {
+                        concept.mention_count += 1;
+                        concept.provenance.record(&Source::Conversation);
+                    }
+                }
+            }
+            let schedule = link
+                .review
+                .get_or_insert_with(|| ReviewSchedule::new(link.truth.confidence(), now));
+            schedule.record(answer, link.energy, link.truth.confidence(), now);
+            let (truth, schedule) = (link.truth.clone(), schedule.clone());
+            let link = kb.links.get(&id)?;
+            (kb.describe_link(link), truth, schedule)
+        }
+    };
+    Some(format!(
+        "{}: {} → {} — próxima revisão em {:.1} dias",
+        answer.label(),
+        label,
+        truth,
+        schedule.interval_days
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Concept, Link, Participant, Role};
+
+    #[test]
+    fn intervals_grow_with_correct_answers_and_reset_on_lapse() {
+        let now = Utc::now();
+        let mut schedule = ReviewSchedule::new(0.8, now);
+        schedule.record(ReviewAnswer::True, 0.5, 0.8, now);
+        assert!((schedule.interval_days - 2.2).abs() < 1e-9);
+        schedule.record(ReviewAnswer::True, 0.5, 0.8, now);
+        assert_eq!(schedule.interval_days, 6.0);
+        schedule.record(ReviewAnswer::Easy, 0.5, 0.8, now);
+        assert!(schedule.interval_days > 6.0 * MIN_EASE);
+
+        schedule.record(ReviewAnswer::False, 0.5, 0.8, now);
+        assert_eq!((schedule.repetitions, schedule.lapses), (0, 1));
+        assert_eq!(schedule.interval_days, 1.0);
+        assert!(schedule.ease >= MIN_EASE);
+        assert!(!schedule.is_due(now));
+    }
+
+    #[test]
+    fn fading_items_are_queued_and_answers_feed_back() {
+        let now = Utc::now();
+        let mut kb = KnowledgeBase::new();
+        let solo = kb.add_concept(Concept::new("solo".into(), TruthValue::proto()));
+        let chuva = kb.add_concept(Concept::new("chuva".into(), TruthValue::proto()));
+        let link = kb.add_link(Link::new(
+            LinkKind::Implication,
+            vec![
+                Participant {
+                    concept_id: chuva,
+                    role: Role::Subject,
+                },
+                Participant {
+                    concept_id: solo,
+                    role: Role::Object,
+                },
+            ],
+            TruthValue::new(0.9, 0.3),
+        ));
+        assert!(due_cards(&kb, now).is_empty());
+
+        for _ in 0..30 {
+            kb.decay_cycle();
+        }
+        let cards = due_cards(&kb, now);
+        assert_eq!(cards.len(), 3);
+        assert_eq!(
+            cards
+                .iter()
+                .find(|c| c.target == ReviewTarget::Link(link))
+                .unwrap()
+                .front,
+            "chuva ⇒ solo"
+        );
+
+        let before = kb.links[&link].truth.confidence();
+        apply_answer(&mut kb, ReviewTarget::Link(link), ReviewAnswer::True, now).unwrap();
+        apply_answer(
+            &mut kb,
+            ReviewTarget::Concept(solo),
+            ReviewAnswer::False,
+            now,
+        )
+        .unwrap();
+        assert!(kb.links[&link].truth.confidence() > before);
+        assert!(kb.concepts[&solo].truth.frequency() < 0.5);
+        assert_eq!(due_cards(&kb, now).len(), 1);
+    }
+}
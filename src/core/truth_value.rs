@@ -90,7 +90,7 @@ const EVIDENTIAL_HORIZON: f64 = 1.0;
 /// let tv = TruthValue::new(0.8, 0.5);
 /// println!("{}", tv); // ⟨0.80, 0.50⟩
 /// ```
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TruthValue {
     /// Evidência positiva (w⁺) — quantidade de observações a favor.
     positive_evidence: f64,
@@ -246,6 +246,15 @@ impl TruthValue {
         }
     }
 
+    /// Desfaz uma [revisão](Self::revision): retira a evidência de `other`
+    /// (nunca abaixo de zero). Usada ao esquecer a fonte que a trouxe.
+    pub fn without(&self, other: &TruthValue) -> TruthValue {
+        TruthValue {
+            positive_evidence: (self.positive_evidence - other.positive_evidence).max(0.0),
+            negative_evidence: (self.negative_evidence - other.negative_evidence).max(0.0),
+        }
+    }
+
     /// **Regra de Dedução NARS** — `S→M + M→P ⊢ S→P`
     ///
     /// Se sabemos que "S implica M" e "M implica P", podemos deduzir que
@@ -0,0 +1,328 @@
+//! # Chunking — Sentenças Inteiras, Páginas e Sobreposição
+//!
+//! A ingestão processa o documento em chunks de ~[`ChunkConfig::max_chars`]
+//! caracteres. Cada chunk:
+//!
+//! - contém **sentenças inteiras** ([`SentenceSegmenter`]); só uma sentença
+//!   maior que o limite é cortada, em espaço;
+//! - não cruza seções nem páginas (PDFs têm uma [`Section`] por página);
+//! - pode repetir as últimas sentenças do chunk anterior
+//!   ([`ChunkConfig::overlap_chars`]);
+//! - carrega sua localização: seção, página e offsets no texto extraído.
+//!
+//! ```text
+//! seção/página ──▶ sentenças ──▶ empacota até max_chars ──▶ Chunk
+//!                                   │                        ├── section, page
+//!                                   └── sobreposição: as     ├── start..end (bytes)
+//!                                       últimas sentenças    └── text (título + sentenças)
+//!                                       que cabem em
+//!                                       overlap_chars
+//! ```
+//!
+//! Os offsets contam bytes no texto extraído do documento inteiro, com as
+//! seções concatenadas na ordem e separadas por `\n\n`. O [`Chunker`]
+//! mantém essa contagem quando as seções chegam página a página.
+//!
+//! ## Configuração
+//!
+//! | Variável | Padrão | Efeito |
+//! |----------|--------|--------|
+//! | `CHUNK_SIZE` | 500 | Caracteres por chunk (mínimo 100) |
+//! | `CHUNK_OVERLAP` | 0 | Caracteres repetidos do chunk anterior (até metade do chunk) |
+//!
+//! A sobreposição vem desligada: sentenças repetidas são extraídas de
+//! novo e reforçam os mesmos conceitos duas vezes.
+
+use crate::nlu::sentence::SentenceSegmenter;
+
+use super::Section;
+
+/// Tamanho e sobreposição dos chunks.
+#[derive(Clone, Debug)]
+pub struct ChunkConfig {
+    /// Caracteres por chunk.
+    pub max_chars: usize,
+    /// Caracteres do fim do chunk anterior repetidos no início do próximo.
+    pub overlap_chars: usize,
+}
+
+impl Default for ChunkConfig {
+    fn default() -> Self {
+        Self {
+            max_chars: 500,
+            overlap_chars: 0,
+        }
+    }
+}
+
+impl ChunkConfig {
+    /// Lê `CHUNK_SIZE` e `CHUNK_OVERLAP` do ambiente.
+    pub fn from_env() -> Self {
+        let max_chars = std::env::var("CHUNK_SIZE")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(500usize)
+            .max(100);
+        let overlap_chars = std::env::var("CHUNK_OVERLAP")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(0usize)
+            .min(max_chars / 2);
+        Self {
+            max_chars,
+            overlap_chars,
+        }
+    }
+}
+
+/// Trecho de texto processado de uma vez, com sua localização.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chunk {
+    /// Caminho de títulos da seção ("Capítulo 2 › Irrigação").
+    pub section: Option<String>,
+    /// Página (1-indexed), em formatos paginados.
+    pub page: Option<usize>,
+    /// Início (byte) no texto extraído do documento.
+    pub start: usize,
+    /// Fim (byte, exclusivo) no texto extraído do documento.
+    pub end: usize,
+    /// Texto do chunk, aberto pelo título da seção.
+    pub text: String,
+    /// Bytes iniciais de `text` (título incluído) que repetem o fim do
+    /// chunk anterior da seção; 0 sem sobreposição.
+    pub overlap: usize,
+}
+
+/// Chunka cada seção separadamente e abre cada chunk com o título da seção.
+///
+/// O título entra com `": "` (`"Irrigação: O gotejamento..."`) para ficar
+/// na primeira sentença do chunk e coocorrer com os conceitos dela.
+pub fn chunk_sections(
+    sections: &[Section],
+    segmenter: &SentenceSegmenter,
+    config: &ChunkConfig,
+) -> Vec<Chunk> {
+    Chunker::new(segmenter, config.clone()).push(sections)
+}
+
+/// Chunking incremental: as seções chegam aos poucos (uma página por vez,
+/// na extração incremental) e os offsets continuam contando do início do
+/// documento.
+pub struct Chunker<'a> {
+    /// Segmentador de sentenças.
+    segmenter: &'a SentenceSegmenter,
+    /// Tamanho e sobreposição.
+    config: ChunkConfig,
+    /// Offset (byte) da próxima seção no texto do documento.
+    offset: usize,
+    /// Chunks produzidos até aqui.
+    count: usize,
+}
+
+impl<'a> Chunker<'a> {
+    /// Chunker no início do documento.
+    pub fn new(segmenter: &'a SentenceSegmenter, config: ChunkConfig) -> Self {
+        Self {
+            segmenter,
+            config,
+            offset: 0,
+            count: 0,
+        }
+    }
+
+    /// Chunks das próximas seções do documento.
+    pub fn push(&mut self, sections: &[Section]) -> Vec<Chunk> {
+        let mut chunks = Vec::new();
+        for section in sections {
+            let path = section.path();
+            let mut previous_end = 0;
+            for (start, end) in chunk_spans(&section.text, self.segmenter, &self.config) {
+                let body = super::collapse_whitespace(&section.text[start..end]);
+                let text = match section.title() {
+                    Some(title) if title.ends_with(['.', '!', '?', ':']) => {
+                        format!("{} {}", title, body)
+                    }
+                    Some(title) => format!("{}: {}", title, body),
+                    None => body.clone(),
+                };
+                let overlap = if previous_end > start {
+                    text.len() - body.len()
+                        + super::collapse_whitespace(&section.text[start..previous_end]).len()
+                } else {
+                    0
+                };
+                previous_end = end;
+                chunks.push(Chunk {
+                    section: path.clone(),
+                    page: section.page,
+                    start: self.offset + start,
+                    end: self.offset + end,
+                    text,
+                    overlap,
+                });
+            }
+            self.offset += section.text.len() + 2;
+        }
+        self.count += chunks.len();
+        tracing::debug!(
+            chunks = chunks.len(),
+            total = self.count,
+            max_chars = self.config.max_chars,
+            overlap_chars = self.config.overlap_chars,
+            "Chunking concluído"
+        );
+        chunks
+    }
+
+    /// Chunks produzidos até aqui (o índice do próximo chunk).
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// Spans `(início, fim)` dos chunks de um texto.
+fn chunk_spans(
+    text: &str,
+    segmenter: &SentenceSegmenter,
+    config: &ChunkConfig,
+) -> Vec<(usize, usize)> {
+    let pieces: Vec<(usize, usize)> = segmenter
+        .split(text)
+        .into_iter()
+        .flat_map(|s| split_long(text, s.start, s.end, config.max_chars))
+        .collect();
+    let chars = |from: usize, to: usize| text[pieces[from].0..pieces[to].1].chars().count();
+
+    let mut spans = Vec::new();
+    let mut first = 0;
+    while first < pieces.len() {
+        let mut last = first;
+        while last + 1 < pieces.len() && chars(first, last + 1) <= config.max_chars {
+            last += 1;
+        }
+        spans.push((pieces[first].0, pieces[last].1));
+        if last + 1 == pieces.len() {
+            break;
+        }
+
+        // Recua até onde a sobreposição cabe, sempre avançando ao menos uma peça
+        let mut next = last + 1;
+        while next - 1 > first && chars(next - 1, last) <= config.overlap_chars {
+            next -= 1;
+        }
+        first = next;
+    }
+    spans
+}
+
+/// Corta uma sentença maior que `max_chars` em pedaços, no último espaço antes do limite.
+fn split_long(text: &str, start: usize, end: usize, max_chars: usize) -> Vec<(usize, usize)> {
+    let mut pieces = Vec::new();
+    let mut from = start;
+    while text[from..end].chars().count() > max_chars {
+        let limit = text[from..end]
+            .char_indices()
+            .nth(max_chars)
+            .map_or(end, |(i, _)| from + i);
+        let cut = text[from..limit]
+            .rfind(char::is_whitespace)
+            .map(|i| from + i)
+            .filter(|&i| i > from)
+            .unwrap_or(limit);
+        pieces.push((from, cut));
+        from = cut + text[cut..end].len() - text[cut..end].trim_start().len();
+    }
+    if from < end {
+        pieces.push((from, end));
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(text: &str, page: Option<usize>) -> Section {
+        Section {
+            headings: Vec::new(),
+            text: text.into(),
+            page,
+        }
+    }
+
+    #[test]
+    fn chunks_keep_sentences_whole_and_carry_page_and_offsets() {
+        let sections = [
+            section("Primeira sentença curta. Segunda sentença, um pouco maior que a primeira? Terceira!", Some(1)),
+            section("Outra página começa aqui.", Some(2)),
+        ];
+        let config = ChunkConfig {
+            max_chars: 60,
+            overlap_chars: 0,
+        };
+        let chunks = chunk_sections(&sections, &SentenceSegmenter::new(), &config);
+
+        let texts: Vec<&str> = chunks.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "Primeira sentença curta.",
+                "Segunda sentença, um pouco maior que a primeira? Terceira!",
+                "Outra página começa aqui.",
+            ]
+        );
+        assert_eq!(chunks[1].page, Some(1));
+        assert_eq!(chunks[2].page, Some(2));
+        let joined = format!("{}\n\n{}", sections[0].text, sections[1].text);
+        assert_eq!(
+            &joined[chunks[2].start..chunks[2].end],
+            "Outra página começa aqui."
+        );
+    }
+
+    #[test]
+    fn chunker_keeps_document_offsets_across_pages() {
+        let sections = [
+            section("Primeira página aqui.", Some(1)),
+            section("Segunda página aqui.", Some(2)),
+        ];
+        let config = ChunkConfig {
+            max_chars: 60,
+            overlap_chars: 0,
+        };
+        let segmenter = SentenceSegmenter::new();
+        let mut chunker = Chunker::new(&segmenter, config.clone());
+
+        let pages: Vec<Chunk> = sections
+            .iter()
+            .flat_map(|s| chunker.push(std::slice::from_ref(s)))
+            .collect();
+        assert_eq!(pages, chunk_sections(&sections, &segmenter, &config));
+        assert_eq!(chunker.count(), 2);
+    }
+
+    #[test]
+    fn overlap_repeats_trailing_sentences() {
+        let sections = [section(
+            "Um dois três. Quatro cinco seis. Sete oito nove. Dez onze doze.",
+            None,
+        )];
+        let config = ChunkConfig {
+            max_chars: 35,
+            overlap_chars: 20,
+        };
+        let chunks = chunk_sections(&sections, &SentenceSegmenter::new(), &config);
+
+        let texts: Vec<&str> = chunks.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "Um dois três. Quatro cinco seis.",
+                "Quatro cinco seis. Sete oito nove.",
+                "Sete oito nove. Dez onze doze."
+            ]
+        );
+        let repeated: Vec<&str> = chunks.iter().map(|c| &c.text[..c.overlap]).collect();
+        assert_eq!(repeated, vec!["", "Quatro cinco seis.", "Sete oito nove."]);
+    }
+}
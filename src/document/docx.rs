@@ -0,0 +1,97 @@
+//! # DOCX — Documentos Word
+//!
+//! Um `.docx` é um ZIP; o texto fica em `word/document.xml`, um `<w:p>`
+//! por parágrafo e o texto em `<w:t>`. Títulos são parágrafos com estilo
+//! `Heading N` (Word em inglês), `TtuloN`/`Título N` (Word em português)
+//! ou `Title` (nível 1).
+
+use std::io::Cursor;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use unicode_normalization::UnicodeNormalization;
+
+use super::{
+    decode_entities, read_entry, DocumentExtractor, DocumentFormat, Section, SectionBuilder,
+};
+
+/// Extrator de DOCX.
+pub struct DocxExtractor {
+    /// Parágrafo `<w:p>…</w:p>`.
+    paragraph: Regex,
+    /// Estilo do parágrafo `<w:pStyle w:val="Heading2"/>`.
+    style: Regex,
+    /// Texto `<w:t>`, tabulação e quebra de linha.
+    run: Regex,
+}
+
+impl Default for DocxExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DocxExtractor {
+    /// Compila as regexes do `document.xml`.
+    pub fn new() -> Self {
+        Self {
+            paragraph: Regex::new(r"(?s)<w:p[ >].*?</w:p>").expect("invalid regex"),
+            style: Regex::new(r#"<w:pStyle\s+w:val="([^"]*)""#).expect("invalid regex"),
+            run: Regex::new(r"(?s)<w:t(?:\s[^>]*)?>(.*?)</w:t>|<w:(tab|br|cr)\b[^>]*/>")
+                .expect("invalid regex"),
+        }
+    }
+}
+
+/// Nível de título de um estilo de parágrafo (`Heading2` → 2).
+fn heading_level(style: &str) -> Option<u8> {
+    let lower = style.to_lowercase();
+    if lower == "title" || lower == "ttulo" || lower == "título" {
+        return Some(1);
+    }
+    let digits = ["heading", "ttulo", "título"]
+        .iter()
+        .find_map(|prefix| lower.strip_prefix(prefix))?;
+    digits
+        .trim()
+        .parse::<u8>()
+        .ok()
+        .filter(|n| (1..=9).contains(n))
+}
+
+impl DocumentExtractor for DocxExtractor {
+    fn format(&self) -> DocumentFormat {
+        DocumentFormat::Docx
+    }
+
+    fn extract(&self, bytes: &[u8]) -> Result<Vec<Section>> {
+        let mut archive =
+            zip::ZipArchive::new(Cursor::new(bytes)).context("DOCX não é um ZIP válido")?;
+        let xml =
+            read_entry(&mut archive, "word/document.xml").context("DOCX sem word/document.xml")?;
+        let xml: String = xml.nfc().collect();
+
+        let mut builder = SectionBuilder::default();
+        for paragraph in self.paragraph.find_iter(&xml) {
+            let paragraph = paragraph.as_str();
+            let text: String = self
+                .run
+                .captures_iter(paragraph)
+                .map(|c| {
+                    c.get(1)
+                        .map(|t| decode_entities(t.as_str()))
+                        .unwrap_or_else(|| " ".into())
+                })
+                .collect();
+            let level = self
+                .style
+                .captures(paragraph)
+                .and_then(|c| heading_level(&c[1]));
+            match level {
+                Some(level) => builder.heading(level, &text),
+                None => builder.paragraph(&text),
+            }
+        }
+        Ok(builder.finish())
+    }
+}
@@ -0,0 +1,157 @@
+//! # EPUB — Livros
+//!
+//! Um `.epub` é um ZIP de capítulos XHTML. A ordem de leitura vem do
+//! pacote OPF:
+//!
+//! ```text
+//! META-INF/container.xml  → <rootfile full-path="OEBPS/content.opf">
+//! OEBPS/content.opf       → <manifest> (id → href) + <spine> (ordem dos idrefs)
+//! OEBPS/cap1.xhtml ...    → HtmlExtractor, capítulo a capítulo
+//! ```
+//!
+//! Sem OPF legível, os arquivos `.xhtml`/`.html` do ZIP são lidos em
+//! ordem alfabética.
+//!
+//! Cada capítulo é lido uma única vez, mesmo que o spine o repita, e
+//! todas as leituras descontam de um orçamento de bytes descompactados
+//! por arquivo (`ZipBudget`): passar dele encerra a extração com erro.
+
+use std::collections::{HashMap, HashSet};
+use std::io::Cursor;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+
+use super::html::HtmlExtractor;
+use super::{
+    decode_entities, DocumentExtractor, DocumentFormat, Section, SectionBuilder, ZipBudget,
+};
+
+/// Extrator de EPUB.
+pub struct EpubExtractor {
+    /// `full-path` do rootfile em `container.xml`.
+    rootfile: Regex,
+    /// Tag `<item>` do manifest.
+    item: Regex,
+    /// Atributo `nome="valor"` de uma tag.
+    attribute: Regex,
+    /// `idref` de um `<itemref>` do spine.
+    itemref: Regex,
+    /// Extrator dos capítulos.
+    html: HtmlExtractor,
+}
+
+impl Default for EpubExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EpubExtractor {
+    /// Compila as regexes do container e do OPF.
+    pub fn new() -> Self {
+        Self {
+            rootfile: Regex::new(r#"<rootfile\b[^>]*\bfull-path="([^"]+)""#)
+                .expect("invalid regex"),
+            item: Regex::new(r"<(?:opf:)?item\b[^>]*>").expect("invalid regex"),
+            attribute: Regex::new(r#"([\w-]+)="([^"]*)""#).expect("invalid regex"),
+            itemref: Regex::new(r#"<(?:opf:)?itemref\b[^>]*\bidref="([^"]+)""#)
+                .expect("invalid regex"),
+            html: HtmlExtractor::new(),
+        }
+    }
+
+    /// Caminhos dos capítulos na ordem do spine (relativos à raiz do ZIP),
+    /// cada um uma única vez.
+    fn spine(
+        &self,
+        archive: &mut zip::ZipArchive<Cursor<&[u8]>>,
+        budget: &mut ZipBudget,
+    ) -> Option<Vec<String>> {
+        let container = budget.read(archive, "META-INF/container.xml").ok()?;
+        let opf_path = self.rootfile.captures(&container)?[1].to_string();
+        let opf = budget.read(archive, &opf_path).ok()?;
+        let base = opf_path.rsplit_once('/').map_or("", |(dir, _)| dir);
+
+        let manifest: HashMap<String, String> = self
+            .item
+            .find_iter(&opf)
+            .filter_map(|tag| {
+                let attrs: HashMap<&str, &str> = self
+                    .attribute
+                    .captures_iter(tag.as_str())
+                    .map(|c| {
+                        (
+                            c.get(1).map_or("", |m| m.as_str()),
+                            c.get(2).map_or("", |m| m.as_str()),
+                        )
+                    })
+                    .collect();
+                let id = attrs.get("id")?;
+                let href = attrs.get("href")?;
+                Some((id.to_string(), join_path(base, &decode_entities(href))))
+            })
+            .collect();
+
+        let mut seen = HashSet::new();
+        let chapters: Vec<String> = self
+            .itemref
+            .captures_iter(&opf)
+            .filter_map(|c| manifest.get(&c[1]))
+            .filter(|href| seen.insert(href.as_str()))
+            .cloned()
+            .collect();
+        (!chapters.is_empty()).then_some(chapters)
+    }
+}
+
+/// Junta um `href` do manifest ao diretório do OPF, resolvendo `.` e `..`
+/// (`OEBPS` + `../Text/c1.xhtml` → `Text/c1.xhtml`). Nunca sobe além da
+/// raiz do ZIP; `href` absoluto parte da raiz.
+fn join_path(base: &str, href: &str) -> String {
+    let base = if href.starts_with('/') { "" } else { base };
+    let mut parts: Vec<&str> = Vec::new();
+    for part in base.split('/').chain(href.split('/')) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+impl DocumentExtractor for EpubExtractor {
+    fn format(&self) -> DocumentFormat {
+        DocumentFormat::Epub
+    }
+
+    fn extract(&self, bytes: &[u8]) -> Result<Vec<Section>> {
+        let mut archive =
+            zip::ZipArchive::new(Cursor::new(bytes)).context("EPUB não é um ZIP válido")?;
+        let mut budget = ZipBudget::new();
+        let chapters = self.spine(&mut archive, &mut budget).unwrap_or_else(|| {
+            let mut names: Vec<String> = archive
+                .file_names()
+                .filter(|n| n.ends_with(".xhtml") || n.ends_with(".html") || n.ends_with(".htm"))
+                .map(String::from)
+                .collect();
+            names.sort();
+            names
+        });
+
+        let mut builder = SectionBuilder::default();
+        for chapter in &chapters {
+            match budget.read(&mut archive, chapter) {
+                Ok(xhtml) => builder.extend(self.html.sections(&xhtml)),
+                Err(e) if budget.is_exceeded() => {
+                    return Err(e.context("EPUB grande demais descompactado"))
+                }
+                Err(e) => tracing::warn!(chapter = %chapter, error = %e, "Capítulo EPUB ilegível"),
+            }
+        }
+        Ok(builder.finish())
+    }
+}
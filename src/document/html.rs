@@ -0,0 +1,109 @@
+//! # HTML — Páginas Exportadas e Capítulos de EPUB
+//!
+//! Varredura de tags por regex (sem DOM): suficiente para páginas
+//! exportadas, onde só interessam títulos e blocos de texto.
+//!
+//! | Tags | Tratamento |
+//! |------|-----------|
+//! | `<h1>`…`<h6>` | Título de seção (nível da tag) |
+//! | `<p>`, `<div>`, `<li>`, `<br>`, `<tr>`, `<blockquote>`... | Fim de parágrafo |
+//! | `<script>`, `<style>`, `<head>`, `<nav>`, `<svg>`, comentários | Descartados com o conteúdo |
+//! | Demais (`<a>`, `<em>`, `<span>`...) | Só o texto |
+//!
+//! Entidades (`&amp;`, `&#233;`) são decodificadas.
+
+use anyhow::Result;
+use regex::Regex;
+use unicode_normalization::UnicodeNormalization;
+
+use super::{decode_entities, DocumentExtractor, DocumentFormat, Section, SectionBuilder};
+
+/// Tags que delimitam blocos de texto.
+#[rustfmt::skip]
+const BLOCK_TAGS: &[&str] = &[
+    "p", "div", "li", "br", "tr", "td", "th", "section", "article", "blockquote", "ul", "ol",
+    "table", "dd", "dt", "figcaption", "pre", "hr", "body", "main", "header", "footer", "aside",
+];
+
+/// Extrator de HTML/XHTML.
+pub struct HtmlExtractor {
+    /// Elementos descartados com todo o conteúdo, e comentários.
+    hidden: Regex,
+    /// Qualquer tag de abertura ou fechamento.
+    tag: Regex,
+}
+
+impl Default for HtmlExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HtmlExtractor {
+    /// Compila as regexes da varredura.
+    pub fn new() -> Self {
+        Self {
+            hidden: Regex::new(
+                r"(?is)<!--.*?-->|<script\b.*?</script\s*>|<style\b.*?</style\s*>|<head\b.*?</head\s*>|<nav\b.*?</nav\s*>|<noscript\b.*?</noscript\s*>|<svg\b.*?</svg\s*>",
+            )
+            .expect("invalid regex"),
+            tag: Regex::new(r"(?s)<(/?)([a-zA-Z][a-zA-Z0-9:]*)[^>]*>").expect("invalid regex"),
+        }
+    }
+
+    /// Seções de um documento HTML já decodificado como texto.
+    pub fn sections(&self, html: &str) -> Vec<Section> {
+        let html: String = html.nfc().collect();
+        let visible = self.hidden.replace_all(&html, " ");
+        let mut builder = SectionBuilder::default();
+        let mut text = String::new();
+        let mut heading: Option<u8> = None;
+        let mut last = 0;
+
+        for caps in self.tag.captures_iter(&visible) {
+            let whole = caps.get(0).expect("match");
+            text.push_str(&decode_entities(&visible[last..whole.start()]));
+            last = whole.end();
+
+            let closing = &caps[1] == "/";
+            let name = caps[2].to_lowercase();
+            let name = name.rsplit(':').next().unwrap_or(&name);
+            let level = name
+                .strip_prefix('h')
+                .and_then(|n| n.parse::<u8>().ok())
+                .filter(|n| (1..=6).contains(n));
+
+            match (level, closing) {
+                (Some(level), false) => {
+                    builder.paragraph(&text);
+                    text.clear();
+                    heading = Some(level);
+                }
+                (Some(_), true) => {
+                    if let Some(level) = heading.take() {
+                        builder.heading(level, &text);
+                        text.clear();
+                    }
+                }
+                (None, _) if heading.is_none() && BLOCK_TAGS.contains(&name) => {
+                    builder.paragraph(&text);
+                    text.clear();
+                }
+                _ => {}
+            }
+        }
+        text.push_str(&decode_entities(&visible[last..]));
+        builder.paragraph(&text);
+        builder.finish()
+    }
+}
+
+impl DocumentExtractor for HtmlExtractor {
+    fn format(&self) -> DocumentFormat {
+        DocumentFormat::Html
+    }
+
+    fn extract(&self, bytes: &[u8]) -> Result<Vec<Section>> {
+        Ok(self.sections(&String::from_utf8_lossy(bytes)))
+    }
+}
@@ -0,0 +1,138 @@
+//! # Markdown — Notas com Títulos
+//!
+//! Parser por linhas, suficiente para notas: não monta AST, só separa
+//! títulos de parágrafos e remove a marcação.
+//!
+//! | Elemento | Tratamento |
+//! |----------|-----------|
+//! | `# Título` … `###### Título`, `===`/`---` sublinhado | Título de seção |
+//! | Linha em branco | Fim de parágrafo |
+//! | `- item`, `* item`, `1. item` | Um parágrafo por item |
+//! | `> citação` | Texto normal |
+//! | Blocos ```` ``` ```` / `~~~` e front matter `---` | Descartados |
+//! | `[texto](url)`, `**negrito**`, `` `código` ``, `<tag>` | Só o texto |
+
+use anyhow::Result;
+use regex::Regex;
+use unicode_normalization::UnicodeNormalization;
+
+use super::{DocumentExtractor, DocumentFormat, Section, SectionBuilder};
+
+/// Extrator de Markdown.
+pub struct MarkdownExtractor {
+    /// `# Título #`
+    atx: Regex,
+    /// Marcador de item de lista.
+    list_item: Regex,
+    /// Imagens `![alt](url)`.
+    image: Regex,
+    /// Links `[texto](url)` e `[texto][ref]`.
+    link: Regex,
+    /// Ênfase, tachado e código inline.
+    emphasis: Regex,
+    /// Tags HTML inline.
+    tag: Regex,
+}
+
+impl Default for MarkdownExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarkdownExtractor {
+    /// Compila as regexes do parser.
+    pub fn new() -> Self {
+        Self {
+            atx: Regex::new(r"^(#{1,6})\s+(.*?)\s*#*\s*$").expect("invalid regex"),
+            list_item: Regex::new(r"^\s*(?:[-*+]|\d+[.)])\s+(?:\[[ xX]\]\s+)?")
+                .expect("invalid regex"),
+            image: Regex::new(r"!\[[^\]]*\]\([^)]*\)").expect("invalid regex"),
+            link: Regex::new(r"\[([^\]]+)\](?:\([^)]*\)|\[[^\]]*\])").expect("invalid regex"),
+            emphasis: Regex::new(r"\*\*|__|~~|\*|`|\b_|_\b").expect("invalid regex"),
+            tag: Regex::new(r"</?[a-zA-Z][^>]*>").expect("invalid regex"),
+        }
+    }
+
+    /// Remove a marcação inline de um trecho.
+    fn inline(&self, text: &str) -> String {
+        let text = self.image.replace_all(text, "");
+        let text = self.link.replace_all(&text, "$1");
+        let text = self.tag.replace_all(&text, "");
+        self.emphasis.replace_all(&text, "").into_owned()
+    }
+}
+
+impl DocumentExtractor for MarkdownExtractor {
+    fn format(&self) -> DocumentFormat {
+        DocumentFormat::Markdown
+    }
+
+    fn extract(&self, bytes: &[u8]) -> Result<Vec<Section>> {
+        let source: String = String::from_utf8_lossy(bytes).nfc().collect();
+        let mut lines = source.lines().peekable();
+        let mut builder = SectionBuilder::default();
+        let mut paragraph: Vec<String> = Vec::new();
+
+        // Front matter YAML
+        if lines.peek().is_some_and(|l| l.trim() == "---") {
+            lines.next();
+            for line in lines.by_ref() {
+                if matches!(line.trim(), "---" | "...") {
+                    break;
+                }
+            }
+        }
+
+        let mut fence: Option<&str> = None;
+        for line in lines {
+            let trimmed = line.trim();
+            if let Some(marker) = fence {
+                if trimmed.starts_with(marker) {
+                    fence = None;
+                }
+                continue;
+            }
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                fence = Some(&trimmed[..3]);
+                flush(&mut builder, &mut paragraph);
+                continue;
+            }
+
+            if trimmed.is_empty() {
+                flush(&mut builder, &mut paragraph);
+            } else if let Some(caps) = self.atx.captures(trimmed) {
+                flush(&mut builder, &mut paragraph);
+                builder.heading(caps[1].len() as u8, &self.inline(&caps[2]));
+            } else if !paragraph.is_empty() && trimmed.chars().all(|c| c == '=') {
+                builder.heading(1, &paragraph.join(" "));
+                paragraph.clear();
+            } else if trimmed.len() >= 3 && trimmed.chars().all(|c| c == '-') {
+                // Sublinhado de título nível 2, ou régua horizontal
+                if !paragraph.is_empty() {
+                    builder.heading(2, &paragraph.join(" "));
+                    paragraph.clear();
+                }
+            } else if trimmed.starts_with('|') && trimmed.chars().all(|c| "|-: ".contains(c)) {
+                // Separador de tabela
+            } else if let Some(item) = self.list_item.find(line) {
+                flush(&mut builder, &mut paragraph);
+                paragraph.push(self.inline(&line[item.end()..]));
+            } else {
+                let text = trimmed.trim_start_matches('>').trim().replace('|', " ");
+                paragraph.push(self.inline(&text));
+            }
+        }
+        flush(&mut builder, &mut paragraph);
+
+        Ok(builder.finish())
+    }
+}
+
+/// Fecha o parágrafo acumulado.
+fn flush(builder: &mut SectionBuilder, paragraph: &mut Vec<String>) {
+    if !paragraph.is_empty() {
+        builder.paragraph(&paragraph.join(" "));
+        paragraph.clear();
+    }
+}
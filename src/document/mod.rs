@@ -0,0 +1,740 @@
+//! # Documentos — Texto Estruturado de Qualquer Formato
+//!
+//! A ingestão ([`crate::ingest`]) só conhecia PDF. Este módulo abstrai a
+//! extração de texto atrás do trait [`DocumentExtractor`], com uma
+//! implementação por formato. Todas devolvem o texto em [`Section`]s: os
+//! títulos do documento viram **contexto de seção** para os chunks.
+//!
+//! | Formato | Extrator | Detecção | Títulos |
+//! |---------|----------|----------|---------|
+//! | PDF | [`pdf::PdfExtractor`] | `%PDF-` | Sumário (outline/bookmarks) do PDF, uma seção por página |
+//! | Markdown | [`markdown::MarkdownExtractor`] | `.md`, `text/markdown` | `#`…`######`, `===`/`---` |
+//! | Texto | [`text::TextExtractor`] | `.txt`, `text/plain`, UTF-8 válido | — |
+//! | HTML | [`html::HtmlExtractor`] | `.html`, `text/html`, `<html`/`<!doctype` | `<h1>`…`<h6>` |
+//! | DOCX | [`docx::DocxExtractor`] | ZIP com `word/document.xml` | Estilos `Heading N`/`Título N` |
+//! | EPUB | [`epub::EpubExtractor`] | ZIP com `mimetype` = `application/epub+zip` | `<h1>`…`<h6>` dos capítulos |
+//!
+//! ## Detecção
+//!
+//! ```text
+//! bytes mágicos (%PDF-, PK\x03\x04 + entradas do ZIP)
+//!   └── MIME type do upload (ignorado se genérico: application/octet-stream)
+//!         └── extensão do arquivo
+//!               └── conteúdo: <html → HTML, UTF-8 válido → texto
+//! ```
+//!
+//! ## Seções
+//!
+//! Cada [`Section`] guarda o caminho de títulos ("Capítulo 2 › Irrigação"),
+//! o texto em parágrafos separados por linha em branco e, em PDFs, a
+//! página — uma seção por página. O [`chunk`] divide as seções em chunks
+//! de sentenças inteiras que carregam seção, página e offsets.
+//!
+//! ## Metadados
+//!
+//! Além das seções, o [`ExtractedDocument`] traz os [`DocumentMetadata`]:
+//! título, autores e palavras-chave quando o formato os declara
+//! (dicionário `Info` do PDF) e o sumário (outline do PDF ou os títulos
+//! das seções). A ingestão transforma documento, autores, palavras-chave
+//! e seções em conceitos e links ([`crate::nlu::structure`]).
+//!
+//! ## Extração Incremental
+//!
+//! [`DocumentExtractor::extract_streaming`] entrega os metadados e depois
+//! o texto em [`ExtractionStep`]s. O PDF entrega uma página por passo
+//! (e as falhas de página, sem abortar); os demais formatos, o documento
+//! inteiro num passo só. A ingestão chunka e embedda cada página enquanto
+//! as seguintes ainda estão sendo extraídas.
+//!
+//! ## Identidade do Documento
+//!
+//! [`content_hash`] (SHA-256 dos bytes) identifica o documento no
+//! registro da KB ([`crate::core::registry`]): o mesmo arquivo enviado
+//! de novo é reconhecido mesmo com outro nome. O registro guarda também
+//! título, páginas e a [`DocumentExtractor::version`] que o leu.
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+
+/// Extração de PDF (pdf_extract + normalização PT-BR).
+pub mod pdf;
+
+/// Extração de Markdown por linhas (títulos ATX/setext, marcação removida).
+pub mod markdown;
+
+/// Texto simples.
+pub mod text;
+
+/// Extração de HTML por tags (também usada nos capítulos de EPUB).
+pub mod html;
+
+/// Extração de DOCX (`word/document.xml`).
+pub mod docx;
+
+/// Extração de EPUB (capítulos na ordem do `spine`).
+pub mod epub;
+
+/// Chunking por sentenças, com página, offsets e sobreposição.
+pub mod chunk;
+
+/// Formato de documento suportado pela ingestão.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DocumentFormat {
+    /// Portable Document Format.
+    Pdf,
+    /// Markdown (notas).
+    Markdown,
+    /// Texto simples.
+    Text,
+    /// Página HTML.
+    Html,
+    /// Documento Word (Office Open XML).
+    Docx,
+    /// Livro EPUB.
+    Epub,
+}
+
+impl DocumentFormat {
+    /// Todos os formatos.
+    pub const ALL: [DocumentFormat; 6] = [
+        DocumentFormat::Pdf,
+        DocumentFormat::Markdown,
+        DocumentFormat::Text,
+        DocumentFormat::Html,
+        DocumentFormat::Docx,
+        DocumentFormat::Epub,
+    ];
+
+    /// Identificador estável (eventos SSE, logs).
+    pub fn name(&self) -> &'static str {
+        match self {
+            DocumentFormat::Pdf => "pdf",
+            DocumentFormat::Markdown => "markdown",
+            DocumentFormat::Text => "text",
+            DocumentFormat::Html => "html",
+            DocumentFormat::Docx => "docx",
+            DocumentFormat::Epub => "epub",
+        }
+    }
+
+    /// Label legível para a interface.
+    pub fn label(&self) -> &'static str {
+        match self {
+            DocumentFormat::Pdf => "PDF",
+            DocumentFormat::Markdown => "Markdown",
+            DocumentFormat::Text => "Texto",
+            DocumentFormat::Html => "HTML",
+            DocumentFormat::Docx => "DOCX",
+            DocumentFormat::Epub => "EPUB",
+        }
+    }
+
+    /// Extensões de arquivo reconhecidas (sem ponto).
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            DocumentFormat::Pdf => &["pdf"],
+            DocumentFormat::Markdown => &["md", "markdown"],
+            DocumentFormat::Text => &["txt", "text"],
+            DocumentFormat::Html => &["html", "htm", "xhtml"],
+            DocumentFormat::Docx => &["docx"],
+            DocumentFormat::Epub => &["epub"],
+        }
+    }
+
+    /// Formato a partir do identificador de [`name()`](DocumentFormat::name).
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.name() == name)
+    }
+
+    /// Formato a partir do MIME type (parâmetros como `; charset=` ignorados).
+    pub fn from_mime(mime: &str) -> Option<Self> {
+        let mime = mime.split(';').next().unwrap_or("").trim().to_lowercase();
+        match mime.as_str() {
+            "application/pdf" => Some(DocumentFormat::Pdf),
+            "text/markdown" | "text/x-markdown" => Some(DocumentFormat::Markdown),
+            "text/plain" => Some(DocumentFormat::Text),
+            "text/html" | "application/xhtml+xml" => Some(DocumentFormat::Html),
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document" => {
+                Some(DocumentFormat::Docx)
+            }
+            "application/epub+zip" => Some(DocumentFormat::Epub),
+            _ => None,
+        }
+    }
+
+    /// Formato a partir da extensão do nome do arquivo.
+    pub fn from_filename(filename: &str) -> Option<Self> {
+        let (_, ext) = filename.rsplit_once('.')?;
+        let ext = ext.to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|f| f.extensions().contains(&ext.as_str()))
+    }
+
+    /// Formato pelos bytes do conteúdo (assinatura e, em ZIPs, entradas).
+    pub fn sniff(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"%PDF-") {
+            return Some(DocumentFormat::Pdf);
+        }
+        if bytes.starts_with(b"PK\x03\x04") {
+            use std::io::Read;
+            let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).ok()?;
+            // Só o começo da entrada: roda no handler, e um `mimetype` gigante
+            // não deve ser descompactado para detectar o formato
+            let mut mimetype = Vec::new();
+            if let Ok(entry) = archive.by_name("mimetype") {
+                let _ = entry.take(MIMETYPE_MAX_BYTES).read_to_end(&mut mimetype);
+            }
+            if mimetype.trim_ascii() == b"application/epub+zip" {
+                return Some(DocumentFormat::Epub);
+            }
+            return archive
+                .by_name("word/document.xml")
+                .is_ok()
+                .then_some(DocumentFormat::Docx);
+        }
+        let head = String::from_utf8_lossy(&bytes[..bytes.len().min(512)])
+            .trim_start()
+            .to_lowercase();
+        if head.starts_with("<!doctype html") || head.starts_with("<html") {
+            return Some(DocumentFormat::Html);
+        }
+        None
+    }
+
+    /// Detecta o formato de um upload. `None` se não for suportado.
+    ///
+    /// Assinaturas binárias têm prioridade (um `.txt` que é PDF é PDF);
+    /// depois o MIME type, a extensão e, por último, texto UTF-8 válido.
+    pub fn detect(bytes: &[u8], mime: Option<&str>, filename: Option<&str>) -> Option<Self> {
+        let format = Self::sniff(bytes)
+            .or_else(|| mime.and_then(Self::from_mime))
+            .or_else(|| filename.and_then(Self::from_filename))
+            .or_else(|| {
+                std::str::from_utf8(bytes)
+                    .ok()
+                    .map(|_| DocumentFormat::Text)
+            })?;
+        // DOCX/EPUB sem assinatura ZIP não são extraíveis
+        let archive = matches!(format, DocumentFormat::Docx | DocumentFormat::Epub);
+        (!archive || bytes.starts_with(b"PK\x03\x04")).then_some(format)
+    }
+}
+
+/// Trecho do documento sob um mesmo título.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Section {
+    /// Caminho de títulos, do mais externo ao mais interno (vazio = sem título).
+    pub headings: Vec<String>,
+    /// Texto da seção, parágrafos separados por `\n\n`.
+    pub text: String,
+    /// Página de origem (1-indexed), em formatos paginados.
+    pub page: Option<usize>,
+}
+
+impl Section {
+    /// Título mais interno da seção.
+    pub fn title(&self) -> Option<&str> {
+        self.headings.last().map(String::as_str)
+    }
+
+    /// Caminho legível ("Capítulo 2 › Irrigação"), se houver títulos.
+    pub fn path(&self) -> Option<String> {
+        (!self.headings.is_empty()).then(|| self.headings.join(" › "))
+    }
+}
+
+/// Entrada do sumário: caminho de títulos até ela e página inicial.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OutlineEntry {
+    /// Caminho de títulos, do mais externo até esta entrada.
+    pub headings: Vec<String>,
+    /// Página em que a entrada começa (1-indexed), em formatos paginados.
+    pub page: Option<usize>,
+}
+
+/// Metadados do documento — conhecidos antes do texto, na extração incremental.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DocumentMetadata {
+    /// Título do documento, se identificável.
+    pub title: Option<String>,
+    /// Número de páginas (só formatos paginados).
+    pub pages: Option<usize>,
+    /// Autores declarados nos metadados.
+    pub authors: Vec<String>,
+    /// Palavras-chave declaradas nos metadados.
+    pub keywords: Vec<String>,
+    /// Sumário: os caminhos de títulos distintos, na ordem do documento.
+    pub outline: Vec<OutlineEntry>,
+}
+
+/// Documento extraído: seções + metadados para o registro.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExtractedDocument {
+    /// Seções na ordem do documento.
+    pub sections: Vec<Section>,
+    /// Título, páginas, autores, palavras-chave e sumário.
+    pub metadata: DocumentMetadata,
+}
+
+impl ExtractedDocument {
+    /// Documento sem paginação nem metadados: o título é o primeiro título
+    /// de seção e o sumário, os caminhos de títulos das seções.
+    pub fn from_sections(sections: Vec<Section>) -> Self {
+        let title = sections.iter().find_map(|s| s.headings.first().cloned());
+        let mut outline: Vec<OutlineEntry> = Vec::new();
+        for section in &sections {
+            for depth in 1..=section.headings.len() {
+                let headings = &section.headings[..depth];
+                if !outline.iter().any(|e| e.headings == headings) {
+                    outline.push(OutlineEntry {
+                        headings: headings.to_vec(),
+                        page: section.page,
+                    });
+                }
+            }
+        }
+        let metadata = DocumentMetadata {
+            title,
+            outline,
+            ..DocumentMetadata::default()
+        };
+        Self { sections, metadata }
+    }
+}
+
+/// Passo da extração incremental ([`DocumentExtractor::extract_streaming`]).
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExtractionStep {
+    /// Metadados — sempre o primeiro passo.
+    Metadata(DocumentMetadata),
+    /// Seções de uma página (PDF) ou do documento inteiro (`page = None`).
+    Sections {
+        /// Página extraída (1-indexed), em formatos paginados.
+        page: Option<usize>,
+        /// Seções com texto (vazio numa página em branco).
+        sections: Vec<Section>,
+    },
+    /// Página que não pôde ser extraída — as demais seguem.
+    PageFailed {
+        /// Página (1-indexed).
+        page: usize,
+        /// Motivo da falha.
+        error: String,
+    },
+}
+
+/// Extrator de texto estruturado de um formato de documento.
+///
+/// Implementações são síncronas e CPU-bound — a ingestão as chama em
+/// `spawn_blocking`.
+pub trait DocumentExtractor: Send + Sync {
+    /// Formato tratado por este extrator.
+    fn format(&self) -> DocumentFormat;
+
+    /// Versão do extrator, gravada no registro de documentos.
+    ///
+    /// Incrementar quando a saída mudar de forma relevante: documentos
+    /// lidos por uma versão anterior aparecem como desatualizados.
+    fn version(&self) -> u32 {
+        1
+    }
+
+    /// Extrai as seções do documento.
+    fn extract(&self, bytes: &[u8]) -> Result<Vec<Section>>;
+
+    /// Extrai seções e metadados (título, páginas, autores, palavras-chave, sumário).
+    fn extract_document(&self, bytes: &[u8]) -> Result<ExtractedDocument> {
+        self.extract(bytes).map(ExtractedDocument::from_sections)
+    }
+
+    /// Extrai passo a passo: `step` recebe os metadados e depois cada
+    /// página assim que fica pronta, e devolve `false` para interromper
+    /// (job cancelado). Um erro só encerra a extração se nenhuma página
+    /// puder ser lida.
+    ///
+    /// O padrão extrai o documento inteiro e o entrega num único passo;
+    /// formatos paginados sobrescrevem para entregar página a página.
+    fn extract_streaming(
+        &self,
+        bytes: &[u8],
+        step: &mut dyn FnMut(ExtractionStep) -> bool,
+    ) -> Result<()> {
+        let document = self.extract_document(bytes)?;
+        if step(ExtractionStep::Metadata(document.metadata)) {
+            step(ExtractionStep::Sections {
+                page: None,
+                sections: document.sections,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// SHA-256 dos bytes do documento, em hexadecimal.
+pub fn content_hash(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// `true` se `hash` tem a forma de [`content_hash`]: exatamente 64
+/// caracteres hexadecimais minúsculos. Hashes vindos da URL são checados
+/// antes de virarem nome de arquivo (`../kb` não passa).
+pub fn is_content_hash(hash: &str) -> bool {
+    hash.len() == 64 && hash.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// Primeira linha não vazia do texto, se for curta (≤ 120 caracteres) —
+/// o título de documentos sem metadados.
+pub fn title_from_text(text: &str) -> Option<String> {
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .filter(|line| line.chars().count() <= 120)
+        .map(str::to_string)
+}
+
+/// Extrator de um formato.
+pub fn extractor_for(format: DocumentFormat) -> Box<dyn DocumentExtractor> {
+    match format {
+        DocumentFormat::Pdf => Box::new(pdf::PdfExtractor),
+        DocumentFormat::Markdown => Box::new(markdown::MarkdownExtractor::new()),
+        DocumentFormat::Text => Box::new(text::TextExtractor),
+        DocumentFormat::Html => Box::new(html::HtmlExtractor::new()),
+        DocumentFormat::Docx => Box::new(docx::DocxExtractor::new()),
+        DocumentFormat::Epub => Box::new(epub::EpubExtractor::new()),
+    }
+}
+
+/// Monta [`Section`]s a partir de títulos e parágrafos, na ordem do documento.
+#[derive(Default)]
+pub(crate) struct SectionBuilder {
+    /// Títulos abertos, com o nível de cada um.
+    path: Vec<(u8, String)>,
+    /// Parágrafos da seção corrente.
+    paragraphs: Vec<String>,
+    /// Seções já fechadas.
+    sections: Vec<Section>,
+}
+
+impl SectionBuilder {
+    /// Abre um título de nível `level` (1 = mais externo), fechando a seção corrente.
+    pub(crate) fn heading(&mut self, level: u8, title: &str) {
+        let title = collapse_whitespace(title);
+        if title.is_empty() {
+            return;
+        }
+        self.flush();
+        self.path.retain(|(l, _)| *l < level);
+        self.path.push((level, title));
+    }
+
+    /// Adiciona um parágrafo à seção corrente.
+    pub(crate) fn paragraph(&mut self, text: &str) {
+        let text = collapse_whitespace(text);
+        if !text.is_empty() {
+            self.paragraphs.push(text);
+        }
+    }
+
+    /// Fecha a seção corrente (se tiver texto).
+    fn flush(&mut self) {
+        if self.paragraphs.is_empty() {
+            return;
+        }
+        self.sections.push(Section {
+            headings: self.path.iter().map(|(_, t)| t.clone()).collect(),
+            text: std::mem::take(&mut self.paragraphs).join("\n\n"),
+            page: None,
+        });
+    }
+
+    /// Seções do documento.
+    pub(crate) fn finish(mut self) -> Vec<Section> {
+        self.flush();
+        self.sections
+    }
+
+    /// Anexa as seções de outro documento (capítulos de EPUB).
+    pub(crate) fn extend(&mut self, sections: Vec<Section>) {
+        self.flush();
+        self.sections.extend(sections);
+    }
+}
+
+/// Colapsa espaços, quebras de linha e tabs em um único espaço.
+pub(crate) fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Decodifica as entidades XML/HTML mais comuns (`&amp;`, `&#231;`, `&#xE7;`...).
+pub(crate) fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Bytes lidos da entrada `mimetype` de um ZIP ao detectar EPUB.
+const MIMETYPE_MAX_BYTES: u64 = 64;
+
+/// Tamanho máximo de uma entrada de ZIP descompactada (contra ZIP bombs).
+const MAX_ENTRY_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Total descompactado máximo de um arquivo ZIP, somando as entradas lidas.
+const MAX_ARCHIVE_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Lê uma entrada de um ZIP como texto UTF-8 (até [`MAX_ENTRY_BYTES`]).
+pub(crate) fn read_entry<R: std::io::Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    name: &str,
+) -> Result<String> {
+    ZipBudget::new().read(archive, name)
+}
+
+/// Orçamento de bytes descompactados de um arquivo ZIP: cada leitura
+/// desconta do total ([`MAX_ARCHIVE_BYTES`]) e falha quando ele acaba —
+/// uma entrada muito compressível listada várias vezes não escapa do limite.
+pub(crate) struct ZipBudget {
+    /// Bytes que ainda podem ser descompactados.
+    remaining: u64,
+    /// `true` depois que uma leitura passou do orçamento.
+    exceeded: bool,
+}
+
+impl ZipBudget {
+    /// Orçamento cheio para um arquivo.
+    pub(crate) fn new() -> Self {
+        Self {
+            remaining: MAX_ARCHIVE_BYTES,
+            exceeded: false,
+        }
+    }
+
+    /// Lê uma entrada como texto UTF-8 (até [`MAX_ENTRY_BYTES`]), descontando
+    /// do orçamento.
+    pub(crate) fn read<R: std::io::Read + std::io::Seek>(
+        &mut self,
+        archive: &mut zip::ZipArchive<R>,
+        name: &str,
+    ) -> Result<String> {
+        use std::io::Read;
+        let limit = MAX_ENTRY_BYTES.min(self.remaining);
+        let mut bytes = Vec::new();
+        archive
+            .by_name(name)?
+            .take(limit + 1)
+            .read_to_end(&mut bytes)?;
+        let len = bytes.len() as u64;
+        if len > self.remaining {
+            self.exceeded = true;
+            anyhow::bail!(
+                "O ZIP passa de {} MB descompactados (entrada {})",
+                MAX_ARCHIVE_BYTES >> 20,
+                name
+            );
+        }
+        anyhow::ensure!(
+            len <= MAX_ENTRY_BYTES,
+            "Entrada {} do ZIP passa de {} MB descompactada",
+            name,
+            MAX_ENTRY_BYTES >> 20
+        );
+        self.remaining -= len;
+        Ok(String::from_utf8(bytes)?)
+    }
+
+    /// `true` se alguma leitura passou do orçamento (a extração deve parar).
+    pub(crate) fn is_exceeded(&self) -> bool {
+        self.exceeded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    /// Monta um ZIP em memória com as entradas dadas.
+    fn zip(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (name, content) in entries {
+            writer
+                .start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn detects_formats_by_magic_mime_and_extension() {
+        let docx = zip(&[("word/document.xml", "<w:document/>")]);
+        let epub = zip(&[("mimetype", "application/epub+zip")]);
+        assert_eq!(
+            DocumentFormat::detect(b"%PDF-1.7", None, Some("x.txt")),
+            Some(DocumentFormat::Pdf)
+        );
+        assert_eq!(
+            DocumentFormat::detect(&docx, None, None),
+            Some(DocumentFormat::Docx)
+        );
+        assert_eq!(
+            DocumentFormat::detect(&epub, None, None),
+            Some(DocumentFormat::Epub)
+        );
+        assert_eq!(
+            DocumentFormat::detect(b"# Nota", None, Some("nota.md")),
+            Some(DocumentFormat::Markdown)
+        );
+        assert_eq!(
+            DocumentFormat::detect(b"<p>oi</p>", Some("text/html; charset=utf-8"), None),
+            Some(DocumentFormat::Html)
+        );
+        assert_eq!(
+            DocumentFormat::detect(b"<!DOCTYPE html><p>oi</p>", None, None),
+            Some(DocumentFormat::Html)
+        );
+        assert_eq!(
+            DocumentFormat::detect("solo fértil".as_bytes(), None, None),
+            Some(DocumentFormat::Text)
+        );
+        assert_eq!(
+            DocumentFormat::detect(&[0xff, 0xfe, 0x00], None, Some("a.docx")),
+            None
+        );
+    }
+
+    #[test]
+    fn markdown_and_html_keep_headings_as_section_context() {
+        let md = "# Solo\n\nA **erosão** reduz a [produtividade](http://x).\n\n## Irrigação\n\n- gotejamento\n\n```\ncódigo\n```\n";
+        let sections = extractor_for(DocumentFormat::Markdown)
+            .extract(md.as_bytes())
+            .unwrap();
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].text, "A erosão reduz a produtividade.");
+        assert_eq!(sections[1].path().as_deref(), Some("Solo › Irrigação"));
+        assert_eq!(sections[1].text, "gotejamento");
+
+        let page = "<html><head><title>t</title><style>p{}</style></head><body><h1>Clima</h1>\
+                    <p>Secas &amp; chuvas</p><script>x()</script><h2>Nordeste</h2><p>Calor&#233;</p></body></html>";
+        let sections = extractor_for(DocumentFormat::Html)
+            .extract(page.as_bytes())
+            .unwrap();
+        assert_eq!(sections[0].title(), Some("Clima"));
+        assert_eq!(sections[0].text, "Secas & chuvas");
+        assert_eq!(sections[1].headings, vec!["Clima", "Nordeste"]);
+        assert_eq!(sections[1].text, "Caloré");
+    }
+
+    #[test]
+    fn reads_docx_and_epub_archives() {
+        let docx = zip(&[(
+            "word/document.xml",
+            r#"<w:document><w:body><w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t>Motor</w:t></w:r></w:p><w:p><w:r><w:t xml:space="preserve">O motor </w:t></w:r><w:r><w:t>aquece.</w:t></w:r></w:p></w:body></w:document>"#,
+        )]);
+        let sections = extractor_for(DocumentFormat::Docx).extract(&docx).unwrap();
+        assert_eq!(
+            sections,
+            vec![Section {
+                headings: vec!["Motor".into()],
+                text: "O motor aquece.".into(),
+                page: None
+            }]
+        );
+
+        let epub = zip(&[
+            ("mimetype", "application/epub+zip"),
+            (
+                "META-INF/container.xml",
+                r#"<container><rootfiles><rootfile full-path="OEBPS/content.opf"/></rootfiles></container>"#,
+            ),
+            (
+                "OEBPS/content.opf",
+                r#"<package><manifest><item id="c2" href="../Text/./c2.xhtml" media-type="application/xhtml+xml"/><item id="c1" href="c1.xhtml" media-type="application/xhtml+xml"/></manifest><spine><itemref idref="c1"/><itemref idref="c2"/></spine></package>"#,
+            ),
+            (
+                "OEBPS/c1.xhtml",
+                "<html><body><h1>Um</h1><p>Primeiro.</p></body></html>",
+            ),
+            (
+                "Text/c2.xhtml",
+                "<html><body><h1>Dois</h1><p>Segundo.</p></body></html>",
+            ),
+        ]);
+        let sections = extractor_for(DocumentFormat::Epub).extract(&epub).unwrap();
+        let titles: Vec<_> = sections.iter().filter_map(|s| s.title()).collect();
+        assert_eq!(titles, vec!["Um", "Dois"]);
+    }
+
+    #[test]
+    fn zip_reads_stop_when_the_archive_budget_runs_out() {
+        let mut archive =
+            zip::ZipArchive::new(std::io::Cursor::new(zip(&[("a.xml", "0123456789")]))).unwrap();
+        let mut budget = ZipBudget {
+            remaining: 15,
+            exceeded: false,
+        };
+        assert_eq!(budget.read(&mut archive, "a.xml").unwrap(), "0123456789");
+        assert!(!budget.is_exceeded());
+        assert!(budget.read(&mut archive, "a.xml").is_err());
+        assert!(budget.is_exceeded());
+    }
+
+    #[test]
+    fn epub_reads_each_spine_chapter_once() {
+        let spine = "<itemref idref=\"c1\"/><itemref idref=\"c1b\"/>".repeat(300);
+        let opf = format!(
+            r#"<package><manifest><item id="c1" href="c1.xhtml"/><item id="c1b" href="./c1.xhtml"/></manifest><spine>{}</spine></package>"#,
+            spine
+        );
+        let epub = zip(&[
+            (
+                "META-INF/container.xml",
+                r#"<container><rootfile full-path="OEBPS/content.opf"/></container>"#,
+            ),
+            ("OEBPS/content.opf", &opf),
+            (
+                "OEBPS/c1.xhtml",
+                "<html><body><h1>Um</h1><p>Primeiro.</p></body></html>",
+            ),
+        ]);
+        let sections = extractor_for(DocumentFormat::Epub).extract(&epub).unwrap();
+        assert_eq!(sections.len(), 1);
+    }
+}
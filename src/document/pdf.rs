@@ -0,0 +1,358 @@
+//! # PDF — pdf_extract + Normalização PT-BR, Página a Página
+//!
+//! O texto sai corrido, em uma [`Section`] **por página** (páginas vazias
+//! são puladas), para que cada chunk saiba de que página veio. A
+//! normalização junta sufixos que o layout separou ("produ ção" → "produção").
+//!
+//! ## Extração Incremental
+//!
+//! O documento é carregado uma vez (`lopdf`, re-exportado pelo
+//! `pdf_extract`) e as páginas saem uma a uma em
+//! [`extract_streaming`](DocumentExtractor::extract_streaming):
+//!
+//! ```text
+//! load_mem ─▶ Metadata (Info + sumário + nº de páginas)
+//!          ─▶ p.1 ─▶ Sections ─▶ (ingestão já chunka e embedda)
+//!          ─▶ p.2 ✗ ─▶ PageFailed (erro ou panic do pdf_extract) ─▶ segue
+//!          ─▶ p.3 ─▶ Sections ...
+//! ```
+//!
+//! Uma página ilegível (fonte quebrada, stream corrompido) vira
+//! [`ExtractionStep::PageFailed`] e a extração continua — antes, a
+//! primeira página com erro encerrava a leitura em silêncio. Panics do
+//! `pdf_extract` numa página são contidos do mesmo jeito. Só um arquivo
+//! que nem carrega (ou cifrado com senha) falha inteiro.
+//!
+//! ## Metadados e Sumário
+//!
+//! | Fonte | Campo | Uso |
+//! |-------|-------|-----|
+//! | `Info /Title` | título | Registro e conceito do documento (ignorado se genérico: "Microsoft Word - x.docx") |
+//! | `Info /Author` | autores | Separados por `;`, `&`, " e "/" and " (vírgula só entre nomes completos) |
+//! | `Info /Keywords` | palavras-chave | Separadas por `;` ou `,` |
+//! | Outline (bookmarks) | títulos de seção | Cada página recebe o caminho do sumário vigente nela |
+//!
+//! ```text
+//! outline:  1 Introdução (p.1)   2 Métodos (p.3)   2.1 Coleta (p.4)
+//! páginas:  p.1, p.2 → [Introdução]
+//!           p.3      → [Métodos]
+//!           p.4, p.5 → [Métodos, Coleta]
+//! ```
+//!
+//! A granularidade é a página: um capítulo que começa no meio da página
+//! já vale para ela inteira. Sem título nos metadados, a primeira linha
+//! curta da primeira página com texto serve de título
+//! ([`title_from_text`](super::title_from_text)). Metadados ou sumário
+//! ilegíveis são ignorados — o texto continua sendo extraído.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::LazyLock;
+
+use anyhow::{Context, Result};
+use pdf_extract::{Dictionary, Document, PlainTextOutput};
+use regex::Regex;
+use unicode_normalization::UnicodeNormalization;
+
+use super::{
+    collapse_whitespace, title_from_text, DocumentExtractor, DocumentFormat, DocumentMetadata,
+    ExtractedDocument, ExtractionStep, OutlineEntry, Section,
+};
+
+/// Palavra separada do sufixo por espaço ("informa ção"), compilada uma vez.
+static BROKEN_SUFFIX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(\w+)\s+(ção|ções|cia|ência|ância|mente|dade|ável|ível|nal|gem|tico|tica|tura|mento|são|sões|oso|osa|ivo|iva|ismo|ista)")
+        .expect("invalid regex")
+});
+
+/// Normaliza texto extraído de PDF para Português Brasileiro.
+pub fn normalize_pdf_text(text: &str) -> String {
+    let normalized: String = text.nfc().collect();
+    BROKEN_SUFFIX.replace_all(&normalized, "$1$2").into_owned()
+}
+
+/// Extrator de PDF via `pdf_extract`.
+pub struct PdfExtractor;
+
+impl DocumentExtractor for PdfExtractor {
+    fn format(&self) -> DocumentFormat {
+        DocumentFormat::Pdf
+    }
+
+    /// 3: páginas ilegíveis são puladas (antes encerravam a extração).
+    fn version(&self) -> u32 {
+        3
+    }
+
+    fn extract(&self, bytes: &[u8]) -> Result<Vec<Section>> {
+        self.extract_document(bytes).map(|doc| doc.sections)
+    }
+
+    fn extract_document(&self, bytes: &[u8]) -> Result<ExtractedDocument> {
+        let mut document = ExtractedDocument::default();
+        self.extract_streaming(bytes, &mut |step| {
+            match step {
+                ExtractionStep::Metadata(metadata) => document.metadata = metadata,
+                ExtractionStep::Sections { sections, .. } => document.sections.extend(sections),
+                ExtractionStep::PageFailed { page, error } => {
+                    tracing::warn!(page, error = %error, "Página do PDF ignorada")
+                }
+            }
+            true
+        })?;
+        if document.metadata.title.is_none() {
+            document.metadata.title = document
+                .sections
+                .first()
+                .and_then(|s| title_from_text(&s.text));
+        }
+        Ok(document)
+    }
+
+    fn extract_streaming(
+        &self,
+        bytes: &[u8],
+        step: &mut dyn FnMut(ExtractionStep) -> bool,
+    ) -> Result<()> {
+        let mut doc = Document::load_mem(bytes).context("Failed to extract text from PDF")?;
+        if doc.is_encrypted() {
+            doc.decrypt("").context("PDF cifrado com senha")?;
+        }
+        let pages = doc.get_pages();
+        let info = read_info(&doc);
+        let outline = read_outline(&doc);
+        let metadata = DocumentMetadata {
+            title: info.title,
+            pages: Some(pages.len()),
+            authors: info.authors,
+            keywords: info.keywords,
+            outline: outline.clone(),
+        };
+        if !step(ExtractionStep::Metadata(metadata)) {
+            return Ok(());
+        }
+
+        for &number in pages.keys() {
+            let page = number as usize;
+            let extracted = panic::catch_unwind(AssertUnwindSafe(|| {
+                let mut text = String::new();
+                pdf_extract::output_doc_page(&doc, &mut PlainTextOutput::new(&mut text), number)
+                    .map(|()| text)
+            }));
+            let next = match extracted {
+                Ok(Ok(text)) => {
+                    let sections = if text.trim().is_empty() {
+                        Vec::new()
+                    } else {
+                        vec![Section {
+                            headings: headings_at(&outline, page),
+                            text: normalize_pdf_text(&text),
+                            page: Some(page),
+                        }]
+                    };
+                    ExtractionStep::Sections {
+                        page: Some(page),
+                        sections,
+                    }
+                }
+                Ok(Err(e)) => ExtractionStep::PageFailed {
+                    page,
+                    error: e.to_string(),
+                },
+                Err(panic) => {
+                    let error = panic
+                        .downcast_ref::<&str>()
+                        .map(|s| s.to_string())
+                        .or_else(|| panic.downcast_ref::<String>().cloned())
+                        .unwrap_or_else(|| "panic no pdf_extract".into());
+                    ExtractionStep::PageFailed { page, error }
+                }
+            };
+            if !step(next) {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Campos úteis do dicionário `Info`.
+#[derive(Default)]
+struct PdfInfo {
+    title: Option<String>,
+    authors: Vec<String>,
+    keywords: Vec<String>,
+}
+
+/// Lê título, autores e palavras-chave do dicionário `Info` do trailer.
+fn read_info(doc: &Document) -> PdfInfo {
+    let Some(info) = doc
+        .trailer
+        .get_deref(b"Info", doc)
+        .ok()
+        .and_then(|info| info.as_dict().ok())
+    else {
+        return PdfInfo::default();
+    };
+    PdfInfo {
+        title: info_text(doc, info, b"Title").and_then(|t| meaningful_title(&t)),
+        authors: info_text(doc, info, b"Author")
+            .map(|a| split_authors(&a))
+            .unwrap_or_default(),
+        keywords: info_text(doc, info, b"Keywords")
+            .map(|k| split_keywords(&k))
+            .unwrap_or_default(),
+    }
+}
+
+/// Texto (PDFDocEncoding ou UTF-16) de uma entrada do `Info`, sem espaços sobrando.
+fn info_text(doc: &Document, info: &Dictionary, key: &[u8]) -> Option<String> {
+    let object = info.get_deref(key, doc).ok()?;
+    let text = collapse_whitespace(&pdf_extract::decode_text_string(object).ok()?);
+    (!text.is_empty()).then(|| text.nfc().collect())
+}
+
+/// Sumário na ordem do documento. PDFs sem outline devolvem lista vazia.
+fn read_outline(doc: &Document) -> Vec<OutlineEntry> {
+    let Ok(toc) = doc.get_toc() else {
+        return Vec::new();
+    };
+    for error in &toc.errors {
+        tracing::debug!(error = %error, "Entrada do sumário ignorada");
+    }
+    outline_paths(
+        toc.toc
+            .into_iter()
+            .map(|entry| (entry.level, entry.title, entry.page)),
+    )
+}
+
+/// Caminho completo de cada entrada `(nível, título, página)`: cada uma
+/// fecha as abertas de nível igual ou mais interno.
+fn outline_paths(entries: impl IntoIterator<Item = (usize, String, usize)>) -> Vec<OutlineEntry> {
+    let mut open: Vec<(usize, String)> = Vec::new();
+    let mut outline = Vec::new();
+    for (level, title, page) in entries {
+        let title = collapse_whitespace(&title);
+        if title.is_empty() {
+            continue;
+        }
+        open.retain(|(l, _)| *l < level);
+        open.push((level, title));
+        outline.push(OutlineEntry {
+            headings: open.iter().map(|(_, t)| t.clone()).collect(),
+            page: Some(page),
+        });
+    }
+    outline
+}
+
+/// Caminho do sumário vigente na página `page`: o da última entrada que começa até ela.
+fn headings_at(outline: &[OutlineEntry], page: usize) -> Vec<String> {
+    outline
+        .iter()
+        .rev()
+        .find(|e| e.page.is_some_and(|p| p <= page))
+        .map(|e| e.headings.clone())
+        .unwrap_or_default()
+}
+
+/// Descarta títulos gerados por ferramentas ("Microsoft Word - tese.docx",
+/// "untitled", nomes de arquivo).
+fn meaningful_title(title: &str) -> Option<String> {
+    let title = title
+        .strip_prefix("Microsoft Word - ")
+        .unwrap_or(title)
+        .trim();
+    let lower = title.to_lowercase();
+    let generic =
+        ["untitled", "sem título", "sem titulo", "title", "título"].contains(&lower.as_str());
+    let filename = [
+        ".doc", ".docx", ".odt", ".rtf", ".tex", ".dvi", ".pdf", ".indd", ".ps",
+    ]
+    .iter()
+    .any(|ext| lower.ends_with(ext));
+    (!title.is_empty() && !generic && !filename).then(|| title.to_string())
+}
+
+/// Separa o campo `Author`: `;`, `&`, " e "/" and " sempre separam; a
+/// vírgula só quando todas as partes têm mais de uma palavra ("Silva, J."
+/// é um nome só).
+pub(crate) fn split_authors(field: &str) -> Vec<String> {
+    let pieces: Vec<String> = field
+        .replace(['&', '\n'], ";")
+        .replace(" and ", ";")
+        .replace(" e ", ";")
+        .split(';')
+        .flat_map(|piece| {
+            let parts: Vec<&str> = piece
+                .split(',')
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+                .collect();
+            if parts.len() > 1 && parts.iter().all(|p| p.split_whitespace().count() > 1) {
+                parts.into_iter().map(str::to_string).collect()
+            } else {
+                vec![piece.trim().to_string()]
+            }
+        })
+        .filter(|name| !name.is_empty())
+        .collect();
+    dedup_case_insensitive(pieces)
+}
+
+/// Separa o campo `Keywords` em `;` ou `,`.
+pub(crate) fn split_keywords(field: &str) -> Vec<String> {
+    let pieces = field
+        .split([';', ',', '\n'])
+        .map(|k| k.trim().trim_end_matches('.').trim().to_string())
+        .filter(|k| !k.is_empty())
+        .collect();
+    dedup_case_insensitive(pieces)
+}
+
+/// Remove repetições ignorando caixa, mantendo a primeira grafia.
+fn dedup_case_insensitive(items: Vec<String>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    items
+        .into_iter()
+        .filter(|item| seen.insert(item.to_lowercase()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata_fields_are_split_and_outline_maps_to_pages() {
+        assert_eq!(
+            split_authors("Maria Souza; João Lima & Ana Costa"),
+            vec!["Maria Souza", "João Lima", "Ana Costa"]
+        );
+        assert_eq!(split_authors("Silva, J."), vec!["Silva, J."]);
+        assert_eq!(
+            split_authors("Maria Souza, João Lima e Ana Costa"),
+            vec!["Maria Souza", "João Lima", "Ana Costa"]
+        );
+        assert_eq!(
+            split_keywords("solo; irrigação, Solo, chuva."),
+            vec!["solo", "irrigação", "chuva"]
+        );
+        assert_eq!(meaningful_title("Microsoft Word - tese_final.docx"), None);
+        assert_eq!(
+            meaningful_title("Manejo do Solo"),
+            Some("Manejo do Solo".into())
+        );
+
+        let outline = outline_paths([
+            (1, "Introdução".to_string(), 1),
+            (1, "Métodos".to_string(), 3),
+            (2, "Coleta".to_string(), 4),
+            (1, "Resultados".to_string(), 6),
+        ]);
+        assert!(headings_at(&outline, 0).is_empty());
+        assert_eq!(headings_at(&outline, 2), vec!["Introdução"]);
+        assert_eq!(headings_at(&outline, 5), vec!["Métodos", "Coleta"]);
+        assert_eq!(headings_at(&outline, 6), vec!["Resultados"]);
+    }
+}
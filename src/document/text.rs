@@ -0,0 +1,27 @@
+//! # Texto Simples
+//!
+//! Sem estrutura: uma única [`Section`] com o texto em NFC. Bytes que não
+//! são UTF-8 válido são substituídos (`U+FFFD`) em vez de rejeitados.
+
+use anyhow::Result;
+use unicode_normalization::UnicodeNormalization;
+
+use super::{DocumentExtractor, DocumentFormat, Section};
+
+/// Extrator de texto simples.
+pub struct TextExtractor;
+
+impl DocumentExtractor for TextExtractor {
+    fn format(&self) -> DocumentFormat {
+        DocumentFormat::Text
+    }
+
+    fn extract(&self, bytes: &[u8]) -> Result<Vec<Section>> {
+        let text: String = String::from_utf8_lossy(bytes).nfc().collect();
+        Ok(vec![Section {
+            headings: Vec::new(),
+            text: text.replace("\r\n", "\n"),
+            page: None,
+        }])
+    }
+}
@@ -37,8 +37,22 @@
 //! - Só processa links com **energia > 0.3** (links relevantes)
 //! - Só cria links que **não existem** ainda na KB (evita duplicação)
 //! - Só cria links com **confiança > 0.05** (evita ruído)
+//!
+//! ## Rederivação
+//!
+//! Quando uma premissa perde evidência ou some (documento
+//! [esquecido](crate::core::provenance::forget_document)),
+//! [`InferenceEngine::rederive`] recalcula a conclusão a partir das
+//! premissas restantes — ou de outra derivação da mesma relação.
+
+use crate::core::link::LinkId;
+use crate::core::{KnowledgeBase, Link, LinkKind, LinkOrigin, Participant, Role, TruthValue};
 
-use crate::core::{KnowledgeBase, Link, LinkKind, Participant, Role};
+/// Energia mínima dos links causais usados como premissa.
+const ENERGY_THRESHOLD: f64 = 0.3;
+
+/// Confiança mínima de uma conclusão.
+const MIN_CONFIDENCE: f64 = 0.05;
 
 /// Resultado de uma inferência — contém o novo link e uma explicação legível.
 ///
@@ -104,10 +118,9 @@ impl InferenceEngine {
     /// adicionados à KB pelo [`Orchestrator`](crate::orchestrator::Orchestrator).
     pub fn infer(kb: &KnowledgeBase) -> Vec<InferenceResult> {
         let mut results = Vec::new();
-        let energy_threshold = 0.3;
 
         // Busca links causais (Implication, Inheritance, Catalyzes) com energia suficiente
-        let active_links = kb.causal_links(energy_threshold);
+        let active_links = kb.causal_links(ENERGY_THRESHOLD);
 
         // Examina todos os pares (i, j) com i ≠ j
         for i in 0..active_links.len() {
@@ -133,7 +146,7 @@ impl InferenceEngine {
                                 // Aplica a regra de dedução do TruthValue
                                 let truth = link_sm.truth.deduction(&link_mp.truth);
                                 // Só cria se a confiança for minimamente significativa
-                                if truth.confidence() > 0.05 {
+                                if truth.confidence() > MIN_CONFIDENCE {
                                     let link = Link::new(
                                         link_sm.kind.clone(),
                                         vec![
@@ -147,6 +160,12 @@ impl InferenceEngine {
                                             },
                                         ],
                                         truth,
+                                    )
+                                    .with_origin(
+                                        LinkOrigin::Inferred {
+                                            rule: "Dedução".to_string(),
+                                            premises: vec![link_sm.id, link_mp.id],
+                                        },
                                     );
                                     // Constrói explicação legível usando labels dos conceitos
                                     let s_label = kb
@@ -190,52 +209,45 @@ impl InferenceEngine {
                     if let (Some(m2), Some(s)) = (link_mp.subject(), link_mp.object()) {
                         // m1 == m2: compartilham o sujeito M
                         // s != p: evita links triviais
-                        if m1 == m2 && s != p {
-                            if !kb.link_exists(&link_sm.kind, s, p) {
-                                // Aplica a regra de indução do TruthValue
-                                let truth = link_sm.truth.induction(&link_mp.truth);
-                                if truth.confidence() > 0.05 {
-                                    // Indução gera link de Similaridade (≈)
-                                    let link = Link::new(
-                                        LinkKind::Similarity,
-                                        vec![
-                                            Participant {
-                                                concept_id: s,
-                                                role: Role::Subject,
-                                            },
-                                            Participant {
-                                                concept_id: p,
-                                                role: Role::Object,
-                                            },
-                                        ],
-                                        truth,
-                                    );
-                                    let s_label = kb
-                                        .concepts
-                                        .get(&s)
-                                        .map(|c| c.label.as_str())
-                                        .unwrap_or("?");
-                                    let p_label = kb
-                                        .concepts
-                                        .get(&p)
-                                        .map(|c| c.label.as_str())
-                                        .unwrap_or("?");
-                                    let m_label = kb
-                                        .concepts
-                                        .get(&m1)
-                                        .map(|c| c.label.as_str())
-                                        .unwrap_or("?");
-                                    let explanation = format!(
-                                        "Indução: {} e {} compartilham {}, então {} ≈ {} {}",
-                                        s_label,
-                                        p_label,
-                                        m_label,
-                                        s_label,
-                                        p_label,
-                                        link.truth
-                                    );
-                                    results.push(InferenceResult { link, explanation });
-                                }
+                        if m1 == m2 && s != p && !kb.link_exists(&link_sm.kind, s, p) {
+                            // Aplica a regra de indução do TruthValue
+                            let truth = link_sm.truth.induction(&link_mp.truth);
+                            if truth.confidence() > MIN_CONFIDENCE {
+                                // Indução gera link de Similaridade (≈)
+                                let link = Link::new(
+                                    LinkKind::Similarity,
+                                    vec![
+                                        Participant {
+                                            concept_id: s,
+                                            role: Role::Subject,
+                                        },
+                                        Participant {
+                                            concept_id: p,
+                                            role: Role::Object,
+                                        },
+                                    ],
+                                    truth,
+                                )
+                                .with_origin(
+                                    LinkOrigin::Inferred {
+                                        rule: "Indução".to_string(),
+                                        premises: vec![link_sm.id, link_mp.id],
+                                    },
+                                );
+                                let s_label =
+                                    kb.concepts.get(&s).map(|c| c.label.as_str()).unwrap_or("?");
+                                let p_label =
+                                    kb.concepts.get(&p).map(|c| c.label.as_str()).unwrap_or("?");
+                                let m_label = kb
+                                    .concepts
+                                    .get(&m1)
+                                    .map(|c| c.label.as_str())
+                                    .unwrap_or("?");
+                                let explanation = format!(
+                                    "Indução: {} e {} compartilham {}, então {} ≈ {} {}",
+                                    s_label, p_label, m_label, s_label, p_label, link.truth
+                                );
+                                results.push(InferenceResult { link, explanation });
                             }
                         }
                     }
@@ -245,4 +257,82 @@ impl InferenceEngine {
 
         results
     }
+
+    /// Recalcula a verdade de uma inferência e devolve as premissas que a
+    /// sustentam.
+    ///
+    /// Com todas as premissas na KB, reaplica a regra a elas (a verdade
+    /// acompanha a evidência atual). Se alguma sumiu, procura entre os
+    /// links causais ativos outra derivação da mesma conclusão pela mesma
+    /// regra e fica com a de maior confiança. `None` se nenhuma resta.
+    pub fn rederive(kb: &KnowledgeBase, conclusion: &Link) -> Option<(TruthValue, Vec<LinkId>)> {
+        let LinkOrigin::Inferred { rule, premises } = &conclusion.origin else {
+            return None;
+        };
+        if let [a, b] = premises.as_slice() {
+            if let (Some(a), Some(b)) = (kb.links.get(a), kb.links.get(b)) {
+                if let Some(truth) = Self::apply(rule, a, b) {
+                    if truth.confidence() > MIN_CONFIDENCE {
+                        return Some((truth, premises.clone()));
+                    }
+                }
+            }
+        }
+
+        let (s, p) = (conclusion.subject()?, conclusion.object()?);
+        let active_links: Vec<&Link> = kb
+            .causal_links(ENERGY_THRESHOLD)
+            .into_iter()
+            .filter(|l| l.id != conclusion.id)
+            .collect();
+        let mut best: Option<(TruthValue, Vec<LinkId>)> = None;
+        for a in &active_links {
+            for b in &active_links {
+                if a.id == b.id {
+                    continue;
+                }
+                let derives = match rule.as_str() {
+                    // S→M + M→P ⊢ S→P
+                    "Dedução" => {
+                        a.kind == conclusion.kind
+                            && a.subject() == Some(s)
+                            && a.object().is_some()
+                            && a.object() == b.subject()
+                            && b.object() == Some(p)
+                    }
+                    // M→P + M→S ⊢ S ≈ P
+                    "Indução" => {
+                        a.subject().is_some()
+                            && a.subject() == b.subject()
+                            && a.object() == Some(p)
+                            && b.object() == Some(s)
+                    }
+                    _ => false,
+                };
+                if !derives {
+                    continue;
+                }
+                let Some(truth) = Self::apply(rule, a, b) else {
+                    continue;
+                };
+                if truth.confidence() > MIN_CONFIDENCE
+                    && best
+                        .as_ref()
+                        .is_none_or(|(t, _)| truth.confidence() > t.confidence())
+                {
+                    best = Some((truth, vec![a.id, b.id]));
+                }
+            }
+        }
+        best
+    }
+
+    /// Aplica a regra (pelo nome gravado na origem) a duas premissas.
+    fn apply(rule: &str, a: &Link, b: &Link) -> Option<TruthValue> {
+        match rule {
+            "Dedução" => Some(a.truth.deduction(&b.truth)),
+            "Indução" => Some(a.truth.induction(&b.truth)),
+            _ => None,
+        }
+    }
 }
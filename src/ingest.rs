@@ -0,0 +1,952 @@
+//! # Ingestão de Documentos — Da Página à Base de Conhecimento
+//!
+//! Este módulo processa documentos (PDF, Markdown, texto, HTML, DOCX,
+//! EPUB) e alimenta a KB com o conhecimento extraído. É usado pela
+//! interface web para upload de documentos.
+//!
+//! ## Pipeline de Ingestão
+//!
+//! ```text
+//! Upload (bytes + formato detectado)
+//!   ├── 1. Abrir e ler metadados → DocumentExtractor::extract_streaming (spawn_blocking)
+//!   │      └── páginas chegam por um canal (PageExtracted / PageFailed) enquanto 3–5 rodam
+//!   ├── 2. Normalizar texto PT-BR → NFC (+ sufixos quebrados no PDF)
+//!   ├── 3. Chunkar cada página em sentenças inteiras → document::chunk::Chunker (CHUNK_SIZE, CHUNK_OVERLAP)
+//!   │      (retomada: mesma config do checkpoint, pula os chunks que a KB registra como aplicados)
+//!   ├── 3b. Estrutura, antes do 1º chunk → nlu::structure (documento, autores, palavras-chave, seções)
+//!   ├── 4. Detectar idioma, extrair e pontuar entidades de cada chunk → top-N
+//!   ├── 5. Por grupo de chunks (~EMBED_BATCH_SIZE × EMBED_CONCURRENCY entidades):
+//!   │      ├── Embeddar as entidades em lotes, com novas tentativas → LM Studio API
+//!   │      └── Aplicar na KB chunk por chunk → NluPipeline (citação = hash do documento + página)
+//!   │             ├── ligar os conceitos do chunk à sua seção ("mencionado em")
+//!   │             ├── marcar o chunk como aplicado → KnowledgeBase::documents
+//!   │             └── salvar KB e depois o checkpoint (KB_SAVE_INTERVAL_SECS) → crate::checkpoint
+//!   ├── 6. Registrar o documento → KnowledgeBase::documents (hash, título, páginas)
+//!   └── 7. Salvar KB em disco e remover o checkpoint
+//! ```
+//!
+//! ## Extração Incremental
+//!
+//! O PDF é extraído página a página numa tarefa bloqueante que alimenta
+//! um canal curto ([`EXTRACTION_BUFFER`] passos): o primeiro grupo de
+//! chunks é embeddado enquanto as páginas seguintes são lidas, e o texto
+//! do documento inteiro nunca fica em memória. Uma página ilegível vira
+//! `PageFailed` e fica no relatório final; o job só falha se nenhuma
+//! página puder ser lida. Cancelar ou falhar fecha o canal, e a extração
+//! para na página seguinte. Até `ExtractionCompleted`, o total de chunks
+//! dos eventos conta só os chunks já conhecidos.
+//!
+//! A deduplicação acontece antes do job: o handler de upload consulta o
+//! registro pelo [`DocumentUpload::hash`] e só reingere se pedido.
+//!
+//! ## Retomada
+//!
+//! Embeddar grupo a grupo (em vez de todas as entidades numa única
+//! request) faz a aplicação começar cedo e limita o que se perde numa
+//! falha do LM Studio. Um job que falha, é cancelado ou morre com o
+//! processo deixa seu [`Checkpoint`] em `data/checkpoints/`; a próxima
+//! ingestão do mesmo conteúdo continua dele. A KB e o checkpoint são
+//! salvos a cada `KB_SAVE_INTERVAL_SECS` e ao cancelar ou falhar; os
+//! índices dos chunks aplicados vão na própria KB, e a retomada pula
+//! exatamente esses: reaplicar um chunk já salvo contaria sua evidência
+//! duas vezes na revisão NARS. Uma KB sem esse progresso (não restaurada
+//! após reiniciar) recomeça do chunk 0.
+//!
+//! ## Contexto de Seção
+//!
+//! O título mais interno da seção abre o texto de cada chunk dela
+//! (`"Irrigação: O gotejamento reduz..."`): o tema da seção participa da
+//! extração e, por estar na primeira sentença, coocorre com os conceitos
+//! dela. O caminho completo de
+//! títulos vai no evento `ChunkStarted`, com a página e os offsets do
+//! chunk no texto extraído.
+//!
+//! ## Estrutura
+//!
+//! Antes do primeiro chunk, o título, os autores, as palavras-chave e as
+//! seções (títulos do documento ou, em PDFs, o sumário) viram conceitos
+//! ligados ao conceito do documento ([`structure`]). Cada chunk liga seus
+//! conceitos à seção mais interna em que está: "o que o capítulo 3 diz
+//! sobre X" vira uma busca pelos vizinhos da seção. Na retomada, a
+//! estrutura já está na KB e só é procurada.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::Instant;
+
+use chrono::Utc;
+
+use anyhow::{Context, Result};
+use parking_lot::RwLock;
+use tokio::sync::mpsc;
+
+use crate::checkpoint::{Checkpoint, CheckpointStore};
+use crate::core::{Citation, DocumentRecord, KnowledgeBase, Source};
+use crate::document::chunk::{Chunk, ChunkConfig, Chunker};
+use crate::document::{self, DocumentFormat, DocumentMetadata, ExtractionStep};
+use crate::jobs::{JobCancelled, JobContext};
+use crate::nlu::extractor::{self, AliasPair};
+use crate::nlu::quantity::{self, ValueMention};
+use crate::nlu::structure::{self, DocumentStructure};
+use crate::nlu::{
+    language, register_aliases, register_attributes, NluConceptInfo, NluLinkInfo, NluPipeline,
+    Passage,
+};
+use crate::persistence::SaveThrottle;
+use crate::web::events::IngestionEvent;
+
+/// Documento enviado para ingestão.
+pub struct DocumentUpload {
+    /// Nome do arquivo.
+    pub filename: String,
+    /// Formato detectado.
+    pub format: DocumentFormat,
+    /// SHA-256 dos bytes ([`document::content_hash`]).
+    pub hash: String,
+    /// Conteúdo do arquivo, compartilhado com a extração sem cópia.
+    pub bytes: Arc<[u8]>,
+}
+
+impl DocumentUpload {
+    /// Cria o upload e calcula o hash do conteúdo.
+    pub fn new(filename: String, format: DocumentFormat, bytes: impl Into<Arc<[u8]>>) -> Self {
+        let bytes = bytes.into();
+        let hash = document::content_hash(&bytes);
+        Self {
+            filename,
+            format,
+            hash,
+            bytes,
+        }
+    }
+}
+
+/// Passos da extração que podem esperar na fila enquanto a ingestão
+/// embedda: a extração anda no máximo algumas páginas à frente, e o
+/// texto do documento inteiro nunca fica em memória de uma vez.
+const EXTRACTION_BUFFER: usize = 4;
+
+/// Candidatos extraídos de um chunk (já cortados no top-N quando o
+/// método de keyphrase não depende de embeddings).
+struct ChunkCandidates {
+    /// Índice do chunk (0-indexed).
+    index: usize,
+    /// Texto, seção, página e offsets do chunk.
+    chunk: Chunk,
+    /// Número de caracteres do chunk.
+    chars: usize,
+    /// Idioma detectado no chunk.
+    language: language::Language,
+    /// Entidades candidatas.
+    entities: Vec<String>,
+    /// Scores de keyphrase (vazio até a seleção por centralidade).
+    scores: Vec<f32>,
+    /// Siglas definidas no chunk ("Inteligência Artificial (IA)").
+    aliases: Vec<AliasPair>,
+    /// Números, datas e quantidades do chunk.
+    values: Vec<ValueMention>,
+}
+
+impl ChunkCandidates {
+    /// Extrai e pontua as entidades de um chunk (rápido, só regex).
+    ///
+    /// Com RAKE/TF-IDF o corte top-N acontece aqui, antes do embedding;
+    /// com centralidade, depois (precisa dos vetores de todos os candidatos).
+    fn extract(nlu: &NluPipeline, index: usize, chunk: Chunk) -> Self {
+        let text = chunk.text.as_str();
+        let language = language::detect(text);
+        let mut entities = nlu.extractor().extract_in(text, language);
+        let aliases = nlu.extractor().extract_aliases(text);
+        extractor::merge_aliases(&mut entities, &aliases);
+        let values = nlu.values().extract(text);
+        quantity::strip_value_entities(&mut entities, &values);
+        let mut scores = Vec::new();
+        let scorer = nlu.keyphrases();
+        if !scorer.needs_embeddings() {
+            let all_scores = scorer.score(text, &entities, language, None);
+            let kept = scorer.select(&all_scores);
+            scores = kept.iter().map(|&k| all_scores[k]).collect();
+            entities = kept.iter().map(|&k| entities[k].clone()).collect();
+        }
+        let chars = text.chars().count();
+        Self {
+            index,
+            chunk,
+            chars,
+            language,
+            entities,
+            scores,
+            aliases,
+            values,
+        }
+    }
+}
+
+/// Processa bytes de um documento: extrai seções, chunka, e alimenta a KB via NLU.
+///
+/// A extração roda em paralelo ([`DocumentExtractor::extract_streaming`]):
+/// cada página extraída é chunkada na hora, e o primeiro grupo de chunks
+/// já está sendo embeddado enquanto as páginas seguintes são lidas. Uma
+/// página que falha vira `PageFailed` e a ingestão segue com as demais;
+/// o job só falha se nenhuma página puder ser lida.
+///
+/// Emite eventos SSE marcados com o ID do job durante todo o processamento.
+/// Se o job for cancelado (checado entre páginas, após cada grupo de
+/// embeddings e antes de cada chunk), emite `Cancelled` e retorna
+/// [`JobCancelled`]; o que já foi aplicado está salvo e o checkpoint
+/// permite retomar. Se existe checkpoint para o conteúdo, continua dele.
+///
+/// ## Fases de Processamento
+///
+/// | Fase | Operação | Custo |
+/// |------|----------|-------|
+/// | 1 | Extração página a página (spawn_blocking, em paralelo) | ~10ms/página |
+/// | 2 | Extração e pontuação de entidades (regex + keyphrases) | ~10ms |
+/// | 3 | Embedding em lotes, por grupo de chunks (LM Studio API) | ~500ms |
+/// | 4 | Aplicação na KB + checkpoint por chunk | ~100ms |
+///
+/// [`DocumentExtractor::extract_streaming`]: document::DocumentExtractor::extract_streaming
+pub async fn ingest_document(
+    upload: &DocumentUpload,
+    nlu: &NluPipeline,
+    kb: &Arc<RwLock<KnowledgeBase>>,
+    job: &JobContext,
+) -> Result<String> {
+    let format = upload.format;
+    let span = tracing::info_span!(
+        "document_ingestion",
+        format = format.name(),
+        filename = %upload.filename
+    );
+    let _guard = span.enter();
+
+    let t_total = Instant::now();
+
+    // Checkpoint de uma execução interrompida: repete a mesma config de chunking
+    let checkpoints = CheckpointStore::default();
+    let previous = checkpoints.load(&upload.hash).unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "Checkpoint ilegível, recomeçando do início");
+        None
+    });
+
+    // ─── Fase 1: Extração incremental (CPU-bound, em spawn_blocking) ──
+    let t_extract = Instant::now();
+    let extractor = document::extractor_for(format);
+    let extractor_version = extractor.version();
+    let bytes_owned = Arc::clone(&upload.bytes);
+    let (tx, mut steps) = mpsc::channel(EXTRACTION_BUFFER);
+    let mut extraction = tokio::task::spawn_blocking(move || {
+        // Envio recusado = ingestão encerrada (cancelada ou com erro): para de extrair
+        extractor.extract_streaming(&bytes_owned, &mut |step| tx.blocking_send(step).is_ok())
+    });
+
+    let first_step = steps.recv().await;
+    let Some(ExtractionStep::Metadata(metadata)) = first_step else {
+        drop(steps);
+        extraction
+            .await
+            .context("spawn_blocking panicked")?
+            .with_context(|| format!("Failed to extract text from {}", format.label()))?;
+        anyhow::bail!("O extrator de {} não entregou os metadados", format.label());
+    };
+    tracing::info!(
+        pages = metadata.pages,
+        title = metadata.title.as_deref().unwrap_or(""),
+        "Documento aberto"
+    );
+
+    let (mut checkpoint, applied_chunks) =
+        resume_point(previous, upload, extractor_version, &mut kb.write());
+    if checkpoint.next_chunk == 0 {
+        if let Err(e) = checkpoints
+            .store_document(upload)
+            .and_then(|()| checkpoints.save(&checkpoint))
+        {
+            tracing::warn!(
+                error = %e,
+                "Falha ao gravar checkpoint inicial; a ingestão não poderá ser retomada"
+            );
+        }
+    }
+    let resumed_from = checkpoint.next_chunk;
+
+    job.emit(IngestionEvent::Started {
+        format: format.name().to_string(),
+        document: upload.hash.clone(),
+        title: metadata.title.clone(),
+        pages: metadata.pages,
+        resumed_from,
+    });
+
+    let t_ingestion = Instant::now();
+    let source = Source::Document(upload.hash.clone());
+    let total_pages = metadata.pages.unwrap_or(0);
+    let mut title = metadata.title.clone();
+    let mut chunker = Chunker::new(nlu.sentences(), checkpoint.chunk_config());
+    let mut pending: Vec<ChunkCandidates> = Vec::new();
+    let mut extracting = true;
+    let (mut text_len, mut section_count, mut has_text, mut extract_ms) =
+        (0usize, 0usize, false, 0u64);
+    let mut failed_pages: Vec<usize> = Vec::new();
+    let (mut sections_by_path, mut structure_applied) = (HashMap::new(), false);
+    let mut chunks_processed = 0usize;
+    let mut throttle = SaveThrottle::from_env();
+    let scorer = nlu.keyphrases();
+    let embedder = nlu.embedder_config();
+    let group_entities = embedder.batch_size * embedder.concurrency;
+
+    loop {
+        // ─── Fases 1 e 2: páginas → chunks → entidades, até completar um grupo ──
+        while extracting
+            && pending.iter().map(|c| c.entities.len()).sum::<usize>() < group_entities.max(1)
+        {
+            if job.is_cancelled() {
+                return cancel(job, kb, &checkpoints, &checkpoint, chunker.count());
+            }
+            match steps.recv().await {
+                Some(ExtractionStep::Sections { page, sections }) => {
+                    let chars: usize = sections.iter().map(|s| s.text.len()).sum();
+                    // Sem título nos metadados, o PDF usa a primeira linha do texto
+                    if title.is_none() && metadata.pages.is_some() && text_len == 0 {
+                        title = sections
+                            .first()
+                            .and_then(|s| document::title_from_text(&s.text));
+                    }
+                    text_len += chars;
+                    section_count += sections.len();
+                    has_text |= sections.iter().any(|s| !s.text.trim().is_empty());
+
+                    let chunks = chunker.push(&sections);
+                    let first_index = chunker.count() - chunks.len();
+                    if let Some(page) = page {
+                        tracing::debug!(page, chars, chunks = chunks.len(), "Página extraída");
+                        job.emit(IngestionEvent::PageExtracted {
+                            page,
+                            total_pages,
+                            chars,
+                            chunks: chunks.len(),
+                            total_chunks: chunker.count(),
+                        });
+                    }
+                    pending.extend(
+                        chunks
+                            .into_iter()
+                            .zip(first_index..)
+                            .filter(|(chunk, i)| {
+                                !applied_chunks.as_ref().is_some_and(|a| a.contains(i))
+                                    && !chunk.text.trim().is_empty()
+                            })
+                            .map(|(chunk, i)| ChunkCandidates::extract(nlu, i, chunk)),
+                    );
+                }
+                Some(ExtractionStep::PageFailed { page, error }) => {
+                    tracing::warn!(
+                        page,
+                        error = %error,
+                        "Página não extraída, seguindo com as demais"
+                    );
+                    failed_pages.push(page);
+                    job.emit(IngestionEvent::PageFailed {
+                        page,
+                        total_pages,
+                        error,
+                    });
+                }
+                Some(ExtractionStep::Metadata(_)) => {}
+                None => {
+                    extracting = false;
+                    extract_ms = t_extract.elapsed().as_millis() as u64;
+                    let extracted = (&mut extraction)
+                        .await
+                        .context("spawn_blocking panicked")
+                        .and_then(|r| r);
+                    if let Err(e) = extracted {
+                        save_progress(kb, &checkpoints, &checkpoint);
+                        return Err(
+                            e.context(format!("Failed to extract text from {}", format.label()))
+                        );
+                    }
+                    let total_chunks = chunker.count();
+                    tracing::info!(
+                        text_len,
+                        sections = section_count,
+                        total_chunks,
+                        failed_pages = failed_pages.len(),
+                        extract_ms,
+                        "Texto extraído e normalizado"
+                    );
+
+                    if !has_text {
+                        if let Err(e) = checkpoints.remove(&upload.hash) {
+                            tracing::warn!(error = %e, "Falha ao remover checkpoint");
+                        }
+                        anyhow::ensure!(
+                            failed_pages.is_empty(),
+                            "Nenhuma página de {} pôde ser extraída ({} falharam)",
+                            format.label(),
+                            failed_pages.len()
+                        );
+                        tracing::warn!("Documento sem texto extraível");
+                        let message = format!("{} vazio ou sem texto extraível.", format.label());
+                        job.emit(IngestionEvent::Error {
+                            message: message.clone(),
+                        });
+                        return Ok(message);
+                    }
+                    if checkpoint.total_chunks > 0 && checkpoint.total_chunks != total_chunks {
+                        tracing::warn!(
+                            previous = checkpoint.total_chunks,
+                            total_chunks,
+                            "O número de chunks mudou desde a execução anterior"
+                        );
+                    }
+                    checkpoint.total_chunks = total_chunks;
+                    job.emit(IngestionEvent::ExtractionCompleted {
+                        text_len,
+                        sections: section_count,
+                        total_chunks,
+                        failed_pages: failed_pages.clone(),
+                        extract_ms,
+                    });
+                }
+            }
+        }
+
+        let count = group_len(&pending, group_entities);
+        if count == 0 {
+            break;
+        }
+        let group: Vec<ChunkCandidates> = pending.drain(..count).collect();
+
+        // ─── Estrutura: documento, autores, palavras-chave e seções ──
+        // Antes do primeiro chunk: o título de um PDF sem metadados vem da primeira página
+        if !structure_applied {
+            let metadata = DocumentMetadata {
+                title: title.clone(),
+                ..metadata.clone()
+            };
+            let document_structure = DocumentStructure::from_metadata(&metadata, &upload.filename);
+            sections_by_path = if applied_chunks.is_none() {
+                let labels: Vec<String> = document_structure
+                    .labels()
+                    .iter()
+                    .map(|l| format!("search_document: {}", l))
+                    .collect();
+                let embeddings = nlu.embed_batches(&labels).await.context(
+                    "Falha no embedding da estrutura do documento (o documento pode ser retomado)",
+                )?;
+                let applied = {
+                    let mut kb_write = kb.write();
+                    kb_write.documents.begin_ingestion(&upload.hash);
+                    structure::apply(&mut kb_write, &document_structure, &embeddings, &source)
+                };
+                emit_details(job, &applied.concept_details, &applied.link_details);
+                checkpoint.new_concepts += applied.new_concepts();
+                checkpoint.new_links += applied.link_details.len();
+                save_progress(kb, &checkpoints, &checkpoint);
+                throttle.saved();
+                applied.sections
+            } else {
+                document_structure.find_sections(&kb.read())
+            };
+            structure_applied = true;
+        }
+
+        // ─── Fases 3 e 4: Embedding do grupo, aplicação chunk a chunk ──
+        // Menções, links e atributos levam o hash do documento como fonte
+        let entity_texts: Vec<String> = group
+            .iter()
+            .flat_map(|c| c.entities.iter().map(|e| format!("search_document: {}", e)))
+            .collect();
+        tracing::info!(
+            chunks = group.len(),
+            entities = entity_texts.len(),
+            "Embedding do grupo de chunks em lotes..."
+        );
+
+        let group_embeddings = match nlu.embed_batches(&entity_texts).await {
+            Ok(embeddings) => embeddings,
+            Err(e) => {
+                save_progress(kb, &checkpoints, &checkpoint);
+                return Err(e.context(format!(
+                    "Falha no embedding a partir do chunk {}/{} (o documento pode ser retomado)",
+                    checkpoint.next_chunk + 1,
+                    chunker.count()
+                )));
+            }
+        };
+
+        if job.is_cancelled() {
+            return cancel(job, kb, &checkpoints, &checkpoint, chunker.count());
+        }
+
+        let mut embedding_offset = 0usize;
+        for candidates in &group {
+            if job.is_cancelled() {
+                return cancel(job, kb, &checkpoints, &checkpoint, chunker.count());
+            }
+
+            let chunk_num = candidates.index + 1;
+            let total_chunks = chunker.count();
+            let language = candidates.language;
+            let location = &candidates.chunk;
+            let count = candidates.entities.len();
+            let chunk_embeddings = &group_embeddings[embedding_offset..embedding_offset + count];
+            embedding_offset += count;
+
+            let (entities, scores, embeddings) = if scorer.needs_embeddings() {
+                let all_scores = scorer.score(
+                    &location.text,
+                    &candidates.entities,
+                    language,
+                    Some(chunk_embeddings),
+                );
+                let kept = scorer.select(&all_scores);
+                (
+                    kept.iter()
+                        .map(|&k| candidates.entities[k].clone())
+                        .collect(),
+                    kept.iter().map(|&k| all_scores[k]).collect(),
+                    kept.iter().map(|&k| chunk_embeddings[k].clone()).collect(),
+                )
+            } else {
+                (
+                    candidates.entities.clone(),
+                    candidates.scores.clone(),
+                    chunk_embeddings.to_vec(),
+                )
+            };
+
+            tracing::info!(
+                chunk = chunk_num,
+                total = total_chunks,
+                chars = candidates.chars,
+                entities = entities.len(),
+                language = language.code(),
+                section = location.section.as_deref().unwrap_or(""),
+                page = location.page,
+                "Processando chunk"
+            );
+
+            job.emit(IngestionEvent::ChunkStarted {
+                chunk: chunk_num,
+                total: total_chunks,
+                chars: candidates.chars,
+                language: language.code().to_string(),
+                section: location.section.clone(),
+                page: location.page,
+                start: location.start,
+                end: location.end,
+            });
+
+            let citation = Citation {
+                source: source.clone(),
+                page: location.page,
+            };
+            let passage = Passage {
+                text: &location.text,
+                language,
+                citation: &citation,
+                overlap: location.overlap,
+            };
+            let result = nlu.apply_entities_to_kb(&entities, &scores, &embeddings, kb, &passage);
+            let section = location
+                .section
+                .as_ref()
+                .and_then(|path| sections_by_path.get(path));
+            let mentions = match section {
+                Some(&section) => {
+                    let concepts: Vec<_> = result
+                        .concept_details
+                        .iter()
+                        .filter_map(|c| c.id.parse().ok())
+                        .collect();
+                    structure::link_mentions(&mut kb.write(), section, &concepts, &citation)
+                }
+                None => Vec::new(),
+            };
+            let attributes = quantity::attach(&location.text, &candidates.values, &entities);
+            let messages =
+                register_aliases(&candidates.aliases, kb)
+                    .into_iter()
+                    .chain(register_attributes(
+                        &attributes,
+                        &result.entity_concepts,
+                        &source,
+                        kb,
+                    ));
+            for message in messages {
+                tracing::info!(chunk = chunk_num, "{}", message);
+            }
+
+            tracing::info!(
+                novos = result.new_concepts.len(),
+                reforçados = result.reinforced_concepts.len(),
+                links = result.new_links.len(),
+                "Chunk processado"
+            );
+
+            emit_details(job, &result.concept_details, &result.link_details);
+            emit_details(job, &[], &mentions);
+
+            let chunk_new_concepts = result.new_concepts.len();
+            let chunk_new_links = result.new_links.len() + mentions.len();
+            chunks_processed += 1;
+
+            // O índice do chunk vai para a KB com a sua evidência: a retomada
+            // pula o que está na KB salva, qualquer que seja a idade do checkpoint
+            kb.write()
+                .documents
+                .mark_applied(&upload.hash, candidates.index);
+            checkpoint.advance(candidates.index, chunk_new_concepts, chunk_new_links);
+            if throttle.due() {
+                save_progress(kb, &checkpoints, &checkpoint);
+                throttle.saved();
+            }
+
+            job.emit(IngestionEvent::ChunkCompleted {
+                chunk: chunk_num,
+                total: total_chunks,
+                new_concepts: chunk_new_concepts,
+                new_links: chunk_new_links,
+            });
+
+            // Cede controle ao runtime tokio entre chunks para que o consumidor SSE
+            // possa drenar e enviar eventos ao cliente HTTP em tempo real.
+            tokio::task::yield_now().await;
+        }
+    }
+
+    let total_chunks = chunker.count();
+    let ingestion_ms = t_ingestion.elapsed().as_millis() as u64;
+    let total_ms = t_total.elapsed().as_millis() as u64;
+
+    let now = Utc::now();
+    let (kb_concepts, kb_links) = {
+        let mut kb_write = kb.write();
+        let record = kb_write.documents.record(DocumentRecord {
+            hash: upload.hash.clone(),
+            filename: upload.filename.clone(),
+            title,
+            format: format.name().to_string(),
+            pages: metadata.pages,
+            size_bytes: upload.bytes.len(),
+            extractor_version,
+            chunks: total_chunks,
+            new_concepts: checkpoint.new_concepts,
+            new_links: checkpoint.new_links,
+            ingest_count: 1,
+            first_ingested_at: now,
+            ingested_at: now,
+        });
+        tracing::info!(
+            hash = %record.hash,
+            ingest_count = record.ingest_count,
+            "Documento registrado"
+        );
+        (kb_write.concept_count(), kb_write.link_count())
+    };
+
+    tracing::info!(
+        chunks_processed,
+        resumed_from,
+        new_concepts = checkpoint.new_concepts,
+        new_links = checkpoint.new_links,
+        kb_concepts,
+        kb_links,
+        failed_pages = failed_pages.len(),
+        extract_ms,
+        ingestion_ms,
+        total_ms,
+        format = format.name(),
+        "Ingestão de documento completa"
+    );
+
+    // ─── Persistência em disco ───────────────────────────────────
+    match crate::persistence::save_kb(kb) {
+        Ok(()) => tracing::info!("KB salva em disco após ingestão"),
+        Err(e) => tracing::error!(error = %e, "Falha ao salvar KB após ingestão"),
+    }
+    if let Err(e) = checkpoints.remove(&upload.hash) {
+        tracing::warn!(error = %e, "Falha ao remover checkpoint da ingestão concluída");
+    }
+
+    // ─── Métricas do sistema ─────────────────────────────────────
+    let throughput_str = if total_ms > 0 {
+        format!(
+            "{:.0} chars/s",
+            text_len as f64 / (total_ms as f64 / 1000.0)
+        )
+    } else {
+        "N/A".into()
+    };
+    let pm = crate::metrics::collect_metrics(Some(throughput_str.clone()));
+    let failed_note = if failed_pages.is_empty() {
+        String::new()
+    } else {
+        let pages: Vec<String> = failed_pages.iter().map(|p| p.to_string()).collect();
+        format!(" Páginas não extraídas: {}.", pages.join(", "))
+    };
+
+    job.emit(IngestionEvent::Completed {
+        format: format.name().to_string(),
+        total_chunks,
+        new_concepts: checkpoint.new_concepts,
+        new_links: checkpoint.new_links,
+        kb_concepts,
+        kb_links,
+        failed_pages,
+        extract_ms,
+        ingestion_ms,
+        total_ms,
+        memory_used_mb: pm.memory_used_mb,
+        memory_total_mb: pm.memory_total_mb,
+        cpu_active_cores: pm.cpu_active_cores,
+        cpu_max_core_percent: pm.cpu_max_core_percent,
+        cpu_total_cores: pm.cpu_total_cores,
+        kb_file_size_bytes: pm.kb_file_size_bytes,
+        gpu_name: pm.gpu_name.clone(),
+        gpu_cores: pm.gpu_cores,
+        gpu_utilization_pct: pm.gpu_utilization_pct,
+        gpu_memory_mb: pm.gpu_memory_mb,
+        throughput: throughput_str.clone(),
+    });
+
+    Ok(format!(
+        "{} processado: {} chunks analisados. {} concepts e {} links criados. \
+         KB total: {} concepts, {} links.{} \
+         Tempo: leitura {}ms, ingestão {}ms, total {}ms. | {}",
+        format.label(),
+        total_chunks,
+        checkpoint.new_concepts,
+        checkpoint.new_links,
+        kb_concepts,
+        kb_links,
+        failed_note,
+        extract_ms,
+        ingestion_ms,
+        total_ms,
+        pm.summary_line(total_ms),
+    ))
+}
+
+/// Emite `ConceptCreated`/`ConceptReinforced` e `LinkCreated` para o grafo.
+fn emit_details(job: &JobContext, concepts: &[NluConceptInfo], links: &[NluLinkInfo]) {
+    for info in concepts {
+        if info.is_new {
+            job.emit(IngestionEvent::ConceptCreated {
+                id: info.id.clone(),
+                label: info.label.clone(),
+                frequency: info.frequency,
+                confidence: info.confidence,
+                energy: info.energy,
+                state: info.state.clone(),
+                entity_type: info.entity_type.clone(),
+            });
+        } else {
+            job.emit(IngestionEvent::ConceptReinforced {
+                id: info.id.clone(),
+                label: info.label.clone(),
+                similarity: info.similarity.unwrap_or(1.0),
+                energy: info.energy,
+            });
+        }
+    }
+    for info in links {
+        job.emit(IngestionEvent::LinkCreated {
+            link_id: info.id.clone(),
+            source_id: info.source_id.clone(),
+            source_label: info.source_label.clone(),
+            target_id: info.target_id.clone(),
+            target_label: info.target_label.clone(),
+            kind: info.kind.clone(),
+            frequency: info.frequency,
+            confidence: info.confidence,
+            energy: info.energy,
+        });
+    }
+}
+
+/// Checkpoint a continuar e chunks a pular.
+///
+/// Os chunks pulados são os que a KB registra como aplicados
+/// ([`DocumentRegistry::applied_chunks`](crate::core::DocumentRegistry::applied_chunks)),
+/// não os contados no checkpoint: ele só é gravado a cada
+/// `KB_SAVE_INTERVAL_SECS`, e a KB em disco pode ter sido salva depois
+/// dele (por outro job, ou antes de um crash). `None` = nada aplicado,
+/// nem a estrutura do documento.
+///
+/// Sem checkpoint da mesma versão do extrator, ou com uma KB que não tem
+/// o progresso dele (não restaurada, resetada), recomeça do chunk 0.
+fn resume_point(
+    previous: Option<Checkpoint>,
+    upload: &DocumentUpload,
+    extractor_version: u32,
+    kb: &mut KnowledgeBase,
+) -> (Checkpoint, Option<BTreeSet<usize>>) {
+    let fresh = || Checkpoint::new(upload, extractor_version, &ChunkConfig::from_env(), 0);
+    let previous = previous.filter(|previous| {
+        let current = previous.extractor_version == extractor_version;
+        if !current {
+            tracing::warn!(
+                extractor_version = previous.extractor_version,
+                total_chunks = previous.total_chunks,
+                "Checkpoint de outra versão do extrator, recomeçando do início"
+            );
+        }
+        current
+    });
+    let Some(mut checkpoint) = previous else {
+        if kb.documents.discard_progress(&upload.hash) {
+            tracing::warn!("Progresso na KB sem checkpoint, recomeçando do início");
+        }
+        return (fresh(), None);
+    };
+    let Some(applied) = kb.documents.applied_chunks(&upload.hash).cloned() else {
+        tracing::warn!(
+            next_chunk = checkpoint.next_chunk,
+            total_chunks = checkpoint.total_chunks,
+            "A KB não tem o progresso do checkpoint (não restaurada?), recomeçando do início"
+        );
+        return (fresh(), None);
+    };
+    checkpoint.next_chunk = applied.last().map_or(0, |&last| last + 1);
+    tracing::info!(
+        next_chunk = checkpoint.next_chunk,
+        applied = applied.len(),
+        total_chunks = checkpoint.total_chunks,
+        "Retomando ingestão do checkpoint"
+    );
+    (checkpoint, Some(applied))
+}
+
+/// Salva a KB e, só se deu certo, o checkpoint: o checkpoint nunca
+/// aponta para chunks que não estão no disco.
+fn save_progress(
+    kb: &Arc<RwLock<KnowledgeBase>>,
+    checkpoints: &CheckpointStore,
+    checkpoint: &Checkpoint,
+) {
+    if let Err(e) = crate::persistence::save_kb(kb) {
+        tracing::error!(
+            error = %e,
+            next_chunk = checkpoint.next_chunk,
+            "Falha ao salvar KB; checkpoint mantido no último salvamento"
+        );
+    } else if let Err(e) = checkpoints.save(checkpoint) {
+        tracing::warn!(
+            error = %e,
+            next_chunk = checkpoint.next_chunk,
+            "Falha ao gravar checkpoint"
+        );
+    }
+}
+
+/// Encerra um job cancelado: salva os chunks aplicados e emite `Cancelled`.
+///
+/// O checkpoint fica para a retomada.
+fn cancel(
+    job: &JobContext,
+    kb: &Arc<RwLock<KnowledgeBase>>,
+    checkpoints: &CheckpointStore,
+    checkpoint: &Checkpoint,
+    total_chunks: usize,
+) -> Result<String> {
+    save_progress(kb, checkpoints, checkpoint);
+    let chunks_processed = checkpoint.next_chunk;
+    tracing::info!(chunks_processed, total_chunks, "Ingestão cancelada");
+    job.emit(IngestionEvent::Cancelled {
+        chunks_processed,
+        total_chunks,
+    });
+    Err(JobCancelled.into())
+}
+
+/// Quantos chunks do início da fila formam o próximo grupo: os
+/// primeiros que somam ao menos `entities` entidades (ou todos, se não
+/// chegam lá).
+///
+/// Cada grupo é embeddado de uma vez ([`NluPipeline::embed_batches`]) e
+/// aplicado antes do próximo: grupos de ~`EMBED_BATCH_SIZE × EMBED_CONCURRENCY`
+/// entidades mantêm todos os lotes concorrentes ocupados.
+fn group_len(chunks: &[ChunkCandidates], entities: usize) -> usize {
+    let mut count = 0;
+    for (i, candidates) in chunks.iter().enumerate() {
+        count += candidates.entities.len();
+        if count >= entities.max(1) {
+            return i + 1;
+        }
+    }
+    chunks.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn upload() -> DocumentUpload {
+        DocumentUpload::new(
+            "notas.md".into(),
+            DocumentFormat::Markdown,
+            b"# Solo\n\nA chuva molha o solo.".to_vec(),
+        )
+    }
+
+    #[test]
+    fn resume_skips_the_chunks_saved_with_the_kb_after_a_throttled_checkpoint() {
+        let upload = upload();
+        let config = ChunkConfig {
+            max_chars: 300,
+            overlap_chars: 20,
+        };
+        // Checkpoint gravado no salvamento anterior, após o chunk 1
+        let mut checkpoint = Checkpoint::new(&upload, 1, &config, 8);
+        checkpoint.advance(1, 3, 2);
+
+        // Chunks 2..=4 aplicados e a KB salva depois (por outro job), sem novo checkpoint
+        let mut kb = KnowledgeBase::new();
+        for index in 0..=4 {
+            kb.documents.mark_applied(&upload.hash, index);
+        }
+        let json = serde_json::to_string(&kb).unwrap();
+        let mut restored: KnowledgeBase = serde_json::from_str(&json).unwrap();
+
+        let (resumed, applied) = resume_point(Some(checkpoint), &upload, 1, &mut restored);
+        let applied = applied.expect("progresso da KB");
+        assert_eq!(
+            applied.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+        assert_eq!(resumed.next_chunk, 5);
+        assert_eq!(resumed.chunk_config().max_chars, 300);
+    }
+
+    #[test]
+    fn a_kb_without_the_checkpoint_progress_restarts_from_the_first_chunk() {
+        let upload = upload();
+        let config = ChunkConfig {
+            max_chars: 300,
+            overlap_chars: 20,
+        };
+        let mut checkpoint = Checkpoint::new(&upload, 1, &config, 8);
+        checkpoint.advance(5, 9, 4);
+
+        // KB não restaurada: nem a estrutura do documento está nela
+        let (resumed, applied) = resume_point(
+            Some(checkpoint.clone()),
+            &upload,
+            1,
+            &mut KnowledgeBase::new(),
+        );
+        assert!(applied.is_none());
+        assert_eq!((resumed.next_chunk, resumed.new_concepts), (0, 0));
+
+        // Sem checkpoint (reingestão), o progresso deixado na KB é descartado
+        let mut kb = KnowledgeBase::new();
+        kb.documents.mark_applied(&upload.hash, 0);
+        let (resumed, applied) = resume_point(None, &upload, 1, &mut kb);
+        assert!(applied.is_none());
+        assert_eq!(resumed.next_chunk, 0);
+        assert!(kb.documents.applied_chunks(&upload.hash).is_none());
+    }
+}
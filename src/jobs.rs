@@ -0,0 +1,608 @@
+//! # Jobs de Ingestão — Fila, Workers e Cancelamento
+//!
+//! Cada upload vira um **job** com ID próprio. O [`JobManager`] limita
+//! quantos documentos são processados ao mesmo tempo (semáforo com
+//! `INGEST_WORKERS` permissões), guarda o estado de cada job e marca
+//! todos os eventos SSE com o ID do job ([`JobEvent`]) — uploads
+//! simultâneos não se misturam mais no Visualizador.
+//!
+//! ## Estados
+//!
+//! ```text
+//! Queued ──(worker livre)──▶ Running ──▶ Done
+//!   │                           ├──────▶ Failed (erro ou pânico)
+//!   └──(DELETE)──▶ Cancelled ◀──┘ (DELETE: para no próximo chunk)
+//! ```
+//!
+//! | Rota | Efeito |
+//! |------|--------|
+//! | `GET /jobs` | Lista os jobs (mais recentes primeiro) |
+//! | `DELETE /jobs/{id}` | Cancela job ativo; remove da lista job já encerrado |
+//!
+//! O cancelamento é cooperativo: a ingestão consulta
+//! [`JobContext::is_cancelled`] entre as fases e entre chunks. Os
+//! chunks já aplicados permanecem na KB.
+//!
+//! ## Retomada
+//!
+//! Jobs cancelados, que falharam ou que morreram com o processo deixam um
+//! [checkpoint](crate::checkpoint) com os bytes do documento.
+//! [`JobManager::resume`] enfileira um novo job a partir dele, que
+//! continua do primeiro chunk não aplicado (`POST /documents/{hash}/resume`).
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::Serialize;
+use tokio::sync::{broadcast, Semaphore};
+use uuid::Uuid;
+
+use crate::checkpoint::CheckpointStore;
+use crate::core::KnowledgeBase;
+use crate::ingest::{self, DocumentUpload};
+use crate::nlu::NluPipeline;
+use crate::web::events::{IngestionEvent, JobEvent};
+
+/// Identificador de um job de ingestão.
+pub type JobId = Uuid;
+
+/// Jobs encerrados mantidos na listagem.
+const FINISHED_JOBS_KEPT: usize = 50;
+
+/// Estado de um job.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// Aguardando worker livre.
+    Queued,
+    /// Em processamento.
+    Running,
+    /// Concluído com sucesso.
+    Done,
+    /// Encerrado com erro.
+    Failed,
+    /// Cancelado pelo usuário.
+    Cancelled,
+}
+
+impl JobStatus {
+    /// `true` se o job já encerrou (não pode mais ser cancelado).
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            JobStatus::Done | JobStatus::Failed | JobStatus::Cancelled
+        )
+    }
+}
+
+/// Erro devolvido pela ingestão quando o job é cancelado.
+#[derive(Debug, thiserror::Error)]
+#[error("ingestão cancelada")]
+pub struct JobCancelled;
+
+/// Estado público de um job (JSON de `GET /jobs`).
+#[derive(Clone, Debug, Serialize)]
+pub struct JobInfo {
+    /// ID do job.
+    pub id: JobId,
+    /// Nome do arquivo enviado.
+    pub filename: String,
+    /// Formato detectado (`pdf`, `markdown`...).
+    pub format: String,
+    /// SHA-256 do conteúdo (chave do registro de documentos).
+    pub content_hash: String,
+    /// Estado atual.
+    pub status: JobStatus,
+    /// Chunks concluídos.
+    pub chunks_done: usize,
+    /// Total de chunks (os conhecidos, enquanto a extração corre).
+    pub total_chunks: usize,
+    /// Páginas já extraídas (ou que falharam).
+    pub pages_done: usize,
+    /// Total de páginas (0 em formatos sem paginação).
+    pub total_pages: usize,
+    /// `true` depois que todo o texto foi extraído.
+    pub extracted: bool,
+    /// Resumo final ou mensagem de erro.
+    pub message: Option<String>,
+    /// Quando o upload foi aceito.
+    pub created_at: DateTime<Utc>,
+    /// Quando um worker começou o processamento.
+    pub started_at: Option<DateTime<Utc>>,
+    /// Quando o job encerrou.
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl JobInfo {
+    /// Fração concluída (0–1). Enquanto um PDF ainda está sendo extraído,
+    /// o total de chunks é parcial: a fração dos chunks é escalada pela
+    /// fração das páginas lidas.
+    pub fn progress(&self) -> f64 {
+        if self.total_chunks == 0 {
+            return 0.0;
+        }
+        let chunks = self.chunks_done as f64 / self.total_chunks as f64;
+        let pages = if self.extracted || self.total_pages == 0 {
+            1.0
+        } else {
+            self.pages_done as f64 / self.total_pages as f64
+        };
+        (chunks * pages).clamp(0.0, 1.0)
+    }
+}
+
+/// Job registrado: estado público + sinal de cancelamento.
+struct JobEntry {
+    /// Estado público.
+    info: JobInfo,
+    /// Sinal de cancelamento, compartilhado com o [`JobContext`].
+    cancelled: Arc<AtomicBool>,
+}
+
+/// Tabela de jobs compartilhada entre o manager e os contextos.
+type JobTable = Arc<RwLock<HashMap<JobId, JobEntry>>>;
+
+/// Configuração da fila de ingestão.
+#[derive(Clone, Debug)]
+pub struct JobConfig {
+    /// Documentos processados simultaneamente.
+    pub workers: usize,
+}
+
+impl JobConfig {
+    /// Cria configuração a partir de variáveis de ambiente.
+    ///
+    /// | Variável | Default |
+    /// |----------|---------|
+    /// | `INGEST_WORKERS` | `2` |
+    pub fn from_env() -> Self {
+        Self {
+            workers: std::env::var("INGEST_WORKERS")
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .filter(|&n: &usize| n > 0)
+                .unwrap_or(2),
+        }
+    }
+}
+
+/// Handle de um job em execução, passado para a ingestão.
+#[derive(Clone)]
+pub struct JobContext {
+    /// ID do job.
+    id: JobId,
+    /// Canal SSE compartilhado.
+    tx: broadcast::Sender<JobEvent>,
+    /// Sinal de cancelamento.
+    cancelled: Arc<AtomicBool>,
+    /// Tabela de jobs (progresso).
+    jobs: JobTable,
+}
+
+impl JobContext {
+    /// Emite um evento marcado com o ID do job e atualiza o progresso.
+    pub fn emit(&self, event: IngestionEvent) {
+        match &event {
+            IngestionEvent::Started {
+                pages,
+                resumed_from,
+                ..
+            } => {
+                self.update(|info| {
+                    info.total_pages = pages.unwrap_or(0);
+                    info.chunks_done = *resumed_from;
+                    info.total_chunks = *resumed_from;
+                });
+            }
+            IngestionEvent::PageExtracted {
+                page, total_chunks, ..
+            } => {
+                self.update(|info| {
+                    info.pages_done = *page;
+                    info.total_chunks = *total_chunks;
+                });
+            }
+            IngestionEvent::PageFailed { page, .. } => {
+                self.update(|info| info.pages_done = *page);
+            }
+            IngestionEvent::ExtractionCompleted { total_chunks, .. } => {
+                self.update(|info| {
+                    info.total_chunks = *total_chunks;
+                    info.pages_done = info.total_pages;
+                    info.extracted = true;
+                });
+            }
+            IngestionEvent::ChunkCompleted { chunk, .. } => {
+                self.update(|info| info.chunks_done = *chunk);
+            }
+            _ => {}
+        }
+        let _ = self.tx.send(JobEvent {
+            job_id: self.id,
+            event,
+        });
+    }
+
+    /// `true` se o usuário pediu o cancelamento.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Aplica uma alteração ao estado público do job.
+    fn update(&self, f: impl FnOnce(&mut JobInfo)) {
+        if let Some(entry) = self.jobs.write().get_mut(&self.id) {
+            f(&mut entry.info);
+        }
+    }
+}
+
+/// Fila de ingestão com pool limitado de workers.
+pub struct JobManager {
+    /// Jobs conhecidos.
+    jobs: JobTable,
+    /// Permissões de worker.
+    workers: Arc<Semaphore>,
+    /// Canal SSE compartilhado.
+    tx: broadcast::Sender<JobEvent>,
+}
+
+impl JobManager {
+    /// Cria o manager com `config.workers` workers.
+    pub fn new(config: JobConfig, tx: broadcast::Sender<JobEvent>) -> Self {
+        tracing::info!(workers = config.workers, "Fila de ingestão configurada");
+        Self {
+            jobs: JobTable::default(),
+            workers: Arc::new(Semaphore::new(config.workers)),
+            tx,
+        }
+    }
+
+    /// Enfileira a ingestão de um documento e devolve o ID do job.
+    pub fn submit(
+        &self,
+        upload: DocumentUpload,
+        nlu: Arc<NluPipeline>,
+        kb: Arc<RwLock<KnowledgeBase>>,
+    ) -> JobId {
+        let id = Uuid::new_v4();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let position = {
+            let mut jobs = self.jobs.write();
+            prune_finished(&mut jobs);
+            let ahead = jobs
+                .values()
+                .filter(|e| !e.info.status.is_finished())
+                .count();
+            jobs.insert(
+                id,
+                JobEntry {
+                    info: JobInfo {
+                        id,
+                        filename: upload.filename.clone(),
+                        format: upload.format.name().to_string(),
+                        content_hash: upload.hash.clone(),
+                        status: JobStatus::Queued,
+                        chunks_done: 0,
+                        total_chunks: 0,
+                        pages_done: 0,
+                        total_pages: 0,
+                        extracted: false,
+                        message: None,
+                        created_at: Utc::now(),
+                        started_at: None,
+                        finished_at: None,
+                    },
+                    cancelled: cancelled.clone(),
+                },
+            );
+            ahead.saturating_sub(self.workers.available_permits())
+        };
+
+        let job = JobContext {
+            id,
+            tx: self.tx.clone(),
+            cancelled,
+            jobs: self.jobs.clone(),
+        };
+        job.emit(IngestionEvent::Queued {
+            filename: upload.filename.clone(),
+            format: upload.format.name().to_string(),
+            position,
+        });
+
+        let workers = self.workers.clone();
+        let label = upload.format.label();
+        let context = job.clone();
+        let work = async move { ingest::ingest_document(&upload, &nlu, &kb, &context).await };
+        tokio::spawn(run(job, workers, label, work));
+
+        id
+    }
+
+    /// Enfileira a retomada de um documento interrompido, a partir do seu checkpoint.
+    ///
+    /// `Ok(None)` se não há checkpoint para o hash.
+    pub fn resume(
+        &self,
+        hash: &str,
+        nlu: Arc<NluPipeline>,
+        kb: Arc<RwLock<KnowledgeBase>>,
+    ) -> anyhow::Result<Option<JobId>> {
+        let Some(upload) = CheckpointStore::default().load_upload(hash)? else {
+            return Ok(None);
+        };
+        tracing::info!(hash, filename = %upload.filename, "Retomando ingestão interrompida");
+        Ok(Some(self.submit(upload, nlu, kb)))
+    }
+
+    /// Jobs conhecidos, mais recentes primeiro.
+    pub fn list(&self) -> Vec<JobInfo> {
+        let mut jobs: Vec<JobInfo> = self.jobs.read().values().map(|e| e.info.clone()).collect();
+        jobs.sort_by_key(|j| std::cmp::Reverse(j.created_at));
+        jobs
+    }
+
+    /// Estado de um job.
+    pub fn get(&self, id: JobId) -> Option<JobInfo> {
+        self.jobs.read().get(&id).map(|e| e.info.clone())
+    }
+
+    /// Job na fila ou em execução com o mesmo conteúdo, se houver.
+    pub fn find_active(&self, content_hash: &str) -> Option<JobInfo> {
+        self.jobs
+            .read()
+            .values()
+            .find(|e| !e.info.status.is_finished() && e.info.content_hash == content_hash)
+            .map(|e| e.info.clone())
+    }
+
+    /// Cancela um job ativo, ou remove da lista um job já encerrado.
+    ///
+    /// Um job na fila é cancelado na hora; um em execução para no próximo
+    /// ponto de verificação (o estado muda para `Cancelled` quando parar).
+    /// Retorna o estado do job após a operação, ou `None` se não existe.
+    pub fn cancel(&self, id: JobId) -> Option<JobInfo> {
+        let mut jobs = self.jobs.write();
+        let entry = jobs.get_mut(&id)?;
+        match entry.info.status {
+            JobStatus::Queued => {
+                entry.cancelled.store(true, Ordering::Relaxed);
+                entry.info.status = JobStatus::Cancelled;
+                entry.info.finished_at = Some(Utc::now());
+                let _ = self.tx.send(JobEvent {
+                    job_id: id,
+                    event: IngestionEvent::Cancelled {
+                        chunks_processed: 0,
+                        total_chunks: 0,
+                    },
+                });
+                Some(entry.info.clone())
+            }
+            JobStatus::Running => {
+                entry.cancelled.store(true, Ordering::Relaxed);
+                Some(entry.info.clone())
+            }
+            _ => jobs.remove(&id).map(|e| e.info),
+        }
+    }
+}
+
+/// Executa um job: espera um worker livre, roda `work` e grava o desfecho.
+///
+/// `work` roda numa task própria: um pânico na ingestão marca o job como
+/// `Failed` com a mensagem do pânico em vez de deixá-lo `Running` para
+/// sempre. A permissão do worker é devolvida ao sair, e o checkpoint do
+/// último salvamento continua em disco para a retomada.
+async fn run(
+    job: JobContext,
+    workers: Arc<Semaphore>,
+    label: &'static str,
+    work: impl Future<Output = anyhow::Result<String>> + Send + 'static,
+) {
+    let Ok(_permit) = workers.acquire_owned().await else {
+        return;
+    };
+    if job.is_cancelled() {
+        return; // cancelado na fila: estado e evento já publicados
+    }
+    job.update(|info| {
+        info.status = JobStatus::Running;
+        info.started_at = Some(Utc::now());
+    });
+
+    let result = match tokio::spawn(work).await {
+        Ok(result) => result,
+        Err(e) if e.is_panic() => Err(anyhow::anyhow!(
+            "pânico na ingestão: {}",
+            panic_message(e.into_panic())
+        )),
+        Err(e) => Err(anyhow::anyhow!("ingestão abortada: {}", e)),
+    };
+    let (status, message) = match result {
+        Ok(summary) => {
+            tracing::info!(job = %job.id, result = %summary, "Job de ingestão concluído");
+            (JobStatus::Done, summary)
+        }
+        Err(e) if e.is::<JobCancelled>() => {
+            tracing::info!(job = %job.id, "Job de ingestão cancelado");
+            (JobStatus::Cancelled, e.to_string())
+        }
+        Err(e) => {
+            tracing::error!(job = %job.id, error = %e, "Job de ingestão falhou");
+            job.emit(IngestionEvent::Error {
+                message: format!("Erro ao processar {}: {}", label, e),
+            });
+            (JobStatus::Failed, e.to_string())
+        }
+    };
+    job.update(|info| {
+        info.status = status;
+        info.message = Some(message);
+        info.finished_at = Some(Utc::now());
+    });
+}
+
+/// Texto de um pânico (`panic!("...")`), quando houver.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload
+            .downcast_ref::<&str>()
+            .map_or("sem mensagem", |m| m)
+            .to_string(),
+    }
+}
+
+/// Descarta os jobs encerrados mais antigos além de [`FINISHED_JOBS_KEPT`].
+fn prune_finished(jobs: &mut HashMap<JobId, JobEntry>) {
+    let mut finished: Vec<(DateTime<Utc>, JobId)> = jobs
+        .values()
+        .filter(|e| e.info.status.is_finished())
+        .map(|e| (e.info.created_at, e.info.id))
+        .collect();
+    if finished.len() <= FINISHED_JOBS_KEPT {
+        return;
+    }
+    finished.sort();
+    for (_, id) in &finished[..finished.len() - FINISHED_JOBS_KEPT] {
+        jobs.remove(id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: JobId, status: JobStatus, created_at: DateTime<Utc>) -> JobEntry {
+        JobEntry {
+            info: JobInfo {
+                id,
+                filename: "notas.md".into(),
+                format: "markdown".into(),
+                content_hash: "abc".into(),
+                status,
+                chunks_done: 0,
+                total_chunks: 0,
+                pages_done: 0,
+                total_pages: 0,
+                extracted: false,
+                message: None,
+                created_at,
+                started_at: None,
+                finished_at: None,
+            },
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    #[test]
+    fn emit_tags_events_and_tracks_progress() {
+        let (tx, mut rx) = broadcast::channel(16);
+        let id = Uuid::new_v4();
+        let jobs = JobTable::default();
+        jobs.write()
+            .insert(id, entry(id, JobStatus::Running, Utc::now()));
+        let job = JobContext {
+            id,
+            tx,
+            cancelled: Arc::new(AtomicBool::new(false)),
+            jobs: jobs.clone(),
+        };
+
+        job.emit(IngestionEvent::Started {
+            format: "pdf".into(),
+            document: "abc".into(),
+            title: None,
+            pages: Some(4),
+            resumed_from: 1,
+        });
+        job.emit(IngestionEvent::PageExtracted {
+            page: 2,
+            total_pages: 4,
+            chars: 900,
+            chunks: 2,
+            total_chunks: 3,
+        });
+        job.emit(IngestionEvent::ChunkCompleted {
+            chunk: 2,
+            total: 3,
+            new_concepts: 4,
+            new_links: 1,
+        });
+
+        let info = &jobs.read()[&id].info;
+        assert_eq!((info.chunks_done, info.total_chunks), (2, 3));
+        assert_eq!((info.pages_done, info.total_pages), (2, 4));
+        assert!(!info.extracted);
+        assert!((info.progress() - 2.0 / 3.0 * 0.5).abs() < 1e-9);
+
+        let json = serde_json::to_value(rx.try_recv().expect("evento")).unwrap();
+        assert_eq!(json["job_id"], id.to_string());
+        assert_eq!(json["type"], "Started");
+        assert_eq!(json["pages"], 4);
+    }
+
+    #[tokio::test]
+    async fn a_panicking_ingest_fails_the_job_and_frees_the_worker() {
+        let (tx, mut rx) = broadcast::channel(16);
+        let id = Uuid::new_v4();
+        let jobs = JobTable::default();
+        jobs.write()
+            .insert(id, entry(id, JobStatus::Queued, Utc::now()));
+        let job = JobContext {
+            id,
+            tx,
+            cancelled: Arc::new(AtomicBool::new(false)),
+            jobs: jobs.clone(),
+        };
+        let workers = Arc::new(Semaphore::new(1));
+
+        run(job, workers.clone(), "Markdown", async {
+            panic!("chunk sem fim")
+        })
+        .await;
+
+        let info = &jobs.read()[&id].info;
+        assert_eq!(info.status, JobStatus::Failed);
+        assert!(info
+            .message
+            .as_deref()
+            .is_some_and(|m| m.contains("chunk sem fim")));
+        assert!(info.finished_at.is_some());
+        assert_eq!(workers.available_permits(), 1);
+        let json = serde_json::to_value(rx.try_recv().expect("evento")).unwrap();
+        assert_eq!(json["type"], "Error");
+    }
+
+    #[test]
+    fn prune_keeps_active_and_recent_finished_jobs() {
+        let mut jobs = HashMap::new();
+        let start = Utc::now();
+        for i in 0..FINISHED_JOBS_KEPT + 5 {
+            let id = Uuid::new_v4();
+            jobs.insert(
+                id,
+                entry(
+                    id,
+                    JobStatus::Done,
+                    start + chrono::Duration::seconds(i as i64),
+                ),
+            );
+        }
+        let active = Uuid::new_v4();
+        jobs.insert(active, entry(active, JobStatus::Queued, start));
+
+        prune_finished(&mut jobs);
+
+        assert_eq!(jobs.len(), FINISHED_JOBS_KEPT + 1);
+        assert!(jobs.contains_key(&active));
+        assert!(jobs.values().all(
+            |e| e.info.created_at >= start + chrono::Duration::seconds(5) || e.info.id == active
+        ));
+    }
+}
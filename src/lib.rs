@@ -0,0 +1,51 @@
+#![allow(dead_code, unused_imports)]
+#![allow(rustdoc::broken_intra_doc_links, rustdoc::invalid_html_tags)]
+//! # Cultivo Epistêmico — Biblioteca
+//!
+//! Os módulos da aplicação, compartilhados pelos binários:
+//!
+//! | Binário | Arquivo | Função |
+//! |---------|---------|--------|
+//! | `semantic-chat` | `src/main.rs` | Servidor web (chat, grafo, ingestão de documentos); `semantic-chat ingest DIR` ingere um diretório ([`bulk`]) |
+//! | `eval` | `src/bin/eval.rs` | Avaliação de extração e intents contra datasets anotados |
+
+// Declaração dos módulos da aplicação.
+
+/// Módulo `agent` — modo agente do chat com ferramentas sobre a KB.
+pub mod agent;
+
+/// Módulo `bulk` — ingestão em lote de um diretório (subcomando `ingest`).
+pub mod bulk;
+
+/// Módulo `checkpoint` — checkpoints por chunk para retomar ingestões interrompidas.
+pub mod checkpoint;
+
+/// Módulo `core` — tipos fundamentais: Concept, Link, TruthValue, KnowledgeBase.
+pub mod core;
+
+/// Módulo `document` — extração de texto estruturado (PDF, Markdown, HTML, DOCX, EPUB).
+pub mod document;
+
+/// Módulo `inference` — motor de inferência NARS (dedução, indução).
+pub mod inference;
+
+/// Módulo `jobs` — fila de jobs de ingestão: workers limitados, estados e cancelamento.
+pub mod jobs;
+
+/// Módulo `metrics` — coleta de métricas de sistema (CPU, RAM, GPU).
+pub mod metrics;
+
+/// Módulo `nlu` — pipeline de compreensão de linguagem natural (via LM Studio).
+pub mod nlu;
+
+/// Módulo `orchestrator` — orquestra o ciclo de cultivo epistêmico.
+pub mod orchestrator;
+
+/// Módulo `ingest` — ingestão de documentos: chunks → entidades → KB, com eventos SSE.
+pub mod ingest;
+
+/// Módulo `persistence` — serialização/desserialização da KB em JSON.
+pub mod persistence;
+
+/// Módulo `web` — servidor web axum, handlers HTTP, templates e SSE.
+pub mod web;
@@ -1,5 +1,3 @@
-#![allow(dead_code, unused_imports)]
-#![allow(rustdoc::broken_intra_doc_links, rustdoc::invalid_html_tags)]
 //! # Cultivo Epistêmico — Semantic Chat
 //!
 //! **Ponto de entrada principal** da aplicação Cultivo Epistêmico.
@@ -15,8 +13,9 @@
 //! ```text
 //! main()
 //!   ├── Configura tracing/logging
-//!   ├── Cria KB vazia (modo LM Studio)
-//!   ├── Cria broadcast channel para SSE
+//!   ├── Cria KB vazia (modo LM Studio) — ou restaura data/kb.json com KB_RESTORE=1
+//!   │     └── sem restauração, descarta checkpoints de ingestões interrompidas
+//!   ├── Cria broadcast channel para SSE e fila de jobs de ingestão
 //!   ├── Monta AppState e Router
 //!   ├── Inicia servidor TCP (porta 3000)
 //!   └── Spawn async task:
@@ -26,32 +25,19 @@
 //!       ├── Cria Orchestrator
 //!       └── Publica em OnceLock (ModelReady)
 //! ```
-
-// Declaração dos módulos da aplicação.
-
-/// Módulo `core` — tipos fundamentais: Concept, Link, TruthValue, KnowledgeBase.
-mod core;
-
-/// Módulo `inference` — motor de inferência NARS (dedução, indução).
-mod inference;
-
-/// Módulo `metrics` — coleta de métricas de sistema (CPU, RAM, GPU).
-mod metrics;
-
-/// Módulo `nlu` — pipeline de compreensão de linguagem natural (via LM Studio).
-mod nlu;
-
-/// Módulo `orchestrator` — orquestra o ciclo de cultivo epistêmico.
-mod orchestrator;
-
-/// Módulo `pdf` — ingestão e processamento de documentos PDF.
-mod pdf;
-
-/// Módulo `persistence` — serialização/desserialização da KB em JSON.
-mod persistence;
-
-/// Módulo `web` — servidor web axum, handlers HTTP, templates e SSE.
-mod web;
+//!
+//! Os módulos vivem na biblioteca (`src/lib.rs`), compartilhada com o
+//! binário de avaliação `eval`.
+//!
+//! ## Subcomandos
+//!
+//! ```text
+//! semantic-chat                                       # servidor web
+//! semantic-chat ingest ./corpus --recursive --glob '*.pdf' [--watch]
+//! ```
+//!
+//! `ingest` roda a ingestão sem o servidor, sobre a KB persistida
+//! ([`semantic_chat::bulk`]).
 
 use std::sync::{Arc, OnceLock};
 
@@ -60,34 +46,114 @@ use parking_lot::RwLock;
 use tokio::sync::broadcast;
 use tracing_subscriber::EnvFilter;
 
-use crate::core::KnowledgeBase;
-use crate::nlu::embedder::{Embedder, EmbedderConfig};
-use crate::nlu::NluPipeline;
-use crate::orchestrator::Orchestrator;
-use crate::web::events::IngestionEvent;
-use crate::web::state::{AppState, ModelReady};
+use semantic_chat::bulk;
+use semantic_chat::checkpoint::CheckpointStore;
+use semantic_chat::core::KnowledgeBase;
+use semantic_chat::jobs::{JobConfig, JobManager};
+use semantic_chat::nlu::embedder::{Embedder, EmbedderConfig};
+use semantic_chat::nlu::NluPipeline;
+use semantic_chat::orchestrator::Orchestrator;
+use semantic_chat::persistence;
+use semantic_chat::web;
+use semantic_chat::web::events::JobEvent;
+use semantic_chat::web::state::{AppState, ModelReady};
 
 /// Função principal assíncrona do Cultivo Epistêmico.
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Configura o sistema de logging/tracing.
+    let mut args = std::env::args().skip(1).peekable();
+    let ingest = args.peek().is_some_and(|a| a == "ingest");
+
+    // Configura o sistema de logging/tracing (no `ingest`, só avisos: a barra de progresso fala).
+    let default_filter = if ingest { "warn" } else { "info" };
     tracing_subscriber::fmt()
         .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_filter)),
         )
         .init();
 
+    if ingest {
+        let options = bulk::BulkOptions::parse(args.skip(1))?;
+        return bulk::run(options).await;
+    }
+    if let Some(arg) = args.next() {
+        anyhow::bail!(
+            "argumento desconhecido: {}\nuso: semantic-chat [ingest DIR ...]\n{}",
+            arg,
+            bulk::USAGE
+        );
+    }
+
     tracing::info!("Cultivo Epistêmico — Starting...");
 
-    // Sempre iniciar com KB vazia (modo LM Studio — troca de modelo de embeddings)
-    let kb = Arc::new(RwLock::new(KnowledgeBase::new()));
-    tracing::info!("KB inicializada vazia (modo LM Studio)");
+    // KB vazia por padrão (modo LM Studio — troca de modelo de embeddings);
+    // KB_RESTORE=1 restaura a KB salva para retomar ingestões interrompidas
+    let restore = std::env::var("KB_RESTORE")
+        .map(|v| matches!(v.trim(), "1" | "true" | "on"))
+        .unwrap_or(false);
+    let kb = if restore {
+        let kb = persistence::load_kb()?;
+        tracing::info!(
+            concepts = kb.concept_count(),
+            links = kb.link_count(),
+            "KB restaurada de data/kb.json"
+        );
+        kb
+    } else {
+        tracing::info!("KB inicializada vazia (modo LM Studio)");
+        KnowledgeBase::new()
+    };
+
+    // Checkpoints sobrevivem ao reinício com a KB restaurada (crash → restart
+    // → "Retomar" em /documentos); só os de documentos já concluídos são velhos.
+    // Sem restauração, a KB vazia não tem os chunks que eles pulariam
+    let checkpoints = CheckpointStore::default();
+    if !restore {
+        match checkpoints.clear() {
+            Ok(0) => {}
+            Ok(n) => tracing::info!(
+                checkpoints = n,
+                "Checkpoints descartados: KB não restaurada (use KB_RESTORE=1 para retomar)"
+            ),
+            Err(e) => tracing::warn!(error = %e, "Falha ao descartar checkpoints"),
+        }
+    }
+    for checkpoint in checkpoints.list() {
+        if kb
+            .documents
+            .get(&checkpoint.hash)
+            .is_some_and(|d| d.ingested_at >= checkpoint.updated_at)
+        {
+            match checkpoints.remove(&checkpoint.hash) {
+                Ok(_) => tracing::info!(
+                    filename = %checkpoint.filename,
+                    hash = %checkpoint.hash,
+                    "Checkpoint removido: documento já ingerido"
+                ),
+                Err(e) => tracing::warn!(
+                    error = %e,
+                    hash = %checkpoint.hash,
+                    "Falha ao remover checkpoint"
+                ),
+            }
+            continue;
+        }
+        tracing::info!(
+            filename = %checkpoint.filename,
+            hash = %checkpoint.hash,
+            next_chunk = checkpoint.next_chunk,
+            total_chunks = checkpoint.total_chunks,
+            "Ingestão interrompida — retome em /documentos"
+        );
+    }
+    let kb = Arc::new(RwLock::new(kb));
 
     // OnceLock para o modelo — será preenchido quando a NLU estiver pronta.
     let model = Arc::new(OnceLock::new());
 
     // Canal broadcast para eventos SSE.
-    let (events_tx, _) = broadcast::channel::<IngestionEvent>(2048);
+    let (events_tx, _) = broadcast::channel::<JobEvent>(2048);
+    let jobs = Arc::new(JobManager::new(JobConfig::from_env(), events_tx.clone()));
     let events_tx = Arc::new(events_tx);
 
     // Estado compartilhado da aplicação.
@@ -95,6 +161,7 @@ async fn main() -> Result<()> {
         model: model.clone(),
         kb: kb.clone(),
         events_tx,
+        jobs,
     };
 
     // Cria o router com todas as rotas.
@@ -0,0 +1,285 @@
+//! # Contexto Conversacional — Histórico Multi-turno e Referências
+//!
+//! Sem histórico, perguntas de continuação como *"e por que isso acontece?"*
+//! chegam ao LLM e ao embedder sem o assunto a que se referem. O
+//! [`ConversationContext`] mantém:
+//!
+//! - **Janela deslizante** das últimas mensagens (`user`/`assistant`),
+//!   enviada como histórico real no request de chat
+//! - **Resumo corrente** das mensagens que saíram da janela, enviado como
+//!   mensagem `system` antes do histórico (gerado pelo LLM com o template
+//!   `prompts/summary.prompt`, ou por concatenação truncada offline)
+//! - **Foco**: labels dos conceitos discutidos por último, usados para
+//!   resolver referências
+//!
+//! ## Resolução de Referências
+//!
+//! ```text
+//! foco = ["fotossíntese"]
+//! "e por que isso acontece?"  → "e por que fotossíntese acontece?"
+//! "fale mais disso"           → "fale mais de fotossíntese"
+//! "e como?"                   → "e como? (sobre fotossíntese)"   ← elipse
+//! ```
+//!
+//! O texto resolvido é usado para **embeddar a consulta**; o LLM recebe o
+//! texto original junto com o histórico.
+//!
+//! ## Configuração
+//!
+//! | Variável | Default | Descrição |
+//! |----------|---------|-----------|
+//! | `CHAT_HISTORY_TURNS` | `6` | Turnos (pares usuário/assistente) na janela |
+
+use std::collections::VecDeque;
+
+use super::embedder::ChatMsg;
+
+/// Tamanho máximo (em caracteres) do resumo gerado offline.
+const FALLBACK_SUMMARY_CHARS: usize = 1500;
+
+/// Pronomes demonstrativos neutros e a forma que assumem ao serem
+/// substituídos pelo label do conceito em foco.
+const DEMONSTRATIVES: &[(&str, &str)] = &[
+    ("isso", ""),
+    ("isto", ""),
+    ("aquilo", ""),
+    ("disso", "de "),
+    ("disto", "de "),
+    ("daquilo", "de "),
+    ("nisso", "em "),
+    ("nisto", "em "),
+    ("naquilo", "em "),
+];
+
+/// Palavras que, sozinhas, não carregam assunto — uma mensagem formada
+/// só por elas é uma elipse ("e como?", "por quê?", "e então?").
+const ELLIPSIS_WORDS: &[&str] = &[
+    "e", "mas", "então", "entao", "por", "que", "quê", "porque", "porquê", "como", "quando",
+    "onde", "qual", "quais", "quanto", "mais", "ainda", "aí", "ai", "sério", "mesmo", "o", "a",
+];
+
+/// Estado conversacional do orquestrador: janela de mensagens, resumo e foco.
+pub struct ConversationContext {
+    /// Mensagens recentes (`user`/`assistant`), da mais antiga para a mais nova.
+    recent: VecDeque<ChatMsg>,
+    /// Resumo das mensagens que saíram da janela.
+    summary: String,
+    /// Labels dos conceitos em foco (mais saliente primeiro).
+    focus: Vec<String>,
+    /// Máximo de mensagens mantidas na janela (2 × turnos).
+    max_messages: usize,
+}
+
+impl ConversationContext {
+    /// Cria um contexto com janela de `max_turns` turnos.
+    pub fn new(max_turns: usize) -> Self {
+        Self {
+            recent: VecDeque::new(),
+            summary: String::new(),
+            focus: Vec::new(),
+            max_messages: max_turns.max(1) * 2,
+        }
+    }
+
+    /// Cria um contexto a partir de `CHAT_HISTORY_TURNS` (padrão: 6).
+    pub fn from_env() -> Self {
+        let turns = std::env::var("CHAT_HISTORY_TURNS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(6);
+        Self::new(turns)
+    }
+
+    /// Registra um turno e retorna as mensagens que saíram da janela
+    /// (a serem incorporadas ao resumo via [`set_summary`](Self::set_summary)).
+    pub fn record_turn(&mut self, user: &str, assistant: Option<&str>) -> Vec<ChatMsg> {
+        self.recent.push_back(ChatMsg::user(user));
+        if let Some(reply) = assistant.filter(|r| !r.trim().is_empty()) {
+            self.recent.push_back(ChatMsg::assistant(reply));
+        }
+        let overflow = self.recent.len().saturating_sub(self.max_messages);
+        self.recent.drain(..overflow).collect()
+    }
+
+    /// Histórico para o request de chat: resumo (como `system`) + janela recente.
+    pub fn history(&self) -> Vec<ChatMsg> {
+        let mut messages = Vec::with_capacity(self.recent.len() + 1);
+        if !self.summary.is_empty() {
+            messages.push(ChatMsg::system(format!(
+                "Resumo da conversa até aqui: {}",
+                self.summary
+            )));
+        }
+        messages.extend(self.recent.iter().cloned());
+        messages
+    }
+
+    /// Resumo corrente da conversa.
+    pub fn summary(&self) -> &str {
+        &self.summary
+    }
+
+    /// Substitui o resumo corrente.
+    pub fn set_summary(&mut self, summary: impl Into<String>) {
+        self.summary = summary.into().trim().to_string();
+    }
+
+    /// Define os conceitos em foco (mais saliente primeiro).
+    pub fn set_focus(&mut self, labels: Vec<String>) {
+        if !labels.is_empty() {
+            self.focus = labels;
+        }
+    }
+
+    /// Labels dos conceitos em foco.
+    pub fn focus(&self) -> &[String] {
+        &self.focus
+    }
+
+    /// Resolve pronomes demonstrativos e elipses usando o conceito em foco.
+    pub fn resolve_references(&self, text: &str) -> String {
+        match self.focus.first() {
+            Some(topic) => resolve_references(text, topic),
+            None => text.to_string(),
+        }
+    }
+
+    /// Esquece todo o contexto (reset da conversa).
+    pub fn clear(&mut self) {
+        self.recent.clear();
+        self.summary.clear();
+        self.focus.clear();
+    }
+}
+
+/// Transcreve mensagens como texto (`Usuário: ...` / `Assistente: ...`).
+pub fn transcript(messages: &[ChatMsg]) -> String {
+    messages
+        .iter()
+        .filter_map(|m| {
+            let speaker = match m.role.as_str() {
+                "user" => "Usuário",
+                "assistant" => "Assistente",
+                _ => return None,
+            };
+            Some(format!(
+                "{}: {}",
+                speaker,
+                m.content.as_deref().unwrap_or("")
+            ))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Resumo offline: concatena o resumo anterior e a transcrição das
+/// mensagens removidas, mantendo apenas os últimos caracteres.
+pub fn fallback_summary(previous: &str, evicted: &[ChatMsg]) -> String {
+    let combined = [previous, &transcript(evicted)]
+        .iter()
+        .filter(|s| !s.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("\n");
+    let total = combined.chars().count();
+    if total <= FALLBACK_SUMMARY_CHARS {
+        return combined;
+    }
+    let tail: String = combined
+        .chars()
+        .skip(total - FALLBACK_SUMMARY_CHARS)
+        .collect();
+    format!("…{}", tail)
+}
+
+/// Substitui demonstrativos neutros por `topic` e completa elipses.
+///
+/// Preserva a pontuação colada às palavras e a contração (`disso` → `de X`).
+pub fn resolve_references(text: &str, topic: &str) -> String {
+    let mut replaced = false;
+    let mut all_ellipsis = true;
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|word| {
+            let start = word
+                .find(|c: char| c.is_alphanumeric())
+                .unwrap_or(word.len());
+            let end = word
+                .rfind(|c: char| c.is_alphanumeric())
+                .map(|i| i + word[i..].chars().next().map_or(1, char::len_utf8))
+                .unwrap_or(start);
+            let (prefix, core, suffix) = (&word[..start], &word[start..end], &word[end..]);
+            let lower = core.to_lowercase();
+            if !core.is_empty() && !ELLIPSIS_WORDS.contains(&lower.as_str()) {
+                all_ellipsis = false;
+            }
+            match DEMONSTRATIVES.iter().find(|(pronoun, _)| *pronoun == lower) {
+                Some((_, preposition)) => {
+                    replaced = true;
+                    format!("{}{}{}{}", prefix, preposition, topic, suffix)
+                }
+                None => word.to_string(),
+            }
+        })
+        .collect();
+
+    if replaced {
+        words.join(" ")
+    } else if all_ellipsis && !words.is_empty() {
+        format!("{} (sobre {})", text.trim(), topic)
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_demonstratives_and_contractions() {
+        assert_eq!(
+            resolve_references("e por que isso acontece?", "fotossíntese"),
+            "e por que fotossíntese acontece?"
+        );
+        assert_eq!(
+            resolve_references("Fale mais disso.", "Rust"),
+            "Fale mais de Rust."
+        );
+    }
+
+    #[test]
+    fn completes_ellipsis_only_without_content_words() {
+        assert_eq!(
+            resolve_references("e como?", "Rust"),
+            "e como? (sobre Rust)"
+        );
+        assert_eq!(
+            resolve_references("o que é ownership?", "Rust"),
+            "o que é ownership?"
+        );
+    }
+
+    #[test]
+    fn window_evicts_oldest_messages() {
+        let mut ctx = ConversationContext::new(1);
+        assert!(ctx.record_turn("oi", Some("olá")).is_empty());
+        let evicted = ctx.record_turn("tudo bem?", Some("sim"));
+        assert_eq!(transcript(&evicted), "Usuário: oi\nAssistente: olá");
+
+        ctx.set_summary("falaram oi");
+        let history = ctx.history();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].role, "system");
+        assert_eq!(history[2].content.as_deref(), Some("sim"));
+    }
+
+    #[test]
+    fn fallback_summary_keeps_tail() {
+        let long = "x".repeat(FALLBACK_SUMMARY_CHARS + 10);
+        let summary = fallback_summary(&long, &[ChatMsg::user("fim")]);
+        assert!(summary.starts_with('…'));
+        assert!(summary.ends_with("Usuário: fim"));
+        assert_eq!(summary.chars().count(), FALLBACK_SUMMARY_CHARS + 1);
+    }
+}
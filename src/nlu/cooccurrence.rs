@@ -0,0 +1,249 @@
+//! # Janelas de Coocorrência — Links a Partir de Sentenças
+//!
+//! Antes, a primeira entidade de cada mensagem/chunk virava o sujeito de
+//! uma `Implication` para todas as outras: a direção dependia da ordem
+//! das palavras e um chunk de 500 chars formava uma estrela em torno de
+//! um termo arbitrário. Agora os links vêm de **janelas de sentenças**:
+//!
+//! ```text
+//! "A chuva molha o solo. O sol seca o solo. Plantas precisam de luz."
+//!   sentenças:  [chuva, solo]   [sol, solo]   [plantas, luz]
+//!   janela = 1: chuva—solo, sol—solo, plantas—luz   (nada de chuva—luz)
+//! ```
+//!
+//! Cada janela alimenta as [`CooccurrenceStats`] da KB. Para cada par que
+//! coocorreu, a observação vira um [`TruthValue`] com frequência dada pela
+//! medida de associação no corpus inteiro e peso igual ao número de
+//! janelas **novas** do trecho em que o par apareceu (`w` janelas →
+//! `c = w / (w + 1)`):
+//!
+//! | Medida | `COOCCURRENCE_MEASURE` | Frequência |
+//! |--------|------------------------|------------|
+//! | [`Npmi`](AssociationMeasure::Npmi) | `npmi` (padrão), `pmi` | `(NPMI + 1) / 2` |
+//! | [`Dice`](AssociationMeasure::Dice) | `dice` | `Dice` |
+//!
+//! Um par que já tem link (em qualquer direção) **revisa** o link
+//! existente (`truth.revision(observação)`): a confiança cresce com cada
+//! coocorrência e a frequência acompanha a associação. Um par novo cria
+//! a `Implication` do conceito mais raro para o mais frequente
+//! (`n(a) ≤ n(b)` → `a ⇒ b`: quando `a` aparece, `b` costuma aparecer);
+//! no empate, vale a ordem de extração das entidades.
+//!
+//! `COOCCURRENCE_WINDOW` (padrão 1) define quantas sentenças consecutivas
+//! formam uma janela. As janelas não se sobrepõem e as sentenças que
+//! repetem o chunk anterior ([`Chunk::overlap`](crate::document::chunk::Chunk::overlap))
+//! ficam de fora: cada sentença conta uma única vez nas estatísticas.
+
+use super::sentence::SentenceSegmenter;
+use crate::core::concept::ConceptId;
+use crate::core::{CooccurrenceStats, TruthValue};
+
+/// Medida de associação que dá a frequência dos links.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AssociationMeasure {
+    /// PMI normalizado, mapeado para `[0, 1]`.
+    #[default]
+    Npmi,
+    /// Coeficiente de Dice.
+    Dice,
+}
+
+impl AssociationMeasure {
+    /// Interpreta o valor de `COOCCURRENCE_MEASURE`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "npmi" | "pmi" => Some(AssociationMeasure::Npmi),
+            "dice" => Some(AssociationMeasure::Dice),
+            _ => None,
+        }
+    }
+
+    /// Frequência em `[0, 1]` da associação entre dois conceitos.
+    pub fn frequency(&self, stats: &CooccurrenceStats, a: ConceptId, b: ConceptId) -> f64 {
+        match self {
+            AssociationMeasure::Npmi => (stats.npmi(a, b) + 1.0) / 2.0,
+            AssociationMeasure::Dice => stats.dice(a, b),
+        }
+    }
+
+    /// Observação de `windows` coocorrências novas: frequência da medida,
+    /// uma evidência por janela (`k = 1`).
+    pub fn observation(
+        &self,
+        stats: &CooccurrenceStats,
+        a: ConceptId,
+        b: ConceptId,
+        windows: u64,
+    ) -> TruthValue {
+        let w = windows as f64;
+        TruthValue::new(self.frequency(stats, a, b), w / (w + 1.0))
+    }
+}
+
+/// Configuração das janelas de coocorrência.
+#[derive(Clone, Debug)]
+pub struct CooccurrenceConfig {
+    /// Sentenças por janela.
+    pub window: usize,
+    /// Medida de associação.
+    pub measure: AssociationMeasure,
+}
+
+impl Default for CooccurrenceConfig {
+    fn default() -> Self {
+        Self {
+            window: 1,
+            measure: AssociationMeasure::Npmi,
+        }
+    }
+}
+
+impl CooccurrenceConfig {
+    /// Lê `COOCCURRENCE_WINDOW` e `COOCCURRENCE_MEASURE`.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let window = std::env::var("COOCCURRENCE_WINDOW")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(defaults.window)
+            .max(1);
+        let measure = std::env::var("COOCCURRENCE_MEASURE")
+            .ok()
+            .and_then(|v| {
+                let measure = AssociationMeasure::from_name(&v);
+                if measure.is_none() {
+                    tracing::warn!(value = %v, "COOCCURRENCE_MEASURE desconhecida, usando npmi");
+                }
+                measure
+            })
+            .unwrap_or(defaults.measure);
+        Self { window, measure }
+    }
+}
+
+/// Janelas de `size` sentenças consecutivas (sem sobreposição), com os
+/// índices das entidades que aparecem em cada uma (busca por palavra
+/// inteira, sem caixa).
+///
+/// Sentenças que começam antes do byte `skip` (sobreposição com o chunk
+/// anterior, já contada) ficam de fora. Entidades que não aparecem
+/// literalmente no texto ficam fora das janelas.
+pub fn windows(
+    text: &str,
+    entities: &[String],
+    segmenter: &SentenceSegmenter,
+    size: usize,
+    skip: usize,
+) -> Vec<Vec<usize>> {
+    let needles: Vec<String> = entities.iter().map(|e| normalize(e)).collect();
+    let sentences: Vec<Vec<usize>> = segmenter
+        .split(text)
+        .into_iter()
+        .filter(|sentence| sentence.start >= skip)
+        .map(|sentence| {
+            let haystack = normalize(sentence.text);
+            needles
+                .iter()
+                .enumerate()
+                .filter(|(_, needle)| contains_word(&haystack, needle))
+                .map(|(i, _)| i)
+                .collect()
+        })
+        .collect();
+    sentences
+        .chunks(size.max(1))
+        .map(|group| {
+            let mut window: Vec<usize> = group.iter().flatten().copied().collect();
+            window.sort_unstable();
+            window.dedup();
+            window
+        })
+        .collect()
+}
+
+/// Pares distintos `(a, b)` que coocorreram em alguma janela, com o número
+/// de janelas, na ordem das janelas (`a` vem antes de `b` na janela em que
+/// o par apareceu primeiro).
+pub fn pairs(windows: &[Vec<ConceptId>]) -> Vec<(ConceptId, ConceptId, u64)> {
+    let mut pairs: Vec<(ConceptId, ConceptId, u64)> = Vec::new();
+    for window in windows {
+        let mut ids: Vec<ConceptId> = Vec::new();
+        for &id in window {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        for (i, &a) in ids.iter().enumerate() {
+            for &b in &ids[i + 1..] {
+                match pairs
+                    .iter_mut()
+                    .find(|(x, y, _)| (*x, *y) == (a, b) || (*x, *y) == (b, a))
+                {
+                    Some((_, _, count)) => *count += 1,
+                    None => pairs.push((a, b, 1)),
+                }
+            }
+        }
+    }
+    pairs
+}
+
+/// Minúsculas com espaços colapsados.
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// `true` se `needle` aparece em `haystack` delimitado por não-alfanuméricos.
+fn contains_word(haystack: &str, needle: &str) -> bool {
+    if needle.is_empty() {
+        return false;
+    }
+    haystack.match_indices(needle).any(|(i, _)| {
+        let before = haystack[..i].chars().next_back();
+        let after = haystack[i + needle.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_group_entities_by_sentence() {
+        let text = "A chuva molha o solo. O sol seca o solo. Plantas precisam de luz.";
+        let entities: Vec<String> = ["chuva", "solo", "sol", "Plantas", "luz"]
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+        let segmenter = SentenceSegmenter::new();
+
+        assert_eq!(
+            windows(text, &entities, &segmenter, 1, 0),
+            vec![vec![0, 1], vec![1, 2], vec![3, 4]]
+        );
+        assert_eq!(
+            windows(text, &entities, &segmenter, 2, 0),
+            vec![vec![0, 1, 2], vec![3, 4]]
+        );
+        // A primeira sentença repete o chunk anterior
+        assert_eq!(
+            windows(text, &entities, &segmenter, 1, 10),
+            vec![vec![1, 2], vec![3, 4]]
+        );
+    }
+
+    #[test]
+    fn pairs_count_each_window_once() {
+        let (a, b, c) = (
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+        );
+        let windows = vec![vec![a, b, a], vec![b, a], vec![c]];
+        assert_eq!(pairs(&windows), vec![(a, b, 2)]);
+    }
+}
@@ -0,0 +1,437 @@
+//! # Correferência — Pronomes e Descrições Definidas entre Turnos
+//!
+//! O [`EntityExtractor`](super::extractor::EntityExtractor) trabalha por
+//! mensagem e descarta pronomes como stopwords: em *"Carlos ligou. Ele
+//! disse que o projeto atrasou"*, "Ele" nunca se conecta a Carlos — e se
+//! a segunda frase vier no turno seguinte, o link `Carlos → projeto`
+//! simplesmente não existe.
+//!
+//! Esta camada reescreve a mensagem **antes da extração**, substituindo
+//! cada referência pelo label do seu antecedente:
+//!
+//! ```text
+//! foco (last_discussed) = ["Carlos", "projeto Apolo"]
+//! "Ele disse que o projeto atrasou"
+//!   → "Carlos disse que projeto Apolo atrasou"
+//! ```
+//!
+//! ## Heurísticas
+//!
+//! | Referência | Exemplos | Antecedente |
+//! |------------|----------|-------------|
+//! | Pronome pessoal | ele, ela, eles, elas | Mais recente com gênero/número compatíveis |
+//! | Contração | dele, nela, deles... | Idem, preservando a preposição (`de X`, `em X`) |
+//! | Descrição definida | "o projeto", "a empresa" | Label com a mesma palavra-núcleo |
+//!
+//! **Recência**: menções anteriores na própria mensagem (nomes
+//! capitalizados) têm prioridade sobre os conceitos do turno anterior.
+//!
+//! **Gênero/número**: inferidos do label — listas de prenomes comuns e
+//! terminações (`-a`, `-ção`, `-dade` → feminino; `-o`, `-mento`, `-or`
+//! → masculino; `-s` → plural, exceto nomes próprios).
+//!
+//! **Idioma**: pronomes, contrações e artigos são do português; mensagens
+//! em inglês passam intactas (em *"I want a project"*, o "a" não é artigo
+//! feminino e não pode virar "projeto Apollo").
+//!
+//! ## Assistência por LLM (opcional)
+//!
+//! Com `COREF_LLM_ASSIST=1`, referências com mais de um antecedente
+//! compatível são desambiguadas pelo LLM (template `prompts/coref.prompt`);
+//! sem resposta válida, vale a escolha por recência.
+
+use super::language::Language;
+
+/// Gênero gramatical de um antecedente ou pronome.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Gender {
+    /// Masculino.
+    Masculine,
+    /// Feminino.
+    Feminine,
+    /// Indeterminado — compatível com qualquer pronome.
+    Unknown,
+}
+
+impl Gender {
+    fn agrees(self, other: Gender) -> bool {
+        self == Gender::Unknown || other == Gender::Unknown || self == other
+    }
+}
+
+/// Número gramatical.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Number {
+    /// Singular.
+    Singular,
+    /// Plural.
+    Plural,
+}
+
+/// Pronomes pessoais de 3ª pessoa: (forma, preposição contraída, gênero, número).
+const PRONOUNS: &[(&str, &str, Gender, Number)] = &[
+    ("ele", "", Gender::Masculine, Number::Singular),
+    ("ela", "", Gender::Feminine, Number::Singular),
+    ("eles", "", Gender::Masculine, Number::Plural),
+    ("elas", "", Gender::Feminine, Number::Plural),
+    ("dele", "de ", Gender::Masculine, Number::Singular),
+    ("dela", "de ", Gender::Feminine, Number::Singular),
+    ("deles", "de ", Gender::Masculine, Number::Plural),
+    ("delas", "de ", Gender::Feminine, Number::Plural),
+    ("nele", "em ", Gender::Masculine, Number::Singular),
+    ("nela", "em ", Gender::Feminine, Number::Singular),
+    ("neles", "em ", Gender::Masculine, Number::Plural),
+    ("nelas", "em ", Gender::Feminine, Number::Plural),
+];
+
+/// Artigos definidos: (forma, gênero, número).
+const ARTICLES: &[(&str, Gender, Number)] = &[
+    ("o", Gender::Masculine, Number::Singular),
+    ("a", Gender::Feminine, Number::Singular),
+    ("os", Gender::Masculine, Number::Plural),
+    ("as", Gender::Feminine, Number::Plural),
+];
+
+/// Prenomes masculinos comuns que fogem da regra da terminação.
+const MASCULINE_NAMES: &[&str] = &[
+    "carlos", "marcos", "lucas", "jonas", "matheus", "mateus", "luís", "luis", "josé", "joão",
+    "davi", "rafael", "daniel", "miguel", "gabriel", "samuel", "paulo", "pedro", "thiago", "tiago",
+    "andré", "felipe", "henrique", "jorge", "vicente", "caio", "igor", "artur", "arthur", "victor",
+    "vitor", "heitor", "enzo", "bernardo", "nicolas", "elias", "tomás",
+];
+
+/// Prenomes femininos comuns que fogem da regra da terminação.
+const FEMININE_NAMES: &[&str] = &[
+    "beatriz", "raquel", "isabel", "mabel", "ester", "carmen", "ingrid", "alice", "beatrice",
+    "lais", "laís", "thais", "thaís", "inês", "ines", "liz", "luz", "rute", "ruth", "yasmin",
+];
+
+/// Palavras capitalizadas no início de frase que não são antecedentes.
+const NON_REFERENTS: &[&str] = &[
+    "o", "a", "os", "as", "um", "uma", "ele", "ela", "eles", "elas", "isso", "isto", "aquilo",
+    "hoje", "ontem", "amanhã", "então", "depois", "mas", "e", "quando", "se", "não", "sim",
+];
+
+/// Candidato a antecedente de uma referência.
+#[derive(Clone, Debug)]
+pub struct Antecedent {
+    /// Label do conceito (ou nome mencionado na mensagem).
+    pub label: String,
+    /// Gênero inferido do label.
+    pub gender: Gender,
+    /// Número inferido do label.
+    pub number: Number,
+}
+
+impl Antecedent {
+    /// Cria um antecedente inferindo gênero e número do label.
+    pub fn from_label(label: &str) -> Self {
+        let (gender, number) = infer_gender_number(label);
+        Self {
+            label: label.to_string(),
+            gender,
+            number,
+        }
+    }
+}
+
+/// Uma referência resolvida na mensagem.
+#[derive(Clone, Debug)]
+pub struct Reference {
+    /// Texto da referência como aparece na mensagem ("Ele", "o projeto").
+    pub surface: String,
+    /// Label do antecedente escolhido.
+    pub antecedent: String,
+    /// Antecedentes compatíveis, do mais provável ao menos provável.
+    pub candidates: Vec<String>,
+    /// Offset (bytes) do início da referência na mensagem.
+    pub start: usize,
+    /// Offset (bytes) do fim da referência na mensagem.
+    pub end: usize,
+    /// Preposição contraída no pronome (`"de "`, `"em "` ou vazia).
+    pub preposition: &'static str,
+}
+
+impl Reference {
+    /// Se há mais de um antecedente compatível (candidata à assistência do LLM).
+    pub fn is_ambiguous(&self) -> bool {
+        self.candidates.len() > 1
+    }
+
+    /// Descrição para o chat: `Ele → Carlos`.
+    pub fn describe(&self) -> String {
+        format!("{} → {}", self.surface, self.antecedent)
+    }
+}
+
+/// Infere gênero e número de um label em Português.
+///
+/// Usa a primeira palavra (núcleo do sintagma: "projeto Apolo" → "projeto")
+/// e trata labels capitalizados como nomes próprios (sempre singulares).
+pub fn infer_gender_number(label: &str) -> (Gender, Number) {
+    let Some(head) = label.split_whitespace().next() else {
+        return (Gender::Unknown, Number::Singular);
+    };
+    let proper = head.chars().next().is_some_and(char::is_uppercase);
+    let lower = head.to_lowercase();
+
+    if MASCULINE_NAMES.contains(&lower.as_str()) {
+        return (Gender::Masculine, Number::Singular);
+    }
+    if FEMININE_NAMES.contains(&lower.as_str()) {
+        return (Gender::Feminine, Number::Singular);
+    }
+
+    let plural = !proper && lower.len() > 3 && lower.ends_with('s') && !lower.ends_with("ês");
+    let number = if plural {
+        Number::Plural
+    } else {
+        Number::Singular
+    };
+    let stem = if plural {
+        &lower[..lower.len() - 1]
+    } else {
+        lower.as_str()
+    };
+    let stem = stem
+        .strip_suffix("õe")
+        .map(|s| format!("{}ão", s))
+        .unwrap_or_else(|| stem.to_string());
+
+    let gender = if [
+        "ção", "são", "dade", "gem", "ice", "ura", "eza", "ência", "ância",
+    ]
+    .iter()
+    .any(|s| stem.ends_with(s))
+    {
+        Gender::Feminine
+    } else if ["mento", "ismo", "or", "ema", "ama"]
+        .iter()
+        .any(|s| stem.ends_with(s))
+    {
+        Gender::Masculine
+    } else if stem.ends_with('a') {
+        Gender::Feminine
+    } else if stem.ends_with('o') {
+        Gender::Masculine
+    } else {
+        Gender::Unknown
+    };
+    (gender, number)
+}
+
+/// Encontra e resolve as referências de `text`.
+///
+/// `prior` são os conceitos do turno anterior (`last_discussed`), do mais
+/// saliente ao menos saliente. Menções capitalizadas anteriores na própria
+/// mensagem têm prioridade (recência). Só textos em português são
+/// analisados; nos demais idiomas não há referências.
+pub fn find_references(text: &str, prior: &[Antecedent], language: Language) -> Vec<Reference> {
+    if language != Language::Portuguese {
+        return Vec::new();
+    }
+    let tokens = tokenize(text);
+    // Menções dentro da mensagem, da mais recente para a mais antiga
+    let mut local: Vec<Antecedent> = Vec::new();
+    let mut references = Vec::new();
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let (start, end, word) = tokens[i];
+        let lower = word.to_lowercase();
+
+        // ─── Pronome pessoal ─────────────────────────────────────
+        if let Some(&(_, preposition, gender, number)) = PRONOUNS.iter().find(|p| p.0 == lower) {
+            let candidates: Vec<String> = local
+                .iter()
+                .chain(prior.iter())
+                .filter(|a| a.number == number && a.gender.agrees(gender))
+                .map(|a| a.label.clone())
+                .fold(Vec::new(), |mut acc, label| {
+                    if !acc.contains(&label) {
+                        acc.push(label);
+                    }
+                    acc
+                });
+            if let Some(first) = candidates.first() {
+                references.push(Reference {
+                    surface: word.to_string(),
+                    antecedent: first.clone(),
+                    candidates,
+                    start,
+                    end,
+                    preposition,
+                });
+            }
+            i += 1;
+            continue;
+        }
+
+        // ─── Descrição definida: artigo + núcleo de um label anterior ──
+        if let Some(&(_, gender, number)) = ARTICLES.iter().find(|a| a.0 == lower) {
+            if let Some(&(_, noun_end, noun)) = tokens.get(i + 1) {
+                let noun_lower = noun.to_lowercase();
+                let matched = local.iter().chain(prior.iter()).find(|a| {
+                    let head = a
+                        .label
+                        .split_whitespace()
+                        .next()
+                        .unwrap_or("")
+                        .to_lowercase();
+                    a.label.split_whitespace().count() > 1
+                        && head == noun_lower
+                        && a.number == number
+                        && a.gender.agrees(gender)
+                });
+                if let Some(antecedent) = matched {
+                    references.push(Reference {
+                        surface: text[start..noun_end].to_string(),
+                        antecedent: antecedent.label.clone(),
+                        candidates: vec![antecedent.label.clone()],
+                        start,
+                        end: noun_end,
+                        preposition: "",
+                    });
+                    i += 2;
+                    continue;
+                }
+            }
+        }
+
+        // ─── Menção capitalizada → novo antecedente local ─────────
+        if word.chars().next().is_some_and(char::is_uppercase)
+            && !NON_REFERENTS.contains(&lower.as_str())
+        {
+            let sentence_start = i == 0 || text[..start].trim_end().ends_with(['.', '!', '?']);
+            let antecedent = Antecedent::from_label(word);
+            // No início de frase, só nomes com gênero reconhecível
+            if !sentence_start || antecedent.gender != Gender::Unknown {
+                local.retain(|a| a.label != antecedent.label);
+                local.insert(0, antecedent);
+            }
+        }
+        i += 1;
+    }
+    references
+}
+
+/// Reescreve `text` substituindo cada referência pelo seu antecedente.
+pub fn apply_references(text: &str, references: &[Reference]) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut cursor = 0;
+    for reference in references {
+        out.push_str(&text[cursor..reference.start]);
+        out.push_str(reference.preposition);
+        out.push_str(&reference.antecedent);
+        cursor = reference.end;
+    }
+    out.push_str(&text[cursor..]);
+    out
+}
+
+/// Divide o texto em palavras com seus offsets (bytes), sem pontuação.
+fn tokenize(text: &str) -> Vec<(usize, usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        match (c.is_alphanumeric() || c == '-', start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                tokens.push((s, i, &text[s..i]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        tokens.push((s, text.len(), &text[s..]));
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(text: &str, prior: &[&str]) -> String {
+        let prior: Vec<Antecedent> = prior.iter().map(|l| Antecedent::from_label(l)).collect();
+        apply_references(text, &find_references(text, &prior, Language::Portuguese))
+    }
+
+    #[test]
+    fn infers_gender_and_number() {
+        assert_eq!(
+            infer_gender_number("Carlos"),
+            (Gender::Masculine, Number::Singular)
+        );
+        assert_eq!(
+            infer_gender_number("Maria"),
+            (Gender::Feminine, Number::Singular)
+        );
+        assert_eq!(
+            infer_gender_number("sustentabilidade"),
+            (Gender::Feminine, Number::Singular)
+        );
+        assert_eq!(
+            infer_gender_number("projetos"),
+            (Gender::Masculine, Number::Plural)
+        );
+        assert_eq!(
+            infer_gender_number("soluções"),
+            (Gender::Feminine, Number::Plural)
+        );
+    }
+
+    #[test]
+    fn resolves_pronoun_within_message() {
+        assert_eq!(
+            resolve("Carlos ligou. Ele disse que atrasou", &[]),
+            "Carlos ligou. Carlos disse que atrasou"
+        );
+    }
+
+    #[test]
+    fn resolves_pronoun_across_turns_by_gender() {
+        assert_eq!(
+            resolve("Ela disse que atrasou", &["Carlos", "Maria"]),
+            "Maria disse que atrasou"
+        );
+        assert_eq!(
+            resolve("O chefe dela ligou", &["Maria"]),
+            "O chefe de Maria ligou"
+        );
+    }
+
+    #[test]
+    fn local_mention_wins_over_prior() {
+        assert_eq!(
+            resolve("Pedro chegou e ele riu", &["Carlos"]),
+            "Pedro chegou e Pedro riu"
+        );
+    }
+
+    #[test]
+    fn resolves_definite_description() {
+        assert_eq!(
+            resolve("O projeto atrasou", &["projeto Apolo"]),
+            "projeto Apolo atrasou"
+        );
+        assert_eq!(
+            resolve("O projeto atrasou", &["projeto"]),
+            "O projeto atrasou"
+        );
+    }
+
+    #[test]
+    fn unresolvable_pronoun_is_kept() {
+        assert_eq!(resolve("Ele disse", &["Maria"]), "Ele disse");
+        assert!(find_references("Ele disse", &[], Language::Portuguese).is_empty());
+    }
+
+    #[test]
+    fn english_text_is_not_rewritten() {
+        let prior = [Antecedent::from_label("project Apollo")];
+        assert!(find_references("I want a project", &prior, Language::English).is_empty());
+        assert!(find_references("She said a project", &prior, Language::English).is_empty());
+    }
+}
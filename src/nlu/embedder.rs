@@ -22,19 +22,75 @@
 //! |----------|---------|-----------|
 //! | `LM_STUDIO_URL` | `http://localhost:1234/v1` | URL base do LM Studio |
 //! | `LM_STUDIO_EMBED_MODEL` | `nomic-embed-text` | Modelo de embeddings |
-//! | `LM_STUDIO_CHAT_MODEL` | `default` | Modelo de chat |
+//! | `LM_STUDIO_CHAT_MODEL` | `default` | Modelo de chat (padrão das tarefas) |
+//! | `LM_STUDIO_MERGE_SYSTEM` | `false` | Mescla `system` em `user` (modelos sem role `system`) |
+//! | `EMBED_BATCH_SIZE` | `64` | Textos por request em [`Embedder::embed_batches`] |
+//! | `EMBED_CONCURRENCY` | `2` | Requests de embedding simultâneas |
+//! | `EMBED_RETRIES` | `3` | Novas tentativas de um batch que falhou |
+//!
+//! Temperatura, `max_tokens` e modelo por tarefa vêm dos templates de
+//! prompt ([`super::prompts`]), via [`ChatParams`].
+//!
+//! ## Embeddings em Lotes
+//!
+//! Um documento grande gera milhares de entidades; uma única request com
+//! todas estoura o timeout e falha inteira. [`Embedder::embed_batches`]
+//! divide os textos em lotes de `EMBED_BATCH_SIZE`, mantém até
+//! `EMBED_CONCURRENCY` requests em voo e repete cada lote que falhar
+//! (backoff exponencial: 500ms, 1s, 2s… até 30s) — a ordem dos vetores
+//! é a ordem dos textos:
+//!
+//! ```text
+//! [t0 … t63] [t64 … t127] [t128 … t191] ...
+//!     │           │            │
+//!     ├─ POST ────┼─ POST      │  (≤ EMBED_CONCURRENCY em voo)
+//!     │  falhou → espera 500ms, 1s, 2s… → repete (até EMBED_RETRIES)
+//!     ▼           ▼            ▼
+//! [v0 … v63] [v64 … v127] [v128 … v191]  (na ordem original)
+//! ```
+//!
+//! ## Modo Offline
+//!
+//! [`Embedder::offline`] não acessa rede: os embeddings são *feature
+//! hashing* de trigramas de caracteres e palavras (768-dim, normalizados),
+//! determinísticos e suficientes para similaridade lexical. Chamadas de
+//! chat falham. Usado pelo binário de avaliação (`eval`), que precisa de
+//! resultados reprodutíveis sem o LM Studio.
+//!
+//! ## Function Calling
+//!
+//! [`Embedder::chat_with_tools`] expõe o formato de *function calling* da
+//! API OpenAI (`tools` + `tool_calls`), usado pelo modo agente do chat
+//! ([`crate::agent`]).
+
+use std::time::Duration;
 
 use anyhow::{Context, Result};
+use futures_util::stream::{self, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 
+/// Espera antes da primeira nova tentativa de um lote (dobra a cada falha).
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Teto da espera entre tentativas, para `EMBED_RETRIES` altos.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
 /// Configuração do Embedder para conexão com LM Studio.
 pub struct EmbedderConfig {
     /// URL base da API do LM Studio (ex: `http://localhost:1234/v1`).
     pub base_url: String,
     /// Nome do modelo de embeddings carregado no LM Studio.
     pub embed_model: String,
-    /// Nome do modelo de chat carregado no LM Studio.
+    /// Nome do modelo de chat carregado no LM Studio (padrão das tarefas).
     pub chat_model: String,
+    /// Mescla o prompt `system` na mensagem `user` (modelos sem role `system`).
+    pub merge_system: bool,
+    /// Textos por request de embedding em [`Embedder::embed_batches`].
+    pub batch_size: usize,
+    /// Requests de embedding simultâneas em [`Embedder::embed_batches`].
+    pub concurrency: usize,
+    /// Novas tentativas de um lote que falhou.
+    pub retries: u32,
 }
 
 impl EmbedderConfig {
@@ -45,6 +101,10 @@ impl EmbedderConfig {
     /// | `LM_STUDIO_URL` | `http://localhost:1234/v1` |
     /// | `LM_STUDIO_EMBED_MODEL` | `nomic-embed-text` |
     /// | `LM_STUDIO_CHAT_MODEL` | `default` |
+    /// | `LM_STUDIO_MERGE_SYSTEM` | `false` |
+    /// | `EMBED_BATCH_SIZE` | `64` |
+    /// | `EMBED_CONCURRENCY` | `2` |
+    /// | `EMBED_RETRIES` | `3` |
     pub fn from_env() -> Self {
         Self {
             base_url: std::env::var("LM_STUDIO_URL")
@@ -53,6 +113,23 @@ impl EmbedderConfig {
                 .unwrap_or_else(|_| "nomic-embed-text".to_string()),
             chat_model: std::env::var("LM_STUDIO_CHAT_MODEL")
                 .unwrap_or_else(|_| "default".to_string()),
+            merge_system: std::env::var("LM_STUDIO_MERGE_SYSTEM")
+                .map(|v| matches!(v.trim(), "1" | "true" | "on"))
+                .unwrap_or(false),
+            batch_size: std::env::var("EMBED_BATCH_SIZE")
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(64usize)
+                .max(1),
+            concurrency: std::env::var("EMBED_CONCURRENCY")
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(2usize)
+                .max(1),
+            retries: std::env::var("EMBED_RETRIES")
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(3),
         }
     }
 }
@@ -66,6 +143,8 @@ pub struct Embedder {
     client: reqwest::Client,
     /// Configuração de conexão (URL, modelos).
     config: EmbedderConfig,
+    /// Embeddings por feature hashing, sem LM Studio (veja [`Embedder::offline`]).
+    offline: bool,
 }
 
 impl Embedder {
@@ -86,7 +165,32 @@ impl Embedder {
             "Embedder configurado para LM Studio"
         );
 
-        Self { client, config }
+        Self {
+            client,
+            config,
+            offline: false,
+        }
+    }
+
+    /// Cria um Embedder offline: embeddings por feature hashing, sem chat.
+    ///
+    /// Determinístico — o mesmo texto gera sempre o mesmo vetor.
+    pub fn offline() -> Self {
+        let config = EmbedderConfig {
+            base_url: "offline".to_string(),
+            embed_model: "feature-hashing".to_string(),
+            chat_model: "none".to_string(),
+            merge_system: false,
+            batch_size: 64,
+            concurrency: 1,
+            retries: 0,
+        };
+        tracing::info!("Embedder offline (feature hashing, sem LLM)");
+        Self {
+            client: reqwest::Client::new(),
+            config,
+            offline: true,
+        }
     }
 
     /// Acessor para a configuração do embedder.
@@ -98,6 +202,9 @@ impl Embedder {
     ///
     /// Faz GET `/models` e verifica se retorna HTTP 200.
     pub async fn health_check(&self) -> Result<()> {
+        if self.offline {
+            return Ok(());
+        }
         let url = format!("{}/models", self.config.base_url);
         let resp = self
             .client
@@ -122,6 +229,9 @@ impl Embedder {
     ///
     /// Faz POST `/embeddings` com o texto como input.
     pub async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        if self.offline {
+            return Ok(hashing_embedding(text));
+        }
         let url = format!("{}/embeddings", self.config.base_url);
         let request = EmbeddingRequest {
             input: serde_json::Value::String(text.to_string()),
@@ -154,6 +264,9 @@ impl Embedder {
     /// Mais eficiente que chamar [`embed()`](Embedder::embed) repetidamente
     /// pois usa uma única request com array de inputs.
     pub async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        if self.offline {
+            return Ok(texts.iter().map(|t| hashing_embedding(t)).collect());
+        }
         if texts.is_empty() {
             return Ok(Vec::new());
         }
@@ -191,22 +304,83 @@ impl Embedder {
         Ok(response.data.into_iter().map(|d| d.embedding).collect())
     }
 
-    /// Envia mensagem para o LLM e retorna a resposta gerada.
+    /// Gera embeddings de muitos textos em lotes, com concorrência limitada
+    /// e novas tentativas por lote.
+    ///
+    /// Lotes de `batch_size` textos, até `concurrency` requests em voo; um
+    /// lote que falha é repetido até `retries` vezes com backoff exponencial.
+    /// Os vetores saem na ordem dos textos. Falha se algum lote esgotar as
+    /// tentativas.
+    pub async fn embed_batches(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        if self.offline || texts.len() <= self.config.batch_size {
+            return self.embed_batch_with_retry(texts).await;
+        }
+        // Futures criados antes do stream: um closure no `map` não é `Send` o
+        // bastante para o `tokio::spawn` do job (limitação de lifetimes)
+        let requests: Vec<_> = texts
+            .chunks(self.config.batch_size)
+            .map(|batch| self.embed_batch_with_retry(batch))
+            .collect();
+        let batches: Vec<Vec<Vec<f32>>> = stream::iter(requests)
+            .buffered(self.config.concurrency)
+            .try_collect()
+            .await?;
+        Ok(batches.into_iter().flatten().collect())
+    }
+
+    /// [`embed_batch()`](Embedder::embed_batch) com até `retries` novas tentativas.
+    async fn embed_batch_with_retry(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut attempt = 0;
+        loop {
+            match self.embed_batch(texts).await {
+                Ok(embeddings) if embeddings.len() == texts.len() => return Ok(embeddings),
+                Ok(embeddings) if attempt >= self.config.retries => {
+                    anyhow::bail!(
+                        "LM Studio devolveu {} embeddings para {} textos",
+                        embeddings.len(),
+                        texts.len()
+                    )
+                }
+                Err(e) if attempt >= self.config.retries => {
+                    return Err(e.context(format!(
+                        "Lote de {} embeddings falhou após {} tentativas",
+                        texts.len(),
+                        attempt + 1
+                    )));
+                }
+                result => {
+                    let delay = RETRY_BACKOFF
+                        .saturating_mul(1 << attempt.min(16))
+                        .min(MAX_RETRY_DELAY);
+                    let reason = result
+                        .err()
+                        .map_or_else(|| "resposta incompleta".to_string(), |e| format!("{:#}", e));
+                    tracing::warn!(
+                        attempt = attempt + 1,
+                        texts = texts.len(),
+                        delay_ms = delay.as_millis() as u64,
+                        error = %reason,
+                        "Lote de embeddings falhou, tentando de novo"
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Envia uma conversa ao LLM e retorna o texto da resposta.
     ///
-    /// Faz POST `/chat/completions` com system prompt embutido na mensagem do usuário.
-    /// O system prompt é mesclado no conteúdo da mensagem `user` para compatibilidade
-    /// com modelos que não suportam a role `system`.
-    pub async fn chat(&self, system_prompt: &str, user_message: &str) -> Result<String> {
+    /// Faz POST `/chat/completions` com as mensagens (`system`, `user`,
+    /// `assistant`) e os parâmetros da tarefa ([`ChatParams`]). Com
+    /// `LM_STUDIO_MERGE_SYSTEM=1`, as mensagens `system` são mescladas na
+    /// primeira mensagem `user`, para modelos que não suportam a role `system`.
+    pub async fn chat(&self, messages: &[ChatMsg], params: &ChatParams) -> Result<String> {
+        if self.offline {
+            anyhow::bail!("Embedder offline não tem modelo de chat");
+        }
         let url = format!("{}/chat/completions", self.config.base_url);
-        let request = ChatRequest {
-            model: self.config.chat_model.clone(),
-            messages: vec![ChatMsg {
-                role: "user".to_string(),
-                content: format!("{}\n\n{}", system_prompt, user_message),
-            }],
-            temperature: 0.7,
-            max_tokens: 512,
-        };
+        let request = self.chat_request(messages, params, None);
 
         let resp = self
             .client
@@ -225,9 +399,170 @@ impl Embedder {
             .choices
             .into_iter()
             .next()
-            .map(|c| c.message.content)
+            .and_then(|c| c.message.content)
             .context("Response de chat vazio")
     }
+
+    /// Envia uma conversa completa com ferramentas disponíveis (function calling).
+    ///
+    /// Faz POST `/chat/completions` com o histórico `messages` e a lista de
+    /// `tools` no formato OpenAI. O modelo pode responder com texto final
+    /// (`content`) ou com chamadas de ferramenta (`tool_calls`) — cabe ao
+    /// chamador executá-las e devolver os resultados como mensagens `tool`.
+    ///
+    /// Com `tools` vazio, o campo é omitido e o modelo é forçado a responder.
+    pub async fn chat_with_tools(
+        &self,
+        messages: &[ChatMsg],
+        tools: &[serde_json::Value],
+        params: &ChatParams,
+    ) -> Result<ChatMsg> {
+        if self.offline {
+            anyhow::bail!("Embedder offline não tem modelo de chat");
+        }
+        let url = format!("{}/chat/completions", self.config.base_url);
+        let tools = if tools.is_empty() {
+            None
+        } else {
+            Some(tools.to_vec())
+        };
+        let request = self.chat_request(messages, params, tools);
+
+        let resp = self
+            .client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await
+            .context("Falha ao enviar request de chat com ferramentas")?;
+
+        let response: ChatResponse = resp
+            .json()
+            .await
+            .context("Falha ao decodificar response de chat com ferramentas")?;
+
+        let message = response
+            .choices
+            .into_iter()
+            .next()
+            .map(|c| c.message)
+            .context("Response de chat vazio")?;
+
+        Ok(ChatMsg {
+            role: "assistant".to_string(),
+            content: message.content,
+            tool_calls: message.tool_calls.filter(|calls| !calls.is_empty()),
+            tool_call_id: None,
+        })
+    }
+
+    /// Monta o corpo de `/chat/completions`, aplicando o modelo da tarefa
+    /// (ou o padrão) e a mesclagem de `system` quando configurada.
+    fn chat_request(
+        &self,
+        messages: &[ChatMsg],
+        params: &ChatParams,
+        tools: Option<Vec<serde_json::Value>>,
+    ) -> ChatRequest {
+        let messages = if self.config.merge_system {
+            merge_system_messages(messages)
+        } else {
+            messages.to_vec()
+        };
+        ChatRequest {
+            model: params
+                .model
+                .clone()
+                .unwrap_or_else(|| self.config.chat_model.clone()),
+            messages,
+            temperature: params.temperature,
+            max_tokens: params.max_tokens,
+            tools,
+        }
+    }
+}
+
+/// Mescla as mensagens `system` no início da primeira mensagem `user`.
+fn merge_system_messages(messages: &[ChatMsg]) -> Vec<ChatMsg> {
+    let system: Vec<&str> = messages
+        .iter()
+        .filter(|m| m.role == "system")
+        .filter_map(|m| m.content.as_deref())
+        .collect();
+    let mut merged: Vec<ChatMsg> = messages
+        .iter()
+        .filter(|m| m.role != "system")
+        .cloned()
+        .collect();
+    if let Some(first_user) = merged.iter_mut().find(|m| m.role == "user") {
+        let content = first_user.content.take().unwrap_or_default();
+        first_user.content = Some(format!("{}\n\n{}", system.join("\n\n"), content));
+    }
+    merged
+}
+
+/// Dimensão dos vetores do modo offline (a mesma do `nomic-embed-text`).
+const OFFLINE_DIM: usize = 768;
+
+/// Embedding por feature hashing: trigramas de caracteres e palavras
+/// inteiras, com sinal pelo hash (FNV-1a), normalizado em L2.
+///
+/// Os prefixos de tarefa (`search_query: `, `search_document: `) são
+/// ignorados, para que consulta e documento caiam no mesmo espaço.
+fn hashing_embedding(text: &str) -> Vec<f32> {
+    let text = text
+        .strip_prefix("search_query: ")
+        .or_else(|| text.strip_prefix("search_document: "))
+        .unwrap_or(text)
+        .to_lowercase();
+
+    let mut vector = vec![0.0f32; OFFLINE_DIM];
+    let mut add = |feature: &str| {
+        let hash = feature.bytes().fold(0xcbf2_9ce4_8422_2325u64, |h, b| {
+            (h ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+        let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+        vector[(hash % OFFLINE_DIM as u64) as usize] += sign;
+    };
+
+    for word in text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        add(word);
+        let chars: Vec<char> = format!(" {} ", word).chars().collect();
+        for gram in chars.windows(3) {
+            add(&gram.iter().collect::<String>());
+        }
+    }
+
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|x| *x /= norm);
+    }
+    vector
+}
+
+/// Parâmetros de uma chamada de chat — definidos por tarefa nos templates
+/// de prompt (veja [`super::prompts`]).
+#[derive(Clone, Debug)]
+pub struct ChatParams {
+    /// Modelo de chat; `None` usa `LM_STUDIO_CHAT_MODEL`.
+    pub model: Option<String>,
+    /// Temperatura de amostragem.
+    pub temperature: f32,
+    /// Máximo de tokens gerados.
+    pub max_tokens: i32,
+}
+
+impl Default for ChatParams {
+    fn default() -> Self {
+        Self {
+            model: None,
+            temperature: 0.7,
+            max_tokens: 512,
+        }
+    }
 }
 
 // ─── Tipos de Request/Response para a API LM Studio ──────────────
@@ -256,12 +591,88 @@ struct ChatRequest {
     messages: Vec<ChatMsg>,
     temperature: f32,
     max_tokens: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<serde_json::Value>>,
 }
 
-#[derive(Serialize)]
-struct ChatMsg {
-    role: String,
-    content: String,
+/// Mensagem de uma conversa com o LLM (formato OpenAI `chat/completions`).
+///
+/// Além de `system`/`user`/`assistant`, suporta as mensagens do fluxo de
+/// function calling: `assistant` com `tool_calls` e `tool` com o resultado.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChatMsg {
+    /// Role da mensagem: `system`, `user`, `assistant` ou `tool`.
+    pub role: String,
+    /// Conteúdo textual (ausente em mensagens `assistant` que só chamam ferramentas).
+    pub content: Option<String>,
+    /// Chamadas de ferramenta solicitadas pelo modelo.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCall>>,
+    /// ID da chamada respondida (apenas em mensagens `tool`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+}
+
+impl ChatMsg {
+    /// Mensagem de role `system`.
+    pub fn system(content: impl Into<String>) -> Self {
+        Self::text("system", content)
+    }
+
+    /// Mensagem de role `user`.
+    pub fn user(content: impl Into<String>) -> Self {
+        Self::text("user", content)
+    }
+
+    /// Mensagem de role `assistant` (turnos anteriores no histórico).
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self::text("assistant", content)
+    }
+
+    /// Resultado da execução de uma ferramenta (role `tool`).
+    pub fn tool(tool_call_id: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            role: "tool".to_string(),
+            content: Some(content.into()),
+            tool_calls: None,
+            tool_call_id: Some(tool_call_id.into()),
+        }
+    }
+
+    fn text(role: &str, content: impl Into<String>) -> Self {
+        Self {
+            role: role.to_string(),
+            content: Some(content.into()),
+            tool_calls: None,
+            tool_call_id: None,
+        }
+    }
+}
+
+/// Chamada de ferramenta solicitada pelo modelo (formato OpenAI).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ToolCall {
+    /// ID da chamada — ecoado na mensagem `tool` de resposta.
+    pub id: String,
+    /// Tipo da chamada (sempre `"function"`).
+    #[serde(rename = "type", default = "default_tool_type")]
+    pub kind: String,
+    /// Função chamada e seus argumentos.
+    pub function: FunctionCall,
+}
+
+/// Nome e argumentos (JSON serializado como string) de uma chamada de função.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FunctionCall {
+    /// Nome da ferramenta.
+    pub name: String,
+    /// Argumentos em JSON (string, como na API OpenAI).
+    #[serde(default)]
+    pub arguments: String,
+}
+
+fn default_tool_type() -> String {
+    "function".to_string()
 }
 
 #[derive(Deserialize)]
@@ -276,5 +687,8 @@ struct ChatChoice {
 
 #[derive(Deserialize)]
 struct ChatResponseMsg {
-    content: String,
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Option<Vec<ToolCall>>,
 }
@@ -0,0 +1,208 @@
+//! # Tipagem de Entidades — Pessoa, Organização, Lugar, Data, Quantidade
+//!
+//! Conceitos nasciam sem tipo: "Carlos", "São Paulo" e "sustentabilidade"
+//! eram todos `Concept` genéricos. Este módulo atribui um
+//! [`EntityType`] a cada entidade extraída, combinando:
+//!
+//! | Fonte | Tipos | Exemplo |
+//! |-------|-------|---------|
+//! | Regex | Data, Quantidade | "1998", "março de 2020", "30%", "12 km" |
+//! | Palavras-chave | Organização, Lugar, Pessoa | "Universidade de...", "Rio ...", "Dr. ..." |
+//! | Gazetteers | Lugar, Pessoa | "Lisboa", "Maria Souza" |
+//! | LLM (opcional) | Todos | nomes próprios sem pista (`ENTITY_TYPE_LLM=1`) |
+//!
+//! O que nenhuma regra reconhece é [`EntityType::Notion`]. O pipeline usa
+//! o tipo para ligar a instância ao conceito-tipo ("Carlos instância de
+//! Pessoa") — veja `link_instance_to_type` em [`crate::nlu`].
+//!
+//! ## Ordem de Avaliação
+//!
+//! ```text
+//! label
+//!   ├── Data?        (ano, dd/mm/aaaa, mês [de ano], século)
+//!   ├── Quantidade?  (número com unidade, %, R$, "milhões")
+//!   ├── Organização? (palavra-chave: Universidade, Banco, Ltda, Inc...)
+//!   ├── Lugar?       (gazetteer ou palavra-chave: Rio, Cidade, Estado de...)
+//!   ├── Pessoa?      (título ou prenome conhecido + sobrenome capitalizado)
+//!   └── Noção        (padrão)
+//! ```
+
+use regex::Regex;
+
+use crate::core::EntityType;
+
+/// Palavras que indicam organização (em qualquer posição do label).
+#[rustfmt::skip]
+const ORGANIZATION_WORDS: &[&str] = &[
+    "universidade", "faculdade", "instituto", "fundação", "empresa", "companhia", "banco",
+    "ministério", "secretaria", "governo", "agência", "associação", "sindicato", "partido",
+    "hospital", "escola", "conselho", "comissão", "tribunal", "ltda", "s.a", "inc",
+    "corp", "corporation", "company", "university", "institute", "bank", "agency",
+    "foundation", "ministry", "council", "ong", "embrapa", "petrobras", "onu", "unesco",
+];
+
+/// Primeira palavra que indica lugar ("Rio Amazonas", "Estado de Goiás").
+#[rustfmt::skip]
+const PLACE_HEADS: &[&str] = &[
+    "rio", "lago", "monte", "serra", "ilha", "cidade", "estado", "município", "bairro", "rua",
+    "avenida", "praia", "baía", "região", "vale", "floresta", "river", "lake", "mount",
+    "city", "state", "island",
+];
+
+/// Gazetteer de lugares frequentes no corpus (países, capitais, regiões).
+#[rustfmt::skip]
+const PLACES: &[&str] = &[
+    "brasil", "portugal", "angola", "moçambique", "argentina", "chile", "uruguai", "paraguai",
+    "bolívia", "peru", "colômbia", "venezuela", "méxico", "estados unidos", "canadá",
+    "frança", "espanha", "itália", "alemanha", "inglaterra", "reino unido", "china", "japão",
+    "índia", "rússia", "áfrica", "europa", "ásia", "américa", "américa latina", "amazônia",
+    "são paulo", "rio de janeiro", "belo horizonte", "brasília", "salvador", "recife",
+    "fortaleza", "curitiba", "porto alegre", "manaus", "belém", "goiânia", "florianópolis",
+    "minas gerais", "bahia", "pernambuco", "paraná", "santa catarina", "rio grande do sul",
+    "lisboa", "porto", "londres", "paris", "madri", "roma", "berlim", "nova york",
+    "brazil", "spain", "france", "germany", "italy", "england", "united states", "london",
+    "new york", "europe", "africa", "asia",
+];
+
+/// Títulos que antecedem nomes de pessoas.
+#[rustfmt::skip]
+const PERSON_TITLES: &[&str] = &[
+    "dr", "dra", "prof", "profa", "professor", "professora", "sr", "sra", "dona", "seu",
+    "mr", "mrs", "ms", "sir",
+];
+
+/// Gazetteer de prenomes comuns (pt/en).
+#[rustfmt::skip]
+const FIRST_NAMES: &[&str] = &[
+    "maria", "ana", "joão", "josé", "carlos", "paulo", "pedro", "lucas", "luiz", "luís",
+    "marcos", "gabriel", "rafael", "daniel", "marcelo", "bruno", "eduardo", "felipe",
+    "rodrigo", "fernando", "ricardo", "antônio", "antonio", "francisco", "juliana", "mariana",
+    "fernanda", "patrícia", "aline", "camila", "amanda", "bruna", "letícia", "larissa",
+    "beatriz", "júlia", "julia", "carla", "sandra", "cláudia", "renata", "luana", "isabel",
+    "raquel", "helena", "clara", "alice", "sofia", "laura", "marta", "teresa", "rosa",
+    "john", "mary", "james", "robert", "michael", "william", "david", "richard", "thomas",
+    "sarah", "elizabeth", "jennifer", "linda", "susan", "karen", "emma", "olivia",
+];
+
+/// Classificador de tipo de entidade por regex, palavras-chave e gazetteers.
+pub struct EntityTyper {
+    /// Datas: anos (1500-2099), dd/mm/aaaa, meses (pt/en) com ou sem ano, séculos.
+    date_re: Regex,
+    /// Quantidades: número (com separadores) seguido opcionalmente de unidade.
+    quantity_re: Regex,
+}
+
+impl Default for EntityTyper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EntityTyper {
+    /// Cria o classificador compilando os regexes uma única vez.
+    pub fn new() -> Self {
+        Self {
+            date_re: Regex::new(
+                r"(?i)^(?:(?:1[5-9]|20)\d{2}|\d{1,2}/\d{1,2}/\d{2,4}|(?:\d{1,2}\s+de\s+)?(?:janeiro|fevereiro|março|abril|maio|junho|julho|agosto|setembro|outubro|novembro|dezembro|january|february|march|april|may|june|july|august|september|october|november|december)(?:\s+(?:de\s+)?\d{4})?|s[ée]culo\s+[xvi]+|anos\s+\d{2,4})$",
+            )
+            .unwrap(),
+            quantity_re: Regex::new(
+                r"(?i)^(?:r\$|us\$|\$|€)?\s*\d+(?:[.,]\d+)*\s*(?:%|por\s*cento|percent|km|m|cm|mm|kg|g|mg|t|l|ml|h|min|s|°c|kwh|mw|gw|mil|milh(?:ão|ões)|bilh(?:ão|ões)|thousand|million|billion|reais|dólares|dollars|anos|dias|horas|pessoas)?$",
+            )
+            .unwrap(),
+        }
+    }
+
+    /// Classifica uma entidade apenas pelo label (sem LLM).
+    pub fn classify(&self, label: &str) -> EntityType {
+        let trimmed = label.trim();
+        let lower = trimmed.to_lowercase();
+        let words: Vec<String> = lower
+            .split_whitespace()
+            .map(|w| {
+                w.trim_matches(|c: char| !c.is_alphanumeric() && c != '.')
+                    .trim_end_matches('.')
+                    .to_string()
+            })
+            .filter(|w| !w.is_empty())
+            .collect();
+
+        if self.date_re.is_match(trimmed) {
+            return EntityType::Date;
+        }
+        if self.quantity_re.is_match(trimmed) {
+            return EntityType::Quantity;
+        }
+        if words
+            .iter()
+            .any(|w| ORGANIZATION_WORDS.contains(&w.as_str()))
+        {
+            return EntityType::Organization;
+        }
+        if PLACES.contains(&lower.as_str())
+            || (words.len() > 1
+                && PLACE_HEADS.contains(&words[0].as_str())
+                && is_capitalized(trimmed))
+        {
+            return EntityType::Place;
+        }
+        if let Some(first) = words.first() {
+            let titled = words.len() > 1 && PERSON_TITLES.contains(&first.as_str());
+            let named = FIRST_NAMES.contains(&first.as_str()) && is_capitalized(trimmed);
+            if titled || named {
+                return EntityType::Person;
+            }
+        }
+        EntityType::Notion
+    }
+}
+
+/// `true` se o label começa com maiúscula (nome próprio).
+pub fn is_capitalized(label: &str) -> bool {
+    label.chars().next().is_some_and(|c| c.is_uppercase())
+}
+
+/// Interpreta a resposta do LLM ("Lugar", "place", "É uma pessoa.").
+pub fn parse_answer(answer: &str) -> Option<EntityType> {
+    let answer = answer.trim().to_lowercase();
+    EntityType::ALL
+        .into_iter()
+        .find(|t| answer.contains(&t.label().to_lowercase()) || answer.contains(t.css_class()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_dates_and_quantities() {
+        let typer = EntityTyper::new();
+        assert_eq!(typer.classify("1998"), EntityType::Date);
+        assert_eq!(typer.classify("março de 2020"), EntityType::Date);
+        assert_eq!(typer.classify("12/05/2021"), EntityType::Date);
+        assert_eq!(typer.classify("30%"), EntityType::Quantity);
+        assert_eq!(typer.classify("12 km"), EntityType::Quantity);
+        assert_eq!(typer.classify("R$ 1.500,00"), EntityType::Quantity);
+    }
+
+    #[test]
+    fn classifies_named_entities() {
+        let typer = EntityTyper::new();
+        assert_eq!(
+            typer.classify("Universidade de São Paulo"),
+            EntityType::Organization
+        );
+        assert_eq!(typer.classify("São Paulo"), EntityType::Place);
+        assert_eq!(typer.classify("Rio Amazonas"), EntityType::Place);
+        assert_eq!(typer.classify("Carlos"), EntityType::Person);
+        assert_eq!(typer.classify("Dr. Silva"), EntityType::Person);
+        assert_eq!(typer.classify("sustentabilidade"), EntityType::Notion);
+    }
+
+    #[test]
+    fn parses_llm_answers() {
+        assert_eq!(parse_answer("Lugar"), Some(EntityType::Place));
+        assert_eq!(parse_answer("É uma pessoa."), Some(EntityType::Person));
+        assert_eq!(parse_answer("não sei"), None);
+    }
+}
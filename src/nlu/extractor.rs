@@ -22,6 +22,7 @@
 //! |-----------|------------|---------|
 //! | 1 | Texto entre aspas | `"inteligência artificial"` |
 //! | 2 | Palavras capitalizadas | `Carlos`, `São Paulo` |
+//! | 2b | Siglas (2-5 maiúsculas) | `IA`, `USP`, `ONGs` |
 //! | 3 | N-grams (bigrams/trigrams) | `base conhecimento` |
 //! | 4 | Palavras individuais ≥ 4 chars | `urgência`, `problema` |
 //!
@@ -31,8 +32,31 @@
 //! - **Verbos**: Heurística por sufixo verbal (ando, endo, indo, ado, ido)
 //! - **Comprimento mínimo**: 2+ chars para aspas, 3+ para capitalizadas, 4+ para individuais
 //! - **Deduplicação**: Case-insensitive via HashSet
+//!
+//! ## Siglas e Aliases
+//!
+//! [`extract_aliases()`](EntityExtractor::extract_aliases) reconhece
+//! padrões definicionais — "Inteligência Artificial (IA)" e
+//! "IA (Inteligência Artificial)" — conferindo se as letras da sigla batem
+//! com as iniciais da forma longa. O pipeline usa os pares para gravar a
+//! sigla como alias do conceito em vez de criar um conceito separado.
+//!
+//! ## Perfis por Idioma
+//!
+//! Stopwords, sufixos verbais, conectores de nomes compostos e a
+//! normalização de palavras quebradas dependem do idioma. O idioma é
+//! detectado por [`language::detect`] ou informado via
+//! [`extract_in()`](EntityExtractor::extract_in):
+//!
+//! | Perfil | Stopwords | Sufixos verbais | Conectores | Palavras quebradas |
+//! |--------|-----------|-----------------|------------|--------------------|
+//! | pt-BR | [`STOPWORDS`] | [`VERB_SUFFIXES`] | de, do, da... | Sim |
+//! | en | [`EN_STOPWORDS`] | [`EN_VERB_SUFFIXES`] | of, the | Não |
 
 use regex::Regex;
+use unicode_normalization::UnicodeNormalization;
+
+use super::language::{self, Language};
 
 /// Stopwords em Português Brasileiro para filtragem de entidades.
 ///
@@ -44,6 +68,7 @@ use regex::Regex;
 /// A lista inclui também algumas palavras de conteúdo que tendem a ser
 /// ruído em extração de entidades, como "coisa", "vez", "dia".
 /// Isso é uma decisão de design para reduzir falsos positivos.
+#[rustfmt::skip]
 const STOPWORDS: &[&str] = &[
     "o", "a", "os", "as", "um", "uma", "uns", "umas", "de", "do", "da", "dos", "das", "em", "no",
     "na", "nos", "nas", "por", "pelo", "pela", "pelos", "pelas", "para", "com", "sem", "sob",
@@ -71,11 +96,84 @@ const STOPWORDS: &[&str] = &[
 /// - `ido` → particípio 2ª/3ª conjugação (corrido, partido)
 const VERB_SUFFIXES: &[&str] = &["ando", "endo", "indo", "ado", "ido"];
 
+/// Stopwords em inglês para o perfil `en`.
+///
+/// Mesma ideia de [`STOPWORDS`]: palavras funcionais e verbos auxiliares
+/// que nunca representam conceitos.
+#[rustfmt::skip]
+const EN_STOPWORDS: &[&str] = &[
+    "the", "a", "an", "and", "or", "but", "if", "then", "else", "of", "to", "in", "on", "at", "by",
+    "for", "with", "without", "from", "into", "onto", "about", "over", "under", "between",
+    "through", "during", "before", "after", "above", "below", "is", "are", "was", "were", "be",
+    "been", "being", "am", "have", "has", "had", "do", "does", "did", "will", "would", "shall",
+    "should", "can", "could", "may", "might", "must", "i", "you", "he", "she", "it", "we", "they",
+    "me", "him", "her", "us", "them", "my", "your", "his", "its", "our", "their", "this", "that",
+    "these", "those", "which", "who", "whom", "whose", "what", "when", "where", "why", "how",
+    "not", "no", "yes", "so", "very", "more", "most", "less", "much", "many", "some", "any",
+    "each", "every", "all", "both", "other", "another", "such", "same", "own", "also", "just",
+    "only", "even", "still", "already", "now", "here", "there", "always", "never", "often",
+    "thing", "things", "time", "times", "way", "said", "says", "than", "because", "since", "while",
+    "although", "though", "however", "therefore", "thus",
+];
+
+/// Sufixos verbais comuns em inglês (gerúndio e passado/particípio regular).
+const EN_VERB_SUFFIXES: &[&str] = &["ing", "ed"];
+
+/// Perfil linguístico usado pelo extrator.
+struct LanguageProfile {
+    /// Stopwords do idioma.
+    stopwords: &'static [&'static str],
+    /// Sufixos verbais do idioma.
+    verb_suffixes: &'static [&'static str],
+    /// Se aplica [`normalize_broken_words`] (heurística calibrada para PT-BR).
+    normalize_fragments: bool,
+}
+
+impl LanguageProfile {
+    fn is_stopword(&self, word: &str) -> bool {
+        self.stopwords.contains(&word)
+    }
+
+    fn looks_like_verb(&self, word: &str) -> bool {
+        self.verb_suffixes.iter().any(|s| word.ends_with(s))
+    }
+}
+
+/// Perfil pt-BR.
+const PT_PROFILE: LanguageProfile = LanguageProfile {
+    stopwords: STOPWORDS,
+    verb_suffixes: VERB_SUFFIXES,
+    normalize_fragments: true,
+};
+
+/// Perfil en.
+const EN_PROFILE: LanguageProfile = LanguageProfile {
+    stopwords: EN_STOPWORDS,
+    verb_suffixes: EN_VERB_SUFFIXES,
+    normalize_fragments: false,
+};
+
+/// Perfil do idioma.
+fn profile(language: Language) -> &'static LanguageProfile {
+    match language {
+        Language::Portuguese => &PT_PROFILE,
+        Language::English => &EN_PROFILE,
+    }
+}
+
+/// Verifica se `word` (minúsculo) é stopword no perfil de `language`.
+///
+/// Usado pelo [`keyphrase`](super::keyphrase) para delimitar frases RAKE.
+pub(crate) fn is_stopword_in(word: &str, language: Language) -> bool {
+    profile(language).is_stopword(word)
+}
+
 /// Palavras reais PT-BR de 2-4 chars que NÃO devem ser tratadas como fragmentos.
 ///
 /// A heurística de normalização de palavras quebradas detecta tokens curtos (2-4 chars)
 /// como possíveis fragmentos de palavras maiores. Esta lista protege palavras reais
 /// comuns para evitar junções indevidas (ex: "caso alto" ficaria "casoalto" sem isso).
+#[rustfmt::skip]
 const SHORT_CONTENT_WORDS: &[&str] = &[
     // 2 chars
     "ar", "ir", "vi",
@@ -110,6 +208,30 @@ pub struct EntityExtractor {
     /// Regex para capturar palavras capitalizadas (possíveis nomes próprios),
     /// incluindo compostos com preposições (ex: "São Paulo do Brasil").
     capitalized_re: Regex,
+    /// Variante inglesa de `capitalized_re` (conectores "of", "the":
+    /// "Bank of England", "Museum of the City").
+    capitalized_en_re: Regex,
+    /// Regex para siglas: 2-5 maiúsculas, com plural opcional ("ONGs").
+    acronym_re: Regex,
+    /// Regex para "forma longa (SIGLA)" — captura a sigla entre parênteses.
+    trailing_acronym_re: Regex,
+    /// Regex para "SIGLA (forma longa)".
+    leading_acronym_re: Regex,
+}
+
+/// Par forma longa ↔ sigla detectado em um padrão definicional.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AliasPair {
+    /// Forma longa — vira (ou já é) o label do conceito.
+    pub label: String,
+    /// Sigla — gravada como alias do conceito.
+    pub alias: String,
+}
+
+impl Default for EntityExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl EntityExtractor {
@@ -124,7 +246,74 @@ impl EntityExtractor {
             // Captura palavras capitalizadas, incluindo compostos com preposições:
             // "Carlos", "São Paulo", "Universidade de São Paulo"
             capitalized_re: Regex::new(r"\b([A-ZÁÀÂÃÉÈÊÍÏÓÔÕÖÚÇ][a-záàâãéèêíïóôõöúçüñ]{2,})(?:\s+(?:de|do|da|dos|das)\s+[A-ZÁÀÂÃÉÈÊÍÏÓÔÕÖÚÇ][a-záàâãéèêíïóôõöúçüñ]+)*\b").unwrap(),
+            capitalized_en_re: Regex::new(r"\b([A-Z][a-z]{2,})(?:\s+(?:of|of the)\s+[A-Z][a-z]+|\s+[A-Z][a-z]+)*\b").unwrap(),
+            acronym_re: Regex::new(r"\b[A-ZÁÉÍÓÚÂÊÔÃÕÇ]{2,5}s?\b").unwrap(),
+            trailing_acronym_re: Regex::new(r"\(\s*([A-ZÁÉÍÓÚÂÊÔÃÕÇ][A-Za-zÁÉÍÓÚÂÊÔÃÕÇáéíóúâêôãõç0-9]{1,9})\s*\)").unwrap(),
+            leading_acronym_re: Regex::new(r"\b([A-ZÁÉÍÓÚÂÊÔÃÕÇ]{2,10})\s*\(([^()]{3,80})\)").unwrap(),
+        }
+    }
+
+    /// Detecta pares forma longa ↔ sigla em padrões definicionais.
+    ///
+    /// | Padrão | Exemplo |
+    /// |--------|---------|
+    /// | forma longa (SIGLA) | "Universidade de São Paulo (USP)" |
+    /// | SIGLA (forma longa) | "IA (Inteligência Artificial)" |
+    ///
+    /// A forma longa é a menor sequência de palavras antes dos parênteses
+    /// cujas iniciais formam a sigla (com ou sem as iniciais de
+    /// preposições: "Estados Unidos da América (EUA)").
+    pub fn extract_aliases(&self, text: &str) -> Vec<AliasPair> {
+        let mut pairs: Vec<AliasPair> = Vec::new();
+        let mut push = |label: String, alias: String| {
+            let exists = pairs.iter().any(|p| p.alias.eq_ignore_ascii_case(&alias));
+            if !exists && !label.eq_ignore_ascii_case(&alias) {
+                pairs.push(AliasPair { label, alias });
+            }
+        };
+
+        for cap in self.trailing_acronym_re.captures_iter(text) {
+            let acronym = cap.get(1).unwrap().as_str();
+            let before = &text[..cap.get(0).unwrap().start()];
+            let words: Vec<&str> = before
+                .split_whitespace()
+                .rev()
+                .take(10)
+                .collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+                .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()))
+                .collect();
+            // A forma longa termina logo antes do parêntese: procura a menor janela final
+            for start in (0..words.len()).rev() {
+                let window = &words[start..];
+                if window
+                    .first()
+                    .is_some_and(|w| ACRONYM_CONNECTORS.contains(&w.to_lowercase().as_str()))
+                {
+                    continue;
+                }
+                if initials_match(window, acronym) {
+                    push(window.join(" "), acronym.to_string());
+                    break;
+                }
+            }
         }
+
+        for cap in self.leading_acronym_re.captures_iter(text) {
+            let acronym = cap.get(1).unwrap().as_str();
+            let inner = cap.get(2).unwrap().as_str().trim();
+            let words: Vec<&str> = inner
+                .split_whitespace()
+                .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()))
+                .filter(|w| !w.is_empty())
+                .collect();
+            if initials_match(&words, acronym) {
+                push(words.join(" "), acronym.to_string());
+            }
+        }
+
+        pairs
     }
 
     /// Extrai entidades candidatas de um texto em Português.
@@ -156,7 +345,21 @@ impl EntityExtractor {
     ///
     /// `Vec<String>` — entidades extraídas, sem duplicação, na ordem de prioridade
     pub fn extract(&self, text: &str) -> Vec<String> {
-        let text = normalize_broken_words(text);
+        self.extract_in(text, language::detect(text))
+    }
+
+    /// Extrai entidades usando o perfil de um idioma já detectado.
+    pub fn extract_in(&self, text: &str, language: Language) -> Vec<String> {
+        let profile = profile(language);
+        let capitalized_re = match language {
+            Language::Portuguese => &self.capitalized_re,
+            Language::English => &self.capitalized_en_re,
+        };
+        let text = if profile.normalize_fragments {
+            normalize_broken_words(text)
+        } else {
+            text.to_string()
+        };
         let mut entities = Vec::new();
         let mut seen = std::collections::HashSet::new();
 
@@ -173,15 +376,33 @@ impl EntityExtractor {
         }
 
         // ─── 2. Palavras capitalizadas (nomes próprios) ──────────
-        for cap in self.capitalized_re.captures_iter(&text) {
-            let entity = cap.get(0).unwrap().as_str().to_string();
+        for cap in capitalized_re.captures_iter(&text) {
+            // Descarta stopwords capitalizadas no início ("The Bank of England")
+            let entity = cap
+                .get(0)
+                .unwrap()
+                .as_str()
+                .split_whitespace()
+                .skip_while(|w| profile.is_stopword(&w.to_lowercase()))
+                .collect::<Vec<_>>()
+                .join(" ");
             let lower = entity.to_lowercase();
-            if !seen.contains(&lower) && !is_stopword(&lower) && entity.len() > 2 {
+            if !seen.contains(&lower) && !profile.is_stopword(&lower) && entity.len() > 2 {
                 seen.insert(lower);
                 entities.push(entity);
             }
         }
 
+        // ─── 2b. Siglas ("IA", "USP") ────────────────────────────
+        for m in self.acronym_re.find_iter(&text) {
+            let acronym = m.as_str();
+            let lower = acronym.to_lowercase();
+            if !seen.contains(&lower) && !profile.is_stopword(&lower) {
+                seen.insert(lower);
+                entities.push(acronym.to_string());
+            }
+        }
+
         // ─── 3. N-grams compostos (bigrams e trigrams) ──────────
         // Captura frases nominais compostas de content words
         let words: Vec<&str> = text.split_whitespace().collect();
@@ -197,7 +418,7 @@ impl EntityExtractor {
                     // Todas as palavras devem ser "significativas"
                     let all_meaningful = cleaned.iter().all(|w| {
                         let wl = w.to_lowercase();
-                        !is_stopword(&wl) && wl.len() >= 5 && !looks_like_verb(&wl)
+                        !profile.is_stopword(&wl) && wl.len() >= 5 && !profile.looks_like_verb(&wl)
                     });
                     if all_meaningful {
                         let phrase: String = cleaned.join(" ");
@@ -217,8 +438,8 @@ impl EntityExtractor {
             let clean = word.trim_matches(|c: char| !c.is_alphanumeric());
             let lower = clean.to_lowercase();
             if clean.len() >= 4
-                && !is_stopword(&lower)
-                && !looks_like_verb(&lower)
+                && !profile.is_stopword(&lower)
+                && !profile.looks_like_verb(&lower)
                 && !seen.contains(&lower)
             {
                 seen.insert(lower);
@@ -230,6 +451,60 @@ impl EntityExtractor {
     }
 }
 
+/// Funde pares de [`extract_aliases()`](EntityExtractor::extract_aliases)
+/// nas entidades extraídas: a forma longa entra (se faltava) e a sigla sai,
+/// para não virar um conceito separado.
+pub fn merge_aliases(entities: &mut Vec<String>, aliases: &[AliasPair]) {
+    for pair in aliases {
+        entities.retain(|e| !e.eq_ignore_ascii_case(&pair.alias));
+        if !entities
+            .iter()
+            .any(|e| e.to_lowercase() == pair.label.to_lowercase())
+        {
+            entities.push(pair.label.clone());
+        }
+    }
+}
+
+/// Conectores ignorados nas iniciais de uma forma longa
+/// ("Universidade **de** São Paulo" → USP).
+const ACRONYM_CONNECTORS: &[&str] = &[
+    "de", "da", "do", "das", "dos", "e", "em", "para", "of", "the", "and", "for",
+];
+
+/// Letra base minúscula, sem diacríticos ("Á" → 'a').
+fn base_letter(c: char) -> Option<char> {
+    c.to_lowercase()
+        .collect::<String>()
+        .nfd()
+        .find(|ch| ch.is_alphanumeric())
+}
+
+/// Verifica se a sigla corresponde às iniciais das palavras — todas, ou
+/// sem os [conectores](ACRONYM_CONNECTORS).
+fn initials_match(words: &[&str], acronym: &str) -> bool {
+    let letters: String = acronym
+        .trim_end_matches('s')
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .filter_map(base_letter)
+        .collect();
+    if letters.len() < 2 {
+        return false;
+    }
+    let initials = |skip_connectors: bool| -> String {
+        words
+            .iter()
+            .filter(|w| !w.is_empty())
+            .filter(|w| {
+                !skip_connectors || !ACRONYM_CONNECTORS.contains(&w.to_lowercase().as_str())
+            })
+            .filter_map(|w| w.chars().next().and_then(base_letter))
+            .collect()
+    };
+    initials(true) == letters || initials(false) == letters
+}
+
 /// Verifica se uma palavra (em lowercase) é uma stopword PT-BR.
 ///
 /// Faz busca linear na lista [`STOPWORDS`]. Para uma lista de ~100 itens,
@@ -265,8 +540,7 @@ fn is_known_short_word(word: &str) -> bool {
 fn is_probable_fragment(token: &str) -> bool {
     let lower = token.to_lowercase();
     let len = lower.chars().count();
-    len >= 2
-        && len <= 4
+    (2..=4).contains(&len)
         && lower.chars().all(|c| c.is_alphabetic())
         && !is_stopword(&lower)
         && !is_known_short_word(&lower)
@@ -296,7 +570,7 @@ fn is_probable_fragment(token: &str) -> bool {
 /// "Excelência Oper acio nal"  → "Excelência Operacional"
 /// "caso alto base"            → "caso alto base" (preservado)
 /// ```
-fn normalize_broken_words(text: &str) -> String {
+pub fn normalize_broken_words(text: &str) -> String {
     let tokens: Vec<&str> = text.split_whitespace().collect();
     if tokens.is_empty() {
         return String::new();
@@ -461,6 +735,58 @@ mod tests {
         assert!(!has_fragment, "Unexpected fragments in {:?}", entities);
     }
 
+    #[test]
+    fn extract_aliases_from_definitional_patterns() {
+        let ext = EntityExtractor::new();
+        let pairs = ext.extract_aliases(
+            "A Inteligência Artificial (IA) é estudada na Universidade de São Paulo (USP). \
+             A ONU (Organização das Nações Unidas) discute o tema.",
+        );
+        let found: Vec<(&str, &str)> = pairs
+            .iter()
+            .map(|p| (p.label.as_str(), p.alias.as_str()))
+            .collect();
+        assert!(
+            found.contains(&("Inteligência Artificial", "IA")),
+            "{:?}",
+            found
+        );
+        assert!(
+            found.contains(&("Universidade de São Paulo", "USP")),
+            "{:?}",
+            found
+        );
+        assert!(
+            found.contains(&("Organização das Nações Unidas", "ONU")),
+            "{:?}",
+            found
+        );
+
+        // Parênteses que não são sigla da forma anterior
+        assert!(ext.extract_aliases("O motor (MX) queimou ontem").is_empty());
+    }
+
+    #[test]
+    fn extract_uses_english_profile() {
+        let ext = EntityExtractor::new();
+        let entities =
+            ext.extract("The Bank of England raised interest rates because inflation was rising");
+        assert!(
+            entities.iter().any(|e| e == "Bank of England"),
+            "{:?}",
+            entities
+        );
+        assert!(entities.iter().any(|e| e == "inflation"), "{:?}", entities);
+        let has_stopword = entities
+            .iter()
+            .any(|e| ["because", "The", "rising"].contains(&e.as_str()));
+        assert!(
+            !has_stopword,
+            "Unexpected English stopwords/verbs in {:?}",
+            entities
+        );
+    }
+
     #[test]
     fn extract_ngram_min_length_filters_short() {
         let ext = EntityExtractor::new();
@@ -0,0 +1,454 @@
+//! # Análise de Lacunas — O Que Perguntar ao Usuário
+//!
+//! A germinação perguntava sempre sobre um único conceito incerto. Este
+//! módulo procura **lacunas** em toda a KB e as ordena pelo ganho de
+//! informação esperado de uma resposta do usuário:
+//!
+//! | Lacuna | Critério | Peso |
+//! |--------|----------|------|
+//! | [`Contradiction`](GapKind::Contradiction) | Links do mesmo par e direção com frequências opostas (ou catalisa × inibe) | 1.5 |
+//! | [`UnverifiedInference`](GapKind::UnverifiedInference) | Link inferido com confiança < 0.5 | 1.2 |
+//! | [`UncertainLink`](GapKind::UncertainLink) | Link extraído/proposto com confiança < 0.5 | 1.0 |
+//! | [`IsolatedConcept`](GapKind::IsolatedConcept) | Conceito ativo sem nenhum link | 0.8 |
+//! | [`UncertainConcept`](GapKind::UncertainConcept) | Candidato clássico (energia > 0.4, confiança < 0.5) | 0.7 |
+//!
+//! Links `InstanceOf` e estruturais (autoria, seções do documento) não
+//! geram lacunas: vêm de tipagem e de metadados, não de afirmações.
+//!
+//! "A ⇒ B" e "B ⇒ A" são afirmações diferentes e não se contradizem; só
+//! [`Similarity`](LinkKind::Similarity) e [`Equivalence`](LinkKind::Equivalence)
+//! comparam o par nas duas direções.
+//!
+//! ## Ganho de Informação Esperado
+//!
+//! ```text
+//! incerteza(tv) = H(frequency) × (1 − confidence)     H = entropia binária (bits)
+//! ganho         = peso × incerteza × energia
+//! contradição   = peso × |f₁ − f₂| × energia média
+//! isolado       = peso × (1 − confidence) × energia
+//! ```
+//!
+//! Um link ⟨0.50, 0.10⟩ recém-extraído tem incerteza 0.9; um link
+//! ⟨0.95, 0.80⟩ já confirmado, ~0.06. A energia prioriza o que está
+//! "quente" na conversa.
+//!
+//! Cada lacuna traz uma descrição (enviada ao LLM via
+//! `prompts/question.prompt`) e uma pergunta de template do
+//! [`QuestionGenerator`] para o modo offline.
+
+use std::collections::HashMap;
+
+use crate::core::concept::ConceptId;
+use crate::core::link::LinkId;
+use crate::core::{Concept, ConceptState, KnowledgeBase, Link, LinkKind, LinkOrigin, TruthValue};
+
+use super::question::QuestionGenerator;
+
+/// Confiança abaixo da qual um link (ou conceito) é considerado incerto.
+const UNCERTAIN_CONFIDENCE: f64 = 0.5;
+
+/// Diferença mínima de frequência para links do mesmo par se contradizerem.
+const CONTRADICTION_SPREAD: f64 = 0.4;
+
+/// Tipo de lacuna, com os elementos da KB envolvidos.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GapKind {
+    /// Dois links sobre o mesmo par de conceitos discordam.
+    Contradiction {
+        /// Primeiro link.
+        first: LinkId,
+        /// Segundo link.
+        second: LinkId,
+    },
+    /// Link derivado pelo motor de inferência, nunca confirmado.
+    UnverifiedInference {
+        /// Link inferido.
+        link: LinkId,
+    },
+    /// Link extraído ou proposto com pouca evidência.
+    UncertainLink {
+        /// Link incerto.
+        link: LinkId,
+    },
+    /// Conceito ativo sem nenhuma relação.
+    IsolatedConcept {
+        /// Conceito isolado.
+        concept: ConceptId,
+    },
+    /// Conceito relevante e pouco compreendido.
+    UncertainConcept {
+        /// Conceito incerto.
+        concept: ConceptId,
+    },
+}
+
+impl GapKind {
+    /// Peso do tipo de lacuna no ganho esperado.
+    fn weight(&self) -> f64 {
+        match self {
+            GapKind::Contradiction { .. } => 1.5,
+            GapKind::UnverifiedInference { .. } => 1.2,
+            GapKind::UncertainLink { .. } => 1.0,
+            GapKind::IsolatedConcept { .. } => 0.8,
+            GapKind::UncertainConcept { .. } => 0.7,
+        }
+    }
+}
+
+/// Lacuna da KB candidata a virar pergunta.
+#[derive(Clone, Debug)]
+pub struct KnowledgeGap {
+    /// Tipo e elementos envolvidos.
+    pub kind: GapKind,
+    /// Ganho de informação esperado (maior = perguntar antes).
+    pub gain: f64,
+    /// Labels dos conceitos envolvidos.
+    pub concepts: Vec<String>,
+    /// Descrições das relações envolvidas.
+    pub links: Vec<String>,
+    /// Descrição da lacuna para o LLM.
+    pub description: String,
+    /// Pergunta de template (sem LLM).
+    pub fallback: String,
+}
+
+impl KnowledgeGap {
+    /// Chave estável da lacuna, para não repetir a mesma pergunta.
+    pub fn key(&self) -> String {
+        match &self.kind {
+            GapKind::Contradiction { first, second } => {
+                let (a, b) = if first < second {
+                    (first, second)
+                } else {
+                    (second, first)
+                };
+                format!("contradiction:{}:{}", a, b)
+            }
+            GapKind::UnverifiedInference { link } | GapKind::UncertainLink { link } => {
+                format!("link:{}", link)
+            }
+            GapKind::IsolatedConcept { concept } | GapKind::UncertainConcept { concept } => {
+                format!("concept:{}", concept)
+            }
+        }
+    }
+}
+
+/// Entropia binária (bits) de uma frequência.
+fn entropy(p: f64) -> f64 {
+    if p <= 0.0 || p >= 1.0 {
+        return 0.0;
+    }
+    -(p * p.log2() + (1.0 - p) * (1.0 - p).log2())
+}
+
+/// Incerteza de um TruthValue: entropia da frequência × falta de confiança.
+pub fn uncertainty(truth: &TruthValue) -> f64 {
+    entropy(truth.frequency()) * (1.0 - truth.confidence())
+}
+
+/// Encontra as lacunas da KB, ordenadas por ganho esperado decrescente.
+pub fn analyze(kb: &KnowledgeBase, questions: &QuestionGenerator) -> Vec<KnowledgeGap> {
+    let mut gaps = Vec::new();
+    let binary = |link: &Link| -> Option<(&Concept, &Concept)> {
+        let source = kb.concepts.get(&link.subject()?)?;
+        let target = kb.concepts.get(&link.object()?)?;
+        Some((source, target))
+    };
+
+    // ─── Contradições: links do mesmo par ────────────────────────
+    // "motor ⇒ calor" e "calor ⇒ motor" são afirmações diferentes: só
+    // relações simétricas (≈, ⇔) agrupam o par sem direção.
+    let mut by_pair: HashMap<(ConceptId, ConceptId), Vec<&Link>> = HashMap::new();
+    for link in kb
+        .links
+        .values()
+        .filter(|l| l.kind != LinkKind::InstanceOf && l.origin != LinkOrigin::Structural)
+    {
+        if let (Some(s), Some(o)) = (link.subject(), link.object()) {
+            let pair = if link.kind.is_symmetric() && o < s {
+                (o, s)
+            } else {
+                (s, o)
+            };
+            by_pair.entry(pair).or_default().push(link);
+        }
+    }
+    for links in by_pair.values().filter(|l| l.len() >= 2) {
+        for (i, first) in links.iter().enumerate() {
+            for second in &links[i + 1..] {
+                let Some(spread) = conflict(first, second) else {
+                    continue;
+                };
+                let Some((source, target)) = binary(first) else {
+                    continue;
+                };
+                let kind = GapKind::Contradiction {
+                    first: first.id,
+                    second: second.id,
+                };
+                let energy = (first.energy + second.energy) / 2.0;
+                let (a, b) = (kb.describe_link(first), kb.describe_link(second));
+                gaps.push(KnowledgeGap {
+                    gain: kind.weight() * spread * energy,
+                    kind,
+                    concepts: vec![source.label.clone(), target.label.clone()],
+                    description: format!(
+                        "Evidências conflitantes entre '{}' e '{}': {} e {}.",
+                        source.label, target.label, a, b
+                    ),
+                    links: vec![a, b],
+                    fallback: questions.for_contradiction(source, target),
+                });
+            }
+        }
+    }
+
+    // ─── Links incertos e inferências não verificadas ────────────
+    for link in kb.links.values() {
+        let structural = link.kind == LinkKind::InstanceOf || link.origin == LinkOrigin::Structural;
+        if structural || link.truth.confidence() >= UNCERTAIN_CONFIDENCE {
+            continue;
+        }
+        let Some((source, target)) = binary(link) else {
+            continue;
+        };
+        let desc = kb.describe_link(link);
+        let (kind, description, fallback) = match &link.origin {
+            LinkOrigin::Inferred { rule, .. } => (
+                GapKind::UnverifiedInference { link: link.id },
+                format!(
+                    "Relação inferida por {} e nunca confirmada: {}.",
+                    rule.to_lowercase(),
+                    desc
+                ),
+                questions.for_inference(source, target, rule),
+            ),
+            _ if link.kind == LinkKind::Implication => (
+                GapKind::UncertainLink { link: link.id },
+                format!("Relação causal com pouca evidência: {}.", desc),
+                questions.for_causal_link(source, target),
+            ),
+            _ => (
+                GapKind::UncertainLink { link: link.id },
+                format!("Relação com pouca evidência: {}.", desc),
+                questions.for_relation(source, target),
+            ),
+        };
+        gaps.push(KnowledgeGap {
+            gain: kind.weight() * uncertainty(&link.truth) * link.energy,
+            kind,
+            concepts: vec![source.label.clone(), target.label.clone()],
+            links: vec![desc],
+            description,
+            fallback,
+        });
+    }
+
+    // ─── Conceitos isolados ─────────────────────────────────────
+    for concept in kb
+        .concepts
+        .values()
+        .filter(|c| c.state == ConceptState::Active)
+    {
+        if !kb.links_for_concept(concept.id).is_empty() {
+            continue;
+        }
+        let kind = GapKind::IsolatedConcept {
+            concept: concept.id,
+        };
+        gaps.push(KnowledgeGap {
+            gain: kind.weight() * (1.0 - concept.truth.confidence()) * concept.energy,
+            kind,
+            concepts: vec![concept.label.clone()],
+            links: Vec::new(),
+            description: format!(
+                "O conceito '{}' não tem nenhuma relação com outros conceitos.",
+                concept.label
+            ),
+            fallback: questions.for_isolated(concept),
+        });
+    }
+
+    // ─── Conceitos incertos (critério clássico da germinação) ────
+    for concept in kb.question_candidates() {
+        if kb.links_for_concept(concept.id).is_empty() {
+            continue; // já coberto como isolado
+        }
+        let kind = GapKind::UncertainConcept {
+            concept: concept.id,
+        };
+        gaps.push(KnowledgeGap {
+            gain: kind.weight() * uncertainty(&concept.truth) * concept.energy,
+            kind,
+            concepts: vec![concept.label.clone()],
+            links: Vec::new(),
+            description: format!(
+                "O conceito '{}' é relevante na conversa mas pouco compreendido {}.",
+                concept.label, concept.truth
+            ),
+            fallback: questions.for_concept(concept),
+        });
+    }
+
+    gaps.sort_by(|a, b| {
+        b.gain
+            .partial_cmp(&a.gain)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    gaps
+}
+
+/// Grau de conflito entre dois links do mesmo par, se houver.
+///
+/// Catalisa × inibe é conflito total; links do mesmo tipo conflitam quando
+/// as frequências ficam em lados opostos de 0.5 e distam ≥ 0.4. Links
+/// direcionais só conflitam com o mesmo sujeito e o mesmo objeto.
+fn conflict(first: &Link, second: &Link) -> Option<f64> {
+    let same_direction = first.subject() == second.subject() && first.object() == second.object();
+    let symmetric = first.kind.is_symmetric() && second.kind.is_symmetric();
+    if !(same_direction || symmetric) {
+        return None;
+    }
+    let opposite_kinds = matches!(
+        (&first.kind, &second.kind),
+        (LinkKind::Catalyzes, LinkKind::Inhibits) | (LinkKind::Inhibits, LinkKind::Catalyzes)
+    );
+    if opposite_kinds {
+        return Some(1.0);
+    }
+    if first.kind != second.kind {
+        return None;
+    }
+    let (f1, f2) = (first.truth.frequency(), second.truth.frequency());
+    let spread = (f1 - f2).abs();
+    ((f1 - 0.5) * (f2 - 0.5) < 0.0 && spread >= CONTRADICTION_SPREAD).then_some(spread)
+}
+
+/// Extrai a pergunta da resposta do LLM: primeira linha não vazia, sem
+/// aspas. `None` se não parecer uma pergunta.
+pub fn parse_question(answer: &str) -> Option<String> {
+    let line = answer.lines().map(str::trim).find(|l| !l.is_empty())?;
+    let question = line
+        .trim_matches(|c| matches!(c, '"' | '\'' | '“' | '”' | '*'))
+        .trim();
+    (question.contains('?') && question.chars().count() <= 300).then(|| question.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Participant, Role};
+
+    fn link(kind: LinkKind, subject: ConceptId, object: ConceptId, truth: TruthValue) -> Link {
+        Link::new(
+            kind,
+            vec![
+                Participant {
+                    concept_id: subject,
+                    role: Role::Subject,
+                },
+                Participant {
+                    concept_id: object,
+                    role: Role::Object,
+                },
+            ],
+            truth,
+        )
+    }
+
+    #[test]
+    fn ranks_contradictions_first_and_finds_isolated_concepts() {
+        let mut kb = KnowledgeBase::new();
+        let motor = kb.add_concept(Concept::new("motor".into(), TruthValue::new(0.9, 0.8)));
+        let calor = kb.add_concept(Concept::new("calor".into(), TruthValue::new(0.9, 0.8)));
+        let solo = kb.add_concept(Concept::new("solo".into(), TruthValue::proto()));
+        let chuva = kb.add_concept(Concept::new("chuva".into(), TruthValue::new(0.9, 0.8)));
+        let firm = TruthValue::new(0.9, 0.6);
+        let catalyzes = kb.add_link(link(LinkKind::Catalyzes, chuva, calor, firm.clone()));
+        let inhibits = kb.add_link(link(LinkKind::Inhibits, chuva, calor, firm));
+        kb.add_link(link(
+            LinkKind::Similarity,
+            chuva,
+            motor,
+            TruthValue::proto(),
+        ));
+
+        let gaps = analyze(&kb, &QuestionGenerator::new());
+        let expected = [
+            GapKind::Contradiction {
+                first: catalyzes,
+                second: inhibits,
+            },
+            GapKind::Contradiction {
+                first: inhibits,
+                second: catalyzes,
+            },
+        ];
+        assert!(expected.contains(&gaps[0].kind));
+        assert!(gaps
+            .iter()
+            .any(|g| g.kind == GapKind::IsolatedConcept { concept: solo }));
+        assert!(gaps
+            .iter()
+            .any(|g| matches!(g.kind, GapKind::UncertainLink { .. })));
+        assert!(gaps.windows(2).all(|w| w[0].gain >= w[1].gain));
+
+        let mut keys: Vec<String> = gaps.iter().map(|g| g.key()).collect();
+        keys.sort();
+        keys.dedup();
+        assert_eq!(keys.len(), gaps.len());
+    }
+
+    #[test]
+    fn opposite_directions_do_not_contradict_each_other() {
+        let mut kb = KnowledgeBase::new();
+        let motor = kb.add_concept(Concept::new("motor".into(), TruthValue::new(0.9, 0.8)));
+        let calor = kb.add_concept(Concept::new("calor".into(), TruthValue::new(0.9, 0.8)));
+        kb.add_link(link(
+            LinkKind::Implication,
+            motor,
+            calor,
+            TruthValue::new(0.9, 0.6),
+        ));
+        kb.add_link(link(
+            LinkKind::Implication,
+            calor,
+            motor,
+            TruthValue::new(0.1, 0.6),
+        ));
+        let gaps = analyze(&kb, &QuestionGenerator::new());
+        assert!(!gaps
+            .iter()
+            .any(|g| matches!(g.kind, GapKind::Contradiction { .. })));
+
+        // ≈ não tem direção: "motor ≈ calor" × "calor ≈ motor" conflitam
+        kb.add_link(link(
+            LinkKind::Similarity,
+            motor,
+            calor,
+            TruthValue::new(0.9, 0.6),
+        ));
+        kb.add_link(link(
+            LinkKind::Similarity,
+            calor,
+            motor,
+            TruthValue::new(0.1, 0.6),
+        ));
+        let gaps = analyze(&kb, &QuestionGenerator::new());
+        let contradictions = gaps
+            .iter()
+            .filter(|g| matches!(g.kind, GapKind::Contradiction { .. }));
+        assert_eq!(contradictions.count(), 1);
+    }
+
+    #[test]
+    fn parses_llm_questions() {
+        assert_eq!(
+            parse_question("\n\"O motor sempre aquece?\"\n"),
+            Some("O motor sempre aquece?".to_string())
+        );
+        assert_eq!(parse_question("Sem pergunta aqui."), None);
+        assert!(uncertainty(&TruthValue::proto()) > uncertainty(&TruthValue::new(0.95, 0.8)));
+    }
+}
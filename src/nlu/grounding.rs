@@ -0,0 +1,235 @@
+//! # Verificação de Alucinação — Ancoragem das Respostas do LLM na KB
+//!
+//! O LLM usado em `handle_query` e `handle_narration` recebe uma lista de
+//! conceitos da KB, mas nada o impede de **inventar fatos** que não estão
+//! lá. Este módulo implementa um passo de pós-processamento que confronta
+//! a resposta gerada com a base de conhecimento.
+//!
+//! ## Algoritmo
+//!
+//! ```text
+//! Resposta do LLM
+//!   ├── 1. Segmentar em afirmações (sentenças)
+//!   ├── 2. Extrair entidades de cada afirmação → EntityExtractor
+//!   ├── 3. Embeddar entidades em batch → LM Studio
+//!   ├── 4. Casar cada entidade com a KB (cosine ≥ 0.78)
+//!   │      ├── casou     → entidade sustentada (conceito + TruthValue)
+//!   │      └── não casou → entidade sem suporte
+//!   ├── 5. Afirmação sem suporte se < 50% das entidades casaram
+//!   └── 6. Confiança da resposta a partir dos TruthValues usados
+//! ```
+//!
+//! ## Confiança da Resposta
+//!
+//! A confiança combina duas dimensões:
+//!
+//! - **Crença**: média da `expectation()` dos conceitos sustentados,
+//!   ponderada pela similaridade do casamento
+//! - **Cobertura**: fração das entidades da resposta que a KB sustenta
+//!
+//! `confiança = crença × cobertura` — uma resposta bem ancorada em conceitos
+//! incertos e uma resposta que cita conceitos sólidos mas inventa metade do
+//! conteúdo recebem, ambas, confiança baixa.
+
+use crate::core::concept::ConceptId;
+
+/// Similaridade mínima para que uma entidade da resposta seja considerada
+/// sustentada por um conceito da KB.
+///
+/// Usa o limite inferior da faixa de auto-similaridade calibrada para
+/// `nomic-embed-text` (veja `docs/calibracao-nomic-embed-text.md`):
+/// abaixo de 0.78 os pares tendem a ser conceitos relacionados, mas distintos.
+pub const GROUNDING_THRESHOLD: f32 = 0.78;
+
+/// Fração mínima de entidades sustentadas para que uma afirmação
+/// seja considerada ancorada na KB.
+const CLAIM_SUPPORT_RATIO: f64 = 0.5;
+
+/// Entidade da resposta que casou com um conceito da KB.
+#[derive(Clone, Debug)]
+pub struct SupportedEntity {
+    /// Entidade como aparece na resposta do LLM.
+    pub entity: String,
+    /// Conceito da KB que sustenta a entidade.
+    pub concept_id: ConceptId,
+    /// Label do conceito da KB.
+    pub concept_label: String,
+    /// Similaridade cosseno entre entidade e conceito.
+    pub similarity: f32,
+    /// `expectation()` do TruthValue do conceito (0.0-1.0).
+    pub expectation: f64,
+}
+
+/// Uma afirmação (sentença) da resposta e seu grau de ancoragem.
+#[derive(Clone, Debug)]
+pub struct Claim {
+    /// Texto da afirmação.
+    pub text: String,
+    /// Entidades da afirmação sustentadas pela KB.
+    pub supported: Vec<String>,
+    /// Entidades da afirmação sem conceito correspondente na KB.
+    pub unsupported: Vec<String>,
+}
+
+impl Claim {
+    /// Uma afirmação é sustentada quando ao menos metade das suas
+    /// entidades casa com a KB. Afirmações sem entidades (conectivos,
+    /// cortesias) são consideradas neutras e, portanto, sustentadas.
+    pub fn is_supported(&self) -> bool {
+        let total = self.supported.len() + self.unsupported.len();
+        total == 0 || self.supported.len() as f64 / total as f64 >= CLAIM_SUPPORT_RATIO
+    }
+}
+
+/// Relatório de verificação de uma resposta do LLM contra a KB.
+#[derive(Clone, Debug, Default)]
+pub struct GroundingReport {
+    /// Afirmações analisadas, na ordem em que aparecem na resposta.
+    pub claims: Vec<Claim>,
+    /// Entidades sustentadas (deduplicadas por conceito).
+    pub supported: Vec<SupportedEntity>,
+    /// Entidades sem suporte na KB (deduplicadas, case-insensitive).
+    pub unsupported: Vec<String>,
+    /// Confiança da resposta (0.0-1.0). Veja [`answer_confidence`].
+    pub confidence: f64,
+}
+
+impl GroundingReport {
+    /// Afirmações que a KB não sustenta — candidatas a alucinação.
+    pub fn unsupported_claims(&self) -> Vec<&Claim> {
+        self.claims.iter().filter(|c| !c.is_supported()).collect()
+    }
+
+    /// Total de entidades avaliadas (sustentadas + sem suporte).
+    pub fn entity_count(&self) -> usize {
+        self.supported.len() + self.unsupported.len()
+    }
+
+    /// Resumo legível em PT-BR para exibição no chat.
+    ///
+    /// Formato: `Confiança 0.62 — 4/5 entidades sustentadas pela KB. Sem suporte: "X"; "Y"`
+    pub fn summary(&self) -> String {
+        let mut text = format!(
+            "Confiança {:.2} — {}/{} entidades sustentadas pela KB.",
+            self.confidence,
+            self.supported.len(),
+            self.entity_count()
+        );
+        let flagged = self.unsupported_claims();
+        if !flagged.is_empty() {
+            let quoted: Vec<String> = flagged.iter().map(|c| format!("\"{}\"", c.text)).collect();
+            text.push_str(&format!(" Sem suporte: {}", quoted.join("; ")));
+        }
+        text
+    }
+}
+
+/// Calcula a confiança de uma resposta a partir das entidades sustentadas.
+///
+/// - `crença = Σ(sim × expectation) / Σ(sim)` sobre as entidades sustentadas
+/// - `cobertura = sustentadas / total`
+/// - `confiança = crença × cobertura`
+///
+/// Retorna 0.0 quando nenhuma entidade é sustentada.
+pub fn answer_confidence(supported: &[SupportedEntity], total_entities: usize) -> f64 {
+    if supported.is_empty() || total_entities == 0 {
+        return 0.0;
+    }
+    let weight: f64 = supported.iter().map(|s| s.similarity as f64).sum();
+    let belief = if weight > 0.0 {
+        supported
+            .iter()
+            .map(|s| s.similarity as f64 * s.expectation)
+            .sum::<f64>()
+            / weight
+    } else {
+        0.0
+    };
+    let coverage = supported.len() as f64 / total_entities as f64;
+    (belief * coverage).clamp(0.0, 1.0)
+}
+
+/// Divide a resposta em afirmações (sentenças) por pontuação final.
+///
+/// Segmentação simples por `.`, `!`, `?` e quebras de linha — suficiente
+/// para respostas curtas do LLM. Marcadores de lista são removidos.
+pub fn split_claims(text: &str) -> Vec<String> {
+    let mut claims = Vec::new();
+    let mut current = String::new();
+    for ch in text.chars() {
+        if ch == '\n' {
+            push_claim(&mut claims, &current);
+            current.clear();
+            continue;
+        }
+        current.push(ch);
+        if matches!(ch, '.' | '!' | '?') {
+            push_claim(&mut claims, &current);
+            current.clear();
+        }
+    }
+    push_claim(&mut claims, &current);
+    claims
+}
+
+/// Adiciona uma afirmação limpa (sem marcadores de lista) se não for vazia.
+fn push_claim(claims: &mut Vec<String>, raw: &str) {
+    let cleaned = raw.trim().trim_start_matches(['-', '*', '•']).trim();
+    if cleaned.chars().any(|c| c.is_alphabetic()) {
+        claims.push(cleaned.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn supported(sim: f32, expectation: f64) -> SupportedEntity {
+        SupportedEntity {
+            entity: "x".into(),
+            concept_id: Uuid::new_v4(),
+            concept_label: "x".into(),
+            similarity: sim,
+            expectation,
+        }
+    }
+
+    #[test]
+    fn split_claims_by_punctuation_and_lines() {
+        let claims = split_claims("Rust é rápido. É seguro?\n- Tem ownership\n\nFim!");
+        assert_eq!(
+            claims,
+            vec!["Rust é rápido.", "É seguro?", "Tem ownership", "Fim!"]
+        );
+    }
+
+    #[test]
+    fn confidence_is_zero_without_support() {
+        assert_eq!(answer_confidence(&[], 3), 0.0);
+    }
+
+    #[test]
+    fn confidence_penalizes_unsupported_entities() {
+        let all = answer_confidence(&[supported(0.9, 0.8), supported(0.9, 0.8)], 2);
+        let half = answer_confidence(&[supported(0.9, 0.8), supported(0.9, 0.8)], 4);
+        assert!((all - 0.8).abs() < 1e-9);
+        assert!((half - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn claim_without_entities_is_neutral() {
+        let claim = Claim {
+            text: "Ok.".into(),
+            supported: vec![],
+            unsupported: vec![],
+        };
+        assert!(claim.is_supported());
+        let claim = Claim {
+            text: "Marte tem oceanos.".into(),
+            supported: vec![],
+            unsupported: vec!["Marte".into(), "oceanos".into()],
+        };
+        assert!(!claim.is_supported());
+    }
+}
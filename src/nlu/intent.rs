@@ -7,13 +7,19 @@
 //! |--------|-------------|---------|
 //! | [`Confirming`](Intent::Confirming) | Usuário confirma/concorda | "sim, exatamente" |
 //! | [`Denying`](Intent::Denying) | Usuário nega/discorda | "não, está errado" |
+//! | [`Correcting`](Intent::Correcting) | Usuário nega e corrige | "na verdade o motor é elétrico" |
 //! | [`Querying`](Intent::Querying) | Usuário pergunta algo | "como funciona?" |
+//! | [`Comparing`](Intent::Comparing) | Usuário compara conceitos | "qual a diferença entre X e Y?" |
+//! | [`Defining`](Intent::Defining) | Usuário define um termo | "entende-se por X ..." |
+//! | [`Commanding`](Intent::Commanding) | Usuário dá uma ordem | "liste os conceitos" |
+//! | [`Greeting`](Intent::Greeting) | Usuário cumprimenta | "olá, bom dia" |
 //! | [`Narrating`](Intent::Narrating) | Usuário narra/informa | "o motor queimou" |
 //!
 //! ## Estratégia Híbrida (Heurística + Embedding)
 //!
 //! ```text
 //! Mensagem do usuário
+//!   ├── 0. Frase já ensinada (igual a um template) → intent do template
 //!   ├── 1. Heurísticas rápidas (keywords + patterns)
 //!   │   → Se match: retorna imediatamente
 //!   └── 2. Embedding similarity (fallback)
@@ -21,29 +27,221 @@
 //!       → Se melhor score > 0.65: retorna intent do template
 //!       → Senão: retorna Narrating (default)
 //! ```
+//!
+//! O resultado ([`IntentClassification`]) traz o score e o segundo
+//! colocado, para o orquestrador e para o feedback na interface.
+//!
+//! ## Templates Configuráveis
+//!
+//! As frases-modelo ficam em `intents.txt` no diretório de prompts
+//! (`PROMPTS_DIR`), em seções `[intent idioma]`. Sem o arquivo vale a
+//! cópia embutida no binário. O feedback "era uma pergunta, não uma
+//! narração" ([`IntentClassifier::learn`]) acrescenta a frase como novo
+//! template, em memória e em `data/intents_learned.txt` — carregado por
+//! cima dos templates, sem tocar no `intents.txt` versionado. Cada linha
+//! do arquivo aprendido é `intent<TAB>idioma<TAB>frase`, então frases que
+//! começam com `#` ou `[` não se confundem com comentários ou seções.
+//!
+//! ## Idiomas
+//!
+//! Heurísticas e templates existem em pt-BR e inglês. O idioma da
+//! mensagem (detectado por [`language::detect`](super::language::detect))
+//! escolhe o conjunto de heurísticas e restringe a comparação aos
+//! templates do mesmo idioma.
 
-use anyhow::Result;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use parking_lot::RwLock;
 
 use super::embedder::Embedder;
+use super::language::Language;
 use crate::core::knowledge_base::cosine_similarity;
 
+/// Similaridade mínima para aceitar o intent de um template.
+const EMBEDDING_THRESHOLD: f32 = 0.65;
+
+/// Frases ensinadas pelo feedback, carregadas por cima dos templates.
+const LEARNED_PATH: &str = "data/intents_learned.txt";
+
 /// Intenção classificada a partir da mensagem do usuário.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Intent {
     /// Usuário está confirmando ou concordando com algo.
     Confirming,
     /// Usuário está negando ou discordando de algo.
     Denying,
+    /// Usuário nega o que foi dito e traz a versão correta.
+    Correcting,
     /// Usuário está fazendo uma pergunta.
     Querying,
+    /// Usuário pede a comparação entre conceitos.
+    Comparing,
+    /// Usuário define um termo.
+    Defining,
+    /// Usuário pede uma ação ("liste", "mostre", "esqueça").
+    Commanding,
+    /// Usuário cumprimenta.
+    Greeting,
     /// Usuário está narrando/informando algo (o caso mais comum).
     Narrating,
 }
 
+impl Intent {
+    /// Todos os intents, na ordem de exibição.
+    pub const ALL: [Intent; 9] = [
+        Intent::Narrating,
+        Intent::Querying,
+        Intent::Confirming,
+        Intent::Denying,
+        Intent::Correcting,
+        Intent::Comparing,
+        Intent::Defining,
+        Intent::Commanding,
+        Intent::Greeting,
+    ];
+
+    /// Identificador estável (arquivo de templates e formulários).
+    pub fn name(&self) -> &'static str {
+        match self {
+            Intent::Confirming => "confirming",
+            Intent::Denying => "denying",
+            Intent::Correcting => "correcting",
+            Intent::Querying => "querying",
+            Intent::Comparing => "comparing",
+            Intent::Defining => "defining",
+            Intent::Commanding => "commanding",
+            Intent::Greeting => "greeting",
+            Intent::Narrating => "narrating",
+        }
+    }
+
+    /// Interpreta o identificador de [`name()`](Self::name).
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase();
+        Self::ALL.into_iter().find(|i| i.name() == name)
+    }
+
+    /// Nome em PT-BR para exibição.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Intent::Confirming => "Confirmação",
+            Intent::Denying => "Negação",
+            Intent::Correcting => "Correção",
+            Intent::Querying => "Pergunta",
+            Intent::Comparing => "Comparação",
+            Intent::Defining => "Definição",
+            Intent::Commanding => "Comando",
+            Intent::Greeting => "Saudação",
+            Intent::Narrating => "Narração",
+        }
+    }
+}
+
+/// Como o intent foi decidido.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClassificationMethod {
+    /// Frase idêntica a um template (ensinado ou do arquivo).
+    Template,
+    /// Heurística de palavras-chave.
+    Heuristic,
+    /// Similaridade de embedding acima do limiar.
+    Embedding,
+    /// Nenhum template passou do limiar — `Narrating` por padrão.
+    Fallback,
+}
+
+/// Resultado da classificação de uma mensagem.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntentClassification {
+    /// Intent escolhido.
+    pub intent: Intent,
+    /// Score do intent escolhido (1.0 para template exato e heurística).
+    pub score: f32,
+    /// Segundo colocado na comparação por embedding, com seu score.
+    ///
+    /// No fallback, é o melhor template rejeitado pelo limiar.
+    pub runner_up: Option<(Intent, f32)>,
+    /// Como o intent foi decidido.
+    pub method: ClassificationMethod,
+    /// Idioma da mensagem.
+    pub language: Language,
+}
+
+impl IntentClassification {
+    /// Classificação com certeza total (template exato ou heurística).
+    fn certain(intent: Intent, method: ClassificationMethod, language: Language) -> Self {
+        Self {
+            intent,
+            score: 1.0,
+            runner_up: None,
+            method,
+            language,
+        }
+    }
+
+    /// Resumo curto para log e interface: `Pergunta 0.72 (2º: Narração 0.61)`.
+    pub fn describe(&self) -> String {
+        let mut text = format!("{} {:.2}", self.intent.label(), self.score);
+        if let Some((intent, score)) = self.runner_up {
+            text.push_str(&format!(" (2º: {} {:.2})", intent.label(), score));
+        }
+        text
+    }
+}
+
+/// Frase-modelo lida do arquivo de templates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemplateDef {
+    /// Intent que a frase representa.
+    pub intent: Intent,
+    /// Idioma da frase.
+    pub language: Language,
+    /// A frase.
+    pub text: String,
+}
+
+/// Interpreta o arquivo de templates (seções `[intent idioma]`).
+pub fn parse_templates(source: &str) -> Result<Vec<TemplateDef>> {
+    let mut defs = Vec::new();
+    let mut section: Option<(Intent, Language)> = None;
+
+    for (n, line) in source.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let mut parts = header.split_whitespace();
+            let intent = parts.next().and_then(Intent::from_name);
+            let language = parts.next().and_then(Language::from_code);
+            match (intent, language) {
+                (Some(intent), Some(language)) => section = Some((intent, language)),
+                _ => bail!("linha {}: seção inválida [{}]", n + 1, header),
+            }
+            continue;
+        }
+        let Some((intent, language)) = section else {
+            bail!("linha {}: frase fora de uma seção", n + 1);
+        };
+        defs.push(TemplateDef {
+            intent,
+            language,
+            text: line.to_string(),
+        });
+    }
+    Ok(defs)
+}
+
 /// Template interno de intent com embedding pré-computado.
 struct IntentTemplate {
     /// O intent que este template representa.
     intent: Intent,
+    /// Idioma da frase-template.
+    language: Language,
+    /// Frase normalizada (minúsculas, sem pontuação final).
+    text: String,
     /// Embedding pré-computado da frase-template (768-dim).
     embedding: Vec<f32>,
 }
@@ -52,123 +250,569 @@ struct IntentTemplate {
 ///
 /// ## Inicialização
 ///
-/// Computa embeddings para ~15 templates via uma única chamada `embed_batch`
-/// (1 HTTP request vs 15 individuais).
+/// Lê as frases de `intents.txt` e computa todos os embeddings via uma
+/// única chamada `embed_batch` (1 HTTP request em vez de um por frase).
 pub struct IntentClassifier {
     /// Templates com embeddings pré-computados para matching por similaridade.
-    templates: Vec<IntentTemplate>,
+    templates: RwLock<Vec<IntentTemplate>>,
+    /// Arquivo das frases ensinadas.
+    learned: PathBuf,
 }
 
 impl IntentClassifier {
-    /// Cria um novo classificador com templates pré-embeddados.
+    /// Cria um novo classificador com os templates de `path` pré-embeddados.
     ///
+    /// Se o arquivo não existir ou for inválido, usa a cópia embutida.
     /// Usa `embed_batch` para computar todos os templates em uma única
     /// chamada HTTP ao LM Studio (muito mais eficiente).
-    pub async fn new(embedder: &Embedder) -> Result<Self> {
-        let template_defs = vec![
-            (Intent::Confirming, vec![
-                "sim, correto, exatamente",
-                "concordo, faz sentido",
-                "é isso mesmo, verdade",
-                "sim faz total sentido",
-                "correto exato preciso",
-            ]),
-            (Intent::Denying, vec![
-                "não, errado, incorreto",
-                "discordo, não é assim",
-                "na verdade é diferente",
-                "não concordo está errado",
-                "isso não está certo",
-            ]),
-            (Intent::Querying, vec![
-                "o que é, como funciona",
-                "por que, qual a razão",
-                "me explique, o que significa",
-                "como assim, pode explicar",
-                "qual o motivo, por quê",
-            ]),
-        ];
-
-        // Coleta todos os textos e intents para batch
-        let mut all_texts: Vec<String> = Vec::new();
-        let mut all_intents: Vec<Intent> = Vec::new();
-
-        for (intent, texts) in &template_defs {
-            for text in texts {
-                all_texts.push(format!("search_query: {}", text));
-                all_intents.push(intent.clone());
-            }
-        }
+    pub async fn new(embedder: &Embedder, path: impl Into<PathBuf>) -> Result<Self> {
+        Self::with_learned(embedder, path, LEARNED_PATH).await
+    }
+
+    /// Como [`new`](Self::new), com as frases ensinadas em `learned`.
+    pub async fn with_learned(
+        embedder: &Embedder,
+        path: impl Into<PathBuf>,
+        learned: impl Into<PathBuf>,
+    ) -> Result<Self> {
+        let (path, learned) = (path.into(), learned.into());
+        let mut defs = load_templates(&path);
+        defs.extend(load_learned(&learned));
+
+        let all_texts: Vec<String> = defs
+            .iter()
+            .map(|d| format!("search_query: {}", d.text))
+            .collect();
 
         // Uma única chamada HTTP para todos os templates
         let all_embeddings = embedder.embed_batch(&all_texts).await?;
 
-        let templates: Vec<IntentTemplate> = all_intents
+        let templates: Vec<IntentTemplate> = defs
             .into_iter()
             .zip(all_embeddings)
-            .map(|(intent, embedding)| IntentTemplate { intent, embedding })
+            .map(|(def, embedding)| IntentTemplate {
+                intent: def.intent,
+                language: def.language,
+                text: normalize(&def.text),
+                embedding,
+            })
             .collect();
 
-        Ok(Self { templates })
+        tracing::info!(
+            path = %path.display(),
+            learned = %learned.display(),
+            templates = templates.len(),
+            "Templates de intent carregados"
+        );
+        Ok(Self {
+            templates: RwLock::new(templates),
+            learned,
+        })
     }
 
     /// Classifica o intent de uma mensagem do usuário.
     ///
-    /// ## Estratégia (2 fases)
+    /// ## Estratégia (3 fases)
+    ///
+    /// ### Fase 0: Template Exato
+    /// Frases ensinadas pelo feedback valem mesmo contra as heurísticas.
     ///
     /// ### Fase 1: Heurísticas Rápidas (~0ms)
-    /// Verifica padrões simples no texto.
+    /// Verifica padrões simples no texto, no idioma da mensagem.
     ///
     /// ### Fase 2: Fallback por Embedding
-    /// Compara o embedding da mensagem com os templates pré-computados.
-    /// Só aceita se similaridade > 0.65, senão retorna `Narrating`.
-    pub async fn classify(&self, text: &str, embedder: &Embedder) -> Result<Intent> {
-        let text_lower = text.to_lowercase().trim().to_string();
+    /// Compara o embedding da mensagem com os templates pré-computados
+    /// do mesmo idioma (melhor score por intent). Só aceita se
+    /// similaridade > 0.65, senão retorna `Narrating`.
+    pub async fn classify(
+        &self,
+        text: &str,
+        language: Language,
+        embedder: &Embedder,
+    ) -> Result<IntentClassification> {
+        // ─── Fase 0: Frase idêntica a um template ────────────────
+        let normalized = normalize(text);
+        let exact = self
+            .templates
+            .read()
+            .iter()
+            .rev()
+            .find(|t| t.language == language && t.text == normalized)
+            .map(|t| t.intent);
+        if let Some(intent) = exact {
+            return Ok(IntentClassification::certain(
+                intent,
+                ClassificationMethod::Template,
+                language,
+            ));
+        }
 
         // ─── Fase 1: Heurísticas rápidas ─────────────────────────
-        if text_lower.starts_with("sim")
-            || text_lower == "correto"
-            || text_lower == "exato"
-            || text_lower.starts_with("faz sentido")
-            || text_lower.starts_with("concordo")
-        {
-            return Ok(Intent::Confirming);
+        let heuristic = match language {
+            Language::Portuguese => classify_portuguese(text),
+            Language::English => classify_english(text),
+        };
+        if let Some(intent) = heuristic {
+            return Ok(IntentClassification::certain(
+                intent,
+                ClassificationMethod::Heuristic,
+                language,
+            ));
         }
 
-        if text_lower.starts_with("não")
-            || text_lower.starts_with("errado")
-            || text_lower.starts_with("discordo")
-            || text_lower.starts_with("incorreto")
+        // ─── Fase 2: Fallback por embedding similarity ───────────
+        let embedding = embedder.embed(&format!("search_query: {}", text)).await?;
+        let mut ranking: Vec<(Intent, f32)> = Vec::new();
+        for template in self
+            .templates
+            .read()
+            .iter()
+            .filter(|t| t.language == language)
         {
-            return Ok(Intent::Denying);
+            let score = cosine_similarity(&embedding, &template.embedding);
+            match ranking
+                .iter_mut()
+                .find(|(intent, _)| *intent == template.intent)
+            {
+                Some(entry) if score > entry.1 => entry.1 = score,
+                Some(_) => {}
+                None => ranking.push((template.intent, score)),
+            }
         }
+        ranking.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
 
-        if text_lower.starts_with("o que")
-            || text_lower.starts_with("como")
-            || text_lower.starts_with("por que")
-            || text_lower.starts_with("qual")
-            || text_lower.contains('?')
-        {
-            return Ok(Intent::Querying);
-        }
+        Ok(match ranking.first().copied() {
+            Some((intent, score)) if score > EMBEDDING_THRESHOLD => IntentClassification {
+                intent,
+                score,
+                runner_up: ranking.get(1).copied(),
+                method: ClassificationMethod::Embedding,
+                language,
+            },
+            best => IntentClassification {
+                intent: Intent::Narrating,
+                score: best.map(|(_, s)| s).unwrap_or(0.0),
+                runner_up: best.filter(|(intent, _)| *intent != Intent::Narrating),
+                method: ClassificationMethod::Fallback,
+                language,
+            },
+        })
+    }
 
-        // ─── Fase 2: Fallback por embedding similarity ───────────
+    /// Ensina o classificador: `text` passa a ser template de `intent`.
+    ///
+    /// O template vale imediatamente (fase 0 e similaridade) e é
+    /// acrescentado ao arquivo de frases ensinadas para sobreviver ao
+    /// reinício. Quebras de linha (`\r\n` inclusive) viram espaço; uma
+    /// frase já ensinada para o mesmo intent é ignorada.
+    pub async fn learn(
+        &self,
+        text: &str,
+        intent: Intent,
+        language: Language,
+        embedder: &Embedder,
+    ) -> Result<()> {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.is_empty() {
+            bail!("frase vazia");
+        }
+        let normalized = normalize(&text);
+        let known = self
+            .templates
+            .read()
+            .iter()
+            .any(|t| t.intent == intent && t.language == language && t.text == normalized);
+        if known {
+            tracing::debug!(
+                intent = intent.name(),
+                text = %text,
+                "Template de intent já conhecido"
+            );
+            return Ok(());
+        }
         let embedding = embedder.embed(&format!("search_query: {}", text)).await?;
-        let mut best_intent = Intent::Narrating;
-        let mut best_score = 0.0f32;
+        self.templates.write().push(IntentTemplate {
+            intent,
+            language,
+            text: normalized,
+            embedding,
+        });
 
-        for template in &self.templates {
-            let score = cosine_similarity(&embedding, &template.embedding);
-            if score > best_score {
-                best_score = score;
-                best_intent = template.intent.clone();
+        if let Some(dir) = self.learned.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.learned)
+            .with_context(|| format!("falha ao abrir {}", self.learned.display()))?;
+        writeln!(file, "{}\t{}\t{}", intent.name(), language.code(), text)?;
+        tracing::info!(
+            intent = intent.name(),
+            language = language.code(),
+            text = %text,
+            "Template de intent aprendido"
+        );
+        Ok(())
+    }
+}
+
+/// Lê os templates de `path`, ou a cópia embutida se o arquivo faltar
+/// ou for inválido.
+fn load_templates(path: &Path) -> Vec<TemplateDef> {
+    if path.exists() {
+        match std::fs::read_to_string(path)
+            .context("falha ao ler arquivo")
+            .and_then(|source| parse_templates(&source))
+        {
+            Ok(defs) => return defs,
+            Err(e) => {
+                tracing::warn!(
+                    path = %path.display(),
+                    error = %e,
+                    "Templates de intent inválidos, usando os embutidos"
+                );
             }
         }
+    }
+    parse_templates(include_str!("../../prompts/intents.txt"))
+        .expect("templates de intent embutidos inválidos")
+}
+
+/// Lê as frases ensinadas (`intent<TAB>idioma<TAB>frase` por linha);
+/// linhas ilegíveis são puladas com um aviso.
+fn load_learned(path: &Path) -> Vec<TemplateDef> {
+    let Ok(source) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    source
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(n, line)| {
+            let mut fields = line.trim_end_matches('\r').splitn(3, '\t');
+            let intent = fields.next().and_then(Intent::from_name);
+            let language = fields.next().and_then(Language::from_code);
+            let text = fields.next().map(str::trim).filter(|t| !t.is_empty());
+            match (intent, language, text) {
+                (Some(intent), Some(language), Some(text)) => Some(TemplateDef {
+                    intent,
+                    language,
+                    text: text.to_string(),
+                }),
+                _ => {
+                    tracing::warn!(
+                        path = %path.display(),
+                        line = n + 1,
+                        "Frase de intent aprendida ilegível"
+                    );
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Normaliza uma frase para comparação exata com templates.
+fn normalize(text: &str) -> String {
+    text.trim()
+        .trim_end_matches(['.', '!', '?'])
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Prefixos de correção em pt-BR ("na verdade, ...").
+#[rustfmt::skip]
+const PT_CORRECTION_PREFIXES: &[&str] = &[
+    "na verdade", "correção", "corrigindo", "eu quis dizer", "quis dizer",
+    "o correto é", "o certo é",
+];
+
+/// Marcadores de comparação em pt-BR.
+#[rustfmt::skip]
+const PT_COMPARISON_MARKERS: &[&str] = &[
+    "diferença entre", "compare ", "comparar ", "comparação entre", " versus ", " vs ",
+];
+
+/// Marcadores de definição em pt-BR.
+#[rustfmt::skip]
+const PT_DEFINITION_MARKERS: &[&str] = &[
+    "defino ", "definição de", "entende-se por", "chamamos de",
+    "é definido como", "é definida como",
+];
+
+/// Verbos de comando em pt-BR (primeira palavra, no imperativo ou infinitivo).
+#[rustfmt::skip]
+const PT_COMMAND_VERBS: &[&str] = &[
+    "liste", "listar", "mostre", "mostrar", "exiba", "resuma",
+    "esqueça", "apague", "remova", "busque", "procure",
+];
+
+/// Primeiras palavras de confirmação em inglês.
+#[rustfmt::skip]
+const EN_CONFIRMATION_WORDS: &[&str] = &[
+    "yes", "yeah", "yep", "correct", "exactly", "right", "agreed",
+];
+
+/// Primeiras palavras de pergunta em inglês.
+#[rustfmt::skip]
+const EN_QUESTION_WORDS: &[&str] = &[
+    "what", "how", "why", "which", "who", "when", "where", "explain",
+];
+
+/// Marcadores de definição em inglês.
+#[rustfmt::skip]
+const EN_DEFINITION_MARKERS: &[&str] = &[
+    "is defined as", "definition of", "by this we mean", "we call",
+];
+
+/// Verbos de comando em inglês (primeira palavra).
+#[rustfmt::skip]
+const EN_COMMAND_VERBS: &[&str] = &[
+    "list", "show", "display", "summarize", "forget", "delete", "remove", "find", "search",
+];
+
+/// Heurísticas rápidas para mensagens em pt-BR.
+fn classify_portuguese(text: &str) -> Option<Intent> {
+    let text_lower = text.to_lowercase().trim().to_string();
+    let first = text_lower
+        .split(|c: char| !c.is_alphanumeric())
+        .find(|w| !w.is_empty())
+        .unwrap_or("");
+
+    if matches!(first, "olá" | "oi" | "saudações")
+        || ["bom dia", "boa tarde", "boa noite", "e aí"]
+            .iter()
+            .any(|g| text_lower.starts_with(g))
+    {
+        return Some(Intent::Greeting);
+    }
+
+    if PT_CORRECTION_PREFIXES
+        .iter()
+        .any(|p| text_lower.starts_with(p))
+    {
+        return Some(Intent::Correcting);
+    }
+
+    if text_lower.starts_with("sim")
+        || text_lower == "correto"
+        || text_lower == "exato"
+        || text_lower.starts_with("faz sentido")
+        || text_lower.starts_with("concordo")
+    {
+        return Some(Intent::Confirming);
+    }
+
+    if text_lower.starts_with("não")
+        || text_lower.starts_with("errado")
+        || text_lower.starts_with("discordo")
+        || text_lower.starts_with("incorreto")
+    {
+        return Some(Intent::Denying);
+    }
+
+    if PT_COMPARISON_MARKERS.iter().any(|p| text_lower.contains(p)) {
+        return Some(Intent::Comparing);
+    }
+
+    if text_lower.starts_with("o que")
+        || text_lower.starts_with("como")
+        || text_lower.starts_with("por que")
+        || text_lower.starts_with("qual")
+        || text_lower.contains('?')
+    {
+        return Some(Intent::Querying);
+    }
+
+    if PT_DEFINITION_MARKERS.iter().any(|p| text_lower.contains(p)) {
+        return Some(Intent::Defining);
+    }
+
+    if PT_COMMAND_VERBS.contains(&first) {
+        return Some(Intent::Commanding);
+    }
+
+    None
+}
 
-        if best_score > 0.65 {
-            Ok(best_intent)
-        } else {
-            Ok(Intent::Narrating)
+/// Heurísticas rápidas para mensagens em inglês.
+///
+/// Compara a **primeira palavra** inteira (e não prefixos), para que
+/// "note", "nothing" ou "whatever" não disparem `Denying`/`Querying`.
+fn classify_english(text: &str) -> Option<Intent> {
+    let text_lower = text.to_lowercase().trim().to_string();
+    let first = text_lower
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .find(|w| !w.is_empty())
+        .unwrap_or("");
+
+    if matches!(first, "hello" | "hi" | "hey" | "greetings")
+        || ["good morning", "good afternoon", "good evening"]
+            .iter()
+            .any(|g| text_lower.starts_with(g))
+    {
+        return Some(Intent::Greeting);
+    }
+
+    if matches!(first, "actually" | "correction")
+        || text_lower.starts_with("i meant")
+        || text_lower.starts_with("let me correct")
+    {
+        return Some(Intent::Correcting);
+    }
+
+    if EN_CONFIRMATION_WORDS.contains(&first)
+        || text_lower.starts_with("i agree")
+        || text_lower.starts_with("makes sense")
+    {
+        return Some(Intent::Confirming);
+    }
+
+    if matches!(first, "no" | "nope" | "wrong" | "incorrect")
+        || text_lower.starts_with("i disagree")
+        || text_lower.starts_with("that's wrong")
+    {
+        return Some(Intent::Denying);
+    }
+
+    if first == "compare"
+        || ["difference between", " versus ", " vs "]
+            .iter()
+            .any(|p| text_lower.contains(p))
+    {
+        return Some(Intent::Comparing);
+    }
+
+    if EN_QUESTION_WORDS.contains(&first) || text_lower.contains('?') {
+        return Some(Intent::Querying);
+    }
+
+    if EN_DEFINITION_MARKERS.iter().any(|p| text_lower.contains(p)) {
+        return Some(Intent::Defining);
+    }
+
+    if EN_COMMAND_VERBS.contains(&first) {
+        return Some(Intent::Commanding);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_heuristics_match_whole_first_word() {
+        assert_eq!(classify_english("Yes, exactly"), Some(Intent::Confirming));
+        assert_eq!(classify_english("No, that is wrong"), Some(Intent::Denying));
+        assert_eq!(
+            classify_english("How does photosynthesis work"),
+            Some(Intent::Querying)
+        );
+        assert_eq!(classify_english("Notes were taken during the audit"), None);
+        assert_eq!(
+            classify_english("Whatever happened, the engine failed"),
+            None
+        );
+    }
+
+    #[test]
+    fn portuguese_heuristics_unchanged() {
+        assert_eq!(
+            classify_portuguese("sim, faz sentido"),
+            Some(Intent::Confirming)
+        );
+        assert_eq!(classify_portuguese("não é assim"), Some(Intent::Denying));
+        assert_eq!(classify_portuguese("o que é isso"), Some(Intent::Querying));
+        assert_eq!(classify_portuguese("o motor queimou"), None);
+    }
+
+    #[test]
+    fn heuristics_cover_new_intents() {
+        assert_eq!(
+            classify_portuguese("Olá, tudo bem?"),
+            Some(Intent::Greeting)
+        );
+        assert_eq!(
+            classify_portuguese("na verdade o motor é elétrico"),
+            Some(Intent::Correcting)
+        );
+        assert_eq!(
+            classify_portuguese("qual a diferença entre fotossíntese e respiração?"),
+            Some(Intent::Comparing)
+        );
+        assert_eq!(
+            classify_portuguese("entende-se por solo o substrato"),
+            Some(Intent::Defining)
+        );
+        assert_eq!(
+            classify_portuguese("liste os conceitos sobre solo"),
+            Some(Intent::Commanding)
+        );
+        assert_eq!(
+            classify_english("Actually, the engine is electric"),
+            Some(Intent::Correcting)
+        );
+        assert_eq!(
+            classify_english("Show me the concepts"),
+            Some(Intent::Commanding)
+        );
+    }
+
+    #[test]
+    fn builtin_templates_parse_and_cover_every_intent() {
+        let defs = parse_templates(include_str!("../../prompts/intents.txt")).unwrap();
+        for intent in Intent::ALL.into_iter().filter(|i| *i != Intent::Narrating) {
+            assert!(
+                defs.iter().any(|d| d.intent == intent),
+                "sem templates para {:?}",
+                intent
+            );
+        }
+        assert!(parse_templates("[asking pt-BR]\nfrase").is_err());
+        assert!(parse_templates("frase sem seção").is_err());
+    }
+
+    #[tokio::test]
+    async fn learned_sentences_persist_apart_from_the_templates() {
+        let dir = std::env::temp_dir().join(format!("intents-test-{}", uuid::Uuid::new_v4()));
+        let (templates, learned) = (
+            dir.join("intents.txt"),
+            dir.join("data").join("learned.txt"),
+        );
+        let embedder = Embedder::offline();
+        let builtin = parse_templates(include_str!("../../prompts/intents.txt"))
+            .unwrap()
+            .len();
+
+        let classifier = IntentClassifier::with_learned(&embedder, &templates, &learned)
+            .await
+            .unwrap();
+        for text in ["# solo fértil\r\n", "[x y] motor", "# solo fértil"] {
+            classifier
+                .learn(text, Intent::Querying, Language::Portuguese, &embedder)
+                .await
+                .unwrap();
         }
+        assert!(!templates.exists());
+
+        let reloaded = IntentClassifier::with_learned(&embedder, &templates, &learned)
+            .await
+            .unwrap();
+        assert_eq!(reloaded.templates.read().len(), builtin + 2);
+        let intent = reloaded
+            .classify("[x y] motor", Language::Portuguese, &embedder)
+            .await
+            .unwrap();
+        assert_eq!(
+            (intent.intent, intent.method),
+            (Intent::Querying, ClassificationMethod::Template)
+        );
+
+        std::fs::remove_dir_all(&dir).ok();
     }
 }
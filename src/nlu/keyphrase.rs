@@ -0,0 +1,356 @@
+//! # Pontuação de Keyphrases — Limite de Entidades por Chunk
+//!
+//! O [`EntityExtractor`](super::extractor::EntityExtractor) é generoso de
+//! propósito: emite todos os bigrams, trigrams e palavras ≥ 4 chars que
+//! não são stopwords. Um chunk de 500 chars de PDF gera dezenas de
+//! candidatos, quase todos de baixo valor ("rates because", "interest").
+//!
+//! Este módulo **pontua** os candidatos e mantém apenas os `top_n` por
+//! mensagem/chunk, preservando a ordem original da extração (que desempata
+//! a direção dos links de [coocorrência](super::cooccurrence)).
+//!
+//! ## Métodos
+//!
+//! | Método | `KEYPHRASE_METHOD` | Score |
+//! |--------|--------------------|-------|
+//! | [`Rake`](ScoringMethod::Rake) | `rake` (padrão) | Soma de `grau/frequência` das palavras (co-ocorrência) |
+//! | [`TfIdf`](ScoringMethod::TfIdf) | `tfidf` | `tf × idf` contra o corpus processado até agora |
+//! | [`Centrality`](ScoringMethod::Centrality) | `centrality` | Cosseno com o centróide dos embeddings dos candidatos |
+//!
+//! `KEYPHRASE_TOP_N` (padrão 8) define o limite; `0` desativa o corte.
+//! O score de cada conceito aparece em `NluConceptInfo::score`.
+//!
+//! ## RAKE
+//!
+//! ```text
+//! "A taxa de juros subiu porque a inflação acelerou"
+//!   frases (stopwords/pontuação como delimitadores):
+//!     [taxa] [juros subiu] [inflação acelerou]
+//!   grau(w) = Σ tamanho das frases que contêm w
+//!   score(w) = grau(w) / freq(w)      → palavras em frases longas pesam mais
+//!   score(candidato) = Σ score(w) das suas palavras
+//! ```
+//!
+//! Centralidade exige os embeddings de **todos** os candidatos; os outros
+//! métodos pontuam antes do embedding e economizam chamadas ao LM Studio.
+
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+
+use super::extractor::is_stopword_in;
+use super::language::Language;
+use crate::core::knowledge_base::cosine_similarity;
+
+/// Método de pontuação de keyphrases.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScoringMethod {
+    /// Grau de co-ocorrência das palavras (RAKE) — só o texto do chunk.
+    #[default]
+    Rake,
+    /// TF-IDF contra o corpus de mensagens/chunks já processados.
+    TfIdf,
+    /// Similaridade com o centróide dos embeddings dos candidatos.
+    Centrality,
+}
+
+impl ScoringMethod {
+    /// Interpreta o valor de `KEYPHRASE_METHOD`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "rake" => Some(ScoringMethod::Rake),
+            "tfidf" | "tf-idf" => Some(ScoringMethod::TfIdf),
+            "centrality" | "embedding" => Some(ScoringMethod::Centrality),
+            _ => None,
+        }
+    }
+}
+
+/// Configuração da seleção de keyphrases.
+#[derive(Clone, Debug)]
+pub struct KeyphraseConfig {
+    /// Método de pontuação.
+    pub method: ScoringMethod,
+    /// Máximo de candidatos mantidos por mensagem/chunk (`0` = sem limite).
+    pub top_n: usize,
+}
+
+impl Default for KeyphraseConfig {
+    fn default() -> Self {
+        Self {
+            method: ScoringMethod::Rake,
+            top_n: 8,
+        }
+    }
+}
+
+impl KeyphraseConfig {
+    /// Lê `KEYPHRASE_METHOD` e `KEYPHRASE_TOP_N`.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let method = std::env::var("KEYPHRASE_METHOD")
+            .ok()
+            .and_then(|v| {
+                let method = ScoringMethod::from_name(&v);
+                if method.is_none() {
+                    tracing::warn!(value = %v, "KEYPHRASE_METHOD desconhecido, usando rake");
+                }
+                method
+            })
+            .unwrap_or(defaults.method);
+        let top_n = std::env::var("KEYPHRASE_TOP_N")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(defaults.top_n);
+        Self { method, top_n }
+    }
+}
+
+/// Frequências de documento para o TF-IDF (corpus crescente).
+#[derive(Default)]
+struct DocumentFrequencies {
+    /// Documentos (mensagens/chunks) observados.
+    documents: usize,
+    /// Em quantos documentos cada palavra apareceu.
+    counts: HashMap<String, usize>,
+}
+
+impl DocumentFrequencies {
+    fn observe(&mut self, words: &[String]) {
+        self.documents += 1;
+        let mut unique: Vec<&String> = words.iter().collect();
+        unique.sort();
+        unique.dedup();
+        for word in unique {
+            *self.counts.entry(word.clone()).or_insert(0) += 1;
+        }
+    }
+
+    /// IDF suavizado: `ln((N + 1) / (df + 1)) + 1`.
+    fn idf(&self, word: &str) -> f32 {
+        let df = self.counts.get(word).copied().unwrap_or(0);
+        (((self.documents + 1) as f32) / ((df + 1) as f32)).ln() + 1.0
+    }
+}
+
+/// Pontua candidatos e seleciona os `top_n` mais relevantes.
+pub struct KeyphraseScorer {
+    /// Método e limite.
+    pub config: KeyphraseConfig,
+    /// Corpus do TF-IDF (só alimentado com `ScoringMethod::TfIdf`).
+    corpus: Mutex<DocumentFrequencies>,
+}
+
+impl KeyphraseScorer {
+    /// Cria um scorer com a configuração dada.
+    pub fn new(config: KeyphraseConfig) -> Self {
+        Self {
+            config,
+            corpus: Mutex::new(DocumentFrequencies::default()),
+        }
+    }
+
+    /// `true` se o método precisa dos embeddings de todos os candidatos.
+    pub fn needs_embeddings(&self) -> bool {
+        self.config.method == ScoringMethod::Centrality
+    }
+
+    /// Pontua cada candidato de `entities` extraído de `text`.
+    ///
+    /// `embeddings` só é usado por [`ScoringMethod::Centrality`]; sem eles,
+    /// cai para RAKE. Com TF-IDF, `text` passa a fazer parte do corpus.
+    pub fn score(
+        &self,
+        text: &str,
+        entities: &[String],
+        language: Language,
+        embeddings: Option<&[Vec<f32>]>,
+    ) -> Vec<f32> {
+        match (self.config.method, embeddings) {
+            (ScoringMethod::Centrality, Some(embeddings)) => centrality_scores(embeddings),
+            (ScoringMethod::TfIdf, _) => {
+                let words = tokenize(text);
+                let mut corpus = self.corpus.lock();
+                corpus.observe(&words);
+                tfidf_scores(&text.to_lowercase(), entities, &corpus)
+            }
+            _ => rake_scores(text, entities, language),
+        }
+    }
+
+    /// Índices dos `top_n` maiores scores, na ordem original.
+    pub fn select(&self, scores: &[f32]) -> Vec<usize> {
+        top_n(scores, self.config.top_n)
+    }
+}
+
+/// Índices dos `n` maiores scores, devolvidos em ordem crescente
+/// (`n == 0` mantém todos).
+pub fn top_n(scores: &[f32], n: usize) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..scores.len()).collect();
+    if n > 0 && scores.len() > n {
+        indices.sort_by(|&a, &b| {
+            scores[b]
+                .partial_cmp(&scores[a])
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.cmp(&b))
+        });
+        indices.truncate(n);
+        indices.sort_unstable();
+    }
+    indices
+}
+
+/// Palavras minúsculas do texto (alfanuméricas).
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect()
+}
+
+/// Scores RAKE: soma de `grau/frequência` das palavras do candidato.
+fn rake_scores(text: &str, entities: &[String], language: Language) -> Vec<f32> {
+    // Frases candidatas: sequências sem stopwords nem pontuação
+    let mut phrases: Vec<Vec<String>> = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+    for token in text.split_whitespace() {
+        let word: String = token
+            .trim_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase();
+        let breaks_after = token.ends_with(['.', ',', ';', ':', '!', '?', ')']);
+        if word.is_empty() || is_stopword_in(&word, language) {
+            if !current.is_empty() {
+                phrases.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        current.push(word);
+        if breaks_after {
+            phrases.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        phrases.push(current);
+    }
+
+    let mut frequency: HashMap<&str, f32> = HashMap::new();
+    let mut degree: HashMap<&str, f32> = HashMap::new();
+    for phrase in &phrases {
+        for word in phrase {
+            *frequency.entry(word).or_insert(0.0) += 1.0;
+            *degree.entry(word).or_insert(0.0) += phrase.len() as f32;
+        }
+    }
+
+    entities
+        .iter()
+        .map(|entity| {
+            tokenize(entity)
+                .iter()
+                .filter(|w| !is_stopword_in(w, language))
+                .map(
+                    |w| match (degree.get(w.as_str()), frequency.get(w.as_str())) {
+                        (Some(d), Some(f)) => d / f,
+                        // Palavra normalizada pelo extrator (fragmentos) — peso neutro
+                        _ => 1.0,
+                    },
+                )
+                .sum()
+        })
+        .collect()
+}
+
+/// Scores TF-IDF: ocorrências do candidato no texto × IDF médio das palavras.
+fn tfidf_scores(text_lower: &str, entities: &[String], corpus: &DocumentFrequencies) -> Vec<f32> {
+    entities
+        .iter()
+        .map(|entity| {
+            let words = tokenize(entity);
+            if words.is_empty() {
+                return 0.0;
+            }
+            let tf = text_lower.matches(&entity.to_lowercase()).count().max(1) as f32;
+            let idf = words.iter().map(|w| corpus.idf(w)).sum::<f32>() / words.len() as f32;
+            tf * idf
+        })
+        .collect()
+}
+
+/// Scores de centralidade: cosseno de cada embedding com o centróide.
+fn centrality_scores(embeddings: &[Vec<f32>]) -> Vec<f32> {
+    let Some(dim) = embeddings.first().map(|e| e.len()) else {
+        return Vec::new();
+    };
+    let mut centroid = vec![0.0f32; dim];
+    for embedding in embeddings {
+        for (c, v) in centroid.iter_mut().zip(embedding) {
+            *c += v;
+        }
+    }
+    embeddings
+        .iter()
+        .map(|e| cosine_similarity(e, &centroid))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn rake_prefers_words_in_longer_phrases() {
+        let text = "A taxa básica de juros subiu. A inflação acelerou.";
+        let scores = rake_scores(
+            text,
+            &strings(&["taxa básica", "inflação"]),
+            Language::Portuguese,
+        );
+        assert!(scores[0] > scores[1], "{:?}", scores);
+    }
+
+    #[test]
+    fn top_n_keeps_original_order() {
+        assert_eq!(top_n(&[0.1, 0.9, 0.5, 0.7], 2), vec![1, 3]);
+        assert_eq!(top_n(&[0.1, 0.9], 0), vec![0, 1]);
+        assert_eq!(top_n(&[0.1, 0.9], 5), vec![0, 1]);
+    }
+
+    #[test]
+    fn tfidf_penalizes_words_seen_everywhere() {
+        let scorer = KeyphraseScorer::new(KeyphraseConfig {
+            method: ScoringMethod::TfIdf,
+            top_n: 1,
+        });
+        let entities = strings(&["sistema", "fotossíntese"]);
+        scorer.score(
+            "o sistema de irrigação",
+            &entities,
+            Language::Portuguese,
+            None,
+        );
+        scorer.score("o sistema elétrico", &entities, Language::Portuguese, None);
+        let scores = scorer.score(
+            "o sistema e a fotossíntese",
+            &entities,
+            Language::Portuguese,
+            None,
+        );
+        assert_eq!(scorer.select(&scores), vec![1]);
+    }
+
+    #[test]
+    fn centrality_favors_typical_embedding() {
+        let embeddings = vec![vec![1.0, 0.0], vec![0.9, 0.1], vec![0.0, 1.0]];
+        let scores = centrality_scores(&embeddings);
+        assert!(
+            scores[0] > scores[2] && scores[1] > scores[2],
+            "{:?}",
+            scores
+        );
+    }
+}
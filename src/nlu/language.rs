@@ -0,0 +1,138 @@
+//! # Detecção de Idioma — Perfis pt-BR e Inglês
+//!
+//! Metade dos PDFs de origem está em inglês, mas o extrator e o
+//! classificador de intenção eram exclusivamente PT-BR: stopwords
+//! inglesas viravam conceitos ("which", "these") e perguntas em inglês
+//! eram lidas como narração.
+//!
+//! Este módulo detecta o idioma **por mensagem e por chunk** e expõe o
+//! [`Language`] usado para escolher o perfil do
+//! [`EntityExtractor`](super::extractor::EntityExtractor) e do
+//! [`IntentClassifier`](super::intent::IntentClassifier). O idioma
+//! detectado é gravado em cada conceito criado (`Concept::language`).
+//!
+//! ## Algoritmo
+//!
+//! Contagem de palavras funcionais exclusivas de cada idioma ("que",
+//! "não", "uma" × "the", "and", "which"), mais um bônus por diacríticos
+//! típicos do português (ã, õ, ç, ê). Empate ou texto sem evidência →
+//! português (idioma padrão do sistema).
+
+use serde::{Deserialize, Serialize};
+
+/// Idiomas suportados pelos perfis de extração e intenção.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Language {
+    /// Português brasileiro (padrão).
+    #[default]
+    #[serde(rename = "pt-BR")]
+    Portuguese,
+    /// Inglês.
+    #[serde(rename = "en")]
+    English,
+}
+
+impl Language {
+    /// Código BCP 47 do idioma (`pt-BR`, `en`) — gravado nos conceitos.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::Portuguese => "pt-BR",
+            Language::English => "en",
+        }
+    }
+
+    /// Nome do idioma para a variável `{language}` dos prompts.
+    pub fn prompt_name(&self) -> &'static str {
+        match self {
+            Language::Portuguese => "português brasileiro",
+            Language::English => "inglês",
+        }
+    }
+
+    /// Interpreta um código (`pt-BR`, `pt`, `en`...).
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.to_lowercase().as_str() {
+            "pt-br" | "pt" | "pt_br" => Some(Language::Portuguese),
+            "en" | "en-us" | "en-gb" => Some(Language::English),
+            _ => None,
+        }
+    }
+}
+
+/// Palavras funcionais frequentes e exclusivas do português.
+const PT_MARKERS: &[&str] = &[
+    "que", "não", "uma", "um", "de", "do", "da", "dos", "das", "em", "no", "na", "para", "com",
+    "por", "os", "as", "é", "são", "foi", "está", "mais", "mas", "como", "também", "isso", "ele",
+    "ela", "seu", "sua", "pelo", "pela", "ao", "à", "entre", "sobre", "quando", "já",
+];
+
+/// Palavras funcionais frequentes e exclusivas do inglês.
+const EN_MARKERS: &[&str] = &[
+    "the", "and", "of", "to", "is", "are", "was", "were", "in", "on", "for", "with", "that",
+    "this", "these", "those", "which", "it", "its", "be", "by", "from", "at", "an", "not", "have",
+    "has", "what", "how", "why", "can", "will", "would", "their", "there", "about",
+];
+
+/// Detecta o idioma de um texto (português em caso de dúvida).
+pub fn detect(text: &str) -> Language {
+    let mut pt = 0usize;
+    let mut en = 0usize;
+    for word in text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        let lower = word.to_lowercase();
+        if PT_MARKERS.contains(&lower.as_str()) {
+            pt += 2;
+        }
+        if EN_MARKERS.contains(&lower.as_str()) {
+            en += 2;
+        }
+        if lower
+            .chars()
+            .any(|c| matches!(c, 'ã' | 'õ' | 'ç' | 'ê' | 'ô' | 'á' | 'é' | 'í' | 'ó' | 'ú'))
+        {
+            pt += 1;
+        }
+    }
+    if en > pt {
+        Language::English
+    } else {
+        Language::Portuguese
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_portuguese_and_english() {
+        assert_eq!(
+            detect("O motor queimou porque a manutenção atrasou"),
+            Language::Portuguese
+        );
+        assert_eq!(
+            detect("The engine failed because maintenance was late"),
+            Language::English
+        );
+        assert_eq!(
+            detect("What is the role of photosynthesis?"),
+            Language::English
+        );
+        assert_eq!(detect("O que é fotossíntese?"), Language::Portuguese);
+    }
+
+    #[test]
+    fn defaults_to_portuguese_without_evidence() {
+        assert_eq!(detect(""), Language::Portuguese);
+        assert_eq!(detect("Rust"), Language::Portuguese);
+    }
+
+    #[test]
+    fn codes_round_trip() {
+        for lang in [Language::Portuguese, Language::English] {
+            assert_eq!(Language::from_code(lang.code()), Some(lang));
+        }
+    }
+}
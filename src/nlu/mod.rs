@@ -14,8 +14,21 @@
 //! |--------|-----------------|
 //! | [`embedder`] | Gera embeddings e chat via LM Studio API |
 //! | [`extractor`] | Extrai entidades candidatas por heurísticas |
-//! | [`intent`] | Classifica intenção (Confirming/Denying/Querying/Narrating) |
-//! | [`question`] | Gera perguntas reflexivas para conceitos incertos |
+//! | [`intent`] | Classifica intenção (templates configuráveis, score e 2º colocado) |
+//! | [`question`] | Templates de perguntas reflexivas (fallback offline) |
+//! | [`gaps`] | Lacunas da KB ordenadas por ganho de informação esperado |
+//! | [`grounding`] | Verifica respostas do LLM contra a KB (alucinação) |
+//! | [`prompts`] | Templates de prompt e parâmetros de chat por tarefa |
+//! | [`context`] | Histórico multi-turno, resumo e resolução de referências |
+//! | [`coref`] | Correferência de pronomes e descrições definidas |
+//! | [`stemmer`] | Stemmer RSLP e chave canônica de conceitos |
+//! | [`language`] | Detecção de idioma (pt-BR/en) por mensagem e chunk |
+//! | [`keyphrase`] | Pontuação de candidatos e corte top-N por chunk |
+//! | [`entity_type`] | Tipagem de entidades (pessoa, lugar, data...) e links `InstanceOf` |
+//! | [`quantity`] | Números, datas e quantidades como atributos de conceitos |
+//! | [`sentence`] | Segmentação de sentenças pt-BR/en (abreviações, `?`, `!`, `…`) |
+//! | [`cooccurrence`] | Janelas de sentenças e verdade dos links por NPMI/Dice |
+//! | [`structure`] | Documento, autores, palavras-chave e seções como conceitos e links |
 
 /// Sub-módulo do embedder via LM Studio.
 pub mod embedder;
@@ -29,18 +42,83 @@ pub mod intent;
 /// Sub-módulo do gerador de perguntas reflexivas.
 pub mod question;
 
+/// Sub-módulo de análise de lacunas da KB para perguntas reflexivas.
+pub mod gaps;
+
+/// Sub-módulo de verificação de alucinação das respostas do LLM.
+pub mod grounding;
+
+/// Sub-módulo de templates de prompt com hot-reload.
+pub mod prompts;
+
+/// Sub-módulo de contexto conversacional multi-turno.
+pub mod context;
+
+/// Sub-módulo de resolução de correferência entre turnos.
+pub mod coref;
+
+/// Sub-módulo do stemmer RSLP para chaves canônicas de conceitos.
+pub mod stemmer;
+
+/// Sub-módulo de detecção de idioma (perfis pt-BR e inglês).
+pub mod language;
+
+/// Sub-módulo de pontuação de keyphrases (RAKE, TF-IDF, centralidade).
+pub mod keyphrase;
+
+/// Sub-módulo de tipagem de entidades nomeadas.
+pub mod entity_type;
+
+/// Sub-módulo de extração de valores (números, datas, dinheiro).
+pub mod quantity;
+
+/// Sub-módulo de segmentação de sentenças (pt-BR e inglês).
+pub mod sentence;
+
+/// Sub-módulo de links por coocorrência em janelas de sentenças.
+pub mod cooccurrence;
+
+/// Sub-módulo da estrutura de documentos (metadados e sumário) como conhecimento.
+pub mod structure;
+
 use anyhow::Result;
 use parking_lot::RwLock;
 use std::sync::Arc;
 use unicode_normalization::UnicodeNormalization;
 
-use crate::core::{Concept, KnowledgeBase, Link, LinkKind, Participant, Role, TruthValue};
+use crate::core::concept::ConceptId;
 use crate::core::knowledge_base::cosine_similarity;
+use crate::core::{
+    Attribute, Citation, Concept, EntityType, KnowledgeBase, Link, LinkKind, Participant, Role,
+    Source, TruthValue,
+};
 
-use embedder::Embedder;
-use extractor::EntityExtractor;
-use intent::{Intent, IntentClassifier};
+use cooccurrence::CooccurrenceConfig;
+use coref::Antecedent;
+use embedder::{ChatMsg, ChatParams, Embedder, EmbedderConfig};
+use entity_type::EntityTyper;
+use extractor::{AliasPair, EntityExtractor};
+use grounding::{Claim, GroundingReport, SupportedEntity, GROUNDING_THRESHOLD};
+use intent::{Intent, IntentClassification, IntentClassifier};
+use keyphrase::{KeyphraseConfig, KeyphraseScorer};
+use language::Language;
+use prompts::{PromptStore, PromptTask, PromptTemplate, PromptVars};
+use quantity::ValueExtractor;
 use question::QuestionGenerator;
+use sentence::SentenceSegmenter;
+
+/// Trecho de onde vieram as entidades de [`NluPipeline::apply_entities_to_kb`].
+pub struct Passage<'a> {
+    /// Texto da mensagem ou do chunk — suas sentenças formam as janelas de coocorrência.
+    pub text: &'a str,
+    /// Idioma detectado no trecho.
+    pub language: Language,
+    /// Fonte e página, registradas na proveniência.
+    pub citation: &'a Citation,
+    /// Bytes iniciais de `text` que repetem o trecho anterior (sobreposição
+    /// de chunks) — suas sentenças ficam fora das janelas de coocorrência.
+    pub overlap: usize,
+}
 
 /// Informação estruturada sobre um conceito processado pelo NLU.
 pub struct NluConceptInfo {
@@ -60,6 +138,11 @@ pub struct NluConceptInfo {
     pub confidence: f64,
     /// Estado do conceito ("active", "dormant", "fading", "archived").
     pub state: String,
+    /// Score de keyphrase da entidade que originou o conceito (veja
+    /// [`keyphrase`]). `None` quando a entidade não foi pontuada.
+    pub score: Option<f32>,
+    /// Tipo de entidade (`person`, `place`...), quando conhecido.
+    pub entity_type: Option<String>,
 }
 
 /// Informação estruturada sobre um link criado pelo NLU.
@@ -100,6 +183,199 @@ pub struct NluResult {
     pub concept_details: Vec<NluConceptInfo>,
     /// Detalhes estruturados dos links criados (para o grafo).
     pub link_details: Vec<NluLinkInfo>,
+    /// Conceito a que cada entidade foi resolvida, na ordem das entidades.
+    pub entity_concepts: Vec<ConceptId>,
+    /// Correferências resolvidas antes da extração (`Ele → Carlos`).
+    pub coreferences: Vec<String>,
+    /// Idioma detectado na mensagem.
+    pub language: Language,
+}
+
+impl NluResult {
+    /// Incorpora ao resultado o conceito-tipo criado e o link `InstanceOf`
+    /// devolvidos por [`link_instance_to_type`].
+    fn absorb_type_link(&mut self, typed: TypeLink) {
+        if let Some(info) = typed.type_concept {
+            self.new_concepts.push(info.label.clone());
+            self.concept_details.push(info);
+        }
+        if let Some((info, desc)) = typed.link {
+            self.new_links.push(desc);
+            self.link_details.push(info);
+        }
+    }
+}
+
+/// Grava as siglas detectadas como aliases dos conceitos de forma longa.
+///
+/// Pares cuja forma longa não virou conceito (cortada pelo top-N) são
+/// ignorados. Retorna as mensagens para o chat.
+pub fn register_aliases(aliases: &[AliasPair], kb: &Arc<RwLock<KnowledgeBase>>) -> Vec<String> {
+    let mut kb = kb.write();
+    let mut messages = Vec::new();
+    for pair in aliases {
+        let Some(id) = kb.find_concept_by_label(&pair.label).map(|c| c.id) else {
+            continue;
+        };
+        if let Some(concept) = kb.concepts.get_mut(&id) {
+            if concept.add_alias(&pair.alias) {
+                tracing::info!(label = %concept.label, alias = %pair.alias, "Alias registrado");
+                messages.push(format!("Alias: {} → {}", pair.alias, concept.label));
+            }
+        }
+    }
+    messages
+}
+
+/// Anexa os valores extraídos aos conceitos a que se referem.
+///
+/// `attributes` vem de [`quantity::attach`] (índice da entidade, valor) e
+/// `concepts` é o conceito resolvido para cada entidade
+/// ([`NluResult::entity_concepts`]). Cada fonte guarda sua própria cópia
+/// do valor, para que esquecer um documento não apague o que outro também
+/// afirma; repetições da mesma fonte são ignoradas. Retorna as mensagens
+/// para o chat.
+pub fn register_attributes(
+    attributes: &[(usize, Attribute)],
+    concepts: &[ConceptId],
+    source: &Source,
+    kb: &Arc<RwLock<KnowledgeBase>>,
+) -> Vec<String> {
+    let mut kb = kb.write();
+    let mut messages = Vec::new();
+    for (entity, attribute) in attributes {
+        let Some(concept) = concepts.get(*entity).and_then(|id| kb.concepts.get_mut(id)) else {
+            continue;
+        };
+        let mut known = concept
+            .attributes
+            .iter()
+            .filter(|a| a.kind == attribute.kind && a.text == attribute.text);
+        if known.clone().any(|a| a.source.as_ref() == Some(source)) {
+            continue;
+        }
+        // Valor já conhecido por outra fonte: só registra a nova evidência
+        if known.next().is_none() {
+            tracing::info!(label = %concept.label, value = %attribute.text, "Atributo registrado");
+            messages.push(format!(
+                "Atributo: {} = {}",
+                concept.label,
+                attribute.describe()
+            ));
+        }
+        concept.attributes.push(Attribute {
+            source: Some(source.clone()),
+            ..attribute.clone()
+        });
+    }
+    messages
+}
+
+/// Resultado de [`link_instance_to_type`].
+struct TypeLink {
+    /// Conceito-tipo, se precisou ser criado.
+    type_concept: Option<NluConceptInfo>,
+    /// Link `InstanceOf` criado e sua descrição (`None` se já existia).
+    link: Option<(NluLinkInfo, String)>,
+}
+
+/// Grava `entity_type` no conceito e o liga ao conceito-tipo
+/// ("Carlos instância de Pessoa"), criando o conceito-tipo se preciso.
+///
+/// O conceito-tipo e o link `InstanceOf` citam `citation`, como os demais
+/// links extraídos: esquecer o documento desfaz a tipagem que só ele trouxe.
+///
+/// [`EntityType::Notion`] só é gravado — não há conceito-tipo "Noção",
+/// que viraria um hub ligado a quase toda a KB.
+fn link_instance_to_type(
+    kb: &mut KnowledgeBase,
+    instance_id: ConceptId,
+    entity_type: EntityType,
+    citation: &Citation,
+) -> TypeLink {
+    let mut typed = TypeLink {
+        type_concept: None,
+        link: None,
+    };
+    let Some(instance) = kb.concepts.get_mut(&instance_id) else {
+        return typed;
+    };
+    instance.entity_type = Some(entity_type);
+    if entity_type == EntityType::Notion {
+        return typed;
+    }
+    let instance_label = instance.label.clone();
+
+    let type_label = entity_type.label();
+    let type_id = match kb.find_concept_by_label(type_label).map(|c| c.id) {
+        Some(id) => id,
+        None => {
+            let mut concept = Concept::new(type_label.to_string(), TruthValue::observed(true));
+            concept.key = stemmer::canonical_key(type_label);
+            concept.language = Some(Language::Portuguese.code().to_string());
+            typed.type_concept = Some(NluConceptInfo {
+                id: concept.id.to_string(),
+                label: type_label.to_string(),
+                is_new: true,
+                similarity: None,
+                energy: concept.energy,
+                frequency: concept.truth.frequency(),
+                confidence: concept.truth.confidence(),
+                state: concept.state.css_class().to_string(),
+                score: None,
+                entity_type: None,
+            });
+            tracing::info!(label = %type_label, "Conceito-tipo criado");
+            kb.add_concept(concept)
+        }
+    };
+    // Cada citação conta como menção: o esquecimento compara as duas
+    if let Some(concept) = kb.concepts.get_mut(&type_id) {
+        if typed.type_concept.is_none() {
+            concept.mention_count += 1;
+        }
+        concept.provenance.cite(citation);
+    }
+
+    if let Some(link) = kb.find_link_mut(&LinkKind::InstanceOf, instance_id, type_id) {
+        link.provenance.cite(citation);
+        return typed;
+    }
+    // Heurísticas erram: confiança moderada, revisável por confirmação
+    let truth = TruthValue::new(1.0, 0.5);
+    let truth_freq = truth.frequency();
+    let truth_conf = truth.confidence();
+    let mut link = Link::new(
+        LinkKind::InstanceOf,
+        vec![
+            Participant {
+                concept_id: instance_id,
+                role: Role::Subject,
+            },
+            Participant {
+                concept_id: type_id,
+                role: Role::Object,
+            },
+        ],
+        truth,
+    );
+    link.provenance.cite(citation);
+    let desc = kb.describe_link(&link);
+    let info = NluLinkInfo {
+        id: link.id.to_string(),
+        source_id: instance_id.to_string(),
+        source_label: instance_label,
+        target_id: type_id.to_string(),
+        target_label: type_label.to_string(),
+        kind: "InstanceOf".to_string(),
+        frequency: truth_freq,
+        confidence: truth_conf,
+        energy: link.energy,
+    };
+    kb.add_link(link);
+    tracing::info!(link = %desc, "Link de tipo criado");
+    typed.link = Some((info, desc));
+    typed
 }
 
 /// Pipeline NLU completo — orquestra todos os componentes de processamento.
@@ -118,6 +394,22 @@ pub struct NluPipeline {
     extractor: EntityExtractor,
     /// Gerador de perguntas reflexivas para o ciclo de germinação.
     pub question_generator: QuestionGenerator,
+    /// Templates de prompt por tarefa (recarregados quando o arquivo muda).
+    prompts: PromptStore,
+    /// Desambigua correferências ambíguas via LLM (`COREF_LLM_ASSIST`).
+    coref_llm_assist: bool,
+    /// Pontuação e corte top-N dos candidatos extraídos.
+    keyphrases: KeyphraseScorer,
+    /// Tipagem de entidades por regex e gazetteers.
+    entity_typer: EntityTyper,
+    /// Consulta o LLM para nomes próprios sem tipo (`ENTITY_TYPE_LLM`).
+    entity_type_llm: bool,
+    /// Extração de números, datas e quantidades.
+    values: ValueExtractor,
+    /// Segmentador de sentenças (janelas de coocorrência).
+    sentences: SentenceSegmenter,
+    /// Tamanho da janela e medida de associação dos links.
+    cooccurrence: CooccurrenceConfig,
 }
 
 impl NluPipeline {
@@ -126,7 +418,9 @@ impl NluPipeline {
     /// Durante a criação, computa os embeddings dos templates de intent
     /// via uma chamada HTTP batch ao LM Studio.
     pub async fn new(embedder: Embedder) -> Result<Self> {
-        let intent_classifier = IntentClassifier::new(&embedder).await?;
+        let prompts = PromptStore::from_env();
+        let intent_classifier =
+            IntentClassifier::new(&embedder, prompts.dir().join("intents.txt")).await?;
         let extractor = EntityExtractor::new();
         let question_generator = QuestionGenerator::new();
 
@@ -135,6 +429,18 @@ impl NluPipeline {
             intent_classifier,
             extractor,
             question_generator,
+            prompts,
+            coref_llm_assist: std::env::var("COREF_LLM_ASSIST")
+                .map(|v| matches!(v.trim(), "1" | "true" | "on"))
+                .unwrap_or(false),
+            keyphrases: KeyphraseScorer::new(KeyphraseConfig::from_env()),
+            entity_typer: EntityTyper::new(),
+            entity_type_llm: std::env::var("ENTITY_TYPE_LLM")
+                .map(|v| matches!(v.trim(), "1" | "true" | "on"))
+                .unwrap_or(false),
+            values: ValueExtractor::new(),
+            sentences: SentenceSegmenter::new(),
+            cooccurrence: CooccurrenceConfig::from_env(),
         })
     }
 
@@ -143,27 +449,72 @@ impl NluPipeline {
         &self.extractor
     }
 
+    /// Acessor público para o segmentador de sentenças.
+    pub fn sentences(&self) -> &SentenceSegmenter {
+        &self.sentences
+    }
+
+    /// Acessor público para o extrator de valores.
+    pub fn values(&self) -> &ValueExtractor {
+        &self.values
+    }
+
+    /// Acessor público para o scorer de keyphrases.
+    pub fn keyphrases(&self) -> &KeyphraseScorer {
+        &self.keyphrases
+    }
+
     /// Gera embeddings em batch via LM Studio.
     pub async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
         self.embedder.embed_batch(texts).await
     }
 
+    /// Gera embeddings em lotes, com concorrência limitada e novas tentativas.
+    pub async fn embed_batches(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        self.embedder.embed_batches(texts).await
+    }
+
+    /// Configuração do embedder (tamanho de lote, concorrência).
+    pub fn embedder_config(&self) -> &EmbedderConfig {
+        self.embedder.config()
+    }
+
     /// Processa uma mensagem do usuário, atualizando a KB.
     ///
     /// ## Passos
     ///
     /// 1. **NFC Normalize** — normaliza Unicode para forma canônica
-    /// 2. **Classificar intent** — Confirming, Denying, Querying, ou Narrating
-    /// 3. **Extrair entidades** — identifica conceitos candidatos no texto
-    /// 4. **Embeddar em batch** — gera vetores via LM Studio
-    /// 5. **Atualizar KB** — cria/reforça conceitos e cria links
-    pub async fn process_message(&self, text: &str, kb: &Arc<RwLock<KnowledgeBase>>) -> Result<NluResult> {
+    /// 2. **Classificar intent** — um dos [`Intent`] (Narrating, Querying...)
+    /// 3. **Resolver correferências** — pronomes e descrições definidas viram
+    ///    o label do antecedente (`focus` = conceitos do turno anterior)
+    /// 4. **Extrair entidades** — identifica conceitos candidatos no texto
+    /// 5. **Extrair valores** — números e datas saem das entidades e viram
+    ///    atributos do conceito mais próximo
+    /// 6. **Pontuar keyphrases** — mantém só os `KEYPHRASE_TOP_N` melhores
+    /// 7. **Embeddar em batch** — gera vetores via LM Studio
+    /// 8. **Atualizar KB** — cria/reforça conceitos e cria links
+    pub async fn process_message(
+        &self,
+        text: &str,
+        kb: &Arc<RwLock<KnowledgeBase>>,
+        focus: &[ConceptId],
+    ) -> Result<NluResult> {
         let text: String = text.nfc().collect();
+        let language = language::detect(&text);
 
-        let intent = self.intent_classifier.classify(&text, &self.embedder).await?;
-        tracing::debug!(intent = ?intent, "Intent classificado");
+        let intent = self
+            .intent_classifier
+            .classify(&text, language, &self.embedder)
+            .await?
+            .intent;
+        tracing::debug!(intent = ?intent, language = language.code(), "Intent classificado");
 
-        let entities = self.extractor.extract(&text);
+        let (text, coreferences) = self.resolve_coreferences(&text, language, focus, kb).await;
+        let mut entities = self.extractor.extract_in(&text, language);
+        let aliases = self.extractor.extract_aliases(&text);
+        extractor::merge_aliases(&mut entities, &aliases);
+        let values = self.values.extract(&text);
+        quantity::strip_value_entities(&mut entities, &values);
 
         if entities.is_empty() {
             tracing::debug!("Nenhuma entidade extraída");
@@ -175,33 +526,199 @@ impl NluPipeline {
                 messages: Vec::new(),
                 concept_details: Vec::new(),
                 link_details: Vec::new(),
+                entity_concepts: Vec::new(),
+                coreferences,
+                language,
             });
         }
 
         tracing::info!(count = entities.len(), entities = ?entities, "Entidades extraídas");
 
-        let embed_texts: Vec<String> = entities
-            .iter()
-            .map(|e| format!("search_document: {}", e))
-            .collect();
-        let embeddings = self.embedder.embed_batch(&embed_texts).await?;
+        let (entities, scores, embeddings) =
+            self.select_keyphrases(&text, entities, language).await?;
 
-        let mut result = self.apply_entities_to_kb(&entities, &embeddings, kb);
+        let citation = Citation::from(Source::Conversation);
+        let passage = Passage {
+            text: &text,
+            language,
+            citation: &citation,
+            overlap: 0,
+        };
+        let mut result = self.apply_entities_to_kb(&entities, &scores, &embeddings, kb, &passage);
         result.intent = intent;
+        result.coreferences = coreferences;
+        result.messages.extend(register_aliases(&aliases, kb));
+        let attributes = quantity::attach(&text, &values, &entities);
+        let messages =
+            register_attributes(&attributes, &result.entity_concepts, &citation.source, kb);
+        result.messages.extend(messages);
+        if self.entity_type_llm {
+            self.refine_entity_types(&text, language, &citation, &mut result, kb)
+                .await;
+        }
 
         Ok(result)
     }
 
+    /// Pede ao LLM o tipo dos conceitos novos com cara de nome próprio que
+    /// as heurísticas deixaram como [`EntityType::Notion`].
+    ///
+    /// Falhas do LLM mantêm `Notion`.
+    async fn refine_entity_types(
+        &self,
+        text: &str,
+        language: Language,
+        citation: &Citation,
+        result: &mut NluResult,
+        kb: &Arc<RwLock<KnowledgeBase>>,
+    ) {
+        let type_names: Vec<&str> = EntityType::ALL.iter().map(|t| t.label()).collect();
+        for i in 0..result.concept_details.len() {
+            let info = &result.concept_details[i];
+            let untyped = info.entity_type.as_deref() == Some(EntityType::Notion.css_class());
+            if !info.is_new || !untyped || !entity_type::is_capitalized(&info.label) {
+                continue;
+            }
+            let vars = PromptVars::new(text)
+                .with("reference", info.label.clone())
+                .with("concepts", type_names.join("\n"))
+                .with("language", language.prompt_name());
+            let entity_type = match self.chat(PromptTask::EntityType, &vars, &[]).await {
+                Ok(answer) => entity_type::parse_answer(&answer),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Falha na tipagem via LLM, mantendo Noção");
+                    None
+                }
+            };
+            let Some(entity_type) = entity_type.filter(|t| *t != EntityType::Notion) else {
+                continue;
+            };
+            let Ok(instance_id) = info.id.parse::<ConceptId>() else {
+                continue;
+            };
+            let typed = link_instance_to_type(&mut kb.write(), instance_id, entity_type, citation);
+            result.concept_details[i].entity_type = Some(entity_type.css_class().to_string());
+            result.absorb_type_link(typed);
+        }
+    }
+
+    /// Pontua os candidatos, mantém os `top_n` e devolve seus embeddings.
+    ///
+    /// Com centralidade todos os candidatos são embeddados antes do corte;
+    /// com RAKE/TF-IDF só os selecionados.
+    async fn select_keyphrases(
+        &self,
+        text: &str,
+        entities: Vec<String>,
+        language: Language,
+    ) -> Result<(Vec<String>, Vec<f32>, Vec<Vec<f32>>)> {
+        let embed = |entities: &[String]| {
+            let texts: Vec<String> = entities
+                .iter()
+                .map(|e| format!("search_document: {}", e))
+                .collect();
+            async move { self.embedder.embed_batch(&texts).await }
+        };
+
+        let all_embeddings = if self.keyphrases.needs_embeddings() {
+            Some(embed(&entities).await?)
+        } else {
+            None
+        };
+        let scores = self
+            .keyphrases
+            .score(text, &entities, language, all_embeddings.as_deref());
+        let kept = self.keyphrases.select(&scores);
+        if kept.len() < entities.len() {
+            tracing::info!(
+                kept = kept.len(),
+                total = entities.len(),
+                "Keyphrases selecionadas"
+            );
+        }
+
+        let selected: Vec<String> = kept.iter().map(|&i| entities[i].clone()).collect();
+        let selected_scores: Vec<f32> = kept.iter().map(|&i| scores[i]).collect();
+        let embeddings = match all_embeddings {
+            Some(all) => kept.iter().map(|&i| all[i].clone()).collect(),
+            None => embed(&selected).await?,
+        };
+        Ok((selected, selected_scores, embeddings))
+    }
+
+    /// Resolve pronomes e descrições definidas de `text` contra os
+    /// conceitos em `focus` e menções anteriores da própria mensagem.
+    /// Mensagens fora do português passam intactas.
+    ///
+    /// Retorna o texto reescrito e as descrições das resoluções. Com
+    /// `COREF_LLM_ASSIST=1`, referências ambíguas são decididas pelo LLM
+    /// (falhas mantêm a escolha por recência).
+    async fn resolve_coreferences(
+        &self,
+        text: &str,
+        language: Language,
+        focus: &[ConceptId],
+        kb: &Arc<RwLock<KnowledgeBase>>,
+    ) -> (String, Vec<String>) {
+        let prior: Vec<Antecedent> = {
+            let kb = kb.read();
+            focus
+                .iter()
+                .filter_map(|id| kb.concepts.get(id))
+                .map(|c| Antecedent::from_label(&c.label))
+                .collect()
+        };
+        let mut references = coref::find_references(text, &prior, language);
+        if references.is_empty() {
+            return (text.to_string(), Vec::new());
+        }
+
+        if self.coref_llm_assist {
+            for reference in references.iter_mut().filter(|r| r.is_ambiguous()) {
+                let vars = PromptVars::new(text)
+                    .with("reference", reference.surface.clone())
+                    .with("concepts", reference.candidates.join("\n"));
+                match self.chat(PromptTask::Coreference, &vars, &[]).await {
+                    Ok(answer) => {
+                        let answer = answer.trim().to_lowercase();
+                        if let Some(choice) = reference
+                            .candidates
+                            .iter()
+                            .find(|c| answer.contains(&c.to_lowercase()))
+                        {
+                            reference.antecedent = choice.clone();
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Falha na correferência via LLM, usando recência")
+                    }
+                }
+            }
+        }
+
+        let descriptions: Vec<String> = references.iter().map(|r| r.describe()).collect();
+        tracing::info!(references = ?descriptions, "Correferências resolvidas");
+        (coref::apply_references(text, &references), descriptions)
+    }
+
     /// Aplica entidades pré-extraídas e seus embeddings à KB.
     ///
     /// Este método é separado de `process_message` para permitir reuso
-    /// pelo módulo de PDF.
+    /// pelo módulo de PDF. `scores` traz o score de keyphrase de cada
+    /// entidade (pode ser vazio). Conceitos novos recebem o código do idioma
+    /// do trecho; a fusão por radical só vale para pt-BR (o RSLP é um
+    /// stemmer do português). Os links `Implication` vêm das janelas de
+    /// sentenças do trecho ([`cooccurrence`]). Menções e links registram a
+    /// citação (fonte e página) na proveniência.
     pub fn apply_entities_to_kb(
         &self,
         entities: &[String],
+        scores: &[f32],
         embeddings: &[Vec<f32>],
         kb: &Arc<RwLock<KnowledgeBase>>,
+        passage: &Passage,
     ) -> NluResult {
+        let (language, citation) = (passage.language, passage.citation);
         let mut new_concepts = Vec::new();
         let mut reinforced_concepts = Vec::new();
         let mut new_links = Vec::new();
@@ -210,13 +727,51 @@ impl NluPipeline {
         let mut link_details = Vec::new();
 
         let mut entity_concept_ids = Vec::new();
+        let mut typed_concepts: Vec<(ConceptId, EntityType)> = Vec::new();
         let mut new_concept_ids_and_embeddings: Vec<(uuid::Uuid, Vec<f32>)> = Vec::new();
 
         // ─── Fase 1: Para cada entidade, encontrar ou criar conceito ───
-        for (entity, embedding) in entities.iter().zip(embeddings.iter()) {
+        for (i, (entity, embedding)) in entities.iter().zip(embeddings.iter()).enumerate() {
+            let score = scores.get(i).copied();
             let mut kb_write = kb.write();
+            let key = match language {
+                Language::Portuguese => stemmer::canonical_key(entity),
+                Language::English => String::new(),
+            };
+            // Alias conhecido ("IA") ou variante morfológica (mesmo radical RSLP)
+            let direct_match = match kb_write.find_concept_by_alias(entity) {
+                Some(concept) => Some((concept.id, "alias")),
+                None if stemmer::is_reliable_key(&key) => kb_write
+                    .find_concept_by_key(&key)
+                    .map(|c| (c.id, "radical")),
+                None => None,
+            };
 
-            if let Some((existing_id, similarity)) = kb_write.find_similar_concept(embedding, 0.90)
+            if let Some((existing_id, how)) = direct_match {
+                // Casamento direto — sem consultar embeddings
+                if let Some(concept) = kb_write.concepts.get_mut(&existing_id) {
+                    concept.reinforce();
+                    tracing::info!(label = %concept.label, entity = %entity, key = %key, how, "Conceito reforçado (direto)");
+                    reinforced_concepts.push(format!(
+                        "{} ({}) → energia {:.2}",
+                        concept.label, how, concept.energy
+                    ));
+                    concept_details.push(NluConceptInfo {
+                        id: existing_id.to_string(),
+                        label: concept.label.clone(),
+                        is_new: false,
+                        similarity: None,
+                        energy: concept.energy,
+                        frequency: concept.truth.frequency(),
+                        confidence: concept.truth.confidence(),
+                        state: concept.state.css_class().to_string(),
+                        score,
+                        entity_type: concept.entity_type.map(|t| t.css_class().to_string()),
+                    });
+                    entity_concept_ids.push(existing_id);
+                }
+            } else if let Some((existing_id, similarity)) =
+                kb_write.find_similar_concept(embedding, 0.90)
             {
                 if let Some(concept) = kb_write.concepts.get_mut(&existing_id) {
                     concept.reinforce();
@@ -234,6 +789,8 @@ impl NluPipeline {
                         frequency: concept.truth.frequency(),
                         confidence: concept.truth.confidence(),
                         state: concept.state.css_class().to_string(),
+                        score,
+                        entity_type: concept.entity_type.map(|t| t.css_class().to_string()),
                     });
                     entity_concept_ids.push(existing_id);
                 }
@@ -251,6 +808,8 @@ impl NluPipeline {
                         frequency: concept.truth.frequency(),
                         confidence: concept.truth.confidence(),
                         state: concept.state.css_class().to_string(),
+                        score,
+                        entity_type: concept.entity_type.map(|t| t.css_class().to_string()),
                     });
                     entity_concept_ids.push(existing);
                 }
@@ -258,6 +817,10 @@ impl NluPipeline {
                 tracing::info!(label = %entity, "Novo conceito criado");
                 let mut concept = Concept::new(entity.clone(), TruthValue::proto());
                 concept.embedding = Some(embedding.clone());
+                concept.key = key;
+                concept.language = Some(language.code().to_string());
+                let entity_type = self.entity_typer.classify(entity);
+                concept.entity_type = Some(entity_type);
                 let id = concept.id;
                 messages.push(format!(
                     "Cristalizando... Novo Concept: {} {}",
@@ -273,67 +836,119 @@ impl NluPipeline {
                     frequency: concept.truth.frequency(),
                     confidence: concept.truth.confidence(),
                     state: concept.state.css_class().to_string(),
+                    score,
+                    entity_type: Some(entity_type.css_class().to_string()),
                 });
                 new_concepts.push(entity.clone());
                 kb_write.add_concept(concept);
                 entity_concept_ids.push(id);
+                typed_concepts.push((id, entity_type));
                 new_concept_ids_and_embeddings.push((id, embedding.clone()));
             }
         }
 
-        // ─── Fase 2: Criar links de Implication entre entidades ───
-        if entity_concept_ids.len() >= 2 {
+        // Cada menção conta como evidência da fonte (conversa ou documento)
+        {
             let mut kb_write = kb.write();
-            let subject_id = entity_concept_ids[0];
-            for &other_id in &entity_concept_ids[1..] {
-                if !kb_write.link_exists(&LinkKind::Implication, subject_id, other_id) {
-                    let truth = TruthValue::proto();
-                    let truth_freq = truth.frequency();
-                    let truth_conf = truth.confidence();
-                    let link = Link::new(
-                        LinkKind::Implication,
-                        vec![
-                            Participant {
-                                concept_id: subject_id,
-                                role: Role::Subject,
-                            },
-                            Participant {
-                                concept_id: other_id,
-                                role: Role::Object,
-                            },
-                        ],
-                        truth,
-                    );
-                    let link_id = link.id.to_string();
-                    let link_energy = link.energy;
-                    let desc = kb_write.describe_link(&link);
-
-                    let source_label = kb_write
-                        .concepts
-                        .get(&subject_id)
-                        .map(|c| c.label.clone())
-                        .unwrap_or_default();
-                    let target_label = kb_write
-                        .concepts
-                        .get(&other_id)
-                        .map(|c| c.label.clone())
-                        .unwrap_or_default();
+            for id in &entity_concept_ids {
+                if let Some(concept) = kb_write.concepts.get_mut(id) {
+                    concept.provenance.cite(citation);
+                }
+            }
+        }
 
-                    kb_write.add_link(link);
-                    tracing::info!(link = %desc, "Novo link criado");
-                    link_details.push(NluLinkInfo {
-                        id: link_id,
-                        source_id: subject_id.to_string(),
-                        source_label,
-                        target_id: other_id.to_string(),
-                        target_label,
-                        kind: "Implication".to_string(),
-                        frequency: truth_freq,
-                        confidence: truth_conf,
-                        energy: link_energy,
-                    });
-                    new_links.push(desc);
+        // ─── Fase 2: Links de Implication por coocorrência em sentenças ───
+        let windows: Vec<Vec<ConceptId>> = cooccurrence::windows(
+            passage.text,
+            &entities[..entity_concept_ids.len()],
+            &self.sentences,
+            self.cooccurrence.window,
+            passage.overlap,
+        )
+        .into_iter()
+        .map(|window| window.into_iter().map(|i| entity_concept_ids[i]).collect())
+        .collect();
+        {
+            let mut kb_write = kb.write();
+            for window in &windows {
+                kb_write.cooccurrence.observe(window, &citation.source);
+            }
+            for (a, b, count) in cooccurrence::pairs(&windows) {
+                let observation =
+                    self.cooccurrence
+                        .measure
+                        .observation(&kb_write.cooccurrence, a, b, count);
+                let existing = if kb_write.link_exists(&LinkKind::Implication, a, b) {
+                    Some((a, b))
+                } else if kb_write.link_exists(&LinkKind::Implication, b, a) {
+                    Some((b, a))
+                } else {
+                    None
+                };
+                if let Some((subject_id, other_id)) = existing {
+                    if let Some(link) =
+                        kb_write.find_link_mut(&LinkKind::Implication, subject_id, other_id)
+                    {
+                        link.truth = link.truth.revision(&observation);
+                        link.provenance.cite_truth(citation, &observation);
+                        tracing::debug!(link = %link.id, truth = %link.truth, "Link revisado por coocorrência");
+                    }
+                    continue;
                 }
+
+                // O conceito mais raro implica o mais frequente
+                let (subject_id, other_id) =
+                    if kb_write.cooccurrence.count(b) < kb_write.cooccurrence.count(a) {
+                        (b, a)
+                    } else {
+                        (a, b)
+                    };
+                let truth_freq = observation.frequency();
+                let truth_conf = observation.confidence();
+                let mut link = Link::new(
+                    LinkKind::Implication,
+                    vec![
+                        Participant {
+                            concept_id: subject_id,
+                            role: Role::Subject,
+                        },
+                        Participant {
+                            concept_id: other_id,
+                            role: Role::Object,
+                        },
+                    ],
+                    observation.clone(),
+                );
+                link.provenance.cite_truth(citation, &observation);
+                let link_id = link.id.to_string();
+                let link_energy = link.energy;
+                let desc = kb_write.describe_link(&link);
+
+                let source_label = kb_write
+                    .concepts
+                    .get(&subject_id)
+                    .map(|c| c.label.clone())
+                    .unwrap_or_default();
+                let target_label = kb_write
+                    .concepts
+                    .get(&other_id)
+                    .map(|c| c.label.clone())
+                    .unwrap_or_default();
+
+                kb_write.add_link(link);
+                tracing::info!(link = %desc, "Novo link criado");
+                link_details.push(NluLinkInfo {
+                    id: link_id,
+                    source_id: subject_id.to_string(),
+                    source_label,
+                    target_id: other_id.to_string(),
+                    target_label,
+                    kind: "Implication".to_string(),
+                    frequency: truth_freq,
+                    confidence: truth_conf,
+                    energy: link_energy,
+                });
+                new_links.push(desc);
             }
         }
 
@@ -349,18 +964,32 @@ impl NluPipeline {
                         }
                         if let Some(ref existing_emb) = existing_concept.embedding {
                             let sim = cosine_similarity(new_emb, existing_emb);
-                            if sim > 0.78 && sim < 0.90 {
-                                if !kb_read.link_exists(&LinkKind::Similarity, *new_id, *existing_id)
-                                    && !kb_read.link_exists(&LinkKind::Similarity, *existing_id, *new_id)
-                                {
-                                    let new_label = kb_read
-                                        .concepts
-                                        .get(new_id)
-                                        .map(|c| c.label.clone())
-                                        .unwrap_or_default();
-                                    let existing_label = existing_concept.label.clone();
-                                    sim_candidates.push((*new_id, *existing_id, sim, new_label, existing_label));
-                                }
+                            if sim > 0.78
+                                && sim < 0.90
+                                && !kb_read.link_exists(
+                                    &LinkKind::Similarity,
+                                    *new_id,
+                                    *existing_id,
+                                )
+                                && !kb_read.link_exists(
+                                    &LinkKind::Similarity,
+                                    *existing_id,
+                                    *new_id,
+                                )
+                            {
+                                let new_label = kb_read
+                                    .concepts
+                                    .get(new_id)
+                                    .map(|c| c.label.clone())
+                                    .unwrap_or_default();
+                                let existing_label = existing_concept.label.clone();
+                                sim_candidates.push((
+                                    *new_id,
+                                    *existing_id,
+                                    sim,
+                                    new_label,
+                                    existing_label,
+                                ));
                             }
                         }
                     }
@@ -372,7 +1001,7 @@ impl NluPipeline {
                     let truth = TruthValue::new(sim as f64, 0.6);
                     let truth_freq = truth.frequency();
                     let truth_conf = truth.confidence();
-                    let link = Link::new(
+                    let mut link = Link::new(
                         LinkKind::Similarity,
                         vec![
                             Participant {
@@ -388,6 +1017,7 @@ impl NluPipeline {
                     );
                     let link_id = link.id.to_string();
                     let link_energy = link.energy;
+                    link.provenance.cite(citation);
                     let desc = format!("{} ≈ {} (sim={:.2})", new_label, existing_label, sim);
 
                     kb_write.add_link(link);
@@ -408,7 +1038,7 @@ impl NluPipeline {
             }
         }
 
-        NluResult {
+        let mut result = NluResult {
             intent: Intent::Narrating,
             new_concepts,
             reinforced_concepts,
@@ -416,7 +1046,18 @@ impl NluPipeline {
             messages,
             concept_details,
             link_details,
+            entity_concepts: entity_concept_ids,
+            coreferences: Vec::new(),
+            language,
+        };
+
+        // ─── Fase 4: Ligar conceitos tipados ao conceito-tipo ───
+        for (id, entity_type) in typed_concepts {
+            let typed = link_instance_to_type(&mut kb.write(), id, entity_type, citation);
+            result.absorb_type_link(typed);
         }
+
+        result
     }
 
     /// Gera embedding para busca por similaridade (modo query).
@@ -424,13 +1065,142 @@ impl NluPipeline {
         self.embedder.embed(&format!("search_query: {}", text)).await
     }
 
-    /// Classifica a intenção de um texto.
-    pub async fn classify_intent(&self, text: &str) -> Result<Intent> {
-        self.intent_classifier.classify(text, &self.embedder).await
+    /// Classifica a intenção de um texto (idioma detectado automaticamente).
+    pub async fn classify_intent(&self, text: &str) -> Result<IntentClassification> {
+        self.intent_classifier
+            .classify(text, language::detect(text), &self.embedder)
+            .await
+    }
+
+    /// Registra `text` como template de `intent` (feedback do usuário).
+    pub async fn learn_intent(&self, text: &str, intent: Intent) -> Result<()> {
+        let text: String = text.nfc().collect();
+        self.intent_classifier
+            .learn(&text, intent, language::detect(&text), &self.embedder)
+            .await
+    }
+
+    /// Template de prompt atual de uma tarefa (com hot-reload).
+    pub fn prompt(&self, task: PromptTask) -> PromptTemplate {
+        self.prompts.get(task)
+    }
+
+    /// Renderiza o template da tarefa com `vars` e o histórico da
+    /// conversa, envia ao LLM via LM Studio e retorna a resposta.
+    pub async fn chat(
+        &self,
+        task: PromptTask,
+        vars: &PromptVars,
+        history: &[ChatMsg],
+    ) -> Result<String> {
+        let template = self.prompts.get(task);
+        let messages = template.render_with_history(vars, history);
+        self.embedder.chat(&messages, &template.params).await
+    }
+
+    /// Envia um histórico de mensagens com ferramentas disponíveis
+    /// (function calling) e retorna a mensagem do assistente.
+    ///
+    /// Usado pelo modo agente (veja [`crate::agent`]).
+    pub async fn chat_with_tools(
+        &self,
+        messages: &[ChatMsg],
+        tools: &[serde_json::Value],
+        params: &ChatParams,
+    ) -> Result<ChatMsg> {
+        self.embedder.chat_with_tools(messages, tools, params).await
     }
 
-    /// Envia mensagem para o LLM via LM Studio e retorna a resposta.
-    pub async fn chat(&self, system_prompt: &str, user_message: &str) -> Result<String> {
-        self.embedder.chat(system_prompt, user_message).await
+    /// Verifica uma resposta do LLM contra a KB (detecção de alucinação).
+    ///
+    /// Extrai entidades de cada afirmação da resposta, embedda todas em
+    /// batch e casa cada uma com o conceito mais similar da KB
+    /// (threshold [`GROUNDING_THRESHOLD`]). A KB **não é modificada** —
+    /// entidades inventadas pelo LLM não viram conceitos.
+    ///
+    /// Veja [`grounding`] para o cálculo da confiança da resposta.
+    pub async fn verify_answer(
+        &self,
+        answer: &str,
+        kb: &Arc<RwLock<KnowledgeBase>>,
+    ) -> Result<GroundingReport> {
+        let claim_entities: Vec<(String, Vec<String>)> = grounding::split_claims(answer)
+            .into_iter()
+            .map(|claim| {
+                let entities = self.extractor.extract(&claim);
+                (claim, entities)
+            })
+            .collect();
+
+        let embed_texts: Vec<String> = claim_entities
+            .iter()
+            .flat_map(|(_, entities)| entities.iter().map(|e| format!("search_query: {}", e)))
+            .collect();
+        if embed_texts.is_empty() {
+            return Ok(GroundingReport {
+                claims: claim_entities
+                    .into_iter()
+                    .map(|(text, _)| Claim {
+                        text,
+                        supported: Vec::new(),
+                        unsupported: Vec::new(),
+                    })
+                    .collect(),
+                ..Default::default()
+            });
+        }
+        let embeddings = self.embedder.embed_batch(&embed_texts).await?;
+
+        let mut report = GroundingReport::default();
+        let mut seen_unsupported = std::collections::HashSet::new();
+        let mut embedding_iter = embeddings.iter();
+        {
+            let kb_read = kb.read();
+            for (text, entities) in claim_entities {
+                let mut claim = Claim {
+                    text,
+                    supported: Vec::new(),
+                    unsupported: Vec::new(),
+                };
+                for entity in entities {
+                    let Some(embedding) = embedding_iter.next() else {
+                        break;
+                    };
+                    let matched = kb_read
+                        .find_similar_concept(embedding, GROUNDING_THRESHOLD)
+                        .and_then(|(id, sim)| kb_read.concepts.get(&id).map(|c| (c, sim)));
+                    match matched {
+                        Some((concept, similarity)) => {
+                            if !report.supported.iter().any(|s| s.concept_id == concept.id) {
+                                report.supported.push(SupportedEntity {
+                                    entity: entity.clone(),
+                                    concept_id: concept.id,
+                                    concept_label: concept.label.clone(),
+                                    similarity,
+                                    expectation: concept.truth.expectation(),
+                                });
+                            }
+                            claim.supported.push(entity);
+                        }
+                        None => {
+                            if seen_unsupported.insert(entity.to_lowercase()) {
+                                report.unsupported.push(entity.clone());
+                            }
+                            claim.unsupported.push(entity);
+                        }
+                    }
+                }
+                report.claims.push(claim);
+            }
+        }
+
+        report.confidence = grounding::answer_confidence(&report.supported, report.entity_count());
+        tracing::info!(
+            confidence = %format!("{:.2}", report.confidence),
+            supported = report.supported.len(),
+            unsupported = report.unsupported.len(),
+            "Resposta do LLM verificada contra a KB"
+        );
+        Ok(report)
     }
 }
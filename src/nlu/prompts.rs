@@ -0,0 +1,432 @@
+//! # Templates de Prompt — Prompts e Parâmetros de Chat Configuráveis
+//!
+//! Os prompts enviados ao LLM ficam em arquivos `.prompt` no diretório
+//! `prompts/` (ou `PROMPTS_DIR`), um por tarefa ([`PromptTask`]). Cada
+//! arquivo define os parâmetros de chat da tarefa e os textos das roles
+//! `system` e `user`, com variáveis entre chaves.
+//!
+//! ## Formato
+//!
+//! ```text
+//! # Comentários começam com '#'
+//! model = qwen2.5-7b-instruct     ← opcional (padrão: LM_STUDIO_CHAT_MODEL)
+//! temperature = 0.7
+//! max_tokens = 512
+//!
+//! [system]
+//! Você é um assistente... conceitos: [{concepts}]. Responda em {language}.
+//!
+//! [user]
+//! {message}
+//! ```
+//!
+//! ## Variáveis
+//!
+//! | Variável | Conteúdo |
+//! |----------|----------|
+//! | `{message}` | Mensagem atual do usuário |
+//! | `{concepts}` | Conceitos relevantes da KB |
+//! | `{links}` | Links relevantes da KB |
+//! | `{history}` | Resumo corrente da conversa (`question`, `summary`) |
+//! | `{language}` | Idioma da resposta (padrão: português brasileiro) |
+//!
+//! Narração, consulta e agente recebem o resumo uma única vez, como
+//! mensagem `system` do histórico ([`PromptTemplate::render_with_history`]).
+//!
+//! Templates específicos podem usar variáveis próprias (ex: `{reference}`
+//! em `coref.prompt`). Variáveis da tabela sem valor são substituídas por
+//! string vazia.
+//!
+//! ## Hot-reload
+//!
+//! O [`PromptStore`] verifica o `mtime` do arquivo a cada uso: editar um
+//! `.prompt` vale na próxima mensagem, sem reiniciar o servidor. Se o
+//! arquivo não existir ou for inválido, vale o template embutido no
+//! binário (cópia dos arquivos de `prompts/` no momento da compilação).
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{bail, Context, Result};
+use parking_lot::RwLock;
+
+use super::embedder::{ChatMsg, ChatParams};
+
+/// Tarefa de chat com template próprio.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PromptTask {
+    /// Resposta curta a uma narração do usuário.
+    Narration,
+    /// Resposta a uma pergunta (modo clássico, conceitos no prompt).
+    Query,
+    /// Prompt de sistema do modo agente com ferramentas.
+    Agent,
+    /// Atualização do resumo corrente da conversa.
+    Summary,
+    /// Desambiguação de correferência (pronome com vários antecedentes).
+    Coreference,
+    /// Tipagem de entidade nomeada que as heurísticas não resolveram.
+    EntityType,
+    /// Pergunta reflexiva sobre uma lacuna da KB (germinação).
+    Question,
+}
+
+impl PromptTask {
+    /// Nome do arquivo do template dentro do diretório de prompts.
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            PromptTask::Narration => "narration.prompt",
+            PromptTask::Query => "query.prompt",
+            PromptTask::Agent => "agent.prompt",
+            PromptTask::Summary => "summary.prompt",
+            PromptTask::Coreference => "coref.prompt",
+            PromptTask::EntityType => "entity_type.prompt",
+            PromptTask::Question => "question.prompt",
+        }
+    }
+
+    /// Template embutido no binário, usado quando o arquivo não existe.
+    fn builtin(&self) -> &'static str {
+        match self {
+            PromptTask::Narration => include_str!("../../prompts/narration.prompt"),
+            PromptTask::Query => include_str!("../../prompts/query.prompt"),
+            PromptTask::Agent => include_str!("../../prompts/agent.prompt"),
+            PromptTask::Summary => include_str!("../../prompts/summary.prompt"),
+            PromptTask::Coreference => include_str!("../../prompts/coref.prompt"),
+            PromptTask::EntityType => include_str!("../../prompts/entity_type.prompt"),
+            PromptTask::Question => include_str!("../../prompts/question.prompt"),
+        }
+    }
+}
+
+/// Template de prompt de uma tarefa: parâmetros de chat + textos das roles.
+#[derive(Clone, Debug)]
+pub struct PromptTemplate {
+    /// Parâmetros da chamada (modelo, temperatura, max_tokens).
+    pub params: ChatParams,
+    /// Texto da role `system` (com variáveis).
+    pub system: String,
+    /// Texto da role `user` (com variáveis).
+    pub user: String,
+}
+
+impl PromptTemplate {
+    /// Interpreta o conteúdo de um arquivo `.prompt`.
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut params = ChatParams::default();
+        let mut system: Option<String> = None;
+        let mut user: Option<String> = None;
+        // Seção corrente: None = cabeçalho de parâmetros
+        let mut section: Option<&mut Option<String>> = None;
+
+        for (number, line) in source.lines().enumerate() {
+            match line.trim() {
+                "[system]" => {
+                    section = Some(&mut system);
+                    continue;
+                }
+                "[user]" => {
+                    section = Some(&mut user);
+                    continue;
+                }
+                _ => {}
+            }
+            if let Some(text) = section.as_deref_mut() {
+                let text = text.get_or_insert_with(String::new);
+                text.push_str(line);
+                text.push('\n');
+                continue;
+            }
+
+            let header = line.trim();
+            if header.is_empty() || header.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = header.split_once('=') else {
+                bail!("linha {}: esperado 'chave = valor'", number + 1);
+            };
+            let value = value.trim();
+            match key.trim() {
+                "model" => params.model = Some(value.to_string()),
+                "temperature" => {
+                    params.temperature = value
+                        .parse()
+                        .with_context(|| format!("linha {}: temperature inválida", number + 1))?
+                }
+                "max_tokens" => {
+                    params.max_tokens = value
+                        .parse()
+                        .with_context(|| format!("linha {}: max_tokens inválido", number + 1))?
+                }
+                other => bail!("linha {}: parâmetro desconhecido '{}'", number + 1, other),
+            }
+        }
+
+        Ok(Self {
+            params,
+            system: system.context("seção [system] ausente")?.trim().to_string(),
+            user: user
+                .unwrap_or_else(|| "{message}".into())
+                .trim()
+                .to_string(),
+        })
+    }
+
+    /// Renderiza o template em mensagens `system` + `user`.
+    pub fn render(&self, vars: &PromptVars) -> Vec<ChatMsg> {
+        self.render_with_history(vars, &[])
+    }
+
+    /// Renderiza o template intercalando o histórico da conversa:
+    /// `system`, histórico (turnos anteriores), `user`.
+    pub fn render_with_history(&self, vars: &PromptVars, history: &[ChatMsg]) -> Vec<ChatMsg> {
+        let mut messages = Vec::with_capacity(history.len() + 2);
+        messages.push(ChatMsg::system(vars.apply(&self.system)));
+        messages.extend(history.iter().cloned());
+        messages.push(ChatMsg::user(vars.apply(&self.user)));
+        messages
+    }
+}
+
+/// Valores das variáveis de um template.
+#[derive(Clone, Debug)]
+pub struct PromptVars {
+    values: BTreeMap<&'static str, String>,
+}
+
+impl PromptVars {
+    /// Cria variáveis para a mensagem do usuário, com idioma padrão PT-BR.
+    pub fn new(message: impl Into<String>) -> Self {
+        let mut values = BTreeMap::new();
+        values.insert("message", message.into());
+        values.insert("language", "português brasileiro".to_string());
+        Self { values }
+    }
+
+    /// Define (ou substitui) uma variável.
+    pub fn with(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.values.insert(name, value.into());
+        self
+    }
+
+    /// Substitui `{nome}` pelos valores numa única passada da esquerda para
+    /// a direita; variáveis conhecidas sem valor viram "". Os valores são
+    /// copiados literalmente (chaves dentro deles não são substituídas) e
+    /// `{...}` desconhecido fica como está.
+    fn apply(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(open) = rest.find('{') {
+            out.push_str(&rest[..open]);
+            let after = &rest[open + 1..];
+            let name = after
+                .find('}')
+                .map(|close| &after[..close])
+                .filter(|name| self.values.contains_key(name) || VARIABLES.contains(name));
+            match name {
+                Some(name) => {
+                    out.push_str(self.values.get(name).map_or("", String::as_str));
+                    rest = &after[name.len() + 1..];
+                }
+                None => {
+                    out.push('{');
+                    rest = after;
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+/// Variáveis conhecidas dos templates.
+const VARIABLES: [&str; 5] = ["message", "concepts", "links", "history", "language"];
+
+/// Entrada do cache: template + `mtime` do arquivo de origem.
+struct CachedTemplate {
+    template: PromptTemplate,
+    modified: Option<SystemTime>,
+}
+
+/// Repositório de templates com hot-reload por `mtime`.
+pub struct PromptStore {
+    /// Diretório dos arquivos `.prompt`.
+    dir: PathBuf,
+    /// Templates já carregados, por tarefa.
+    cache: RwLock<HashMap<PromptTask, CachedTemplate>>,
+}
+
+impl PromptStore {
+    /// Cria um repositório lendo templates de `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Cria um repositório a partir de `PROMPTS_DIR` (padrão: `prompts`).
+    pub fn from_env() -> Self {
+        let dir = std::env::var("PROMPTS_DIR").unwrap_or_else(|_| "prompts".to_string());
+        tracing::info!(dir = %dir, "Templates de prompt");
+        Self::new(dir)
+    }
+
+    /// Diretório dos templates.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Retorna o template da tarefa, recarregando o arquivo se mudou.
+    pub fn get(&self, task: PromptTask) -> PromptTemplate {
+        let path = self.dir.join(task.file_name());
+        let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+
+        if let Some(cached) = self.cache.read().get(&task) {
+            if cached.modified == modified {
+                return cached.template.clone();
+            }
+        }
+
+        let template = match modified {
+            Some(_) => match std::fs::read_to_string(&path)
+                .context("falha ao ler arquivo")
+                .and_then(|source| PromptTemplate::parse(&source))
+            {
+                Ok(template) => {
+                    tracing::info!(path = %path.display(), "Template de prompt carregado");
+                    template
+                }
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "Template inválido, usando o embutido");
+                    Self::builtin(task)
+                }
+            },
+            None => Self::builtin(task),
+        };
+
+        self.cache.write().insert(
+            task,
+            CachedTemplate {
+                template: template.clone(),
+                modified,
+            },
+        );
+        template
+    }
+
+    /// Template embutido no binário.
+    fn builtin(task: PromptTask) -> PromptTemplate {
+        PromptTemplate::parse(task.builtin()).expect("template embutido inválido")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_templates_parse() {
+        for task in [
+            PromptTask::Narration,
+            PromptTask::Query,
+            PromptTask::Agent,
+            PromptTask::Summary,
+            PromptTask::Coreference,
+            PromptTask::EntityType,
+            PromptTask::Question,
+        ] {
+            let template = PromptStore::builtin(task);
+            assert!(!template.system.is_empty());
+            assert_eq!(template.user, "{message}");
+        }
+    }
+
+    #[test]
+    fn parse_reads_params_and_sections() {
+        let template = PromptTemplate::parse(
+            "# comentário\nmodel = m1\ntemperature = 0.3\nmax_tokens = 64\n\n[system]\nSis {concepts}\n[user]\nUsr {message}\n",
+        )
+        .unwrap();
+        assert_eq!(template.params.model.as_deref(), Some("m1"));
+        assert!((template.params.temperature - 0.3).abs() < 1e-6);
+        assert_eq!(template.params.max_tokens, 64);
+        assert_eq!(template.system, "Sis {concepts}");
+        assert_eq!(template.user, "Usr {message}");
+    }
+
+    #[test]
+    fn parse_rejects_unknown_param() {
+        assert!(PromptTemplate::parse("top_k = 3\n[system]\nx").is_err());
+        assert!(PromptTemplate::parse("temperature = 0.5\n").is_err());
+    }
+
+    #[test]
+    fn render_substitutes_and_blanks_missing_vars() {
+        let template =
+            PromptTemplate::parse("[system]\nC: {concepts} | H: {history} | L: {language}")
+                .unwrap();
+        let messages = template.render(&PromptVars::new("oi").with("concepts", "Rust"));
+        assert_eq!(messages[0].role, "system");
+        assert_eq!(
+            messages[0].content.as_deref(),
+            Some("C: Rust | H:  | L: português brasileiro")
+        );
+        assert_eq!(messages[1].content.as_deref(), Some("oi"));
+
+        let history = [ChatMsg::user("antes"), ChatMsg::assistant("resposta")];
+        let messages = template.render_with_history(&PromptVars::new("agora"), &history);
+        let roles: Vec<&str> = messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["system", "user", "assistant", "user"]);
+    }
+
+    #[test]
+    fn render_copies_values_with_braces_verbatim() {
+        let template = PromptTemplate::parse(
+            "[system]\nC: {concepts} | {json} | {{message}}\n[user]\n{message}",
+        )
+        .unwrap();
+        let vars =
+            PromptVars::new("use {concepts} e {\"a\": 1}").with("concepts", "{history} {language}");
+        let messages = template.render(&vars);
+        assert_eq!(
+            messages[0].content.as_deref(),
+            Some("C: {history} {language} | {json} | {use {concepts} e {\"a\": 1}}")
+        );
+        assert_eq!(
+            messages[1].content.as_deref(),
+            Some("use {concepts} e {\"a\": 1}")
+        );
+    }
+
+    #[test]
+    fn store_reloads_changed_file() {
+        let dir = std::env::temp_dir().join(format!("prompts-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let store = PromptStore::new(&dir);
+        assert!(store
+            .get(PromptTask::Query)
+            .system
+            .contains("cultivo epistêmico"));
+
+        let path = dir.join(PromptTask::Query.file_name());
+        std::fs::write(&path, "[system]\nversão 1").unwrap();
+        assert_eq!(store.get(PromptTask::Query).system, "versão 1");
+
+        std::fs::write(&path, "temperature = 0.1\n[system]\nversão 2").unwrap();
+        // Garante mtime diferente em sistemas de arquivos com baixa resolução
+        let later = SystemTime::now() + std::time::Duration::from_secs(5);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        let template = store.get(PromptTask::Query);
+        assert_eq!(template.system, "versão 2");
+        assert!((template.params.temperature - 0.1).abs() < 1e-6);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,562 @@
+//! # Extração de Valores — Números, Quantidades, Percentuais, Dinheiro e Datas
+//!
+//! O [`EntityExtractor`](super::extractor::EntityExtractor) ignora
+//! números ou os trata como ruído ("2024" virava um conceito). Este módulo
+//! extrai os valores de forma estruturada e os **anexa como
+//! [`Attribute`]s** ao conceito mais próximo na mesma frase:
+//!
+//! ```text
+//! "o lote 42 atrasou 3 dias em março de 2024"
+//!        │  │        │           │
+//!        │  42       3 dias      março de 2024
+//!        │  Number   Quantity    Date (2024-03)
+//!        └── todos anexados a "lote" (entidade precedente mais próxima)
+//! ```
+//!
+//! ## Prioridade (sem sobreposição)
+//!
+//! | Ordem | Tipo | Exemplos |
+//! |-------|------|----------|
+//! | 1 | Dinheiro | "R$ 1.500,00", "US$ 3 milhões", "20 reais" |
+//! | 2 | Percentual | "30%", "12,5 por cento" |
+//! | 3 | Data | "12/03/2024", "12 de março de 2024", "em 2024" |
+//! | 4 | Quantidade | "3 dias", "12 km", "40 kg" |
+//! | 5 | Número | "42", "3,5" |
+//!
+//! Números aceitam separadores pt-BR ("1.500,25") e en ("1,500.25").
+
+use chrono::Utc;
+use regex::{Captures, Regex};
+
+use crate::core::{Attribute, ValueKind};
+
+/// Número com separadores de milhar/decimal.
+const NUMBER: &str = r"\d+(?:[.,]\d+)*";
+
+/// Meses (pt/en) na ordem do calendário — o índice + 1 é o mês.
+const MONTHS: &[(&str, &str)] = &[
+    ("janeiro", "january"),
+    ("fevereiro", "february"),
+    ("março", "march"),
+    ("abril", "april"),
+    ("maio", "may"),
+    ("junho", "june"),
+    ("julho", "july"),
+    ("agosto", "august"),
+    ("setembro", "september"),
+    ("outubro", "october"),
+    ("novembro", "november"),
+    ("dezembro", "december"),
+];
+
+/// Unidades reconhecidas em quantidades.
+const UNITS: &[&str] = &[
+    "segundos",
+    "segundo",
+    "minutos",
+    "minuto",
+    "horas",
+    "hora",
+    "dias",
+    "dia",
+    "semanas",
+    "semana",
+    "meses",
+    "mês",
+    "anos",
+    "ano",
+    "seconds",
+    "minutes",
+    "hours",
+    "days",
+    "weeks",
+    "months",
+    "years",
+    "km",
+    "m",
+    "cm",
+    "mm",
+    "kg",
+    "g",
+    "mg",
+    "t",
+    "toneladas",
+    "litros",
+    "l",
+    "ml",
+    "°c",
+    "kwh",
+    "mw",
+    "gw",
+    "unidades",
+    "pessoas",
+    "peças",
+    "itens",
+    "lotes",
+    "units",
+    "people",
+    "items",
+];
+
+/// Monta `(início, fim, atributo)` a partir das capturas de uma regex.
+type MentionBuilder<'a> = &'a dyn Fn(&Captures) -> Option<(usize, usize, Attribute)>;
+
+/// Valor encontrado no texto.
+#[derive(Clone, Debug)]
+pub struct ValueMention {
+    /// Valor tipado.
+    pub attribute: Attribute,
+    /// Início (byte) do trecho no texto.
+    pub start: usize,
+    /// Fim (byte, exclusivo) do trecho no texto.
+    pub end: usize,
+}
+
+/// Extrator de valores por regex (compiladas uma vez).
+pub struct ValueExtractor {
+    /// "R$ 1.500,00", "US$ 3 milhões".
+    money_prefix_re: Regex,
+    /// "20 reais", "3 mil dólares".
+    money_suffix_re: Regex,
+    /// "30%", "12,5 por cento".
+    percent_re: Regex,
+    /// "12/03/2024".
+    numeric_date_re: Regex,
+    /// "12 de março de 2024", "março de 2024", "March 12, 2024".
+    month_date_re: Regex,
+    /// "em 2024", "desde 1998".
+    year_re: Regex,
+    /// "3 dias", "12 km".
+    quantity_re: Regex,
+    /// "42".
+    number_re: Regex,
+}
+
+impl Default for ValueExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ValueExtractor {
+    /// Compila as regexes de valores.
+    pub fn new() -> Self {
+        let months: Vec<&str> = MONTHS.iter().flat_map(|(pt, en)| [*pt, *en]).collect();
+        let months = months.join("|");
+        let units: Vec<String> = UNITS.iter().map(|u| regex::escape(u)).collect();
+        let scale = r"(?:\s+(mil|milhão|milhões|bilhão|bilhões|thousand|million|billion))?";
+        Self {
+            money_prefix_re: Regex::new(&format!(
+                r"(?i)(R\$|US\$|\$|€|£)\s*({NUMBER}){scale}"
+            ))
+            .unwrap(),
+            money_suffix_re: Regex::new(&format!(
+                r"(?i)\b({NUMBER}){scale}\s+(reais|real|dólares|dólar|euros|euro|dollars|dollar)\b"
+            ))
+            .unwrap(),
+            percent_re: Regex::new(&format!(
+                r"(?i)\b({NUMBER})\s*(%|por\s*cento\b|percent\b)"
+            ))
+            .unwrap(),
+            numeric_date_re: Regex::new(r"\b(\d{1,2})/(\d{1,2})/(\d{4})\b").unwrap(),
+            month_date_re: Regex::new(&format!(
+                r"(?i)\b(?:(\d{{1,2}})\s+de\s+)?({months})(?:\s+(\d{{1,2}}),?)?\s+(?:de\s+)?(\d{{4}})\b"
+            ))
+            .unwrap(),
+            year_re: Regex::new(
+                r"(?i)\b(?:em|de|desde|até|in|since|until)\s+((?:1[5-9]|20)\d{2})\b",
+            )
+            .unwrap(),
+            quantity_re: Regex::new(&format!(
+                r"(?i)\b({NUMBER})\s*({})(?:\b|$)",
+                units.join("|")
+            ))
+            .unwrap(),
+            number_re: Regex::new(&format!(r"\b{NUMBER}\b")).unwrap(),
+        }
+    }
+
+    /// Extrai todos os valores de `text`, em ordem de posição.
+    pub fn extract(&self, text: &str) -> Vec<ValueMention> {
+        let mut mentions: Vec<ValueMention> = Vec::new();
+
+        let mut scan = |re: &Regex, build: MentionBuilder| {
+            for cap in re.captures_iter(text) {
+                let Some((start, end, attribute)) = build(&cap) else {
+                    continue;
+                };
+                let overlaps = mentions.iter().any(|m| start < m.end && m.start < end);
+                if !overlaps {
+                    mentions.push(ValueMention {
+                        attribute,
+                        start,
+                        end,
+                    });
+                }
+            }
+        };
+
+        scan(&self.money_prefix_re, &|cap| {
+            let value = parse_number(&cap[2])? * scale_factor(cap.get(3).map(|m| m.as_str()));
+            let whole = cap.get(0)?;
+            Some((
+                whole.start(),
+                whole.end(),
+                attribute(
+                    ValueKind::Money,
+                    Some(value),
+                    Some(currency(&cap[1])),
+                    None,
+                    whole.as_str(),
+                ),
+            ))
+        });
+        scan(&self.money_suffix_re, &|cap| {
+            let value = parse_number(&cap[1])? * scale_factor(cap.get(2).map(|m| m.as_str()));
+            let whole = cap.get(0)?;
+            Some((
+                whole.start(),
+                whole.end(),
+                attribute(
+                    ValueKind::Money,
+                    Some(value),
+                    Some(currency(&cap[3])),
+                    None,
+                    whole.as_str(),
+                ),
+            ))
+        });
+        scan(&self.percent_re, &|cap| {
+            let whole = cap.get(0)?;
+            Some((
+                whole.start(),
+                whole.end(),
+                attribute(
+                    ValueKind::Percentage,
+                    Some(parse_number(&cap[1])?),
+                    Some("%".into()),
+                    None,
+                    whole.as_str(),
+                ),
+            ))
+        });
+        scan(&self.numeric_date_re, &|cap| {
+            let (day, month): (u32, u32) = (cap[1].parse().ok()?, cap[2].parse().ok()?);
+            if !(1..=31).contains(&day) || !(1..=12).contains(&month) {
+                return None;
+            }
+            let whole = cap.get(0)?;
+            let date = format!("{}-{:02}-{:02}", &cap[3], month, day);
+            Some((
+                whole.start(),
+                whole.end(),
+                attribute(ValueKind::Date, None, None, Some(date), whole.as_str()),
+            ))
+        });
+        scan(&self.month_date_re, &|cap| {
+            let month = month_number(&cap[2])?;
+            let day = cap
+                .get(1)
+                .or(cap.get(3))
+                .and_then(|m| m.as_str().parse::<u32>().ok());
+            let date = match day {
+                Some(day) if (1..=31).contains(&day) => {
+                    format!("{}-{:02}-{:02}", &cap[4], month, day)
+                }
+                _ => format!("{}-{:02}", &cap[4], month),
+            };
+            let whole = cap.get(0)?;
+            Some((
+                whole.start(),
+                whole.end(),
+                attribute(ValueKind::Date, None, None, Some(date), whole.as_str()),
+            ))
+        });
+        scan(&self.year_re, &|cap| {
+            let year = cap.get(1)?;
+            Some((
+                year.start(),
+                year.end(),
+                attribute(
+                    ValueKind::Date,
+                    None,
+                    None,
+                    Some(year.as_str().to_string()),
+                    year.as_str(),
+                ),
+            ))
+        });
+        scan(&self.quantity_re, &|cap| {
+            let whole = cap.get(0)?;
+            let unit = cap[2].to_lowercase();
+            Some((
+                whole.start(),
+                whole.end(),
+                attribute(
+                    ValueKind::Quantity,
+                    Some(parse_number(&cap[1])?),
+                    Some(unit),
+                    None,
+                    whole.as_str().trim(),
+                ),
+            ))
+        });
+        scan(&self.number_re, &|cap| {
+            let whole = cap.get(0)?;
+            Some((
+                whole.start(),
+                whole.end(),
+                attribute(
+                    ValueKind::Number,
+                    Some(parse_number(whole.as_str())?),
+                    None,
+                    None,
+                    whole.as_str(),
+                ),
+            ))
+        });
+
+        mentions.sort_by_key(|m| m.start);
+        mentions
+    }
+}
+
+/// Monta um [`Attribute`] registrado agora.
+fn attribute(
+    kind: ValueKind,
+    value: Option<f64>,
+    unit: Option<String>,
+    date: Option<String>,
+    text: &str,
+) -> Attribute {
+    Attribute {
+        kind,
+        value,
+        unit,
+        date,
+        text: text.to_string(),
+        recorded_at: Utc::now(),
+        source: None,
+    }
+}
+
+/// Converte "1.500,25", "1,500.25", "3,5" ou "42" em `f64`.
+///
+/// Com os dois separadores, o último é o decimal. Com só um, ele é
+/// decimal se seguido de 1-2 dígitos ("3,5") e milhar caso contrário
+/// ("1.500").
+pub fn parse_number(raw: &str) -> Option<f64> {
+    let last_dot = raw.rfind('.');
+    let last_comma = raw.rfind(',');
+    let normalized = match (last_dot, last_comma) {
+        (Some(d), Some(c)) if c > d => raw.replace('.', "").replace(',', "."),
+        (Some(_), Some(_)) => raw.replace(',', ""),
+        (Some(i), None) | (None, Some(i)) => {
+            let separator = raw.as_bytes()[i] as char;
+            let decimals = raw.len() - i - 1;
+            if raw.matches(separator).count() == 1 && decimals <= 2 {
+                raw.replace(separator, ".")
+            } else {
+                raw.replace(separator, "")
+            }
+        }
+        (None, None) => raw.to_string(),
+    };
+    normalized.parse().ok()
+}
+
+/// Multiplicador de "mil", "milhões", "billion"...
+fn scale_factor(scale: Option<&str>) -> f64 {
+    match scale.map(|s| s.to_lowercase()) {
+        Some(s) if s == "mil" || s == "thousand" => 1e3,
+        Some(s) if s.starts_with("milh") || s == "million" => 1e6,
+        Some(s) if s.starts_with("bilh") || s == "billion" => 1e9,
+        _ => 1.0,
+    }
+}
+
+/// Código ISO 4217 da moeda.
+fn currency(symbol: &str) -> String {
+    let code = match symbol.to_lowercase().as_str() {
+        "r$" | "reais" | "real" => "BRL",
+        "us$" | "$" | "dólares" | "dólar" | "dollars" | "dollar" => "USD",
+        "€" | "euros" | "euro" => "EUR",
+        "£" => "GBP",
+        _ => "?",
+    };
+    code.to_string()
+}
+
+/// Número do mês (1-12) a partir do nome em pt ou en.
+fn month_number(name: &str) -> Option<usize> {
+    let name = name.to_lowercase();
+    MONTHS
+        .iter()
+        .position(|(pt, en)| *pt == name || *en == name)
+        .map(|i| i + 1)
+}
+
+/// Remove das entidades os candidatos que são só parte de um valor
+/// ("2024", "março") — eles viram atributos, não conceitos.
+///
+/// Compara palavras inteiras: "lote" sobrevive a "3 lotes".
+pub fn strip_value_entities(entities: &mut Vec<String>, mentions: &[ValueMention]) {
+    let mentions: Vec<Vec<String>> = mentions
+        .iter()
+        .map(|m| {
+            words(&m.attribute.text)
+                .into_iter()
+                .map(|(_, _, w)| w)
+                .collect()
+        })
+        .collect();
+    entities.retain(|entity| {
+        let entity: Vec<String> = words(entity).into_iter().map(|(_, _, w)| w).collect();
+        !mentions.iter().any(|m| contains_words(m, &entity))
+    });
+}
+
+/// Palavras do texto em minúsculas, com a posição em bytes de cada uma.
+fn words(text: &str) -> Vec<(usize, usize, String)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        match (c.is_alphanumeric() || c == '_', start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                words.push((s, i, text[s..i].to_lowercase()));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+/// `haystack` contém `needle` como sequência contígua de palavras.
+fn contains_words(haystack: &[String], needle: &[String]) -> bool {
+    !needle.is_empty() && haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+/// Associa cada valor à entidade precedente mais próxima na mesma frase
+/// (ou, sem precedente, à seguinte mais próxima).
+///
+/// Retorna pares `(índice da entidade em entities, atributo)`.
+pub fn attach(
+    text: &str,
+    mentions: &[ValueMention],
+    entities: &[String],
+) -> Vec<(usize, Attribute)> {
+    // Posições de cada entidade no texto (palavras inteiras, sem caixa)
+    let text_words = words(text);
+    let lowered: Vec<String> = text_words.iter().map(|(_, _, w)| w.clone()).collect();
+    let mut positions: Vec<(usize, usize, usize)> = Vec::new();
+    for (index, entity) in entities.iter().enumerate() {
+        let entity: Vec<String> = words(entity).into_iter().map(|(_, _, w)| w).collect();
+        if entity.is_empty() {
+            continue;
+        }
+        for (i, window) in lowered.windows(entity.len()).enumerate() {
+            if window == entity.as_slice() {
+                positions.push((text_words[i].0, text_words[i + entity.len() - 1].1, index));
+            }
+        }
+    }
+
+    // Ponto só encerra frase quando seguido de espaço ("1.500" não)
+    let sentence_of = |pos: usize| -> usize {
+        let prefix = &text[..pos];
+        prefix
+            .char_indices()
+            .filter(|&(i, c)| match c {
+                '!' | '?' | ';' | '\n' => true,
+                '.' => prefix[i + 1..]
+                    .chars()
+                    .next()
+                    .is_none_or(char::is_whitespace),
+                _ => false,
+            })
+            .count()
+    };
+
+    let mut attached = Vec::new();
+    for mention in mentions {
+        let sentence = sentence_of(mention.start);
+        let same_sentence = positions.iter().filter(|(start, end, _)| {
+            let overlaps = *start < mention.end && mention.start < *end;
+            !overlaps && sentence_of(*start) == sentence
+        });
+        let preceding = same_sentence
+            .clone()
+            .filter(|(_, end, _)| *end <= mention.start)
+            .max_by_key(|(_, end, _)| *end);
+        let following = same_sentence
+            .filter(|(start, _, _)| *start >= mention.end)
+            .min_by_key(|(start, _, _)| *start);
+        if let Some(&(_, _, entity)) = preceding.or(following) {
+            attached.push((entity, mention.attribute.clone()));
+        }
+    }
+    attached
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_both_decimal_conventions() {
+        assert_eq!(parse_number("1.500,25"), Some(1500.25));
+        assert_eq!(parse_number("1,500.25"), Some(1500.25));
+        assert_eq!(parse_number("3,5"), Some(3.5));
+        assert_eq!(parse_number("1.500"), Some(1500.0));
+        assert_eq!(parse_number("42"), Some(42.0));
+    }
+
+    #[test]
+    fn extracts_typed_values_and_attaches_them() {
+        let extractor = ValueExtractor::new();
+        let text = "o lote 42 atrasou 3 dias em março de 2024 e custou R$ 1.500,00, 30% acima";
+        let mentions = extractor.extract(text);
+        let kinds: Vec<(ValueKind, String)> = mentions
+            .iter()
+            .map(|m| (m.attribute.kind, m.attribute.describe()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (ValueKind::Number, "42".to_string()),
+                (ValueKind::Quantity, "3 dias".to_string()),
+                (ValueKind::Date, "2024-03".to_string()),
+                (ValueKind::Money, "1500 BRL".to_string()),
+                (ValueKind::Percentage, "30%".to_string()),
+            ]
+        );
+
+        let mut entities = vec!["lote".to_string(), "março".to_string(), "2024".to_string()];
+        strip_value_entities(&mut entities, &mentions);
+        assert_eq!(entities, vec!["lote".to_string()]);
+
+        let attached = attach(text, &mentions, &entities);
+        assert_eq!(attached.len(), 5);
+        assert!(attached.iter().all(|&(entity, _)| entity == 0));
+    }
+
+    #[test]
+    fn keeps_entities_that_only_share_a_prefix_with_a_value() {
+        let extractor = ValueExtractor::new();
+        let text = "o euro subiu 20 euros e o lote atrasou 3 lotes em 3 dias; cada dia conta";
+        let mentions = extractor.extract(text);
+
+        let mut entities = vec!["euro".to_string(), "lote".to_string(), "dia".to_string()];
+        strip_value_entities(&mut entities, &mentions);
+        assert_eq!(entities, vec!["euro", "lote", "dia"]);
+
+        // "dia" casa a palavra inteira "dia", não o "dia" dentro de "dias"
+        let attached = attach(text, &mentions, &entities);
+        assert!(attached.iter().all(|&(entity, _)| entity != 2));
+    }
+}
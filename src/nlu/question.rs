@@ -30,6 +30,13 @@
 //! | `for_concept` | Conceito individual | Aprofundar entendimento |
 //! | `for_relation` | Par de conceitos | Explorar conexões |
 //! | `for_causal_link` | Relação causal | Validar causalidade |
+//! | `for_inference` | Link inferido | Verificar uma dedução/indução |
+//! | `for_contradiction` | Links conflitantes | Resolver contradição |
+//! | `for_isolated` | Conceito sem links | Conectar ao resto da KB |
+//!
+//! Esses templates são o *fallback* offline: normalmente a lacuna
+//! escolhida pelo [`gaps`](super::gaps) é formulada pelo LLM
+//! (`prompts/question.prompt`).
 //!
 //! ## Seleção Determinística
 //!
@@ -46,6 +53,12 @@ use crate::core::Concept;
 /// permite fácil extensão com novos templates.
 pub struct QuestionGenerator;
 
+impl Default for QuestionGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl QuestionGenerator {
     /// Cria um novo gerador de perguntas.
     pub fn new() -> Self {
@@ -183,4 +196,50 @@ impl QuestionGenerator {
             % templates.len();
         templates.into_iter().nth(idx).unwrap()
     }
+
+    /// Gera uma pergunta para verificar um link derivado pelo motor de
+    /// inferência (`rule` = "Dedução", "Indução"...).
+    pub fn for_inference(&self, source: &Concept, target: &Concept, rule: &str) -> String {
+        let templates = vec![
+            format!(
+                "Por {}, concluí que '{}' se relaciona com '{}'. Isso procede?",
+                rule.to_lowercase(),
+                source.label,
+                target.label
+            ),
+            format!(
+                "Deduzi uma ligação entre '{}' e '{}'. Você confirma?",
+                source.label, target.label
+            ),
+        ];
+
+        let idx = (source.id.as_bytes()[0] as usize + target.id.as_bytes()[0] as usize)
+            % templates.len();
+        templates.into_iter().nth(idx).unwrap()
+    }
+
+    /// Gera uma pergunta sobre evidências conflitantes entre dois conceitos.
+    pub fn for_contradiction(&self, source: &Concept, target: &Concept) -> String {
+        format!(
+            "Tenho informações conflitantes sobre '{}' e '{}'. Qual é a relação correta entre eles?",
+            source.label, target.label
+        )
+    }
+
+    /// Gera uma pergunta para conectar um conceito ainda sem relações.
+    pub fn for_isolated(&self, concept: &Concept) -> String {
+        let templates = vec![
+            format!(
+                "'{}' ainda não se liga a nada do que conversamos. Com o que ele se relaciona?",
+                concept.label
+            ),
+            format!(
+                "O que '{}' causa ou de que ele depende?",
+                concept.label
+            ),
+        ];
+
+        let idx = concept.id.as_bytes()[0] as usize % templates.len();
+        templates.into_iter().nth(idx).unwrap()
+    }
 }
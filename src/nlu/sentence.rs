@@ -0,0 +1,224 @@
+//! # Segmentação de Sentenças — pt-BR e Inglês
+//!
+//! O chunking da ingestão cortava o texto em `". "`: perdia sentenças
+//! terminadas em `?`, `!` ou `…` e quebrava em abreviações ("Dr. Silva",
+//! "p. 42", "e.g. rice"). Este segmentador decide cada fronteira olhando
+//! o terminador, o token antes dele e o caractere seguinte:
+//!
+//! ```text
+//! "O Dr. Silva plantou 2.5 ha. Choveu? Sim! Veja a fig. 3."
+//!    │          │        │     │       │     │     │
+//!    abrev.     decimal  fim   fim     fim   abrev. fim
+//!                        └─ "O Dr. Silva plantou 2.5 ha."
+//!                              └─ "Choveu?"  └─ "Sim!"  └─ "Veja a fig. 3."
+//! ```
+//!
+//! ## Regras
+//!
+//! | Situação | Fronteira? |
+//! |----------|------------|
+//! | Linha em branco (parágrafo) | Sempre |
+//! | `.` `!` `?` `…` (e aspas/parênteses de fechamento) + espaço + maiúscula, dígito ou abertura | Sim |
+//! | Terminador sem espaço depois (`2.5`, `www.site`) | Não |
+//! | Próximo caractere minúsculo (`"Será? talvez"`) | Não |
+//! | `.` após abreviação conhecida (`Sr.`, `Prof.`, `fig.`, `e.g.`) | Não |
+//! | `.` após inicial maiúscula (`J. R. Tolkien`) | Não |
+//!
+//! As sentenças são devolvidas como fatias do texto original, com os
+//! offsets (bytes) de início e fim — o chunking usa esses offsets para
+//! localizar cada chunk no documento.
+
+use std::collections::HashSet;
+
+/// Abreviações (minúsculas, sem o ponto final) que não encerram sentença.
+///
+/// `etc.` fica de fora: no fim de uma enumeração ela costuma encerrar a
+/// sentença, e a maiúscula seguinte decide.
+#[rustfmt::skip]
+const ABBREVIATIONS: &[&str] = &[
+    // pt-BR
+    "sr", "sra", "srta", "srs", "dr", "dra", "drs", "prof", "profa", "profs", "eng", "arq", "adv",
+    "av", "r", "ex", "exa", "p", "pp", "pág", "págs", "pag", "cap", "caps", "vol", "vols", "n",
+    "nº", "núm", "num", "art", "arts", "fig", "figs", "tab", "eq", "cf", "aprox", "séc", "obs",
+    "ed", "eds", "org", "orgs", "coord", "trad", "ltda", "cia", "op", "cit", "ibid", "id",
+    "tel", "dep", "depto", "gov",
+    // inglês
+    "mr", "mrs", "ms", "jr", "st", "vs", "al", "e.g", "i.e", "inc", "ltd", "co", "no",
+    "approx", "dept", "est", "viz",
+];
+
+/// Pontuação que encerra uma sentença.
+const TERMINATORS: &[char] = &['.', '!', '?', '…'];
+
+/// Fechamentos que podem seguir o terminador (`"Choveu?"`, `(fim.)`).
+const CLOSERS: &[char] = &['"', '\'', '”', '’', '»', ')', ']'];
+
+/// Aberturas que podem iniciar a próxima sentença.
+const OPENERS: &[char] = &['"', '\'', '“', '‘', '«', '(', '[', '—', '–', '-', '¿', '¡'];
+
+/// Sentença encontrada no texto.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Sentence<'a> {
+    /// Texto da sentença (sem espaços nas pontas).
+    pub text: &'a str,
+    /// Início (byte) no texto original.
+    pub start: usize,
+    /// Fim (byte, exclusivo) no texto original.
+    pub end: usize,
+}
+
+/// Segmentador de sentenças por regras (abreviações carregadas uma vez).
+pub struct SentenceSegmenter {
+    /// Abreviações conhecidas, em minúsculas e sem o ponto final.
+    abbreviations: HashSet<&'static str>,
+}
+
+impl Default for SentenceSegmenter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SentenceSegmenter {
+    /// Cria o segmentador com as abreviações pt-BR e inglesas.
+    pub fn new() -> Self {
+        Self {
+            abbreviations: ABBREVIATIONS.iter().copied().collect(),
+        }
+    }
+
+    /// Divide o texto em sentenças, na ordem.
+    pub fn split<'a>(&self, text: &'a str) -> Vec<Sentence<'a>> {
+        let mut sentences = Vec::new();
+        let mut start = 0;
+        let mut chars = text.char_indices().peekable();
+
+        while let Some((i, c)) = chars.next() {
+            // Parágrafo: quebra de linha seguida (após espaços) de outra
+            if c == '\n' {
+                let rest = &text[i + 1..];
+                let blank = rest.trim_start_matches([' ', '\t', '\r']).starts_with('\n');
+                if blank {
+                    push_sentence(text, start, i, &mut sentences);
+                    start = i + 1;
+                }
+                continue;
+            }
+            if !TERMINATORS.contains(&c) {
+                continue;
+            }
+
+            // Consome a sequência de terminadores e fechamentos ("?!", "...", ".)")
+            let mut end = i + c.len_utf8();
+            let mut only_period = c == '.';
+            let mut periods = usize::from(c == '.');
+            while let Some(&(j, next)) = chars.peek() {
+                if TERMINATORS.contains(&next) || CLOSERS.contains(&next) {
+                    only_period &= next == '.' || CLOSERS.contains(&next);
+                    periods += usize::from(next == '.');
+                    end = j + next.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+
+            let after = &text[end..];
+            if !after.is_empty() && !after.starts_with(char::is_whitespace) {
+                continue;
+            }
+            if let Some(next) = after.trim_start().chars().next() {
+                if !starts_sentence(next) {
+                    continue;
+                }
+                if only_period && periods == 1 && self.is_abbreviation(&text[start..i]) {
+                    continue;
+                }
+            }
+
+            push_sentence(text, start, end, &mut sentences);
+            start = end;
+        }
+
+        push_sentence(text, start, text.len(), &mut sentences);
+        sentences
+    }
+
+    /// `true` se o texto (até o ponto) termina numa abreviação ou inicial.
+    fn is_abbreviation(&self, before: &str) -> bool {
+        let word = before
+            .rsplit(|c: char| c.is_whitespace() || OPENERS.contains(&c))
+            .next()
+            .unwrap_or("");
+        if word.is_empty() {
+            return false;
+        }
+        let mut letters = word.chars();
+        let initial =
+            matches!((letters.next(), letters.next()), (Some(c), None) if c.is_uppercase());
+        initial || self.abbreviations.contains(word.to_lowercase().as_str())
+    }
+}
+
+/// `true` se o caractere pode abrir uma sentença (maiúscula, dígito, abertura...).
+fn starts_sentence(c: char) -> bool {
+    !c.is_lowercase()
+}
+
+/// Adiciona `text[start..end]` sem os espaços das pontas, se não for vazio.
+fn push_sentence<'a>(text: &'a str, start: usize, end: usize, sentences: &mut Vec<Sentence<'a>>) {
+    let slice = &text[start..end];
+    let trimmed = slice.trim_start();
+    let start = start + (slice.len() - trimmed.len());
+    let trimmed = trimmed.trim_end();
+    if !trimmed.is_empty() {
+        sentences.push(Sentence {
+            text: trimmed,
+            start,
+            end: start + trimmed.len(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(text: &str) -> Vec<&str> {
+        SentenceSegmenter::new()
+            .split(text)
+            .into_iter()
+            .map(|s| s.text)
+            .collect()
+    }
+
+    #[test]
+    fn splits_on_all_terminators_but_not_abbreviations() {
+        assert_eq!(
+            texts("O Dr. Silva plantou 2.5 ha. Choveu? Sim! Veja a fig. 3 e J. R. Tolkien… Fim."),
+            vec![
+                "O Dr. Silva plantou 2.5 ha.",
+                "Choveu?",
+                "Sim!",
+                "Veja a fig. 3 e J. R. Tolkien…",
+                "Fim."
+            ]
+        );
+        assert_eq!(
+            texts("He said \"stop.\" Then left, e.g. home.\n\nNew paragraph without dot"),
+            vec![
+                "He said \"stop.\"",
+                "Then left, e.g. home.",
+                "New paragraph without dot"
+            ]
+        );
+    }
+
+    #[test]
+    fn offsets_point_into_the_original_text() {
+        let text = "  Primeira frase.   Segunda?  ";
+        for sentence in SentenceSegmenter::new().split(text) {
+            assert_eq!(&text[sentence.start..sentence.end], sentence.text);
+        }
+    }
+}
@@ -0,0 +1,323 @@
+//! # Stemmer RSLP — Chave Canônica de Conceitos em Português
+//!
+//! Variantes morfológicas como "sustentabilidade"/"sustentável" ou
+//! "projeto"/"projetos" dependiam apenas do threshold de 0.90 em
+//! `find_similar_concept` e da faixa de auto-similaridade 0.78–0.90
+//! (veja `docs/calibracao-nomic-embed-text.md`) — caro e instável.
+//!
+//! Este módulo implementa um stemmer no estilo **RSLP** (Removedor de
+//! Sufixos da Língua Portuguesa, Orengo & Huyck, 2001) e deriva dele uma
+//! **chave canônica** por conceito, consultada *antes* da similaridade
+//! por embedding.
+//!
+//! ## Passos do RSLP
+//!
+//! ```text
+//! palavra
+//!   ├── 1. Plural        (ões → ão, ais → al, s → ∅ ...)
+//!   ├── 2. Advérbio      (mente → ∅)
+//!   ├── 3. Feminino      (ora → or, ada → ado, esa → ês ...)
+//!   ├── 4. Aumentativo/diminutivo (inho, zinho, íssimo ...)
+//!   ├── 5. Sufixo nominal (abilidade, idade, ável, ção ...)
+//!   │      └── removeu? → pula para 8
+//!   ├── 6. Sufixo verbal (aríamos, ando, ar ...)
+//!   ├── 7. Vogal temática (a, e, o) se 5 e 6 nada removeram
+//!   └── 8. Remoção de acentos
+//! ```
+//!
+//! Cada regra tem um **tamanho mínimo de radical** e exceções, como no
+//! algoritmo original; a lista aqui é um subconjunto das regras mais
+//! produtivas.
+//!
+//! ## Chave Canônica
+//!
+//! `canonical_key("Base de Conhecimentos")` → `"bas conhec"`: minúsculas,
+//! palavras funcionais removidas, cada palavra reduzida ao radical.
+//! Chaves com radicais muito curtos (< 4 letras) não são
+//! [confiáveis](is_reliable_key) para fusão: "casa" e "caso" colidem em
+//! "cas".
+
+use unicode_normalization::UnicodeNormalization;
+
+/// Regra de remoção de sufixo: (sufixo, tamanho mínimo do radical,
+/// substituição, exceções).
+type Rule = (&'static str, usize, &'static str, &'static [&'static str]);
+
+/// Passo 1 — redução de plural (aplicado apenas a palavras terminadas em "s").
+#[rustfmt::skip]
+const PLURAL_RULES: &[Rule] = &[
+    ("ns", 1, "m", &[]),
+    ("ões", 3, "ão", &[]),
+    ("ães", 1, "ão", &["mães"]),
+    ("ais", 1, "al", &["cais", "mais"]),
+    ("éis", 2, "el", &[]),
+    ("eis", 2, "el", &[]),
+    ("óis", 2, "ol", &[]),
+    ("is", 2, "il", &["lápis", "cais", "mais", "crúcis", "biquínis", "pois", "depois", "dois", "leis"]),
+    ("les", 3, "l", &[]),
+    ("res", 3, "r", &["árvores"]),
+    ("s", 2, "", &[
+        "aliás", "pires", "lápis", "cais", "mais", "mas", "menos", "férias", "fezes", "pêsames",
+        "crúcis", "gás", "atrás", "moisés", "através", "convés", "ês", "país", "após", "ambas",
+        "ambos", "messias", "depois",
+    ]),
+];
+
+/// Passo 2 — advérbios.
+const ADVERB_RULES: &[Rule] = &[("mente", 4, "", &["experimente"])];
+
+/// Passo 3 — redução de feminino (aplicado apenas a palavras terminadas em "a").
+#[rustfmt::skip]
+const FEMININE_RULES: &[Rule] = &[
+    ("ona", 3, "ão", &["abandona", "lona", "iona", "cortisona", "monótona", "maratona", "acetona", "detona", "carona"]),
+    ("ora", 3, "or", &[]),
+    ("na", 4, "no", &["carona", "abandona", "lona", "iona", "cortisona", "monótona", "maratona", "acetona", "detona", "guiana", "campana", "grana", "caravana", "banana", "paisana"]),
+    ("inha", 3, "inho", &["rainha", "linha", "minha"]),
+    ("esa", 3, "ês", &["mesa", "obesa", "princesa", "turquesa", "ilesa", "pesa", "presa"]),
+    ("osa", 3, "oso", &["mucosa", "prosa"]),
+    ("íaca", 3, "íaco", &[]),
+    ("ica", 3, "ico", &["dica"]),
+    ("ada", 2, "ado", &["pitada"]),
+    ("ida", 3, "ido", &["vida"]),
+    ("ída", 3, "ido", &["recaída", "saída", "dúvida"]),
+    ("ima", 3, "imo", &["vítima"]),
+    ("iva", 3, "ivo", &["saliva", "oliva"]),
+    ("eira", 3, "eiro", &["bandeira", "feira", "cadeira", "eira", "beira", "cordeira", "fronteira", "lixeira", "primeira"]),
+];
+
+/// Passo 4 — aumentativos e diminutivos.
+#[rustfmt::skip]
+const AUGMENTATIVE_RULES: &[Rule] = &[
+    ("díssimo", 5, "", &[]),
+    ("abilíssimo", 5, "", &[]),
+    ("íssimo", 3, "", &[]),
+    ("ésimo", 3, "", &[]),
+    ("érrimo", 4, "", &[]),
+    ("zinho", 2, "", &[]),
+    ("quinho", 4, "c", &[]),
+    ("uinho", 4, "", &[]),
+    ("adinho", 3, "", &[]),
+    ("inho", 3, "", &["caminho", "cominho"]),
+    ("alhão", 4, "", &[]),
+    ("uça", 4, "", &[]),
+    ("aço", 4, "", &["antebraço"]),
+    ("adão", 4, "", &[]),
+    ("ázio", 3, "", &["topázio"]),
+    ("arraz", 4, "", &[]),
+    ("zão", 2, "", &["coalizão"]),
+    ("arrão", 4, "", &[]),
+];
+
+/// Passo 5 — sufixos nominais (mais longos primeiro).
+#[rustfmt::skip]
+const NOUN_RULES: &[Rule] = &[
+    ("encialista", 4, "", &[]),
+    ("alista", 5, "", &[]),
+    ("abilidade", 5, "", &[]),
+    ("ibilidade", 5, "", &[]),
+    ("icionista", 4, "", &[]),
+    ("cionista", 5, "", &[]),
+    ("ionista", 5, "", &[]),
+    ("iamento", 4, "", &[]),
+    ("amento", 3, "", &["firmamento", "fundamento", "departamento"]),
+    ("imento", 3, "", &[]),
+    ("alizado", 4, "", &[]),
+    ("atizado", 4, "", &[]),
+    ("izado", 5, "", &["organizado", "pulverizado"]),
+    ("ividade", 5, "", &[]),
+    ("idade", 4, "", &["autoridade", "comunidade"]),
+    ("ização", 3, "", &[]),
+    ("ação", 3, "", &["nação", "mação"]),
+    ("ição", 3, "", &["eleição", "tradição"]),
+    ("ução", 3, "", &[]),
+    ("ância", 3, "", &["ambulância"]),
+    ("ência", 3, "", &[]),
+    ("ável", 2, "", &["afável", "razoável", "potável", "vulnerável"]),
+    ("ível", 3, "", &["possível"]),
+    ("ista", 4, "", &["lista", "pista", "vista"]),
+    ("ismo", 3, "", &["cinismo"]),
+    ("ativo", 4, "", &["pejorativo", "relativo"]),
+    ("tivo", 4, "", &["relativo"]),
+    ("ivo", 4, "", &["passivo", "possessivo", "pejorativo", "positivo"]),
+    ("ador", 3, "", &[]),
+    ("edor", 3, "", &[]),
+    ("idor", 4, "", &["ouvidor"]),
+    ("atória", 5, "", &[]),
+    ("ório", 3, "", &["território"]),
+    ("ário", 3, "", &["voluntário", "salário", "aniversário", "diário", "lionário", "armário"]),
+    ("ério", 6, "", &[]),
+    ("eiro", 3, "", &["desfiladeiro", "pioneiro", "mosteiro"]),
+    ("agem", 3, "", &["coragem", "chantagem", "vantagem", "carruagem"]),
+    ("ês", 4, "", &[]),
+    ("eza", 3, "", &[]),
+    ("ez", 4, "", &[]),
+    ("esco", 4, "", &[]),
+    ("ante", 2, "", &["gigante", "elefante", "adiante", "possante", "instante", "restaurante"]),
+    ("ástico", 4, "", &["eclesiástico"]),
+    ("ático", 3, "", &[]),
+    ("ico", 4, "", &["tico", "público", "explico"]),
+    ("oso", 3, "", &["precioso"]),
+    ("ional", 4, "", &[]),
+    ("al", 4, "", &["afinal", "animal", "estatal", "bissexual", "desleal", "fiscal", "formal", "pessoal", "liberal", "postal", "virtual", "visual", "pontual", "sideral", "sucursal"]),
+    ("ente", 4, "", &["acidente", "parente", "ambiente", "oriente", "ocidente", "semente", "frente"]),
+    ("or", 2, "", &["motor", "melhor", "redor", "rigor", "sensor", "tambor", "tumor", "assessor", "benfeitor", "pastor", "terior", "favor", "autor"]),
+    ("ano", 4, "", &[]),
+    ("ura", 4, "", &["imatura", "acupuntura", "costura"]),
+];
+
+/// Passo 6 — sufixos verbais (mais longos primeiro).
+#[rustfmt::skip]
+const VERB_RULES: &[Rule] = &[
+    ("aríamos", 2, "", &[]),
+    ("eríamos", 2, "", &[]),
+    ("iríamos", 3, "", &[]),
+    ("ássemos", 2, "", &[]),
+    ("êssemos", 2, "", &[]),
+    ("íssemos", 3, "", &[]),
+    ("aremos", 2, "", &[]),
+    ("eremos", 2, "", &[]),
+    ("iremos", 3, "", &[]),
+    ("ávamos", 2, "", &[]),
+    ("íamos", 3, "", &[]),
+    ("assem", 2, "", &[]),
+    ("essem", 2, "", &[]),
+    ("issem", 3, "", &[]),
+    ("ando", 2, "", &[]),
+    ("endo", 3, "", &[]),
+    ("indo", 3, "", &[]),
+    ("ondo", 3, "", &[]),
+    ("aram", 2, "", &[]),
+    ("eram", 3, "", &[]),
+    ("iram", 3, "", &[]),
+    ("avam", 2, "", &[]),
+    ("arem", 2, "", &[]),
+    ("erem", 3, "", &[]),
+    ("irem", 3, "", &[]),
+    ("ava", 2, "", &[]),
+    ("ado", 2, "", &[]),
+    ("ido", 3, "", &[]),
+    ("ara", 2, "", &["arara", "prepara"]),
+    ("era", 3, "", &["acelera", "espera"]),
+    ("ira", 3, "", &[]),
+    ("ar", 2, "", &["azar", "bazaar", "patamar"]),
+    ("er", 2, "", &["éter", "pier"]),
+    ("ir", 3, "", &["freir"]),
+    ("ou", 2, "", &[]),
+    ("am", 2, "", &[]),
+    ("em", 2, "", &["alguém"]),
+];
+
+/// Passo 7 — vogais temáticas.
+const VOWEL_RULES: &[Rule] = &[("a", 3, "", &[]), ("e", 3, "", &[]), ("o", 3, "", &["bo"])];
+
+/// Palavras funcionais ignoradas na chave canônica.
+#[rustfmt::skip]
+const FUNCTION_WORDS: &[&str] = &[
+    "o", "a", "os", "as", "um", "uma", "de", "do", "da", "dos", "das", "em", "no", "na", "nos",
+    "nas", "e", "para", "por", "com", "sem", "ao", "à", "the", "of", "and",
+];
+
+/// Tamanho mínimo de cada radical para que a chave seja usada em fusões.
+const MIN_RELIABLE_STEM: usize = 4;
+
+/// Aplica a primeira regra do passo que casar. Retorna `true` se removeu.
+fn apply_step(word: &mut String, rules: &[Rule]) -> bool {
+    for &(suffix, min_stem, replacement, exceptions) in rules {
+        if let Some(stem) = word.strip_suffix(suffix) {
+            if stem.chars().count() >= min_stem && !exceptions.contains(&word.as_str()) {
+                *word = format!("{}{}", stem, replacement);
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Reduz uma palavra (já em minúsculas) ao seu radical RSLP, sem acentos.
+pub fn stem(word: &str) -> String {
+    let mut w = word.to_lowercase();
+    if w.chars().count() < 3 {
+        return remove_accents(&w);
+    }
+
+    if w.ends_with('s') {
+        apply_step(&mut w, PLURAL_RULES);
+    }
+    apply_step(&mut w, ADVERB_RULES);
+    if w.ends_with('a') {
+        apply_step(&mut w, FEMININE_RULES);
+    }
+    apply_step(&mut w, AUGMENTATIVE_RULES);
+    if !apply_step(&mut w, NOUN_RULES) && !apply_step(&mut w, VERB_RULES) {
+        apply_step(&mut w, VOWEL_RULES);
+    }
+    remove_accents(&w)
+}
+
+/// Chave canônica de um label: palavras funcionais removidas e cada
+/// palavra restante reduzida ao radical.
+///
+/// `"Sustentabilidade"` e `"sustentável"` → `"sustent"`.
+pub fn canonical_key(label: &str) -> String {
+    let normalized: String = label.nfc().collect::<String>().to_lowercase();
+    normalized
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty() && !FUNCTION_WORDS.contains(w))
+        .map(stem)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Se a chave é específica o bastante para fundir conceitos sem
+/// consultar embeddings (todos os radicais com ≥ 4 letras).
+pub fn is_reliable_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .split(' ')
+            .all(|stem| stem.chars().count() >= MIN_RELIABLE_STEM)
+}
+
+/// Remove diacríticos (decomposição NFD + descarte das marcas combinantes).
+fn remove_accents(word: &str) -> String {
+    word.nfd()
+        .filter(|c| !('\u{0300}'..='\u{036f}').contains(c))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_morphological_variants() {
+        assert_eq!(
+            canonical_key("sustentabilidade"),
+            canonical_key("sustentável")
+        );
+        assert_eq!(canonical_key("projeto"), canonical_key("projetos"));
+        assert_eq!(canonical_key("Organização"), canonical_key("organizações"));
+        assert_eq!(canonical_key("professora"), canonical_key("professor"));
+    }
+
+    #[test]
+    fn ignores_function_words_and_case() {
+        assert_eq!(
+            canonical_key("Base de Conhecimento"),
+            canonical_key("base conhecimentos")
+        );
+    }
+
+    #[test]
+    fn keeps_distinct_concepts_apart() {
+        assert_ne!(canonical_key("energia"), canonical_key("engenharia"));
+        assert_ne!(canonical_key("fotossíntese"), canonical_key("síntese"));
+    }
+
+    #[test]
+    fn short_stems_are_not_reliable() {
+        assert_eq!(stem("casa"), stem("caso"));
+        assert!(!is_reliable_key(&canonical_key("casa")));
+        assert!(is_reliable_key(&canonical_key("sustentabilidade")));
+        assert!(!is_reliable_key(""));
+    }
+}
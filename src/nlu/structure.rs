@@ -0,0 +1,465 @@
+//! # Estrutura do Documento — Metadados e Sumário como Conhecimento
+//!
+//! O texto de um documento vira conceitos e links pela extração de
+//! entidades; a **estrutura** dele (quem escreveu, de que trata, como se
+//! divide) vinha sendo descartada. Este módulo a transforma em conceitos
+//! e links [`Structural`](LinkOrigin::Structural):
+//!
+//! ```text
+//!   Maria Souza ──autor de──▶ Manejo do Solo ◀──parte de── Capítulo 3 — Manejo do Solo
+//!                                   │                              ▲
+//!                               trata de                    mencionado em
+//!                                   ▼                              │
+//!                               irrigação                      gotejamento
+//! ```
+//!
+//! | Conceito | Label | Tipo |
+//! |----------|-------|------|
+//! | Documento | Título (ou nome do arquivo) | [`EntityType::Document`] |
+//! | Autor | Nome, como nos metadados | [`EntityType::Person`] |
+//! | Palavra-chave | Como nos metadados (reaproveita conceito parecido) | [`EntityType::Notion`] |
+//! | Seção | `"{caminho} — {documento}"` | [`EntityType::Notion`] |
+//!
+//! | Link | Sujeito | Objeto |
+//! |------|---------|--------|
+//! | `autor de` | Autor | Documento |
+//! | `trata de` | Documento | Palavra-chave |
+//! | `PartOf` | Seção | Seção-mãe, ou o documento |
+//! | `mencionado em` | Conceito extraído de um chunk | Seção mais interna do chunk |
+//!
+//! Os links de menção respondem a perguntas como "o que o capítulo 3 diz
+//! sobre X": a busca encontra a seção (o label tem embedding) e os
+//! vizinhos dela são os conceitos que aparecem ali.
+//!
+//! Tudo cita o documento na proveniência: esquecer o documento
+//! ([`crate::core::provenance::forget_document`]) remove a estrutura que
+//! só ele sustentava. Metadados não são afirmações incertas — os links
+//! nascem com verdade observada e ficam fora de lacunas e revisão.
+
+use std::collections::HashMap;
+
+use crate::core::concept::ConceptId;
+use crate::core::{
+    Citation, Concept, EntityType, KnowledgeBase, Link, LinkKind, LinkOrigin, Participant, Role,
+    Source, TruthValue,
+};
+use crate::document::DocumentMetadata;
+
+use super::{link_instance_to_type, stemmer, NluConceptInfo, NluLinkInfo};
+
+/// Similaridade mínima para uma palavra-chave reaproveitar um conceito existente.
+const KEYWORD_SIMILARITY: f32 = 0.90;
+
+/// Estrutura de um documento, antes de ir para a KB.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DocumentStructure {
+    /// Label do conceito do documento.
+    pub document: String,
+    /// Autores declarados.
+    pub authors: Vec<String>,
+    /// Palavras-chave declaradas.
+    pub keywords: Vec<String>,
+    /// Caminhos de títulos do sumário, na ordem do documento, e a página
+    /// inicial de cada um.
+    pub sections: Vec<(Vec<String>, Option<usize>)>,
+}
+
+impl DocumentStructure {
+    /// Estrutura a partir dos metadados; o label é o título ou, sem ele, o nome do arquivo.
+    pub fn from_metadata(metadata: &DocumentMetadata, filename: &str) -> Self {
+        Self {
+            document: metadata
+                .title
+                .clone()
+                .unwrap_or_else(|| filename.to_string()),
+            authors: metadata.authors.clone(),
+            keywords: metadata.keywords.clone(),
+            sections: metadata
+                .outline
+                .iter()
+                .map(|e| (e.headings.clone(), e.page))
+                .collect(),
+        }
+    }
+
+    /// Label do conceito de uma seção: `"Capítulo 2 › Irrigação — Manejo do Solo"`.
+    pub fn section_label(&self, path: &[String]) -> String {
+        format!("{} — {}", path.join(" › "), self.document)
+    }
+
+    /// Labels a embeddar, na ordem que [`apply`] espera: documento,
+    /// autores, palavras-chave e seções.
+    pub fn labels(&self) -> Vec<String> {
+        std::iter::once(self.document.clone())
+            .chain(self.authors.iter().cloned())
+            .chain(self.keywords.iter().cloned())
+            .chain(
+                self.sections
+                    .iter()
+                    .map(|(path, _)| self.section_label(path)),
+            )
+            .collect()
+    }
+
+    /// Conceitos de seção já na KB, pelo caminho — para retomar uma
+    /// ingestão sem aplicar a estrutura de novo.
+    pub fn find_sections(&self, kb: &KnowledgeBase) -> HashMap<String, ConceptId> {
+        self.sections
+            .iter()
+            .filter_map(|(path, _)| {
+                let id = kb.find_concept_by_label(&self.section_label(path))?.id;
+                Some((path.join(" › "), id))
+            })
+            .collect()
+    }
+}
+
+/// Resultado de [`apply`].
+#[derive(Default)]
+pub struct StructureResult {
+    /// Conceito do documento.
+    pub document: Option<ConceptId>,
+    /// Conceito de cada seção, pelo caminho ([`crate::document::Section::path`]).
+    pub sections: HashMap<String, ConceptId>,
+    /// Conceitos criados ou reforçados.
+    pub concept_details: Vec<NluConceptInfo>,
+    /// Links criados.
+    pub link_details: Vec<NluLinkInfo>,
+}
+
+impl StructureResult {
+    /// Quantos conceitos foram criados.
+    pub fn new_concepts(&self) -> usize {
+        self.concept_details.iter().filter(|c| c.is_new).count()
+    }
+}
+
+/// Aplica a estrutura na KB. `embeddings` segue a ordem de [`DocumentStructure::labels`].
+pub fn apply(
+    kb: &mut KnowledgeBase,
+    structure: &DocumentStructure,
+    embeddings: &[Vec<f32>],
+    source: &Source,
+) -> StructureResult {
+    let mut result = StructureResult::default();
+    let mut embeddings = embeddings.iter();
+    let citation = Citation::from(source.clone());
+
+    let document = upsert_concept(
+        kb,
+        &structure.document,
+        embeddings.next(),
+        Some(EntityType::Document),
+        false,
+        &citation,
+        &mut result,
+    );
+    result.document = Some(document);
+
+    for author in &structure.authors {
+        let id = upsert_concept(
+            kb,
+            author,
+            embeddings.next(),
+            Some(EntityType::Person),
+            false,
+            &citation,
+            &mut result,
+        );
+        upsert_link(
+            kb,
+            LinkKind::Custom("autor de".into()),
+            id,
+            document,
+            &citation,
+            &mut result,
+        );
+    }
+
+    for keyword in &structure.keywords {
+        let id = upsert_concept(
+            kb,
+            keyword,
+            embeddings.next(),
+            None,
+            true,
+            &citation,
+            &mut result,
+        );
+        if id != document {
+            upsert_link(
+                kb,
+                LinkKind::Custom("trata de".into()),
+                document,
+                id,
+                &citation,
+                &mut result,
+            );
+        }
+    }
+
+    for (path, page) in &structure.sections {
+        let label = structure.section_label(path);
+        let section_citation = Citation {
+            source: source.clone(),
+            page: *page,
+        };
+        let id = upsert_concept(
+            kb,
+            &label,
+            embeddings.next(),
+            None,
+            false,
+            &section_citation,
+            &mut result,
+        );
+        let parent = match path.len() {
+            1 => document,
+            n => result
+                .sections
+                .get(&path[..n - 1].join(" › "))
+                .copied()
+                .unwrap_or(document),
+        };
+        upsert_link(
+            kb,
+            LinkKind::PartOf,
+            id,
+            parent,
+            &section_citation,
+            &mut result,
+        );
+        result.sections.insert(path.join(" › "), id);
+    }
+
+    tracing::info!(
+        document = %structure.document,
+        authors = structure.authors.len(),
+        keywords = structure.keywords.len(),
+        sections = structure.sections.len(),
+        links = result.link_details.len(),
+        "Estrutura do documento aplicada"
+    );
+    result
+}
+
+/// Liga os conceitos de um chunk à seção dele (`X mencionado em Seção`).
+///
+/// A própria seção e os conceitos-tipo ("Pessoa") ficam de fora.
+pub fn link_mentions(
+    kb: &mut KnowledgeBase,
+    section: ConceptId,
+    concepts: &[ConceptId],
+    citation: &Citation,
+) -> Vec<NluLinkInfo> {
+    let mut result = StructureResult::default();
+    for &id in concepts {
+        let is_type = kb
+            .concepts
+            .get(&id)
+            .is_none_or(|c| c.entity_type.is_none() && EntityType::from_name(&c.label).is_some());
+        if id != section && !is_type {
+            upsert_link(
+                kb,
+                LinkKind::Custom("mencionado em".into()),
+                id,
+                section,
+                citation,
+                &mut result,
+            );
+        }
+    }
+    result.link_details
+}
+
+/// Reforça o conceito de mesmo label (ou, com `similar`, de embedding
+/// parecido) ou cria um novo; cita a fonte e, se tipado, liga ao conceito-tipo.
+fn upsert_concept(
+    kb: &mut KnowledgeBase,
+    label: &str,
+    embedding: Option<&Vec<f32>>,
+    entity_type: Option<EntityType>,
+    similar: bool,
+    citation: &Citation,
+    result: &mut StructureResult,
+) -> ConceptId {
+    let existing = kb
+        .find_concept_by_label(label)
+        .map(|c| (c.id, None))
+        .or_else(|| {
+            let embedding = embedding.filter(|_| similar)?;
+            kb.find_similar_concept(embedding, KEYWORD_SIMILARITY)
+                .map(|(id, sim)| (id, Some(sim)))
+        });
+    let (id, is_new, similarity) = match existing {
+        Some((id, similarity)) => {
+            if let Some(concept) = kb.concepts.get_mut(&id) {
+                concept.reinforce();
+                if concept.embedding.is_none() {
+                    concept.embedding = embedding.cloned();
+                }
+            }
+            (id, false, similarity)
+        }
+        None => {
+            let mut concept = Concept::new(label.to_string(), TruthValue::observed(true));
+            concept.key = stemmer::canonical_key(label);
+            concept.embedding = embedding.cloned();
+            concept.entity_type = Some(EntityType::Notion);
+            tracing::info!(label = %label, "Conceito estrutural criado");
+            (kb.add_concept(concept), true, None)
+        }
+    };
+    let Some(concept) = kb.concepts.get_mut(&id) else {
+        return id;
+    };
+    concept.provenance.cite(citation);
+    result.concept_details.push(NluConceptInfo {
+        id: id.to_string(),
+        label: concept.label.clone(),
+        is_new,
+        similarity,
+        energy: concept.energy,
+        frequency: concept.truth.frequency(),
+        confidence: concept.truth.confidence(),
+        state: concept.state.css_class().to_string(),
+        score: None,
+        entity_type: concept.entity_type.map(|t| t.css_class().to_string()),
+    });
+
+    if let Some(entity_type) = entity_type {
+        let index = result.concept_details.len() - 1;
+        result.concept_details[index].entity_type = Some(entity_type.css_class().to_string());
+        let typed = link_instance_to_type(kb, id, entity_type, citation);
+        if let Some(info) = typed.type_concept {
+            result.concept_details.push(info);
+        }
+        if let Some((info, _)) = typed.link {
+            result.link_details.push(info);
+        }
+    }
+    id
+}
+
+/// Cita a fonte num link estrutural existente ou cria um novo.
+fn upsert_link(
+    kb: &mut KnowledgeBase,
+    kind: LinkKind,
+    subject: ConceptId,
+    object: ConceptId,
+    citation: &Citation,
+    result: &mut StructureResult,
+) {
+    if let Some(link) = kb.find_link_mut(&kind, subject, object) {
+        link.provenance.cite(citation);
+        return;
+    }
+    let mut link = Link::new(
+        kind.clone(),
+        vec![
+            Participant {
+                concept_id: subject,
+                role: Role::Subject,
+            },
+            Participant {
+                concept_id: object,
+                role: Role::Object,
+            },
+        ],
+        TruthValue::observed(true),
+    )
+    .with_origin(LinkOrigin::Structural);
+    link.provenance.cite(citation);
+    let label = |id: ConceptId| {
+        kb.concepts
+            .get(&id)
+            .map(|c| c.label.clone())
+            .unwrap_or_default()
+    };
+    let info = NluLinkInfo {
+        id: link.id.to_string(),
+        source_id: subject.to_string(),
+        source_label: label(subject),
+        target_id: object.to_string(),
+        target_label: label(object),
+        kind: match &kind {
+            LinkKind::Custom(name) => name.clone(),
+            other => format!("{:?}", other),
+        },
+        frequency: link.truth.frequency(),
+        confidence: link.truth.confidence(),
+        energy: link.energy,
+    };
+    tracing::debug!(link = %kb.describe_link(&link), "Link estrutural criado");
+    kb.add_link(link);
+    result.link_details.push(info);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::provenance::forget_document;
+    use crate::document::{ExtractedDocument, Section};
+
+    #[test]
+    fn structure_links_authors_and_sections_and_is_forgotten_with_the_document() {
+        let section = |headings: &[&str], page| Section {
+            headings: headings.iter().map(|h| h.to_string()).collect(),
+            text: "Texto.".into(),
+            page: Some(page),
+        };
+        let mut extracted = ExtractedDocument::from_sections(vec![
+            section(&["Capítulo 3"], 4),
+            section(&["Capítulo 3", "Irrigação"], 5),
+        ]);
+        extracted.metadata.title = Some("Manejo do Solo".into());
+        extracted.metadata.authors = vec!["Maria Souza".into()];
+        extracted.metadata.keywords = vec!["solo".into()];
+        let structure = DocumentStructure::from_metadata(&extracted.metadata, "manejo.pdf");
+        assert_eq!(structure.sections.len(), 2);
+        assert_eq!(
+            structure.section_label(&structure.sections[1].0),
+            "Capítulo 3 › Irrigação — Manejo do Solo"
+        );
+
+        let mut kb = KnowledgeBase::new();
+        let source = Source::Document("abc".into());
+        let result = apply(&mut kb, &structure, &[], &source);
+        let document = result.document.unwrap();
+        let chapter = result.sections["Capítulo 3"];
+        let irrigation = result.sections["Capítulo 3 › Irrigação"];
+        let author = kb.find_concept_by_label("Maria Souza").unwrap().id;
+        assert!(kb.link_exists(&LinkKind::Custom("autor de".into()), author, document));
+        assert!(kb.link_exists(&LinkKind::PartOf, irrigation, chapter));
+        assert!(kb.link_exists(&LinkKind::PartOf, chapter, document));
+        assert_eq!(
+            kb.concepts[&document].entity_type,
+            Some(EntityType::Document)
+        );
+
+        let gotejamento = kb.add_concept(Concept::new("gotejamento".into(), TruthValue::proto()));
+        let links = link_mentions(
+            &mut kb,
+            irrigation,
+            &[gotejamento, irrigation],
+            &Citation::from(source),
+        );
+        assert_eq!(links.len(), 1);
+        assert!(kb
+            .links
+            .values()
+            .all(|l| l.origin == LinkOrigin::Structural || l.kind == LinkKind::InstanceOf));
+
+        forget_document(&mut kb, "abc");
+        assert!(kb.find_concept_by_label("Manejo do Solo").is_none());
+        assert!(kb
+            .find_concept_by_label("Capítulo 3 — Manejo do Solo")
+            .is_none());
+        assert!(kb.links_for_concept(gotejamento).is_empty());
+        // Conceito-tipo e link InstanceOf vieram só deste documento
+        assert!(kb
+            .find_concept_by_label(EntityType::Person.label())
+            .is_none());
+        assert!(kb.links.values().all(|l| l.kind != LinkKind::InstanceOf));
+    }
+}
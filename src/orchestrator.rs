@@ -12,32 +12,68 @@
 //!   ├── 1. SEMEADURA (Intent::Narrating)
 //!   │   └── NLU processa → extrai entidades → cria/reforça conceitos
 //!   │   └── LLM gera resposta natural baseada nos conceitos
+//!   │   └── Resposta verificada contra a KB (alucinação)
 //!   │
 //!   ├── 2. FOTOSSINTESE (Inferência)
 //!   │   └── InferenceEngine deduz/induz novos links
 //!   │
 //!   ├── 3. GERMINACAO (Perguntas reflexivas a cada ~2 turnos)
-//!   │   └── QuestionGenerator cria perguntas para conceitos incertos
+//!   │   └── gaps::analyze ordena lacunas da KB por ganho de informação
+//!   │   └── Lacunas abaixo de MIN_QUESTION_GAIN não viram pergunta
+//!   │   └── LLM formula a pergunta (template do QuestionGenerator offline)
 //!   │
 //!   ├── 4. CONFIRMACAO/NEGACAO (Intent::Confirming/Denying)
 //!   │   └── Ajusta TruthValues dos conceitos recentes
+//!   │   └── Correção (Intent::Correcting) = negação + nova semeadura
 //!   │
 //!   └── 5. PODA (Decay a cada ~10 turnos)
 //!       └── Conceitos inativos perdem energia
+//!       └── Itens esmaecidos entram na revisão espaçada (/revisao)
 //! ```
+//!
+//! ## Roteamento por Intent
+//!
+//! | Intent | Tratamento |
+//! |--------|-----------|
+//! | Narrating, Defining | Semeadura + fotossíntese |
+//! | Correcting | Negação dos conceitos recentes + semeadura + fotossíntese |
+//! | Querying, Comparing, Commanding | Consulta (ou modo agente, se ativo) |
+//! | Confirming, Denying | Revisão dos TruthValues recentes |
+//! | Greeting | Saudação, sem tocar na KB |
+//!
+//! A classificação completa (score e 2º colocado) fica em
+//! [`Orchestrator::last_intent`] para o feedback de intenção na interface.
+//!
+//! Cada turno é registrado no [`ConversationContext`]: as chamadas ao LLM
+//! recebem o histórico recente como mensagens e o resumo corrente da
+//! conversa, e perguntas de continuação têm suas referências resolvidas.
 
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 use std::sync::Arc;
 
 use anyhow::Result;
+use chrono::Utc;
 use parking_lot::RwLock;
 
+use crate::agent::{self, AgentConfig};
 use crate::core::concept::ConceptId;
-use crate::core::{KnowledgeBase, TruthValue};
+use crate::core::{review, KnowledgeBase, TruthValue};
 use crate::inference::InferenceEngine;
-use crate::nlu::intent::Intent;
+use crate::nlu::context::{self, ConversationContext};
+use crate::nlu::gaps;
+use crate::nlu::intent::{Intent, IntentClassification};
+use crate::nlu::language::Language;
+use crate::nlu::prompts::{PromptTask, PromptVars};
 use crate::nlu::NluPipeline;
 
+/// Ganho esperado mínimo para uma lacuna virar pergunta — abaixo disso a
+/// KB já está bem resolvida e a pergunta só interrompe a conversa.
+const MIN_QUESTION_GAIN: f64 = 0.05;
+
+/// Quantas lacunas já perguntadas o orquestrador lembra (as mais antigas
+/// saem primeiro).
+const MAX_ASKED_GAPS: usize = 64;
+
 /// Mensagem no chat — o resultado de cada processamento pelo orquestrador.
 pub struct ChatMessage {
     /// Role semântica da mensagem.
@@ -61,6 +97,10 @@ pub enum MessageRole {
     Alert,
     /// Resposta gerada pelo LLM.
     Assistant,
+    /// Verificação da resposta do LLM contra a KB (confiança + afirmações sem suporte).
+    Verification,
+    /// Chamada de ferramenta do modo agente sobre a KB.
+    Tool,
 }
 
 /// Orquestrador do ciclo de cultivo epistêmico.
@@ -73,12 +113,21 @@ pub struct Orchestrator {
     last_discussed: Vec<ConceptId>,
     /// Fila FIFO de perguntas pendentes.
     pending_questions: VecDeque<String>,
+    /// Chaves das lacunas já perguntadas, da mais antiga à mais recente
+    /// (não repetir a mesma pergunta).
+    asked_gaps: VecDeque<String>,
     /// Turnos desde a última pergunta reflexiva.
     turns_since_question: u32,
     /// Total de turnos na conversa atual.
     total_turns: u32,
     /// Turnos desde o último ciclo de poda.
     turns_since_decay: u32,
+    /// Configuração do modo agente (perguntas respondidas com ferramentas).
+    agent: AgentConfig,
+    /// Histórico multi-turno, resumo corrente e conceitos em foco.
+    context: ConversationContext,
+    /// Classificação de intenção da última mensagem.
+    last_intent: Option<IntentClassification>,
 }
 
 impl Orchestrator {
@@ -89,12 +138,21 @@ impl Orchestrator {
             kb,
             last_discussed: Vec::new(),
             pending_questions: VecDeque::new(),
+            asked_gaps: VecDeque::new(),
             turns_since_question: 0,
             total_turns: 0,
             turns_since_decay: 0,
+            agent: AgentConfig::from_env(),
+            context: ConversationContext::from_env(),
+            last_intent: None,
         }
     }
 
+    /// Classificação de intenção da última mensagem processada.
+    pub fn last_intent(&self) -> Option<&IntentClassification> {
+        self.last_intent.as_ref()
+    }
+
     /// Processa uma mensagem do usuário e retorna as respostas do sistema.
     pub async fn process_message(&mut self, user_text: &str) -> Result<Vec<ChatMessage>> {
         let mut responses = Vec::new();
@@ -102,7 +160,15 @@ impl Orchestrator {
         self.turns_since_question += 1;
         self.turns_since_decay += 1;
 
-        let intent = self.nlu.classify_intent(user_text).await?;
+        let classification = self.nlu.classify_intent(user_text).await?;
+        tracing::info!(
+            intent = %classification.describe(),
+            method = ?classification.method,
+            "Intent classificado"
+        );
+        let intent = classification.intent;
+        let language = classification.language;
+        self.last_intent = Some(classification);
 
         match intent {
             Intent::Confirming => {
@@ -111,22 +177,32 @@ impl Orchestrator {
             Intent::Denying => {
                 responses.extend(self.handle_confirmation(false));
             }
-            Intent::Querying => {
-                responses.extend(self.handle_query(user_text).await?);
+            Intent::Correcting => {
+                responses.extend(self.handle_confirmation(false));
+                responses.extend(self.handle_narration(user_text).await?);
             }
-            Intent::Narrating => {
+            Intent::Querying | Intent::Comparing | Intent::Commanding => {
+                responses.extend(self.handle_query(user_text, language).await?);
+            }
+            Intent::Narrating | Intent::Defining => {
                 responses.extend(self.handle_narration(user_text).await?);
             }
+            Intent::Greeting => {
+                responses.push(greeting(language));
+            }
         }
 
         // Fotossintese — inferência após narração
-        if intent == Intent::Narrating {
+        if matches!(
+            intent,
+            Intent::Narrating | Intent::Defining | Intent::Correcting
+        ) {
             responses.extend(self.run_inference());
         }
 
         // Germinacao — perguntas reflexivas a cada ~2 turnos
         if self.turns_since_question >= 2 {
-            if let Some(question) = self.generate_question() {
+            if let Some(question) = self.generate_question(language).await {
                 responses.push(ChatMessage {
                     role: MessageRole::Question,
                     content: question,
@@ -141,15 +217,53 @@ impl Orchestrator {
             self.turns_since_decay = 0;
         }
 
+        self.record_turn(user_text, &responses).await;
+
         Ok(responses)
     }
 
+    /// Registra o turno no contexto conversacional e incorpora ao resumo
+    /// as mensagens que saíram da janela de histórico.
+    async fn record_turn(&mut self, user_text: &str, responses: &[ChatMessage]) {
+        let reply: Vec<&str> = responses
+            .iter()
+            .filter(|m| m.role == MessageRole::Assistant)
+            .map(|m| m.content.as_str())
+            .collect();
+        let evicted = self.context.record_turn(user_text, Some(&reply.join("\n")));
+        if evicted.is_empty() {
+            return;
+        }
+
+        let vars =
+            PromptVars::new(context::transcript(&evicted)).with("history", self.context.summary());
+        let summary = match self.nlu.chat(PromptTask::Summary, &vars, &[]).await {
+            Ok(summary) => summary,
+            Err(e) => {
+                tracing::warn!(error = %e, "Falha ao resumir conversa, usando resumo truncado");
+                context::fallback_summary(self.context.summary(), &evicted)
+            }
+        };
+        self.context.set_summary(summary);
+    }
+
     /// Processa uma mensagem narrativa (informativa).
     async fn handle_narration(&mut self, text: &str) -> Result<Vec<ChatMessage>> {
         let mut messages = Vec::new();
 
         // Processa via NLU — cria/reforça conceitos, cria links
-        let result = self.nlu.process_message(text, &self.kb).await?;
+        let result = self
+            .nlu
+            .process_message(text, &self.kb, &self.last_discussed)
+            .await?;
+
+        // Reporta correferências resolvidas (pronomes → conceitos)
+        for reference in &result.coreferences {
+            messages.push(ChatMessage {
+                role: MessageRole::System,
+                content: format!("Correferência: {}", reference),
+            });
+        }
 
         // Reporta conceitos cristalizados (novos)
         for msg in &result.messages {
@@ -179,13 +293,16 @@ impl Orchestrator {
         {
             let kb_read = self.kb.read();
             let mut discussed = Vec::new();
+            let mut labels = Vec::new();
             for name in result.new_concepts.iter().chain(result.reinforced_concepts.iter()) {
                 let base_name = name.split(" (").next().unwrap_or(name).split(" →").next().unwrap_or(name);
                 if let Some(concept) = kb_read.find_concept_by_label(base_name) {
                     discussed.push(concept.id);
+                    labels.push(concept.label.clone());
                 }
             }
             self.last_discussed = discussed;
+            self.context.set_focus(labels);
         }
 
         // Gera resposta natural via LLM
@@ -197,19 +314,24 @@ impl Orchestrator {
             .collect();
 
         if !concept_list.is_empty() {
-            let system_prompt = format!(
-                "Você é um assistente de cultivo epistêmico. O usuário acabou de dizer algo e o sistema extraiu os seguintes conceitos: [{}].\n\
-                Gere uma resposta curta (1-2 frases) reconhecendo o que foi dito e conectando com conceitos existentes na base de conhecimento.\n\
-                Responda em português brasileiro.",
-                concept_list.join(", ")
-            );
-
-            match self.nlu.chat(&system_prompt, text).await {
+            // Template prompts/narration.prompt
+            let vars = PromptVars::new(text)
+                .with("concepts", concept_list.join(", "))
+                .with("links", result.new_links.join("; "))
+                .with("language", result.language.prompt_name());
+
+            match self
+                .nlu
+                .chat(PromptTask::Narration, &vars, &self.context.history())
+                .await
+            {
                 Ok(llm_response) => {
+                    let verification = self.verify_llm_response(&llm_response).await;
                     messages.push(ChatMessage {
                         role: MessageRole::Assistant,
                         content: llm_response,
                     });
+                    messages.extend(verification);
                 }
                 Err(e) => {
                     tracing::warn!(error = %e, "Falha ao gerar resposta LLM para narração");
@@ -282,13 +404,50 @@ impl Orchestrator {
     }
 
     /// Processa uma query/pergunta do usuário.
-    async fn handle_query(&self, text: &str) -> Result<Vec<ChatMessage>> {
+    ///
+    /// Pronomes e elipses ("e por que isso acontece?") são resolvidos pelo
+    /// conceito em foco antes de embeddar a consulta; o LLM recebe o texto
+    /// original junto com o histórico da conversa. No modo agente, o foco
+    /// passa a ser os conceitos que as ferramentas devolveram. `language` é
+    /// o idioma já detectado na classificação de intenção.
+    async fn handle_query(&mut self, text: &str, language: Language) -> Result<Vec<ChatMessage>> {
+        let history = self.context.history();
         let mut messages = Vec::new();
 
-        let embedding = self.nlu.embed_query(text).await?;
+        let resolved = self.context.resolve_references(text);
+        if resolved != text {
+            messages.push(ChatMessage {
+                role: MessageRole::System,
+                content: format!("Entendi como: \"{}\"", resolved),
+            });
+        }
+
+        if self.agent.enabled {
+            match agent::run(
+                &self.nlu,
+                &self.kb,
+                &self.agent,
+                &resolved,
+                language,
+                &history,
+            )
+            .await
+            {
+                Ok(outcome) => {
+                    self.context.set_focus(outcome.concepts());
+                    messages.extend(self.agent_messages(outcome).await);
+                    return Ok(messages);
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "Modo agente falhou, usando consulta clássica");
+                }
+            }
+        }
+
+        let embedding = self.nlu.embed_query(&resolved).await?;
 
         // Coleta dados da KB em escopo fechado (sem manter guard across await)
-        let concept_descriptions = {
+        let matches = {
             let kb = self.kb.read();
 
             let mut matches: Vec<(String, String, f32, f64, Vec<String>)> = Vec::new();
@@ -310,48 +469,64 @@ impl Orchestrator {
             }
             matches.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
 
+            matches.truncate(5);
             matches
-                .into_iter()
-                .take(5)
-                .map(|(label, truth, sim, energy, link_desc)| {
-                    let link_text = if link_desc.is_empty() {
-                        String::new()
-                    } else {
-                        format!(" | Links: {}", link_desc.join("; "))
-                    };
-                    format!("- {} {} (sim={:.2}, energia={:.2}){}", label, truth, sim, energy, link_text)
-                })
-                .collect::<Vec<String>>()
         }; // kb guard dropped here
 
-        if concept_descriptions.is_empty() {
+        if matches.is_empty() {
             messages.push(ChatMessage {
                 role: MessageRole::System,
                 content: "Não encontrei conceitos relacionados na base de conhecimento.".into(),
             });
         } else {
-            // Gera resposta natural via LLM
-            let system_prompt = format!(
-                "Você é um assistente de cultivo epistêmico. O usuário perguntou: \"{}\".\n\
-                Os conceitos mais relevantes na base de conhecimento são:\n{}\n\
-                Gere uma resposta informativa em português brasileiro baseada nesses conceitos.",
-                text,
-                concept_descriptions.join("\n")
-            );
-
-            match self.nlu.chat(&system_prompt, text).await {
+            let concept_lines: Vec<String> = matches
+                .iter()
+                .map(|(label, truth, sim, energy, _)| {
+                    format!(
+                        "- {} {} (sim={:.2}, energia={:.2})",
+                        label, truth, sim, energy
+                    )
+                })
+                .collect();
+            let mut link_lines: Vec<String> = Vec::new();
+            for (_, _, _, _, link_desc) in &matches {
+                for desc in link_desc {
+                    let line = format!("- {}", desc);
+                    if !link_lines.contains(&line) {
+                        link_lines.push(line);
+                    }
+                }
+            }
+
+            self.context
+                .set_focus(matches.iter().map(|(label, ..)| label.clone()).collect());
+
+            // Gera resposta natural via LLM (template prompts/query.prompt)
+            let vars = PromptVars::new(text)
+                .with("concepts", concept_lines.join("\n"))
+                .with("links", link_lines.join("\n"))
+                .with("language", language.prompt_name());
+
+            match self.nlu.chat(PromptTask::Query, &vars, &history).await {
                 Ok(llm_response) => {
+                    let verification = self.verify_llm_response(&llm_response).await;
                     messages.push(ChatMessage {
                         role: MessageRole::Assistant,
                         content: llm_response,
                     });
+                    messages.extend(verification);
                 }
                 Err(e) => {
                     tracing::warn!(error = %e, "Falha ao gerar resposta LLM para query");
-                    for desc in &concept_descriptions {
+                    for (line, (_, _, _, _, link_desc)) in concept_lines.iter().zip(&matches) {
+                        let content = if link_desc.is_empty() {
+                            line.clone()
+                        } else {
+                            format!("{} | Links: {}", line, link_desc.join("; "))
+                        };
                         messages.push(ChatMessage {
                             role: MessageRole::System,
-                            content: desc.clone(),
+                            content,
                         });
                     }
                 }
@@ -361,6 +536,54 @@ impl Orchestrator {
         Ok(messages)
     }
 
+    /// Verifica a resposta do LLM contra a KB e gera a mensagem de verificação.
+    ///
+    /// Falhas de verificação (ex: LM Studio indisponível para embeddings)
+    /// não impedem a resposta — apenas são logadas.
+    async fn verify_llm_response(&self, response: &str) -> Option<ChatMessage> {
+        match self.nlu.verify_answer(response, &self.kb).await {
+            Ok(report) if report.entity_count() > 0 => Some(ChatMessage {
+                role: MessageRole::Verification,
+                content: report.summary(),
+            }),
+            Ok(_) => None,
+            Err(e) => {
+                tracing::warn!(error = %e, "Falha ao verificar resposta do LLM contra a KB");
+                None
+            }
+        }
+    }
+
+    /// Converte o resultado do agente em mensagens de chat: uma mensagem
+    /// [`MessageRole::Tool`] por chamada de ferramenta, a resposta final e
+    /// a verificação contra a KB.
+    async fn agent_messages(&self, outcome: agent::AgentOutcome) -> Vec<ChatMessage> {
+        let mut messages: Vec<ChatMessage> = outcome
+            .steps
+            .into_iter()
+            .map(|step| ChatMessage {
+                role: MessageRole::Tool,
+                content: format!("{}({}) → {}", step.tool, step.arguments, step.summary),
+            })
+            .collect();
+
+        if outcome.answer.trim().is_empty() {
+            messages.push(ChatMessage {
+                role: MessageRole::System,
+                content: "O agente não produziu uma resposta.".into(),
+            });
+            return messages;
+        }
+
+        let verification = self.verify_llm_response(&outcome.answer).await;
+        messages.push(ChatMessage {
+            role: MessageRole::Assistant,
+            content: outcome.answer,
+        });
+        messages.extend(verification);
+        messages
+    }
+
     /// Executa um ciclo de inferência (fotossíntese).
     fn run_inference(&self) -> Vec<ChatMessage> {
         let mut messages = Vec::new();
@@ -383,26 +606,52 @@ impl Orchestrator {
     }
 
     /// Gera uma pergunta reflexiva (germinação).
-    fn generate_question(&mut self) -> Option<String> {
+    ///
+    /// Perguntas pendentes têm prioridade; senão escolhe a lacuna de maior
+    /// ganho ainda não perguntada e pede ao LLM que a formule. Sem LLM (ou
+    /// com resposta que não seja pergunta), usa o template da lacuna.
+    ///
+    /// Sem lacuna com ganho ≥ [`MIN_QUESTION_GAIN`], não pergunta nada.
+    /// Lacunas que deixaram de existir (a KB mudou) são esquecidas e podem
+    /// ser perguntadas de novo se voltarem.
+    async fn generate_question(&mut self, language: Language) -> Option<String> {
         if let Some(q) = self.pending_questions.pop_front() {
             return Some(q);
         }
 
-        let kb = self.kb.read();
-        let candidates = kb.question_candidates();
-
-        if let Some(concept) = candidates.first() {
-            Some(
-                self.nlu
-                    .question_generator
-                    .for_concept(concept),
-            )
-        } else {
-            None
+        let gap = {
+            let kb = self.kb.read();
+            let gaps = gaps::analyze(&kb, &self.nlu.question_generator);
+            let current: HashSet<String> = gaps.iter().map(|g| g.key()).collect();
+            self.asked_gaps.retain(|key| current.contains(key));
+            gaps.into_iter()
+                .take_while(|g| g.gain >= MIN_QUESTION_GAIN)
+                .find(|g| !self.asked_gaps.contains(&g.key()))
+        }?;
+        if self.asked_gaps.len() >= MAX_ASKED_GAPS {
+            self.asked_gaps.pop_front();
+        }
+        self.asked_gaps.push_back(gap.key());
+        tracing::debug!(gap = %gap.description, gain = gap.gain, "Lacuna escolhida para pergunta");
+
+        let vars = PromptVars::new(gap.description.clone())
+            .with("concepts", gap.concepts.join(", "))
+            .with("links", gap.links.join("; "))
+            .with("history", self.context.summary())
+            .with("language", language.prompt_name());
+        match self.nlu.chat(PromptTask::Question, &vars, &[]).await {
+            Ok(answer) => Some(gaps::parse_question(&answer).unwrap_or(gap.fallback)),
+            Err(e) => {
+                tracing::debug!("Pergunta via LLM indisponível: {}", e);
+                Some(gap.fallback)
+            }
         }
     }
 
     /// Executa um ciclo de poda (decay).
+    ///
+    /// Conceitos que entram em Fading viram cartões da revisão espaçada;
+    /// o alerta aponta para a página `/revisao` com o total pendente.
     fn run_decay(&mut self) -> Vec<ChatMessage> {
         let mut messages = Vec::new();
         let mut kb = self.kb.write();
@@ -413,7 +662,7 @@ impl Orchestrator {
                 messages.push(ChatMessage {
                     role: MessageRole::Alert,
                     content: format!(
-                        "'{}' está esmaecendo (energia: {:.2}). Deseja reforçar?",
+                        "'{}' está esmaecendo (energia: {:.2}).",
                         concept.label, concept.energy
                     ),
                 });
@@ -421,11 +670,13 @@ impl Orchestrator {
         }
 
         if !newly_fading.is_empty() {
+            let due = review::due_cards(&kb, Utc::now()).len();
             messages.push(ChatMessage {
                 role: MessageRole::Alert,
                 content: format!(
-                    "Poda: {} conceitos entrando em Fading.",
-                    newly_fading.len()
+                    "Poda: {} conceitos entrando em Fading. {} itens aguardam revisão em /revisao.",
+                    newly_fading.len(),
+                    due
                 ),
             });
         }
@@ -437,9 +688,12 @@ impl Orchestrator {
     pub fn reset(&mut self) {
         self.last_discussed.clear();
         self.pending_questions.clear();
+        self.asked_gaps.clear();
         self.turns_since_question = 0;
         self.total_turns = 0;
         self.turns_since_decay = 0;
+        self.context.clear();
+        self.last_intent = None;
     }
 
     /// Reforça um conceito manualmente (acionado pela sidebar).
@@ -456,3 +710,19 @@ impl Orchestrator {
         }
     }
 }
+
+/// Resposta a uma saudação — não passa pelo LLM nem altera a KB.
+fn greeting(language: Language) -> ChatMessage {
+    let content = match language {
+        Language::Portuguese => {
+            "Olá! Conte-me algo que você sabe ou pergunte sobre o que já aprendi."
+        }
+        Language::English => {
+            "Hello! Tell me something you know or ask about what I have learned so far."
+        }
+    };
+    ChatMessage {
+        role: MessageRole::Assistant,
+        content: content.into(),
+    }
+}
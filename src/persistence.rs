@@ -5,54 +5,117 @@
 //!
 //! ## Formato de Armazenamento
 //!
-//! A KB é salva como JSON "pretty-printed" para facilitar inspeção manual.
+//! A KB é salva como JSON compacto: com os embeddings, a versão
+//! "pretty-printed" era várias vezes maior e lenta de reescrever
+//! (`jq . data/kb.json` a formata para inspeção manual).
 //! O índice `concept_links` é marcado `#[serde(skip)]` e reconstruído
-//! após carregamento via [`KnowledgeBase::rebuild_index()`].
+//! após carregamento via [`KnowledgeBase::rebuild_index()`]. O registro
+//! de documentos lidos (`documents`) vai junto no mesmo arquivo.
 //!
 //! ## Quando a KB é Salva?
 //!
 //! - Após cada mensagem processada pelo orquestrador
-//! - Após ingestão completa de um PDF
+//! - Durante a ingestão de um documento, no máximo a cada
+//!   `KB_SAVE_INTERVAL_SECS` ([`SaveThrottle`]), sempre antes do
+//!   [checkpoint](crate::checkpoint), e ao fim, cancelamento ou falha
 //!
-//! ## ⚠️ Atomicidade
+//! ## Atomicidade
 //!
-//! A escrita **não é atômica** — crash durante escrita pode corromper
-//! o arquivo. Aceitável para PoC; produção usaria write-rename pattern.
+//! A escrita vai para um arquivo temporário em `data/` e é renomeada sobre o arquivo
+//! anterior ([`crate::checkpoint::write_atomic`]): um crash no meio deixa
+//! a versão anterior intacta.
+//!
+//! Salvamentos são serializados por um mutex que cobre o snapshot **e** a
+//! escrita: com dois jobs de ingestão, um snapshot antigo nunca é
+//! renomeado por cima de um mais novo (o que faria o checkpoint do outro
+//! job apontar para chunks que não estão mais no disco).
+//!
+//! ## Restauração
+//!
+//! O servidor inicia com a KB vazia, a menos que `KB_RESTORE=1`: aí
+//! [`load_kb()`] restaura a KB salva. Os checkpoints de ingestões
+//! interrompidas sobrevivem ao reinício nos dois casos (só os de
+//! documentos já registrados como concluídos são removidos); com a KB
+//! restaurada, a retomada continua exatamente de onde parou.
 
 use std::path::Path;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 
 use crate::core::KnowledgeBase;
+use crate::nlu::stemmer;
 
 /// Caminho do arquivo de persistência da KB (relativo à raiz do projeto).
 const KB_PATH: &str = "data/kb.json";
 
-/// Salva a KnowledgeBase em disco como JSON pretty-printed.
+/// Ordena os salvamentos: snapshot e escrita acontecem sob o mesmo lock.
+static SAVE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Salva a KnowledgeBase em disco como JSON compacto.
 ///
 /// Cria o diretório `data/` se não existir. Adquire um read lock
-/// na KB — múltiplas leituras simultâneas são permitidas.
+/// na KB — múltiplas leituras simultâneas são permitidas. A escrita é
+/// atômica (arquivo temporário + `rename`) e ordenada: quem tira o
+/// snapshot depois escreve depois (um lock global de salvamento).
 ///
 /// # Erros
 ///
 /// Retorna erro se não conseguir criar o diretório, serializar,
 /// ou escrever no arquivo.
 pub fn save_kb(kb: &Arc<RwLock<KnowledgeBase>>) -> Result<()> {
-    let path = Path::new(KB_PATH);
-    // Garante que o diretório data/ existe
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)
-            .context("Falha ao criar diretório data/")?;
+    let _ordered = SAVE_LOCK.lock();
+    // Serializa sob read lock — múltiplas leituras simultâneas são OK
+    let json = {
+        let kb_read = kb.read();
+        serde_json::to_vec(&*kb_read)
+            .context("Falha ao serializar KnowledgeBase")?
+    };
+    crate::checkpoint::write_atomic(Path::new(KB_PATH), &json)
+        .context("Falha ao escrever data/kb.json")
+}
+
+/// Limita os salvamentos da KB durante uma ingestão.
+///
+/// Reescrever a KB inteira após cada chunk custa O(chunks × KB). A
+/// ingestão salva a KB (e só depois o checkpoint) quando o intervalo
+/// venceu; um crash reaplica no máximo os chunks desse intervalo.
+///
+/// | Variável | Default |
+/// |----------|---------|
+/// | `KB_SAVE_INTERVAL_SECS` | `10` (`0` = após cada chunk) |
+#[derive(Clone, Debug)]
+pub struct SaveThrottle {
+    /// Intervalo mínimo entre salvamentos.
+    interval: Duration,
+    /// Último salvamento.
+    last: Instant,
+}
+
+impl SaveThrottle {
+    /// Cria o limitador a partir de variáveis de ambiente.
+    pub fn from_env() -> Self {
+        let secs = std::env::var("KB_SAVE_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(10);
+        Self {
+            interval: Duration::from_secs(secs),
+            last: Instant::now(),
+        }
+    }
+
+    /// `true` se o intervalo desde o último salvamento venceu.
+    pub fn due(&self) -> bool {
+        self.last.elapsed() >= self.interval
+    }
+
+    /// Registra um salvamento.
+    pub fn saved(&mut self) {
+        self.last = Instant::now();
     }
-    // Adquire read lock — múltiplas leituras simultâneas são OK
-    let kb_read = kb.read();
-    let json = serde_json::to_string_pretty(&*kb_read)
-        .context("Falha ao serializar KnowledgeBase")?;
-    std::fs::write(path, json)
-        .context("Falha ao escrever data/kb.json")?;
-    Ok(())
 }
 
 /// Carrega a KnowledgeBase do disco, ou cria uma vazia se não existir.
@@ -76,5 +139,13 @@ pub fn load_kb() -> Result<KnowledgeBase> {
         .context("Falha ao desserializar data/kb.json")?;
     // Reconstrói o índice concept_links (não serializado)
     kb.rebuild_index();
+    // KBs anteriores ao stemmer não têm chave canônica (o RSLP só vale para pt-BR)
+    for concept in kb
+        .concepts
+        .values_mut()
+        .filter(|c| c.key.is_empty() && c.language.as_deref() != Some("en"))
+    {
+        concept.key = stemmer::canonical_key(&concept.label);
+    }
     Ok(kb)
 }
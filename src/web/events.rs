@@ -1,31 +1,52 @@
-//! # Eventos SSE de Ingestão de PDF
+//! # Eventos SSE de Ingestão de Documentos
 //!
 //! Define o enum [`IngestionEvent`] — todos os eventos emitidos durante
-//! o processamento de um PDF, enviados em tempo real ao frontend via
-//! Server-Sent Events (SSE).
+//! o processamento de um documento (PDF, Markdown, HTML, DOCX, EPUB...),
+//! enviados em tempo real ao frontend via Server-Sent Events (SSE).
+//! Cada evento viaja dentro de um [`JobEvent`], marcado com o ID do job
+//! de ingestão ([`crate::jobs`]) que o emitiu.
 //!
 //! ## Ciclo de Vida dos Eventos
 //!
 //! ```text
-//! Started → [ChunkStarted → ConceptCreated* → ConceptReinforced*
-//!            → LinkCreated* → ChunkCompleted]×N → Completed
-//!                                          ou → Error
+//! Queued → Started → [ChunkStarted → ConceptCreated* → ConceptReinforced*
+//!                     → LinkCreated* → ChunkCompleted]×N → Completed
+//!                                                   ou → Cancelled
+//!                                                   ou → Error
 //! ```
 //!
+//! Em PDFs, a extração corre junto com a ingestão: `PageExtracted` (ou
+//! `PageFailed`) chega a cada página, intercalado com os eventos de
+//! chunk, e `ExtractionCompleted` fecha o total de chunks. Até lá, o
+//! `total` dos eventos de chunk conta só os chunks já conhecidos.
+//!
 //! ## Serialização
 //!
-//! Usa `#[serde(tag = "type")]` para produzir JSON com discriminador:
+//! Usa `#[serde(tag = "type")]` para produzir JSON com discriminador, e
+//! o `job_id` do [`JobEvent`] entra achatado (`#[serde(flatten)]`):
 //!
 //! ```json
-//! { "type": "ConceptCreated", "id": "uuid", "label": "IA" }
+//! { "job_id": "uuid", "type": "ConceptCreated", "id": "uuid", "label": "IA" }
 //! ```
 //!
 //! O frontend (JavaScript) faz `JSON.parse(e.data)` e usa `ev.type`
-//! para decidir como renderizar cada evento.
+//! para decidir como renderizar cada evento, e `ev.job_id` para
+//! acompanhar um único job.
 
 use serde::Serialize;
+use uuid::Uuid;
 
-/// Evento emitido durante ingestão de PDF, enviado via SSE ao frontend.
+/// Evento de ingestão marcado com o job que o emitiu.
+#[derive(Clone, Debug, Serialize)]
+pub struct JobEvent {
+    /// ID do job de ingestão.
+    pub job_id: Uuid,
+    /// O evento em si (campos achatados no JSON).
+    #[serde(flatten)]
+    pub event: IngestionEvent,
+}
+
+/// Evento emitido durante ingestão de documento, enviado via SSE ao frontend.
 ///
 /// Cada variante corresponde a um momento específico do processamento.
 /// O frontend usa esses eventos para atualizar a barra de progresso,
@@ -33,15 +54,71 @@ use serde::Serialize;
 #[derive(Clone, Debug, Serialize)]
 #[serde(tag = "type")]
 pub enum IngestionEvent {
-    /// Ingestão iniciada — texto extraído e chunks calculados.
+    /// Job aceito e aguardando um worker livre.
+    Queued {
+        /// Nome do arquivo enviado.
+        filename: String,
+        /// Formato detectado.
+        format: String,
+        /// Jobs à frente na fila (0 = começa assim que houver worker).
+        position: usize,
+    },
+
+    /// Ingestão iniciada — documento aberto e metadados lidos.
     ///
-    /// É o primeiro evento emitido. O frontend usa para inicializar
-    /// a barra de progresso com o total de chunks.
+    /// É o primeiro evento emitido, antes do texto: em PDFs as páginas
+    /// ainda vão ser extraídas (`PageExtracted`). O frontend usa para
+    /// inicializar a barra de progresso.
     Started {
+        /// Formato detectado (`pdf`, `markdown`, `text`, `html`, `docx`, `epub`).
+        format: String,
+        /// SHA-256 do conteúdo (chave do registro e da proveniência).
+        document: String,
+        /// Título dos metadados, se houver.
+        title: Option<String>,
+        /// Número de páginas, em formatos paginados.
+        pages: Option<usize>,
+        /// Chunks já aplicados numa execução anterior (retomada de checkpoint).
+        resumed_from: usize,
+    },
+
+    /// Página extraída (formatos paginados) — chega enquanto os chunks
+    /// das páginas anteriores já estão sendo processados.
+    PageExtracted {
+        /// Página (1-indexed).
+        page: usize,
+        /// Total de páginas.
+        total_pages: usize,
+        /// Caracteres extraídos da página (0 = página sem texto).
+        chars: usize,
+        /// Chunks da página.
+        chunks: usize,
+        /// Chunks conhecidos até aqui.
+        total_chunks: usize,
+    },
+
+    /// Página que não pôde ser extraída; a ingestão segue com as demais.
+    PageFailed {
+        /// Página (1-indexed).
+        page: usize,
+        /// Total de páginas.
+        total_pages: usize,
+        /// Motivo da falha.
+        error: String,
+    },
+
+    /// Extração concluída — o total de chunks é definitivo.
+    ExtractionCompleted {
         /// Comprimento total do texto extraído (em caracteres).
         text_len: usize,
+        /// Número de seções (trechos sob um mesmo título; páginas, no PDF).
+        sections: usize,
         /// Número total de chunks após divisão.
         total_chunks: usize,
+        /// Páginas que falharam.
+        failed_pages: Vec<usize>,
+        /// Tempo de extração (ms), em paralelo com a ingestão.
+        extract_ms: u64,
     },
 
     /// Início do processamento de um chunk individual.
@@ -51,10 +128,20 @@ pub enum IngestionEvent {
     ChunkStarted {
         /// Número do chunk atual (1-indexed).
         chunk: usize,
-        /// Total de chunks.
+        /// Total de chunks (conhecidos até aqui, enquanto a extração corre).
         total: usize,
         /// Número de caracteres neste chunk.
         chars: usize,
+        /// Idioma detectado no chunk (`pt-BR`, `en`).
+        language: String,
+        /// Caminho de títulos da seção do chunk ("Capítulo 2 › Irrigação").
+        section: Option<String>,
+        /// Página do chunk (1-indexed), em formatos paginados.
+        page: Option<usize>,
+        /// Início (byte) do chunk no texto extraído.
+        start: usize,
+        /// Fim (byte, exclusivo) do chunk no texto extraído.
+        end: usize,
     },
 
     /// Novo conceito cristalizado na KB.
@@ -75,6 +162,8 @@ pub enum IngestionEvent {
         energy: f64,
         /// Estado CSS ("active", "dormant", "fading", "archived").
         state: String,
+        /// Tipo de entidade (`person`, `place`...), quando conhecido.
+        entity_type: Option<String>,
     },
 
     /// Conceito existente reforçado (entidade remapeada por similaridade).
@@ -126,7 +215,7 @@ pub enum IngestionEvent {
     ChunkCompleted {
         /// Número do chunk concluído.
         chunk: usize,
-        /// Total de chunks.
+        /// Total de chunks (conhecidos até aqui, enquanto a extração corre).
         total: usize,
         /// Conceitos novos neste chunk.
         new_concepts: usize,
@@ -139,6 +228,8 @@ pub enum IngestionEvent {
     /// É o evento final (exceto Error). Contém estatísticas completas
     /// da ingestão e métricas de hardware para exibição no frontend.
     Completed {
+        /// Formato do documento ingerido.
+        format: String,
         /// Total de chunks processados.
         total_chunks: usize,
         /// Total de novos conceitos criados.
@@ -149,7 +240,9 @@ pub enum IngestionEvent {
         kb_concepts: usize,
         /// Total de links na KB após ingestão.
         kb_links: usize,
-        /// Tempo de extração de texto do PDF (ms).
+        /// Páginas que não puderam ser extraídas.
+        failed_pages: Vec<usize>,
+        /// Tempo de extração de texto (ms), em paralelo com a ingestão.
         extract_ms: u64,
         /// Tempo de processamento NLU + KB (ms).
         ingestion_ms: u64,
@@ -180,6 +273,16 @@ pub enum IngestionEvent {
         throughput: String,
     },
 
+    /// Ingestão cancelada pelo usuário (`DELETE /jobs/{id}`).
+    ///
+    /// Os chunks já aplicados permanecem na KB.
+    Cancelled {
+        /// Chunks aplicados antes do cancelamento.
+        chunks_processed: usize,
+        /// Chunks conhecidos do documento (0 se cancelado na fila).
+        total_chunks: usize,
+    },
+
     /// Erro durante a ingestão.
     ///
     /// Pode ocorrer em qualquer fase. O frontend exibe como alerta
@@ -13,13 +13,23 @@
 //! | `metodologia` | GET | HTML estático | Artigo embutido |
 //! | `visualizador` | GET | HTML completo | Página visualizador |
 //! | `model_status` | GET | JSON | Polling de readiness |
-//! | `sse_events` | GET | SSE stream | Eventos de ingestão |
+//! | `sse_events` | GET | SSE stream | Eventos de ingestão (`?job=` filtra um job) |
 //! | `chat` | POST | HTMX fragment | Fragmento de mensagem |
-//! | `upload_pdf` | POST | HTMX fragment | Confirmação de upload |
+//! | `upload_document` | POST | HTMX fragment | Confirmação de upload (PDF, MD, TXT, HTML, DOCX, EPUB) |
+//! | `list_jobs` | GET | JSON | Jobs de ingestão e seus estados |
+//! | `cancel_job` | DELETE | JSON | Cancela (ou remove) um job |
 //! | `knowledge_sidebar` | GET | HTMX fragment | Conteúdo da sidebar |
 //! | `graph_data` | GET | JSON | Dados do grafo 3D |
+//! | `concept_attributes` | GET | JSON | Valores tipados dos conceitos |
 //! | `reinforce_concept` | POST | HTMX fragment | Feedback de reforço |
+//! | `intent_feedback` | POST | HTMX fragment | Ensina template de intent |
 //! | `reset_knowledge` | POST | HTMX fragment | Confirmação de reset |
+//! | `review_page` | GET | HTML completo | Página de revisão espaçada |
+//! | `documents_page` | GET | HTML completo | Documentos lidos pela KB |
+//! | `forget_document` | DELETE | HTMX fragment | Esquece um documento (retira sua evidência) |
+//! | `resume_document` | POST | HTMX fragment | Retoma uma ingestão interrompida do checkpoint |
+//! | `review_next` | GET | HTMX fragment | Próximo cartão de revisão |
+//! | `review_answer` | POST | HTMX fragment | Aplica resposta + próximo cartão |
 //!
 //! ## Guarda de Model Ready
 //!
@@ -30,7 +40,8 @@
 use std::convert::Infallible;
 use std::time::{Duration, Instant};
 
-use axum::extract::{Multipart, Path, State};
+use axum::extract::{Multipart, Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::sse::{Event as SseEvent, KeepAlive, Sse};
 use axum::response::Html;
 use axum::Json;
@@ -41,9 +52,15 @@ use uuid::Uuid;
 
 use super::state::AppState;
 use super::templates;
+use crate::checkpoint::CheckpointStore;
+use crate::core::provenance;
+use crate::core::review::{self, ReviewAnswer, ReviewTarget};
+use crate::core::ValueKind;
+use crate::document::{self, DocumentFormat};
+use crate::ingest::DocumentUpload;
+use crate::jobs::JobInfo;
+use crate::nlu::intent::Intent;
 use crate::orchestrator::MessageRole;
-use crate::pdf;
-use crate::web::events::IngestionEvent;
 
 /// Resposta do endpoint `/status` — indica se o modelo ML está pronto.
 #[derive(serde::Serialize)]
@@ -87,6 +104,10 @@ pub struct GraphConcept {
     pub state: String,
     /// Número de menções (exibido como tooltip).
     pub mention_count: u32,
+    /// Idioma de origem (`pt-BR`, `en`), quando conhecido.
+    pub language: Option<String>,
+    /// Tipo de entidade (`person`, `place`...) — cor/filtro por tipo.
+    pub entity_type: Option<String>,
 }
 
 /// Link serializado para o grafo 3D.
@@ -110,6 +131,45 @@ pub struct GraphLink {
     pub energy: f64,
 }
 
+// ─── Tipos para o endpoint /events ───────────────────────────────
+
+/// Filtro de `/events` (query string).
+#[derive(serde::Deserialize)]
+pub struct EventsQuery {
+    /// Só os eventos deste job (todos, se ausente).
+    pub job: Option<Uuid>,
+}
+
+// ─── Tipos para o endpoint /knowledge/attributes ─────────────────
+
+/// Filtros de `/knowledge/attributes` (query string).
+#[derive(serde::Deserialize)]
+pub struct AttributeQuery {
+    /// UUID ou label/alias do conceito.
+    pub concept: Option<String>,
+    /// Tipo do valor (`number`, `quantity`, `percentage`, `money`, `date`).
+    pub kind: Option<String>,
+}
+
+/// Atributo serializado com o conceito a que pertence.
+#[derive(serde::Serialize)]
+pub struct AttributeRow {
+    /// UUID do conceito.
+    pub concept_id: String,
+    /// Label do conceito.
+    pub label: String,
+    /// Tipo do valor.
+    pub kind: String,
+    /// Valor numérico normalizado.
+    pub value: Option<f64>,
+    /// Unidade ("dias", "%", "BRL").
+    pub unit: Option<String>,
+    /// Data ISO 8601 (possivelmente parcial).
+    pub date: Option<String>,
+    /// Trecho original do texto.
+    pub text: String,
+}
+
 /// Converte Maud Markup em resposta Html<String> do Axum.
 fn markup_to_html(m: maud::Markup) -> Html<String> {
     Html(m.into_string())
@@ -155,6 +215,13 @@ pub async fn visualizador() -> Html<String> {
     markup_to_html(templates::visualizador_page())
 }
 
+/// GET `/revisao` — Página de revisão espaçada.
+///
+/// Não depende do modelo ML: os cartões vêm direto da KB.
+pub async fn review_page() -> Html<String> {
+    markup_to_html(templates::review_page())
+}
+
 /// GET `/status` — Verifica se o modelo ML está pronto.
 ///
 /// Retorna JSON `{ "ready": true/false }`.
@@ -165,10 +232,11 @@ pub async fn model_status(State(state): State<AppState>) -> Json<StatusResponse>
     })
 }
 
-/// GET `/events` — Stream SSE de eventos de ingestão de PDF.
+/// GET `/events?job=<id>` — Stream SSE de eventos de ingestão.
 ///
 /// Cria um subscriber no canal broadcast e converte cada
-/// [`IngestionEvent`] em um `SseEvent` com JSON serializado.
+/// [`JobEvent`](crate::web::events::JobEvent) em um `SseEvent` com JSON
+/// serializado. Com `job`, só os eventos daquele job são enviados.
 ///
 /// ## Keep-Alive
 ///
@@ -181,10 +249,12 @@ pub async fn model_status(State(state): State<AppState>) -> Json<StatusResponse>
 /// são silenciosamente descartadas (filter_map retorna None).
 pub async fn sse_events(
     State(state): State<AppState>,
+    Query(query): Query<EventsQuery>,
 ) -> Sse<impl futures_util::Stream<Item = Result<SseEvent, Infallible>>> {
     let rx = state.events_tx.subscribe();
-    let stream = BroadcastStream::new(rx).filter_map(|result| async move {
+    let stream = BroadcastStream::new(rx).filter_map(move |result| async move {
         match result {
+            Ok(event) if query.job.is_some_and(|job| job != event.job_id) => None,
             Ok(event) => {
                 // Serializa o evento como JSON
                 let data = serde_json::to_string(&event).ok()?;
@@ -248,6 +318,7 @@ pub async fn chat(
     let t0 = Instant::now();
     let mut orchestrator = model.orchestrator.lock().await;
     let responses = orchestrator.process_message(&user_text).await;
+    let classification = orchestrator.last_intent().cloned();
     drop(orchestrator); // libera Mutex o mais rápido possível
     let elapsed_ms = t0.elapsed().as_millis() as u64;
 
@@ -268,6 +339,9 @@ pub async fn chat(
                 div class="message user-message" {
                     div class="message-role" { "Você" }
                     div class="message-content" { (user_text) }
+                    @if let Some(classification) = &classification {
+                        (templates::intent_feedback(&user_text, classification))
+                    }
                 }
                 // Respostas do sistema (cada uma com sua role/estilo)
                 @for msg in &messages {
@@ -306,25 +380,75 @@ pub struct ChatForm {
     pub message: String,
 }
 
-/// POST `/upload` — Upload de PDF para ingestão em background.
+/// Dados do formulário de feedback de intenção.
+#[derive(serde::Deserialize)]
+pub struct IntentFeedbackForm {
+    /// Mensagem original do usuário.
+    pub message: String,
+    /// Intent correto (`querying`, `narrating`...).
+    pub intent: String,
+}
+
+/// POST `/intent/feedback` — Ensina a mensagem como template do intent
+/// escolhido pelo usuário.
+///
+/// A frase vale na próxima mensagem (e após reiniciar, via `intents.txt`).
+pub async fn intent_feedback(
+    State(state): State<AppState>,
+    axum::Form(form): axum::Form<IntentFeedbackForm>,
+) -> Html<String> {
+    let Some(model) = state.model.get() else {
+        return loading_response();
+    };
+    let Some(intent) = Intent::from_name(&form.intent) else {
+        return markup_to_html(html! {
+            div class="message system-message error" {
+                "Intenção desconhecida"
+            }
+        });
+    };
+
+    markup_to_html(match model.nlu.learn_intent(&form.message, intent).await {
+        Ok(()) => html! {
+            div class="message system-message reinforced" {
+                div class="message-role" { "Intenção" }
+                div class="message-content" {
+                    (format!("Aprendido: \"{}\" → {}", form.message.trim(), intent.label()))
+                }
+            }
+        },
+        Err(e) => {
+            tracing::warn!(error = %e, "Falha ao aprender template de intent");
+            html! {
+                div class="message system-message error" {
+                    div class="message-content" { (format!("Erro: {}", e)) }
+                }
+            }
+        }
+    })
+}
+
+/// POST `/upload` — Upload de documento para ingestão em background.
 ///
 /// ## Fluxo
 ///
 /// ```text
-/// 1. Lê campo "pdf" do multipart form
+/// 1. Lê campo "document" (ou "pdf", formulários antigos) e "reingest" do multipart form
 /// 2. Verifica se modelo está pronto
-/// 3. Spawna task blocking para processar PDF em background
-/// 4. Retorna IMEDIATAMENTE com confirmação de recebimento
-/// 5. Background: ingest_pdf() emite SSE events durante processamento
+/// 3. Detecta o formato (bytes mágicos → MIME → extensão → texto)
+/// 4. Deduplica pelo SHA-256: job ativo com o mesmo conteúdo → aponta para ele;
+///    já no registro da KB → ignora, salvo `reingest=on`
+/// 5. Enfileira um job de ingestão (JobManager::submit)
+/// 6. Retorna IMEDIATAMENTE com o ID do job
+/// 7. Background: ingest_document() emite SSE events e registra o documento
 /// ```
 ///
 /// ## Processamento em Background
 ///
-/// O processamento é feito em `spawn_blocking` porque:
-/// - Forward pass do BERTimbau é CPU-bound
-/// - Não queremos bloquear o executor async do Tokio
-/// - O usuário vê o progresso via SSE no Visualizador
-pub async fn upload_pdf(
+/// O job espera um worker livre (`INGEST_WORKERS`). A extração roda em
+/// `spawn_blocking` (CPU-bound); as chamadas ao LM Studio são async. O
+/// usuário acompanha o job no Visualizador e pode cancelá-lo.
+pub async fn upload_document(
     State(state): State<AppState>,
     mut multipart: Multipart,
 ) -> Html<String> {
@@ -332,84 +456,293 @@ pub async fn upload_pdf(
         return loading_response();
     };
 
+    let mut upload = None;
+    let mut reingest = false;
     while let Ok(Some(field)) = multipart.next_field().await {
         let name = field.name().unwrap_or("").to_string();
-        if name == "pdf" {
-            let filename = field
-                .file_name()
-                .unwrap_or("documento.pdf")
-                .to_string();
-
-            match field.bytes().await {
-                Ok(bytes) => {
-                    tracing::info!(size_bytes = bytes.len(), filename = %filename, "PDF upload recebido");
-
-                    // Clona recursos para a task em background
-                    let nlu = model.nlu.clone();
-                    let kb = state.kb.clone();
-                    let tx = state.events_tx.clone();
-
-                    // Processa em background (I/O-bound: HTTP calls ao LM Studio)
-                    tokio::spawn(async move {
-                        match pdf::ingest_pdf(&bytes, &nlu, &kb, &tx).await {
-                            Ok(msg) => {
-                                tracing::info!(result = %msg, "PDF background ingestion complete");
-                            }
-                            Err(e) => {
-                                tracing::error!(error = %e, "PDF background ingestion failed");
-                                let _ = tx.send(IngestionEvent::Error {
-                                    message: format!("Erro ao processar PDF: {}", e),
-                                });
-                            }
-                        }
-                    });
-
-                    // Retorna imediatamente — progresso via SSE
-                    return markup_to_html(html! {
-                        div class="message system-message pdf-result" {
-                            div class="message-role" { "PDF Ingestão" }
-                            div class="message-content" {
-                                "📄 Upload de " strong { (filename) } " recebido. "
-                                "Processamento iniciado em background. "
-                                a href="/visualizador" target="_blank" {
-                                    "Acompanhe em tempo real no Visualizador →"
-                                }
-                            }
-                        }
-                    });
+        if name == "reingest" {
+            reingest = field
+                .text()
+                .await
+                .is_ok_and(|v| matches!(v.trim(), "1" | "true" | "on"));
+            continue;
+        }
+        if name != "document" && name != "pdf" {
+            continue;
+        }
+        let filename = field.file_name().unwrap_or("documento").to_string();
+        let mime = field.content_type().map(str::to_string);
+
+        let bytes = match field.bytes().await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::warn!(error = %e, "Falha ao ler bytes do upload");
+                return markup_to_html(html! {
+                    div class="message system-message error" {
+                        div class="message-role" { "Erro" }
+                        div class="message-content" { (format!("Erro no upload: {}", e)) }
+                    }
+                });
+            }
+        };
+        upload = Some((filename, mime, bytes));
+    }
+
+    let Some((filename, mime, bytes)) = upload else {
+        tracing::warn!("Nenhum campo de documento encontrado no upload multipart");
+        return markup_to_html(html! {
+            div class="message system-message error" {
+                div class="message-role" { "Erro" }
+                div class="message-content" { "Nenhum documento encontrado no upload." }
+            }
+        });
+    };
+
+    let Some(format) = DocumentFormat::detect(&bytes, mime.as_deref(), Some(&filename)) else {
+        tracing::warn!(filename = %filename, mime = ?mime, "Formato de documento não suportado");
+        return markup_to_html(html! {
+            div class="message system-message error" {
+                div class="message-role" { "Erro" }
+                div class="message-content" {
+                    "Formato não suportado: " strong { (filename) } ". "
+                    "Envie PDF, Markdown, texto, HTML, DOCX ou EPUB."
                 }
-                Err(e) => {
-                    tracing::warn!(error = %e, "Falha ao ler bytes do PDF");
-                    return markup_to_html(html! {
-                        div class="message system-message error" {
-                            div class="message-role" { "Erro" }
-                            div class="message-content" { (format!("Erro no upload: {}", e)) }
-                        }
-                    });
+            }
+        });
+    };
+    let upload = DocumentUpload::new(filename, format, &bytes[..]);
+    tracing::info!(
+        size_bytes = upload.bytes.len(),
+        filename = %upload.filename,
+        format = format.name(),
+        hash = %upload.hash,
+        reingest,
+        "Upload de documento recebido"
+    );
+
+    // Mesmo conteúdo já na fila ou em execução: acompanha o job existente
+    if let Some(job) = state.jobs.find_active(&upload.hash) {
+        tracing::info!(job = %job.id, "Upload duplicado de documento em processamento");
+        return markup_to_html(html! {
+            div class="message system-message pdf-result" {
+                div class="message-role" { (format.label()) " Duplicado" }
+                div class="message-content" {
+                    "📄 " strong { (upload.filename) } " já está sendo processado (job "
+                    code { (job.id.to_string()[..8]) } "). "
+                    a href=(format!("/visualizador?job={}", job.id)) target="_blank" {
+                        "Acompanhe no Visualizador →"
+                    }
                 }
             }
+        });
+    }
+
+    // Interrompido antes: o job continua do checkpoint, lido ou não; com
+    // "Reingerir", descarta o checkpoint e recomeça do chunk 0
+    let checkpoints = CheckpointStore::default();
+    let mut checkpoint = checkpoints.load(&upload.hash).ok().flatten();
+    if reingest && checkpoint.take().is_some() {
+        if let Err(e) = checkpoints.remove(&upload.hash) {
+            tracing::warn!(
+                error = %e,
+                hash = %upload.hash,
+                "Falha ao descartar checkpoint na reingestão"
+            );
+        }
+    }
+
+    // Já lido: só reingere se pedido (senão reforçaria tudo de novo)
+    if !reingest && checkpoint.is_none() {
+        let kb = state.kb.read();
+        if let Some(record) = kb.documents.get(&upload.hash) {
+            tracing::info!(hash = %record.hash, "Upload duplicado ignorado");
+            let outdated = record.extractor_version < document::extractor_for(format).version();
+            return markup_to_html(html! {
+                div class="message system-message pdf-result" {
+                    div class="message-role" { (format.label()) " Já Lido" }
+                    div class="message-content" {
+                        "📄 " strong { (record.display_name()) } " já foi lido em "
+                        (record.ingested_at.format("%d/%m/%Y %H:%M")) " UTC ("
+                        (record.chunks) " chunks, " (record.new_concepts) " conceitos). "
+                        @if outdated {
+                            "Foi lido por uma versão anterior do extrator. "
+                        }
+                        "Upload ignorado para não reforçar os mesmos conceitos de novo. "
+                        "Marque " em { "Reingerir" }
+                        " e envie de novo para processá-lo mesmo assim. "
+                        a href="/documentos" target="_blank" { "Ver documentos lidos →" }
+                    }
+                }
+            });
         }
     }
 
-    tracing::warn!("Nenhum campo PDF encontrado no upload multipart");
+    let filename = upload.filename.clone();
+    let job_id = state
+        .jobs
+        .submit(upload, model.nlu.clone(), state.kb.clone());
+
+    // Retorna imediatamente — progresso via SSE
     markup_to_html(html! {
-        div class="message system-message error" {
-            div class="message-role" { "Erro" }
-            div class="message-content" { "Nenhum arquivo PDF encontrado no upload." }
+        div class="message system-message pdf-result" {
+            div class="message-role" { (format.label()) " Ingestão" }
+            div class="message-content" {
+                "📄 Upload de " strong { (filename) } " recebido. "
+                @if let Some(checkpoint) = &checkpoint {
+                    "Ingestão interrompida antes: retomando do chunk "
+                    (checkpoint.next_chunk + 1) "/"
+                    @if checkpoint.total_chunks > 0 { (checkpoint.total_chunks) } @else { "?" }
+                    ". "
+                } @else if reingest {
+                    "Reingestão solicitada. "
+                }
+                "Job " code { (job_id.to_string()[..8]) } " na fila de processamento. "
+                a href=(format!("/visualizador?job={}", job_id)) target="_blank" {
+                    "Acompanhe em tempo real no Visualizador →"
+                }
+            }
         }
     })
 }
 
+/// GET `/documentos` — Documentos que a KB já leu.
+pub async fn documents_page(State(state): State<AppState>) -> Html<String> {
+    // Checkpoints de jobs ativos não são interrupções
+    let interrupted: Vec<_> = CheckpointStore::default()
+        .list()
+        .into_iter()
+        .filter(|c| state.jobs.find_active(&c.hash).is_none())
+        .collect();
+    let kb = state.kb.read();
+    let documents = kb.documents.list();
+    markup_to_html(templates::documents_page(&documents, &interrupted))
+}
+
+/// DELETE `/documents/{hash}` — Esquece um documento.
+///
+/// Retira a evidência do documento da KB, remove conceitos e links que só
+/// ele sustentava, enfraquece os compartilhados e invalida inferências
+/// sem premissas ([`provenance::forget_document`]). Recusa enquanto um
+/// job com o mesmo conteúdo estiver ativo. Retorna a linha da tabela.
+pub async fn forget_document(
+    State(state): State<AppState>,
+    Path(hash): Path<String>,
+) -> Html<String> {
+    if !document::is_content_hash(&hash) {
+        return markup_to_html(templates::forgotten_row("documento", Err("hash inválido.")));
+    }
+    let name = state
+        .kb
+        .read()
+        .documents
+        .get(&hash)
+        .map(|d| d.display_name().to_string())
+        .unwrap_or_else(|| format!("sha256 {}", &hash[..12]));
+
+    if state.jobs.find_active(&hash).is_some() {
+        return markup_to_html(templates::forgotten_row(
+            &name,
+            Err("em processamento; cancele o job antes de esquecer."),
+        ));
+    }
+
+    let report = provenance::forget_document(&mut state.kb.write(), &hash);
+    if let Err(e) = CheckpointStore::default().remove(&hash) {
+        tracing::warn!(error = %e, "Falha ao remover checkpoint do documento esquecido");
+    }
+    if let Err(e) = crate::persistence::save_kb(&state.kb) {
+        tracing::error!(error = %e, "Falha ao salvar KB após esquecer documento");
+    }
+    if !report.was_registered && report.is_empty() {
+        return markup_to_html(templates::forgotten_row(
+            &name,
+            Err("documento desconhecido."),
+        ));
+    }
+    markup_to_html(templates::forgotten_row(&name, Ok(&report)))
+}
+
+/// POST `/documents/{hash}/resume` — Retoma uma ingestão interrompida.
+///
+/// Enfileira um job com os bytes guardados no checkpoint; ele continua do
+/// primeiro chunk não aplicado ([`crate::checkpoint`]). Recusa enquanto o
+/// modelo carrega ou um job com o mesmo conteúdo estiver ativo. Retorna a
+/// linha da tabela de ingestões interrompidas.
+pub async fn resume_document(
+    State(state): State<AppState>,
+    Path(hash): Path<String>,
+) -> Html<String> {
+    if !document::is_content_hash(&hash) {
+        return markup_to_html(templates::resumed_row("documento", Err("hash inválido.")));
+    }
+    let store = CheckpointStore::default();
+    let name = store
+        .load(&hash)
+        .ok()
+        .flatten()
+        .map(|c| c.filename)
+        .unwrap_or_else(|| format!("sha256 {}", &hash[..12]));
+
+    let Some(model) = state.model.get() else {
+        return markup_to_html(templates::resumed_row(
+            &name,
+            Err("⏳ modelo carregando, tente de novo em instantes."),
+        ));
+    };
+    if let Some(job) = state.jobs.find_active(&hash) {
+        return markup_to_html(templates::resumed_row(&name, Ok(job.id)));
+    }
+    match state
+        .jobs
+        .resume(&hash, model.nlu.clone(), state.kb.clone())
+    {
+        Ok(Some(job_id)) => markup_to_html(templates::resumed_row(&name, Ok(job_id))),
+        Ok(None) => markup_to_html(templates::resumed_row(
+            &name,
+            Err("nenhum checkpoint para este documento."),
+        )),
+        Err(e) => {
+            tracing::error!(error = %e, hash = %hash, "Falha ao retomar ingestão");
+            markup_to_html(templates::resumed_row(
+                &name,
+                Err("checkpoint ilegível; envie o documento de novo."),
+            ))
+        }
+    }
+}
+
+/// GET `/jobs` — Jobs de ingestão, mais recentes primeiro.
+pub async fn list_jobs(State(state): State<AppState>) -> Json<Vec<JobInfo>> {
+    Json(state.jobs.list())
+}
+
+/// DELETE `/jobs/{id}` — Cancela um job na fila ou em execução.
+///
+/// Um job já encerrado é removido da listagem. Retorna o estado do job
+/// após a operação, ou 404 se o ID não existe.
+pub async fn cancel_job(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<JobInfo>, StatusCode> {
+    state.jobs.cancel(id).map(Json).ok_or(StatusCode::NOT_FOUND)
+}
+
 /// POST `/knowledge/reset` — Limpa toda a KB e reseta o orquestrador.
 ///
 /// Executa reset completo:
 /// 1. Limpa todos os conceitos e links da KB
 /// 2. Persiste KB vazia em disco
-/// 3. Reseta estado do orquestrador (contadores, filas)
+/// 3. Descarta os checkpoints de ingestões interrompidas
+/// 4. Reseta estado do orquestrador (contadores, filas)
 pub async fn reset_knowledge(State(state): State<AppState>) -> Html<String> {
     // Limpa KB completamente
     state.kb.write().clear();
 
+    // Checkpoints retomariam documentos pela metade numa KB que não os tem
+    if let Err(e) = CheckpointStore::default().clear() {
+        tracing::warn!(error = %e, "Falha ao descartar checkpoints após reset");
+    }
+
     // Persiste KB vazia em disco
     if let Err(e) = crate::persistence::save_kb(&state.kb) {
         tracing::error!(error = %e, "Falha ao salvar KB vazia após reset");
@@ -491,6 +824,70 @@ pub async fn reinforce_concept(
     })
 }
 
+/// POST `/knowledge/alias/{id}` — Adiciona um alias a um conceito via sidebar.
+///
+/// O alias vem do header `HX-Prompt` (botão com `hx-prompt`). Persiste a
+/// KB quando o alias é novo.
+pub async fn add_alias(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Html<String> {
+    let Ok(uuid) = Uuid::parse_str(&id) else {
+        return markup_to_html(html! {
+            div class="message system-message error" {
+                "ID inválido"
+            }
+        });
+    };
+    let alias = headers
+        .get("HX-Prompt")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim().to_string())
+        .unwrap_or_default();
+    if alias.is_empty() {
+        return Html(String::new());
+    }
+
+    let result = {
+        let mut kb = state.kb.write();
+        let taken = kb
+            .find_concept_by_label(&alias)
+            .filter(|c| c.id != uuid)
+            .map(|c| c.label.clone());
+        match (kb.concepts.get_mut(&uuid), taken) {
+            (None, _) => Err("Conceito não encontrado".to_string()),
+            (Some(_), Some(other)) => Err(format!("\"{}\" já nomeia o conceito {}", alias, other)),
+            (Some(concept), None) => Ok((concept.add_alias(&alias), concept.label.clone())),
+        }
+    };
+
+    match result {
+        Ok((added, label)) => {
+            if added {
+                if let Err(e) = crate::persistence::save_kb(&state.kb) {
+                    tracing::error!(error = %e, "Falha ao salvar KB após alias");
+                }
+            }
+            markup_to_html(html! {
+                div class="message system-message reinforced" {
+                    div class="message-role" { "Alias" }
+                    div class="message-content" {
+                        @if added {
+                            (alias) " → " (label)
+                        } @else {
+                            "\"" (alias) "\" já nomeia " (label)
+                        }
+                    }
+                }
+            })
+        }
+        Err(msg) => markup_to_html(html! {
+            div class="message system-message error" { (msg) }
+        }),
+    }
+}
+
 /// GET `/knowledge/graph` — Dados JSON do grafo para visualização 3D.
 ///
 /// Retorna todos os conceitos (nós) e links (arestas) da KB
@@ -512,6 +909,8 @@ pub async fn graph_data(State(state): State<AppState>) -> Json<GraphData> {
             energy: c.energy,
             state: c.state.css_class().to_string(),
             mention_count: c.mention_count,
+            language: c.language.clone(),
+            entity_type: c.entity_type.map(|t| t.css_class().to_string()),
         })
         .collect();
 
@@ -536,6 +935,62 @@ pub async fn graph_data(State(state): State<AppState>) -> Json<GraphData> {
     Json(GraphData { concepts, links })
 }
 
+/// GET `/knowledge/attributes?concept=<id|label>&kind=<tipo>` — Consulta
+/// os valores tipados (números, datas, quantidades) dos conceitos.
+///
+/// Sem filtros, lista os atributos de todos os conceitos. Um `concept`
+/// ou `kind` desconhecido retorna lista vazia.
+pub async fn concept_attributes(
+    State(state): State<AppState>,
+    Query(query): Query<AttributeQuery>,
+) -> Json<Vec<AttributeRow>> {
+    let kb = state.kb.read();
+
+    let concept_id = match query
+        .concept
+        .as_deref()
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+    {
+        Some(concept) => match Uuid::parse_str(concept)
+            .ok()
+            .or_else(|| kb.find_concept_by_label(concept).map(|c| c.id))
+        {
+            Some(id) => Some(id),
+            None => return Json(Vec::new()),
+        },
+        None => None,
+    };
+    let kind = match query.kind.as_deref().filter(|k| !k.trim().is_empty()) {
+        Some(kind) => match ValueKind::from_name(kind) {
+            Some(kind) => Some(kind),
+            None => return Json(Vec::new()),
+        },
+        None => None,
+    };
+
+    let rows = kb
+        .concepts
+        .values()
+        .filter(|c| concept_id.is_none_or(|id| c.id == id))
+        .flat_map(|c| {
+            c.distinct_attributes()
+                .filter(|a| kind.is_none_or(|k| a.kind == k))
+                .map(|a| AttributeRow {
+                    concept_id: c.id.to_string(),
+                    label: c.label.clone(),
+                    kind: a.kind.name().to_string(),
+                    value: a.value,
+                    unit: a.unit.clone(),
+                    date: a.date.clone(),
+                    text: a.text.clone(),
+                })
+        })
+        .collect();
+
+    Json(rows)
+}
+
 // ─── Extensões de MessageRole para HTML ──────────────────────────
 
 impl MessageRole {
@@ -547,6 +1002,8 @@ impl MessageRole {
     /// - `inference` → fundo azulado, ícone 🧪
     /// - `question` → fundo verde, ícone 🌱
     /// - `alert` → fundo alaranjado, ícone ⚠️
+    /// - `verification` → fundo âmbar, afirmações sem suporte na KB
+    /// - `inference tool` → estilo de inferência, chamadas de ferramenta do agente
     pub fn css_class(&self) -> &'static str {
         match self {
             MessageRole::User => "user",
@@ -555,6 +1012,8 @@ impl MessageRole {
             MessageRole::Question => "question",
             MessageRole::Alert => "alert",
             MessageRole::Assistant => "assistant",
+            MessageRole::Verification => "verification",
+            MessageRole::Tool => "inference tool",
         }
     }
 
@@ -567,6 +1026,66 @@ impl MessageRole {
             MessageRole::Question => "Germinação",
             MessageRole::Alert => "Alerta",
             MessageRole::Assistant => "Assistente",
+            MessageRole::Verification => "Verificação",
+            MessageRole::Tool => "Ferramenta",
         }
     }
 }
+
+/// Formulário de resposta da revisão espaçada (`POST /review/answer`).
+#[derive(serde::Deserialize)]
+pub struct ReviewAnswerForm {
+    /// Tipo do item (`concept` ou `link`).
+    pub target: String,
+    /// UUID do item.
+    pub id: String,
+    /// Resposta (`false`, `unsure`, `true`, `easy`).
+    pub answer: String,
+}
+
+/// GET `/review/next` — Fragment com o cartão mais urgente da fila.
+pub async fn review_next(State(state): State<AppState>) -> Html<String> {
+    let kb = state.kb.read();
+    let cards = review::due_cards(&kb, chrono::Utc::now());
+    markup_to_html(templates::review_card(cards.first(), cards.len(), None))
+}
+
+/// POST `/review/answer` — Registra a resposta a um cartão.
+///
+/// A resposta entra como evidência NARS e reforço no item, que é
+/// reagendado (SM-2). Persiste a KB e devolve o próximo cartão.
+pub async fn review_answer(
+    State(state): State<AppState>,
+    axum::Form(form): axum::Form<ReviewAnswerForm>,
+) -> Html<String> {
+    let (Some(target), Some(answer)) = (
+        ReviewTarget::parse(&form.target, &form.id),
+        ReviewAnswer::from_name(&form.answer),
+    ) else {
+        return markup_to_html(html! {
+            div class="message system-message error" { "Resposta inválida" }
+        });
+    };
+
+    let now = chrono::Utc::now();
+    let feedback = review::apply_answer(&mut state.kb.write(), target, answer, now);
+    if feedback.is_some() {
+        if let Err(e) = crate::persistence::save_kb(&state.kb) {
+            tracing::error!(error = %e, "Falha ao salvar KB após revisão");
+        }
+    }
+    let feedback = feedback.unwrap_or_else(|| "Item não encontrado".to_string());
+    tracing::info!(
+        target = target.kind_name(),
+        answer = answer.name(),
+        "Revisão registrada"
+    );
+
+    let kb = state.kb.read();
+    let cards = review::due_cards(&kb, now);
+    markup_to_html(templates::review_card(
+        cards.first(),
+        cards.len(),
+        Some(&feedback),
+    ))
+}
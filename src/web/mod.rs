@@ -13,14 +13,22 @@
 //! │  ├── GET  /                    → index (chat principal) │
 //! │  ├── GET  /metodologia         → artigo HTML estático   │
 //! │  ├── GET  /visualizador        → grafo 3D + SSE feed   │
+//! │  ├── GET  /revisao             → revisão espaçada       │
+//! │  ├── GET  /documentos          → documentos lidos       │
 //! │  ├── GET  /status              → JSON: modelo pronto?   │
 //! │  ├── GET  /events              → SSE stream (ingestão)  │
 //! │  ├── POST /chat                → HTMX fragment          │
-//! │  ├── POST /upload              → PDF multipart (50MB)   │
+//! │  ├── POST /upload              → documento (50MB) → job │
+//! │  ├── GET  /jobs                → JSON: jobs de ingestão │
+//! │  ├── DELETE /jobs/{id}         → cancela job            │
+//! │  ├── DELETE /documents/{hash}  → esquece documento      │
+//! │  ├── POST /documents/{hash}/resume → retoma ingestão    │
 //! │  ├── GET  /knowledge/sidebar   → HTMX fragment          │
 //! │  ├── GET  /knowledge/graph     → JSON (3D graph data)   │
 //! │  ├── POST /knowledge/reinforce → HTMX fragment          │
-//! │  └── POST /knowledge/reset     → HTMX fragment          │
+//! │  ├── POST /knowledge/reset     → HTMX fragment          │
+//! │  ├── GET  /review/next         → HTMX fragment          │
+//! │  └── POST /review/answer       → HTMX fragment          │
 //! ├─────────────────────────────────────────────────────────┤
 //! │ Static Assets (tower_http::ServeDir → /assets/)         │
 //! └─────────────────────────────────────────────────────────┘
@@ -31,7 +39,7 @@
 //! | Módulo | Responsabilidade |
 //! |--------|------------------|
 //! | [`state`] | Estado compartilhado (`AppState`, `ModelReady`) |
-//! | [`events`] | Enum de eventos SSE para ingestão de PDF |
+//! | [`events`] | Enum de eventos SSE para ingestão de documentos |
 //! | [`handlers`] | Handlers Axum para cada rota |
 //! | [`templates`] | Templates Maud (HTML server-side) |
 
@@ -41,7 +49,7 @@ pub mod state;
 pub mod templates;
 
 use axum::extract::DefaultBodyLimit;
-use axum::routing::{get, post};
+use axum::routing::{delete, get, post};
 use axum::Router;
 use tower_http::services::ServeDir;
 
@@ -51,11 +59,14 @@ use state::AppState;
 ///
 /// ## Rotas Registradas
 ///
-/// - **Páginas HTML**: `/`, `/metodologia`, `/visualizador`
-/// - **API JSON**: `/status`, `/knowledge/graph`
-/// - **HTMX fragments**: `/chat`, `/knowledge/sidebar`, `/knowledge/reinforce/{id}`, `/knowledge/reset`
-/// - **SSE stream**: `/events`
-/// - **Upload**: `/upload` (limite de 50MB para PDFs)
+/// - **Páginas HTML**: `/`, `/metodologia`, `/visualizador`, `/revisao`, `/documentos`
+/// - **API JSON**: `/status`, `/knowledge/graph`, `/knowledge/attributes`,
+///   `/jobs`, `DELETE /jobs/{id}`
+/// - **HTMX fragments**: `/chat`, `/knowledge/sidebar`, `/knowledge/reinforce/{id}`,
+///   `/knowledge/alias/{id}`, `/knowledge/reset`, `/intent/feedback`,
+///   `/review/next`, `/review/answer`, `DELETE /documents/{hash}`
+/// - **SSE stream**: `/events` (`?job=<id>` filtra um job)
+/// - **Upload**: `/upload` (limite de 50MB; PDF, Markdown, texto, HTML, DOCX, EPUB)
 /// - **Estáticos**: `/assets/*` → diretório `assets/`
 ///
 /// O estado `AppState` é compartilhado entre todos os handlers via
@@ -66,20 +77,81 @@ pub fn create_router(state: AppState) -> Router {
         .route("/", get(handlers::index))
         .route("/metodologia", get(handlers::metodologia))
         .route("/visualizador", get(handlers::visualizador))
+        .route("/revisao", get(handlers::review_page))
+        .route("/documentos", get(handlers::documents_page))
         // ── API JSON ──────────────────────────────────────────
         .route("/status", get(handlers::model_status))
         .route("/events", get(handlers::sse_events))
+        .route("/jobs", get(handlers::list_jobs))
+        .route("/jobs/{id}", delete(handlers::cancel_job))
         // ── HTMX fragments ───────────────────────────────────
         .route("/chat", post(handlers::chat))
+        .route("/intent/feedback", post(handlers::intent_feedback))
         .route(
             "/upload",
-            post(handlers::upload_pdf).layer(DefaultBodyLimit::max(50 * 1024 * 1024)),
+            post(handlers::upload_document).layer(DefaultBodyLimit::max(50 * 1024 * 1024)),
         )
         .route("/knowledge/sidebar", get(handlers::knowledge_sidebar))
         .route("/knowledge/graph", get(handlers::graph_data))
+        .route("/knowledge/attributes", get(handlers::concept_attributes))
         .route("/knowledge/reinforce/{id}", post(handlers::reinforce_concept))
+        .route("/knowledge/alias/{id}", post(handlers::add_alias))
         .route("/knowledge/reset", post(handlers::reset_knowledge))
+        .route("/review/next", get(handlers::review_next))
+        .route("/review/answer", post(handlers::review_answer))
+        .route("/documents/{hash}", delete(handlers::forget_document))
+        .route("/documents/{hash}/resume", post(handlers::resume_document))
         // ── Arquivos estáticos ────────────────────────────────
         .nest_service("/assets", ServeDir::new("assets"))
         .with_state(state)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, OnceLock};
+
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use parking_lot::RwLock;
+    use tokio::sync::broadcast;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::core::KnowledgeBase;
+    use crate::jobs::{JobConfig, JobManager};
+
+    #[tokio::test]
+    async fn document_routes_reject_hashes_outside_the_checkpoint_dir() {
+        let (tx, _) = broadcast::channel(16);
+        let state = AppState {
+            model: Arc::new(OnceLock::new()),
+            kb: Arc::new(RwLock::new(KnowledgeBase::new())),
+            events_tx: Arc::new(tx.clone()),
+            jobs: Arc::new(JobManager::new(JobConfig { workers: 1 }, tx)),
+        };
+        let requests = [
+            ("DELETE", "/documents/..%2Fkb"),
+            ("POST", "/documents/..%2F..%2Fkb/resume"),
+            // "aéééééé": o corte no byte 12 cairia no meio de um "é"
+            ("DELETE", "/documents/a%C3%A9%C3%A9%C3%A9%C3%A9%C3%A9%C3%A9"),
+        ];
+        for (method, uri) in requests {
+            let request = Request::builder()
+                .method(method)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap();
+            let response = create_router(state.clone()).oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert!(
+                String::from_utf8_lossy(&body).contains("hash inválido"),
+                "{} {}",
+                method,
+                uri
+            );
+        }
+    }
+}
@@ -10,6 +10,7 @@
 //! │ AppState       │     │ ModelReady       │
 //! │  ├── kb ✓      │     │  ├── orchestrator│
 //! │  ├── events_tx ✓│    │  └── nlu         │
+//! │  ├── jobs ✓    │     │                 │
 //! │  └── model: ∅  │←────│  (set via OnceLock)
 //! └────────────────┘     └─────────────────┘
 //!       ↓ Web server                ↓ async init
@@ -22,9 +23,10 @@ use parking_lot::RwLock;
 use tokio::sync::{broadcast, Mutex};
 
 use crate::core::KnowledgeBase;
+use crate::jobs::JobManager;
 use crate::nlu::NluPipeline;
 use crate::orchestrator::Orchestrator;
-use crate::web::events::IngestionEvent;
+use crate::web::events::JobEvent;
 
 /// Modelo ML + NLU, inicializado em background.
 ///
@@ -44,6 +46,8 @@ pub struct AppState {
     pub model: Arc<OnceLock<ModelReady>>,
     /// Base de conhecimento compartilhada, protegida por `RwLock`.
     pub kb: Arc<RwLock<KnowledgeBase>>,
-    /// Canal broadcast para eventos SSE de ingestão de PDF.
-    pub events_tx: Arc<broadcast::Sender<IngestionEvent>>,
+    /// Canal broadcast para eventos SSE de ingestão, marcados com o job.
+    pub events_tx: Arc<broadcast::Sender<JobEvent>>,
+    /// Fila de jobs de ingestão (workers limitados, cancelamento).
+    pub jobs: Arc<JobManager>,
 }
@@ -19,12 +19,17 @@
 //! | [`full_page()`] | Página completa | Chat + sidebar + grafo 3D |
 //! | [`visualizador_page()`] | Página completa | Grafo full-screen + SSE |
 //! | [`sidebar_content()`] | Fragment HTMX | Lista de conceitos ativos/fading |
+//! | [`intent_feedback()`] | Fragment HTMX | Intenção detectada + correção |
+//! | [`review_page()`] | Página completa | Revisão espaçada (flashcards) |
+//! | [`review_card()`] | Fragment HTMX | Próximo cartão + feedback da resposta |
+//! | [`documents_page()`] | Página completa | Registro de documentos lidos |
+//! | [`forgotten_row()`] | Fragment HTMX | Resultado do esquecimento de um documento |
 //!
 //! ## Layout Principal (`full_page`)
 //!
 //! ```text
 //! ┌──────────────── nav-bar ────────────────────┐
-//! │ CE │ Chat │ Visualizador │ Metodologia │ ● │
+//! │ CE │ Chat │ Visualizador │ Revisão │ Documentos │ Metodologia │ ● │
 //! ├──────────────────────────┬──────────────────┤
 //! │                          │ Grafo 3D / Lista │
 //! │    Chat Messages         │   ┌──────────┐   │
@@ -35,18 +40,23 @@
 //! │    └─────────────┘       │  Conceitos Ativos│
 //! │                          │  Conceitos Fading│
 //! ├──────────────────────────┴──────────────────┤
-//! │ [📄 PDF] [🗑 Reset] [_______________][Send] │
+//! │ [📄 Doc] [🗑 Reset] [_______________][Send] │
 //! └─────────────────────────────────────────────┘
 //! ```
 
 use maud::{html, Markup, PreEscaped, DOCTYPE};
 
-use crate::core::KnowledgeBase;
+use crate::checkpoint::Checkpoint;
+use crate::core::provenance::ForgetReport;
+use crate::core::review::ReviewCard;
+use crate::core::{DocumentRecord, EntityType, KnowledgeBase, ReviewAnswer};
+use crate::jobs::JobId;
+use crate::nlu::intent::{Intent, IntentClassification};
 
 /// Página principal do chat — layout completo com sidebar e grafo 3D.
 ///
 /// Inclui:
-/// - **Nav bar** com links para Chat, Visualizador, Metodologia
+/// - **Nav bar** com links para Chat, Visualizador, Revisão, Metodologia
 /// - **Chat panel** (esquerda) com mensagens e input
 /// - **Sidebar** (direita) com tabs para Grafo 3D e lista de conceitos
 /// - **Scripts**: HTMX, graph3d.js, e JavaScript inline para interatividade
@@ -58,7 +68,7 @@ use crate::core::KnowledgeBase;
 /// - Auto-scroll do chat quando novas mensagens chegam (MutationObserver)
 /// - Polling do status do modelo (/status) a cada 3s
 /// - Refresh do grafo após cada mensagem enviada
-/// - SSE listener para mostrar resultado de ingestão PDF no chat
+/// - SSE listener para mostrar resultado de ingestão de documentos no chat
 pub fn full_page() -> Markup {
     html! {
         (DOCTYPE)
@@ -90,6 +100,14 @@ pub fn full_page() -> Markup {
                                 span class="nav-link-icon" { "◎" }
                                 span class="nav-link-label" { "Visualizador" }
                             }
+                            a href="/revisao" class="nav-link" {
+                                span class="nav-link-icon" { "🔁" }
+                                span class="nav-link-label" { "Revisão" }
+                            }
+                            a href="/documentos" class="nav-link" {
+                                span class="nav-link-icon" { "📚" }
+                                span class="nav-link-label" { "Documentos" }
+                            }
                             a href="/metodologia" class="nav-link" {
                                 span class="nav-link-icon" { "📖" }
                                 span class="nav-link-label" { "Metodologia" }
@@ -127,7 +145,7 @@ pub fn full_page() -> Markup {
                                             }
                                             span class="welcome-feature" {
                                                 span class="welcome-feature-icon" { "📄" }
-                                                "Upload PDF"
+                                                "Upload de documentos"
                                             }
                                             span class="welcome-feature" {
                                                 span class="welcome-feature-icon" { "🍂" }
@@ -139,7 +157,7 @@ pub fn full_page() -> Markup {
                             }
 
                             div class="chat-input-area" {
-                                // Upload PDF
+                                // Upload de documento (PDF, Markdown, texto, HTML, DOCX, EPUB)
                                 form id="upload-form"
                                     enctype="multipart/form-data"
                                     hx-post="/upload"
@@ -147,11 +165,17 @@ pub fn full_page() -> Markup {
                                     hx-swap="beforeend"
                                     hx-encoding="multipart/form-data" {
                                     label class="upload-btn" {
-                                        "📄 PDF"
-                                        input type="file" name="pdf" accept=".pdf"
+                                        "📄 Documento"
+                                        input type="file" name="document"
+                                            accept=".pdf,.md,.markdown,.txt,.html,.htm,.docx,.epub"
                                             style="display:none"
                                             onchange="this.form.requestSubmit()";
                                     }
+                                    label class="upload-option"
+                                        title="Processa de novo um documento já lido" {
+                                        input type="checkbox" name="reingest" value="on";
+                                        " Reingerir"
+                                    }
                                 }
 
                                 // Reset KB button
@@ -198,6 +222,22 @@ pub fn full_page() -> Markup {
                                 div class="graph-container" {
                                     canvas #graph3d class="graph-canvas" {}
                                     div #graphStats class="graph-stats" {}
+                                    div class="graph-controls" {
+                                        select #graphColorBy title="Cor dos nós"
+                                            onchange="if (_graph3d) _graph3d.setColorBy(this.value)" {
+                                            option value="state" { "Cor: estado" }
+                                            option value="type" { "Cor: tipo" }
+                                        }
+                                        select #graphTypeFilter title="Filtrar por tipo"
+                                            onchange="if (_graph3d) _graph3d.setTypeFilter(this.value)" {
+                                            option value="" { "Todos os tipos" }
+                                            @for entity_type in EntityType::ALL {
+                                                option value=(entity_type.css_class()) {
+                                                    (entity_type.label())
+                                                }
+                                            }
+                                        }
+                                    }
                                 }
                             }
 
@@ -285,7 +325,7 @@ document.body.addEventListener('htmx:afterRequest', function(e) {
   }
 });
 
-// SSE: listen for PDF ingestion events — real-time graph + completion summary
+// SSE: listen for document ingestion events — real-time graph + completion summary
 (function() {
   function fmtDur(ms) {
     if (ms < 1000) return ms + 'ms';
@@ -310,6 +350,7 @@ document.body.addEventListener('htmx:afterRequest', function(e) {
             confidence: ev.confidence,
             energy: ev.energy,
             state: ev.state,
+            entity_type: ev.entity_type,
             mention_count: 1
           });
         }
@@ -351,7 +392,8 @@ document.body.addEventListener('htmx:afterRequest', function(e) {
           var kbSz = ev.kb_file_size_bytes < 1024*1024
             ? (ev.kb_file_size_bytes/1024).toFixed(1)+' KB'
             : (ev.kb_file_size_bytes/(1024*1024)).toFixed(1)+' MB';
-          metricsHtml = '<br><span style="font-family:\'DM Mono\',monospace;font-size:12px;color:var(--bone)">' +
+          metricsHtml = '<br>' +
+            '<span style="font-family:\'DM Mono\',monospace;font-size:12px;color:var(--bone)">' +
             '\u26a1 RAM ' + ev.memory_used_mb.toFixed(1) + ' MB' +
             ' | CPU ' + ev.cpu_active_cores + '/' + ev.cpu_total_cores +
             ' cores peak ' + ev.cpu_max_core_percent.toFixed(1) + '%' +
@@ -362,11 +404,14 @@ document.body.addEventListener('htmx:afterRequest', function(e) {
             '</span>';
         }
         div.innerHTML =
-          '<div class="message-role">PDF Completo</div>' +
+          '<div class="message-role">' + (ev.format || 'documento').toUpperCase() + ' Completo</div>' +
           '<div class="message-content">' +
             '\u{1f4c4} Ingestão finalizada: ' + ev.total_chunks + ' chunks \u2192 ' +
             ev.new_concepts + ' conceitos, ' + ev.new_links + ' links. ' +
-            'KB: ' + ev.kb_concepts + ' conceitos, ' + ev.kb_links + ' links.<br>' +
+            'KB: ' + ev.kb_concepts + ' conceitos, ' + ev.kb_links + ' links.' +
+            (ev.failed_pages && ev.failed_pages.length
+              ? ' Páginas não extraídas: ' + ev.failed_pages.join(', ') + '.'
+              : '') + '<br>' +
             '<span style="font-family:\'DM Mono\',monospace;font-size:12px;color:var(--bone)">' +
             '\u23f1 Leitura: ' + fmtDur(ev.extract_ms) +
             ' | Ingestão: ' + fmtDur(ev.ingestion_ms) +
@@ -389,7 +434,7 @@ document.body.addEventListener('htmx:afterRequest', function(e) {
 
 /// Página do Visualizador — grafo 3D em tela cheia + feed de atividade SSE.
 ///
-/// Layout dedicado para monitoramento de ingestão de PDF em tempo real:
+/// Layout dedicado para monitoramento de ingestão de documentos em tempo real:
 /// - **Grafo 3D** (esquerda) — Canvas WebGL com force-directed graph
 /// - **Feed de atividade** (direita) — logs SSE + barra de progresso
 /// - **Painel de propriedades** — detalhes de nó/aresta selecionado
@@ -426,6 +471,14 @@ pub fn visualizador_page() -> Markup {
                                 span class="nav-link-icon" { "◎" }
                                 span class="nav-link-label" { "Visualizador" }
                             }
+                            a href="/revisao" class="nav-link" {
+                                span class="nav-link-icon" { "🔁" }
+                                span class="nav-link-label" { "Revisão" }
+                            }
+                            a href="/documentos" class="nav-link" {
+                                span class="nav-link-icon" { "📚" }
+                                span class="nav-link-label" { "Documentos" }
+                            }
                             a href="/metodologia" class="nav-link" {
                                 span class="nav-link-icon" { "📖" }
                                 span class="nav-link-label" { "Metodologia" }
@@ -462,7 +515,12 @@ pub fn visualizador_page() -> Markup {
                             // Activity panel
                             div id="panel-activity" class="viz-panel-content active" {
                                 div class="activity-progress" id="progress-container" style="display:none" {
-                                    div class="progress-label" id="progress-label" { "Aguardando..." }
+                                    div class="progress-header" {
+                                        div class="progress-label" id="progress-label" { "Aguardando..." }
+                                        button class="progress-cancel" id="progress-cancel"
+                                            style="display:none"
+                                            onclick="cancelCurrentJob()" { "Cancelar" }
+                                    }
                                     div class="progress-bar" {
                                         div class="progress-fill" id="progress-fill" style="width:0%" {}
                                     }
@@ -504,6 +562,7 @@ pub fn visualizador_page() -> Markup {
 /// - Barra de energia visual (CSS width %)
 /// - Contagem de menções
 /// - Botão "↑" para reforço via HTMX POST
+/// - Aliases ("IA") e botão "+ alias" (`hx-prompt`)
 ///
 /// Se a KB estiver vazia, exibe mensagem de boas-vindas.
 pub fn sidebar_content(kb: &KnowledgeBase) -> Markup {
@@ -538,12 +597,23 @@ pub fn sidebar_content(kb: &KnowledgeBase) -> Markup {
                             }
                             span class="mention-count" { "×" (concept.mention_count) }
                         }
+                        @if !concept.aliases.is_empty() {
+                            div class="concept-aliases" { "aka " (concept.aliases.join(", ")) }
+                        }
                         button class="reinforce-btn"
                             hx-post=(format!("/knowledge/reinforce/{}", concept.id))
                             hx-target="#chat-messages"
                             hx-swap="beforeend" {
                             "↑"
                         }
+                        button class="alias-btn"
+                            title="Adicionar alias"
+                            hx-post=(format!("/knowledge/alias/{}", concept.id))
+                            hx-prompt=(format!("Novo alias para \"{}\"", concept.label))
+                            hx-target="#chat-messages"
+                            hx-swap="beforeend" {
+                            "+ alias"
+                        }
                     }
                 }
             }
@@ -584,3 +654,386 @@ pub fn sidebar_content(kb: &KnowledgeBase) -> Markup {
         }
     }
 }
+
+/// Intenção detectada de uma mensagem do usuário, com formulário de
+/// correção ("era uma pergunta, não uma narração").
+///
+/// Escolher outro intent e enviar faz POST em `/intent/feedback`, que
+/// ensina a frase como novo template do intent escolhido.
+pub fn intent_feedback(message: &str, classification: &IntentClassification) -> Markup {
+    html! {
+        form class="intent-feedback"
+            hx-post="/intent/feedback"
+            hx-target="#chat-messages"
+            hx-swap="beforeend" {
+            input type="hidden" name="message" value=(message);
+            span class="intent-badge" title="Intenção detectada (score e 2º colocado)" {
+                (classification.describe())
+            }
+            select name="intent" title="Intenção correta" {
+                @for intent in Intent::ALL {
+                    option value=(intent.name()) selected[intent == classification.intent] {
+                        (intent.label())
+                    }
+                }
+            }
+            button type="submit" class="intent-btn" title="Ensinar esta frase" { "Corrigir" }
+        }
+    }
+}
+
+/// Página de revisão espaçada — um cartão por vez.
+///
+/// O cartão é carregado via HTMX (`GET /review/next`) e cada resposta
+/// (`POST /review/answer`) devolve o feedback junto com o próximo cartão.
+pub fn review_page() -> Markup {
+    html! {
+        (DOCTYPE)
+        html lang="pt-BR" {
+            head {
+                meta charset="UTF-8";
+                meta name="viewport" content="width=device-width, initial-scale=1.0";
+                title { "Cultivo Epistêmico — Revisão" }
+                link rel="stylesheet" href="/assets/style.css";
+                script src="/assets/htmx.min.js" {}
+            }
+            body {
+                div class="app-shell" {
+                    nav class="nav-bar" {
+                        a href="/" class="nav-brand" {
+                            span class="nav-brand-icon" { "CE" }
+                            span class="nav-brand-text" {
+                                "Cultivo " em { "Epistêmico" }
+                            }
+                        }
+
+                        div class="nav-links" {
+                            a href="/" class="nav-link" {
+                                span class="nav-link-icon" { "💬" }
+                                span class="nav-link-label" { "Chat" }
+                            }
+                            a href="/visualizador" class="nav-link" {
+                                span class="nav-link-icon" { "◎" }
+                                span class="nav-link-label" { "Visualizador" }
+                            }
+                            a href="/revisao" class="nav-link active" {
+                                span class="nav-link-icon" { "🔁" }
+                                span class="nav-link-label" { "Revisão" }
+                            }
+                            a href="/documentos" class="nav-link" {
+                                span class="nav-link-icon" { "📚" }
+                                span class="nav-link-label" { "Documentos" }
+                            }
+                            a href="/metodologia" class="nav-link" {
+                                span class="nav-link-icon" { "📖" }
+                                span class="nav-link-label" { "Metodologia" }
+                            }
+                        }
+                    }
+
+                    div class="review-container" {
+                        div id="review-card" hx-get="/review/next" hx-trigger="load" {
+                            div class="review-empty" { "Carregando cartões..." }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Fragment com o próximo cartão de revisão (ou a fila vazia).
+///
+/// - **Frente**: conceito ou afirmação ("chuva ⇒ solo") + pergunta
+/// - **Verso** (`details`): TruthValue, energia, atributos, relações, agenda
+/// - **Botões**: uma resposta por [`ReviewAnswer`], via `hx-post`
+pub fn review_card(card: Option<&ReviewCard>, remaining: usize, feedback: Option<&str>) -> Markup {
+    html! {
+        @if let Some(msg) = feedback {
+            div class="review-feedback" { (msg) }
+        }
+        @if let Some(card) = card {
+            div class="review-count" { (remaining) " para revisar" }
+            div class="review-flashcard" {
+                div class="review-front" { (card.front) }
+                div class="review-prompt" { (card.prompt) }
+                details class="review-back" {
+                    summary { "Ver o que a KB sabe" }
+                    div class="review-meta" {
+                        (card.truth) " · energia " (format!("{:.2}", card.energy))
+                        @if let Some(schedule) = &card.schedule {
+                            " · intervalo " (format!("{:.1}", schedule.interval_days)) " dias"
+                            " · lapsos " (schedule.lapses)
+                        }
+                    }
+                    @for line in &card.context {
+                        div class="review-context" { (line) }
+                    }
+                }
+                form class="review-answers" hx-post="/review/answer" hx-target="#review-card" {
+                    input type="hidden" name="target" value=(card.target.kind_name());
+                    input type="hidden" name="id" value=(card.target.id().to_string());
+                    @for answer in ReviewAnswer::ALL {
+                        button type="submit" name="answer" value=(answer.name())
+                            class={ "review-btn review-" (answer.name()) } {
+                            (answer.label())
+                        }
+                    }
+                }
+            }
+        } @else {
+            div class="review-empty" {
+                div class="review-empty-icon" { "🌿" }
+                p { "Nada para revisar agora." }
+                p class="hint" { "Conceitos e relações esmaecidos aparecem aqui conforme a agenda." }
+            }
+        }
+    }
+}
+
+/// Página com os documentos que a KB já leu (registro por hash do conteúdo).
+///
+/// Uma linha por documento, mais recentes primeiro: título/arquivo,
+/// formato, páginas, tamanho, chunks, conceitos/links da última leitura,
+/// número de ingestões, versão do extrator e o botão "Esquecer"
+/// (`DELETE /documents/{hash}`, que troca a linha por [`forgotten_row()`]).
+pub fn documents_page(documents: &[&DocumentRecord], interrupted: &[Checkpoint]) -> Markup {
+    html! {
+        (DOCTYPE)
+        html lang="pt-BR" {
+            head {
+                meta charset="UTF-8";
+                meta name="viewport" content="width=device-width, initial-scale=1.0";
+                title { "Cultivo Epistêmico — Documentos" }
+                link rel="stylesheet" href="/assets/style.css";
+                script src="/assets/htmx.min.js" {}
+            }
+            body {
+                div class="app-shell" {
+                    nav class="nav-bar" {
+                        a href="/" class="nav-brand" {
+                            span class="nav-brand-icon" { "CE" }
+                            span class="nav-brand-text" {
+                                "Cultivo " em { "Epistêmico" }
+                            }
+                        }
+
+                        div class="nav-links" {
+                            a href="/" class="nav-link" {
+                                span class="nav-link-icon" { "💬" }
+                                span class="nav-link-label" { "Chat" }
+                            }
+                            a href="/visualizador" class="nav-link" {
+                                span class="nav-link-icon" { "◎" }
+                                span class="nav-link-label" { "Visualizador" }
+                            }
+                            a href="/revisao" class="nav-link" {
+                                span class="nav-link-icon" { "🔁" }
+                                span class="nav-link-label" { "Revisão" }
+                            }
+                            a href="/documentos" class="nav-link active" {
+                                span class="nav-link-icon" { "📚" }
+                                span class="nav-link-label" { "Documentos" }
+                            }
+                            a href="/metodologia" class="nav-link" {
+                                span class="nav-link-icon" { "📖" }
+                                span class="nav-link-label" { "Metodologia" }
+                            }
+                        }
+                    }
+
+                    div class="documents-container" {
+                        @if !interrupted.is_empty() {
+                            h1 class="documents-title" { "Ingestões interrompidas" }
+                            table class="documents-table documents-interrupted" {
+                                thead {
+                                    tr {
+                                        th { "Documento" }
+                                        th { "Formato" }
+                                        th { "Progresso" }
+                                        th { "Conceitos / Links" }
+                                        th { "Iniciada" }
+                                        th { "Último chunk" }
+                                        th {}
+                                    }
+                                }
+                                tbody {
+                                    @for checkpoint in interrupted {
+                                        tr {
+                                            td {
+                                                div class="documents-name" { (checkpoint.filename) }
+                                                div class="documents-hash" title=(checkpoint.hash) {
+                                                    "sha256 "
+                                                    (checkpoint.hash[..12.min(checkpoint.hash.len())])
+                                                }
+                                            }
+                                            td { (checkpoint.format) }
+                                            td {
+                                                (checkpoint.next_chunk) " / "
+                                                @if checkpoint.total_chunks > 0 {
+                                                    (checkpoint.total_chunks)
+                                                } @else {
+                                                    "?"
+                                                }
+                                                " chunks"
+                                            }
+                                            td { (checkpoint.new_concepts) " / " (checkpoint.new_links) }
+                                            td { (checkpoint.started_at.format("%d/%m/%Y %H:%M")) }
+                                            td { (checkpoint.updated_at.format("%d/%m/%Y %H:%M")) }
+                                            td {
+                                                button class="documents-resume"
+                                                    hx-post=(format!("/documents/{}/resume", checkpoint.hash))
+                                                    hx-target="closest tr"
+                                                    hx-swap="outerHTML" {
+                                                    "Retomar"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        h1 class="documents-title" { "Documentos lidos" }
+                        @if documents.is_empty() {
+                            div class="review-empty" {
+                                div class="review-empty-icon" { "📚" }
+                                p { "Nenhum documento lido ainda." }
+                                p class="hint" {
+                                    "Envie um PDF, Markdown, texto, HTML, DOCX ou EPUB pelo chat."
+                                }
+                            }
+                        } @else {
+                            div class="documents-count" { (documents.len()) " documentos" }
+                            table class="documents-table" {
+                                thead {
+                                    tr {
+                                        th { "Documento" }
+                                        th { "Formato" }
+                                        th { "Páginas" }
+                                        th { "Tamanho" }
+                                        th { "Chunks" }
+                                        th { "Conceitos / Links" }
+                                        th { "Leituras" }
+                                        th { "Última leitura" }
+                                        th {}
+                                    }
+                                }
+                                tbody {
+                                    @for doc in documents {
+                                        tr {
+                                            td {
+                                                div class="documents-name" { (doc.display_name()) }
+                                                @if doc.title.is_some() {
+                                                    div class="documents-file" { (doc.filename) }
+                                                }
+                                                div class="documents-hash" title=(doc.hash) {
+                                                    "sha256 " (doc.hash[..12.min(doc.hash.len())])
+                                                    " · extrator v" (doc.extractor_version)
+                                                }
+                                            }
+                                            td { (doc.format) }
+                                            td {
+                                                @match doc.pages {
+                                                    Some(pages) => (pages),
+                                                    None => "—",
+                                                }
+                                            }
+                                            td { (format_size(doc.size_bytes)) }
+                                            td { (doc.chunks) }
+                                            td { (doc.new_concepts) " / " (doc.new_links) }
+                                            td { (doc.ingest_count) }
+                                            td { (doc.ingested_at.format("%d/%m/%Y %H:%M")) }
+                                            td {
+                                                button class="documents-forget"
+                                                    hx-delete=(format!("/documents/{}", doc.hash))
+                                                    hx-target="closest tr"
+                                                    hx-swap="outerHTML"
+                                                    hx-confirm=(format!(
+                                                        "Esquecer \"{}\"? Conceitos e links que só ele \
+                                                         sustenta serão removidos.",
+                                                        doc.display_name()
+                                                    )) {
+                                                    "Esquecer"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Linha da tabela de documentos após o esquecimento (ou a recusa).
+pub fn forgotten_row(name: &str, report: Result<&ForgetReport, &str>) -> Markup {
+    html! {
+        tr class="documents-forgotten" {
+            td colspan="9" {
+                @match report {
+                    Ok(report) => {
+                        strong { (name) } " esquecido. "
+                        (report.removed_concepts.len()) " conceitos e "
+                        (report.removed_links) " links removidos; "
+                        (report.downgraded_concepts) " conceitos e "
+                        (report.downgraded_links) " links enfraquecidos"
+                        @if report.removed_attributes > 0 {
+                            "; " (report.removed_attributes) " atributos removidos"
+                        }
+                        @let inferences = report.invalidated_inferences
+                            + report.revalidated_inferences
+                            + report.rederived_inferences;
+                        @if inferences > 0 {
+                            "; inferências: " (report.invalidated_inferences) " invalidadas, "
+                            (report.rederived_inferences) " recalculadas, "
+                            (report.revalidated_inferences) " mantidas por revisão"
+                        }
+                        @if report.removed_windows > 0 {
+                            "; " (report.removed_windows) " janelas de coocorrência retiradas"
+                        }
+                        "."
+                        @if !report.removed_concepts.is_empty() {
+                            div class="documents-file" { (report.removed_concepts.join(", ")) }
+                        }
+                    }
+                    Err(reason) => {
+                        strong { (name) } ": " (reason)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Linha de uma ingestão interrompida após pedir a retomada (ou a recusa).
+pub fn resumed_row(name: &str, job: Result<JobId, &str>) -> Markup {
+    html! {
+        tr class="documents-resumed" {
+            td colspan="7" {
+                @match job {
+                    Ok(id) => {
+                        strong { (name) } " retomado: job " code { (id.to_string()[..8]) } " na fila. "
+                        a href=(format!("/visualizador?job={}", id)) target="_blank" {
+                            "Acompanhe no Visualizador →"
+                        }
+                    }
+                    Err(reason) => {
+                        strong { (name) } ": " (reason)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Tamanho de arquivo legível ("340 KB", "2.1 MB").
+fn format_size(bytes: usize) -> String {
+    if bytes < 1024 * 1024 {
+        format!("{:.0} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}